@@ -20,10 +20,9 @@ class BridgeWaiter {
     }
 
     /**
-     * @param {oasis.types.longnum} id
+     * @param {string} idStr
      */
-    wait(id) {
-        const idStr = '' + id;
+    wait(idStr) {
         if (!(idStr in this.promised)) {
             this.promised[idStr] = new Promise((resolve, reject) => {
                 this.requested[idStr] = {resolve, reject};
@@ -33,11 +32,10 @@ class BridgeWaiter {
     }
 
     /**
-     * @param {oasis.types.longnum} id
+     * @param {string} idStr
      * @param {E} event
      */
-    observe(id, event) {
-        const idStr = '' + id;
+    observe(idStr, event) {
         if (idStr in this.requested) {
             const {resolve, reject} = this.requested[idStr];
             delete this.requested[idStr];
@@ -49,6 +47,27 @@ class BridgeWaiter {
 
 }
 
+/**
+ * Operations are numbered independently for each denomination.
+ *
+ * @param {Uint8Array} denomination
+ * @param {oasis.types.longnum} id
+ */
+function sequenceKey(denomination, id) {
+    return oasis.misc.toHex(denomination) + '/' + id;
+}
+
+/**
+ * @param {Map<Uint8Array, oasis.types.longnum> | undefined} sequences
+ * @param {Uint8Array} denomination
+ */
+function nextSequence(sequences, denomination) {
+    for (const [d, id] of sequences || []) {
+        if (oasis.misc.toHex(d) === oasis.misc.toHex(denomination)) return BigInt(id);
+    }
+    return 0n;
+}
+
 const nic = new oasis.client.NodeInternal('http://localhost:42280');
 const accountsWrapper = new oasisRT.accounts.Wrapper(BRIDGE_RUNTIME_ID);
 const bridgeWrapper = new oasisBridge.Wrapper(BRIDGE_RUNTIME_ID);
@@ -111,11 +130,17 @@ async function witnessIn(label, witness, id, amount, target, consensusChainConte
     });
 
     console.log('in witness', label, 'releasing', id, amount, target);
+    const params = await bridgeWrapper.queryParameters().query(nic);
+    const release = /** @type {oasisBridge.Release} */ ({
+        id,
+        target,
+        amount,
+    });
+    const sig = await witness.sign(oasisBridge.RELEASE_SIGNATURE_CONTEXT, oasisBridge.witnessMessage(params, id, {release}));
     const tw = bridgeWrapper.callRelease()
         .setBody({
-            id,
-            amount,
-            target,
+            ...release,
+            sig,
         })
         .setSignerInfo([siWitness])
         .setFeeAmount(FEE_FREE)
@@ -129,9 +154,10 @@ async function witnessIn(label, witness, id, amount, target, consensusChainConte
  * @param {string} label
  * @param {oasis.signature.ContextSigner} witness
  * @param {oasis.types.longnum} id
+ * @param {oasisBridge.Operation} op
  * @param {string} consensusChainContext
  */
-async function witnessOut(label, witness, id, consensusChainContext) {
+async function witnessOut(label, witness, id, op, consensusChainContext) {
     console.log('out witness', label, 'getting nonce');
     const nonce = await accountsWrapper.queryNonce()
         .setArgs({
@@ -144,10 +170,12 @@ async function witnessOut(label, witness, id, consensusChainContext) {
         nonce,
     });
 
-    const sig = oasis.misc.fromString(`signature:${btoa(String.fromCharCode.apply(null, witness.public()))}`);
+    const params = await bridgeWrapper.queryParameters().query(nic);
+    const sig = await witness.sign(oasisBridge.WITNESS_SIGNATURE_CONTEXT, oasisBridge.witnessMessage(params, id, op));
     console.log('out witness', label, 'witnessing', id, sig);
     const tw = bridgeWrapper.callWitness()
         .setBody({
+            denomination: /** @type {oasisBridge.Lock} */ (op.lock).amount[1],
             id,
             sig,
         })
@@ -178,7 +206,7 @@ export const playground = (async function () {
 
     const lockWaiter = /** @type {BridgeWaiter<oasisBridge.LockEvent>} */ (new BridgeWaiter());
     const releaseWaiter = /** @type {BridgeWaiter<oasisBridge.ReleaseEvent>} */ (new BridgeWaiter());
-    const witnessesSignedWaiter = /** @type {BridgeWaiter<oasisBridge.WitnessSignatures>} */ (new BridgeWaiter());
+    const witnessesSignedWaiter = /** @type {BridgeWaiter<oasisBridge.QuorumCertificate>} */ (new BridgeWaiter());
 
     // The user and witnesses are normally on different computers and would each watch blocks on their
     // own, but for simplicity in this example, we're running a single shared subscription.
@@ -198,15 +226,16 @@ export const playground = (async function () {
         oasisBridge.moduleEventHandler({
             [oasisBridge.EVENT_LOCK_CODE]: (e, lockEvent) => {
                 console.log('observed lock', lockEvent);
-                lockWaiter.observe(lockEvent.id, lockEvent);
+                lockWaiter.observe(sequenceKey(lockEvent.amount[1], lockEvent.id), lockEvent);
             },
             [oasisBridge.EVENT_RELEASE_CODE]: (e, releaseEvent) => {
                 console.log('observed release', releaseEvent);
-                releaseWaiter.observe(releaseEvent.id, releaseEvent);
+                releaseWaiter.observe(sequenceKey(releaseEvent.amount[1], releaseEvent.id), releaseEvent);
             },
-            [oasisBridge.EVENT_WITNESSES_SIGNED_CODE]: (e, witnessSignatures) => {
-                console.log('observed witnesses signed', witnessSignatures);
-                witnessesSignedWaiter.observe(witnessSignatures.id, witnessSignatures);
+            [oasisBridge.EVENT_WITNESSES_SIGNED_CODE]: (e, quorumCertificate) => {
+                console.log('observed witnesses signed', quorumCertificate);
+                const lock = /** @type {oasisBridge.Lock} */ (quorumCertificate.op.lock);
+                witnessesSignedWaiter.observe(sequenceKey(lock.amount[1], quorumCertificate.id), quorumCertificate);
             },
         }),
     ]);
@@ -239,14 +268,18 @@ export const playground = (async function () {
     // Out flow.
     {
         console.log('out user locking');
-        const id = await userOut('alice', new oasis.signature.BlindContextSigner(alice), oasis.misc.fromHex('0000000000000000000000000000000000000000'), [oasis.quantity.fromBigInt(10n), oasisRT.token.NATIVE_DENOMINATION], consensusChainContext);
+        const lock = /** @type {oasisBridge.Lock} */ ({
+            target: oasis.misc.fromHex('0000000000000000000000000000000000000000'),
+            amount: [oasis.quantity.fromBigInt(10n), oasisRT.token.NATIVE_DENOMINATION],
+        });
+        const id = await userOut('alice', new oasis.signature.BlindContextSigner(alice), lock.target, lock.amount, consensusChainContext);
         console.log('out waiting for lock event');
-        await lockWaiter.wait(id);
+        await lockWaiter.wait(sequenceKey(lock.amount[1], id));
         console.log('out witnesses signing');
-        await witnessOut('bob', new oasis.signature.BlindContextSigner(bob), id, consensusChainContext);
-        await witnessOut('charlie', new oasis.signature.BlindContextSigner(charlie), id, consensusChainContext);
+        await witnessOut('bob', new oasis.signature.BlindContextSigner(bob), id, {lock}, consensusChainContext);
+        await witnessOut('charlie', new oasis.signature.BlindContextSigner(charlie), id, {lock}, consensusChainContext);
         console.log('out waiting for witnesses signed event');
-        await witnessesSignedWaiter.wait(id);
+        await witnessesSignedWaiter.wait(sequenceKey(lock.amount[1], id));
         console.log('out done');
     }
     // In flow.
@@ -255,25 +288,25 @@ export const playground = (async function () {
         const numbers = await bridgeWrapper.queryNextSequenceNumbers()
             .query(nic);
         console.log('next sequence numbers', numbers);
-        const localReleaseID = BigInt(numbers.in);
-        const remoteReleaseID = BigInt(numbers.in) + 1n;
 
         // Local denomination.
         const localAmount = /** @type {oasisRT.types.BaseUnits} */ ([oasis.quantity.fromBigInt(10n), oasisRT.token.NATIVE_DENOMINATION]);
+        const localReleaseID = nextSequence(numbers.in, localAmount[1]);
         console.log('in local witnesses signing');
         await witnessIn('bob', new oasis.signature.BlindContextSigner(bob), localReleaseID, localAmount, aliceAddress, consensusChainContext);
         await witnessIn('charlie', new oasis.signature.BlindContextSigner(charlie), localReleaseID, localAmount, aliceAddress, consensusChainContext);
         console.log('in local waiting for release event');
-        await releaseWaiter.wait(localReleaseID);
+        await releaseWaiter.wait(sequenceKey(localAmount[1], localReleaseID));
         console.log('in local done');
 
         // Remote denomination.
         const remoteAmount = /** @type {oasisRT.types.BaseUnits} */ ([oasis.quantity.fromBigInt(10n), oasis.misc.fromString('oETH')]);
+        const remoteReleaseID = nextSequence(numbers.in, remoteAmount[1]);
         console.log('in remote witnesses signing');
         await witnessIn('bob', new oasis.signature.BlindContextSigner(bob), remoteReleaseID, remoteAmount, aliceAddress, consensusChainContext);
         await witnessIn('charlie', new oasis.signature.BlindContextSigner(charlie), remoteReleaseID, remoteAmount, aliceAddress, consensusChainContext);
         console.log('in remote waiting for release event');
-        await releaseWaiter.wait(remoteReleaseID);
+        await releaseWaiter.wait(sequenceKey(remoteAmount[1], remoteReleaseID));
         console.log('in remote done');
     }
     // Parameters.
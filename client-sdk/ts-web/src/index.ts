@@ -12,18 +12,159 @@ export const ERR_INVALID_SEQUENCE_NUMBER_CODE = 3;
 export const ERR_INSUFFICIENT_BALANCE_CODE = 4;
 export const ERR_ALREADY_SUBMITTED_SIGNATURE_CODE = 5;
 export const ERR_UNSUPPORTED_DENOMINATION_CODE = 6;
+export const ERR_INVALID_TARGET_CODE = 7;
+export const ERR_INVALID_SIGNATURE_CODE = 8;
+export const ERR_INSUFFICIENT_BOND_CODE = 9;
+export const ERR_DUPLICATE_EVIDENCE_CODE = 10;
+export const ERR_INVALID_NONCE_CODE = 11;
+export const ERR_RESERVE_EXCEEDED_CODE = 12;
+export const ERR_AMOUNT_TOO_SMALL_CODE = 13;
+export const ERR_AMOUNT_TOO_LARGE_CODE = 14;
+export const ERR_PAUSED_CODE = 15;
+export const ERR_DENOMINATION_FROZEN_CODE = 16;
+export const ERR_RATE_LIMIT_EXCEEDED_CODE = 17;
+export const ERR_ACCOUNT_LIMIT_EXCEEDED_CODE = 18;
+export const ERR_ADDRESS_BLOCKED_CODE = 19;
+export const ERR_ADDRESS_NOT_ALLOWED_CODE = 20;
+export const ERR_ATTESTATION_EXPIRED_CODE = 21;
+export const ERR_DEPOSIT_ALREADY_PROCESSED_CODE = 22;
+export const ERR_TOO_MANY_VOTES_CODE = 23;
+export const ERR_BATCH_TOO_LARGE_CODE = 24;
+export const ERR_INVALID_CLAIM_PROOF_CODE = 25;
+export const ERR_ALREADY_CLAIMED_CODE = 26;
+export const ERR_TOO_MANY_PENDING_CODE = 27;
+export const ERR_UNSUPPORTED_REMOTE_CHAIN_CODE = 28;
 
 // Callable methods.
 export const METHOD_LOCK = 'bridge.Lock';
 export const METHOD_WITNESS = 'bridge.Witness';
+export const METHOD_WITNESS_BATCH = 'bridge.WitnessBatch';
 export const METHOD_RELEASE = 'bridge.Release';
+export const METHOD_RETRACT_VOTE = 'bridge.RetractVote';
+export const METHOD_SET_WITNESS_SIGNING_KEY = 'bridge.SetWitnessSigningKey';
+export const METHOD_UPDATE_WITNESSES = 'bridge.UpdateWitnesses';
+export const METHOD_ADD_WITNESS = 'bridge.AddWitness';
+export const METHOD_REMOVE_WITNESS = 'bridge.RemoveWitness';
+export const METHOD_REACTIVATE_WITNESS = 'bridge.ReactivateWitness';
+export const METHOD_SET_WITNESS_METADATA = 'bridge.SetWitnessMetadata';
+export const METHOD_ROTATE_WITNESS_KEY = 'bridge.RotateWitnessKey';
+export const METHOD_BOND = 'bridge.Bond';
+export const METHOD_UNBOND = 'bridge.Unbond';
+export const METHOD_SUBMIT_EVIDENCE = 'bridge.SubmitEvidence';
+export const METHOD_SET_FEE_SCHEDULE = 'bridge.SetFeeSchedule';
+export const METHOD_SET_REMOTE_CONTRACT = 'bridge.SetRemoteContract';
+export const METHOD_REGISTER_DENOMINATION = 'bridge.RegisterDenomination';
+export const METHOD_ATTEST_REMOTE_LOCKED = 'bridge.AttestRemoteLocked';
+export const METHOD_PAUSE = 'bridge.Pause';
+export const METHOD_RESUME = 'bridge.Resume';
+export const METHOD_FREEZE_DENOMINATION = 'bridge.FreezeDenomination';
+export const METHOD_UNFREEZE_DENOMINATION = 'bridge.UnfreezeDenomination';
+export const METHOD_SET_DENOMINATION_METADATA = 'bridge.SetDenominationMetadata';
+export const METHOD_CLAIM = 'bridge.Claim';
+export const METHOD_CLAIM_BALANCE = 'bridge.ClaimBalance';
+export const METHOD_CANCEL_RELEASE = 'bridge.CancelRelease';
+export const METHOD_RECOVER = 'bridge.Recover';
+export const METHOD_CANCEL_RECOVERY = 'bridge.CancelRecovery';
+export const METHOD_UPDATE_BLOCKLIST = 'bridge.UpdateBlocklist';
+export const METHOD_ALLOW = 'bridge.Allow';
+export const METHOD_DISALLOW = 'bridge.Disallow';
 // Queries.
 export const METHOD_NEXT_SEQUENCE_NUMBERS = 'bridge.NextSequenceNumbers';
 export const METHOD_PARAMETERS = 'bridge.Parameters';
+export const METHOD_QUORUM_CERTIFICATE = 'bridge.QuorumCertificate';
+export const METHOD_OUT_OPERATION = 'bridge.OutOperation';
+export const METHOD_IN_OPERATION = 'bridge.InOperation';
+export const METHOD_OPERATION_STATUS = 'bridge.OperationStatus';
+export const METHOD_SIGNING_PROGRESS = 'bridge.SigningProgress';
+export const METHOD_REMOTE_CONTRACT = 'bridge.RemoteContract';
+export const METHOD_WITNESS_SIGNING_KEYS = 'bridge.WitnessSigningKeys';
+export const METHOD_WITNESS_SET_AT = 'bridge.WitnessSetAt';
+export const METHOD_WITNESS_LIVENESS = 'bridge.WitnessLiveness';
+export const METHOD_WITNESSES = 'bridge.Witnesses';
+export const METHOD_WITNESS_BONDS = 'bridge.WitnessBonds';
+export const METHOD_VOTE_NONCES = 'bridge.VoteNonces';
+export const METHOD_SUBSIDY_POOL = 'bridge.SubsidyPool';
+export const METHOD_REMOTE_RESERVES = 'bridge.RemoteReserves';
+export const METHOD_STATS = 'bridge.Stats';
+export const METHOD_TOTAL_LOCKED = 'bridge.TotalLocked';
+export const METHOD_ESTIMATE_LOCK = 'bridge.EstimateLock';
+export const METHOD_PAUSED = 'bridge.Paused';
+export const METHOD_FROZEN_DENOMINATIONS = 'bridge.FrozenDenominations';
+export const METHOD_PAUSE_STATUS = 'bridge.PauseStatus';
+export const METHOD_DENOMINATION_INFO = 'bridge.DenominationInfo';
+export const METHOD_LOCAL_DENOMINATION = 'bridge.LocalDenomination';
+export const METHOD_SEQUENCE_STORAGE = 'bridge.SequenceStorage';
+export const METHOD_EXPORT_STATE = 'bridge.ExportState';
+export const METHOD_RATE_LIMIT_VOLUMES = 'bridge.RateLimitVolumes';
+export const METHOD_RATE_LIMIT_QUOTA = 'bridge.RateLimitQuota';
+export const METHOD_CLAIMS_ROOT = 'bridge.ClaimsRoot';
+export const METHOD_CLAIMABLE_BALANCES = 'bridge.ClaimableBalances';
+export const METHOD_PENDING_OUTGOING = 'bridge.PendingOutgoing';
+export const METHOD_PENDING_INCOMING = 'bridge.PendingIncoming';
+export const METHOD_HISTORY = 'bridge.History';
+export const METHOD_RELEASES_FOR = 'bridge.ReleasesFor';
+export const METHOD_PENDING_RELEASES = 'bridge.PendingReleases';
+export const METHOD_PENDING_RECOVERIES = 'bridge.PendingRecoveries';
+export const METHOD_ALLOWED_ADDRESSES = 'bridge.AllowedAddresses';
+export const METHOD_CONFLICTS = 'bridge.Conflicts';
+export const METHOD_PROCESSED_DEPOSIT = 'bridge.ProcessedDeposit';
+export const METHOD_INVARIANT_VIOLATIONS = 'bridge.InvariantViolations';
 
 export const EVENT_LOCK_CODE = 1;
 export const EVENT_RELEASE_CODE = 2;
 export const EVENT_WITNESSES_SIGNED_CODE = 3;
+export const EVENT_WITNESS_SET_UPDATED_CODE = 4;
+export const EVENT_WITNESS_ADDED_CODE = 5;
+export const EVENT_WITNESS_REMOVED_CODE = 6;
+export const EVENT_WITNESS_KEY_ROTATED_CODE = 7;
+export const EVENT_WITNESS_INACTIVE_CODE = 8;
+export const EVENT_WITNESS_REACTIVATED_CODE = 9;
+export const EVENT_WITNESS_BONDED_CODE = 10;
+export const EVENT_WITNESS_UNBONDING_CODE = 11;
+export const EVENT_SLASHED_CODE = 12;
+export const EVENT_WITNESS_JAILED_CODE = 13;
+export const EVENT_WITNESS_REWARDED_CODE = 14;
+export const EVENT_VOTE_RETRACTED_CODE = 15;
+export const EVENT_WITNESS_FEE_PAID_CODE = 16;
+export const EVENT_FEE_SCHEDULE_CHANGED_CODE = 17;
+export const EVENT_REMOTE_LOCKED_ATTESTED_CODE = 18;
+export const EVENT_PAUSED_CODE = 19;
+export const EVENT_RESUMED_CODE = 20;
+export const EVENT_DENOMINATION_FROZEN_CODE = 21;
+export const EVENT_DENOMINATION_UNFROZEN_CODE = 22;
+export const EVENT_CIRCUIT_BREAKER_TRIPPED_CODE = 23;
+export const EVENT_RELEASE_QUEUED_CODE = 24;
+export const EVENT_RELEASE_CANCELLED_CODE = 25;
+export const EVENT_ADDRESS_ALLOWED_CODE = 26;
+export const EVENT_ADDRESS_DISALLOWED_CODE = 27;
+export const EVENT_DIVERGENCE_DETECTED_CODE = 28;
+export const EVENT_RECOVERY_SCHEDULED_CODE = 29;
+export const EVENT_FUNDS_RECOVERED_CODE = 30;
+export const EVENT_RECOVERY_CANCELLED_CODE = 31;
+export const EVENT_CLAIMED_CODE = 32;
+export const EVENT_OPERATION_EXPIRED_CODE = 33;
+export const EVENT_STALE_VOTES_CLEARED_CODE = 34;
+export const EVENT_BALANCE_CLAIMED_CODE = 35;
+export const EVENT_REMOTE_CONTRACT_CHANGED_CODE = 36;
+export const EVENT_DENOMINATION_REGISTERED_CODE = 37;
+export const EVENT_PENDING_RELEASE_FAILED_CODE = 38;
+
+/**
+ * Number of decimals that local and remote amounts of a denomination are
+ * expressed with. Locked amounts are rounded down to amounts that have a
+ * remote equivalent, leaving the dust with the caller.
+ */
+export interface DecimalScale {
+    local?: number;
+    remote?: number;
+}
+
+/**
+ * EIP-712 signing domain of the remote bridge contract.
+ */
+export interface EIP712Domain {
+    verifying_contract: Uint8Array;
+}
 
 /**
  * Lock call.
@@ -31,13 +172,39 @@ export const EVENT_WITNESSES_SIGNED_CODE = 3;
 export interface Lock {
     target: Uint8Array;
     amount: oasisRT.types.BaseUnits;
+    /**
+     * Remote chain that the lock targets, which must be the configured remote
+     * chain if given.
+     */
+    remote_chain_id?: oasis.types.longnum;
+    /**
+     * Attestation over the caller address, required when a lock attester is
+     * configured.
+     */
+    attestation?: LockAttestation;
+}
+
+/**
+ * Attestation by the lock attester that an address may lock funds.
+ */
+export interface LockAttestation {
+    /**
+     * Round after which the attestation is no longer accepted.
+     */
+    expiration: oasis.types.longnum;
+    sig: Uint8Array;
 }
 
 export interface LockEvent {
     id: oasis.types.longnum;
+    remote_chain_id: oasis.types.longnum;
     owner: Uint8Array;
     target: Uint8Array;
     amount: oasisRT.types.BaseUnits;
+    /**
+     * Fee deducted from the locked amount, which is not part of `amount`.
+     */
+    fee: oasisRT.types.BaseUnits;
 }
 
 /**
@@ -45,14 +212,105 @@ export interface LockEvent {
  */
 export interface LockResult {
     id: oasis.types.longnum;
+    fee: oasisRT.types.BaseUnits;
+}
+
+/**
+ * Next sequence numbers query.
+ */
+export interface NextSequenceNumbersQuery {
+    /**
+     * Remote chain to return the sequence numbers of. Defaults to the
+     * currently configured remote chain.
+     */
+    remote_chain_id?: oasis.types.longnum;
+    /**
+     * Only return the sequence numbers of the given denomination.
+     */
+    denomination?: Uint8Array;
+}
+
+/**
+ * Sequence storage entries query.
+ */
+export interface SequenceStorageQuery {
+    /**
+     * Remote chain to return the sequence storage entries of. Defaults to the
+     * currently configured remote chain.
+     */
+    remote_chain_id?: oasis.types.longnum;
+}
+
+/**
+ * State export query.
+ */
+export interface ExportStateQuery {
+    /**
+     * Only return entries with keys after the given key, as returned by the
+     * previous chunk.
+     */
+    after?: Uint8Array;
+}
+
+/**
+ * Raw entry of the module state.
+ */
+export interface StateEntry {
+    key: Uint8Array;
+    value: unknown;
+}
+
+/**
+ * Chunk of the module state export.
+ */
+export interface StateChunk {
+    entries?: StateEntry[];
+    /**
+     * Key to continue the export after, if there are more entries.
+     */
+    next?: Uint8Array;
+}
+
+/**
+ * Raw storage entry in the runtime state.
+ */
+export interface StorageEntry {
+    /**
+     * Full key of the entry, including the module prefix.
+     */
+    key: Uint8Array;
+    /**
+     * CBOR-encoded value of the entry, if present.
+     */
+    value?: Uint8Array;
+}
+
+/**
+ * Storage entries holding the next sequence numbers of traffic with a remote
+ * chain. The entries do not carry a Merkle proof. A proof of them against the
+ * state root must be obtained separately for the keys, e.g. through the
+ * storage sync interface of a node.
+ */
+export interface SequenceStorage {
+    round: oasis.types.longnum;
+    state_root: Uint8Array;
+    remote_chain_id?: oasis.types.longnum;
+    in: StorageEntry;
+    out: StorageEntry;
 }
 
 /**
- * Next event sequence numbers.
+ * Next event sequence numbers. Incoming and outgoing operations are numbered
+ * independently for each remote chain and denomination.
  */
 export interface NextSequenceNumbers {
-    in: oasis.types.longnum;
-    out: oasis.types.longnum;
+    remote_chain_id?: oasis.types.longnum;
+    in?: Map<Uint8Array, oasis.types.longnum>;
+    out?: Map<Uint8Array, oasis.types.longnum>;
+    witness_set_updates?: oasis.types.longnum;
+    fee_schedule_updates?: oasis.types.longnum;
+    remote_contract_updates?: oasis.types.longnum;
+    denomination_registrations?: oasis.types.longnum;
 }
 
 /**
@@ -63,18 +321,55 @@ export interface Operation {
     release?: Release;
 }
 
+/**
+ * Signer of a multisig witness.
+ */
+export interface MultisigSigner {
+    public_key: oasisRT.types.PublicKey;
+    weight: oasis.types.longnum;
+}
+
+/**
+ * Configuration of a multisig witness. Any of the signers can submit
+ * transactions on behalf of the witness, but payload signatures must be made
+ * by signers whose weights add up to at least the threshold.
+ */
+export interface MultisigConfig {
+    signers: MultisigSigner[];
+    threshold: oasis.types.longnum;
+}
+
+/**
+ * Witness in the witness set.
+ */
+export type WitnessKey = oasisRT.types.PublicKey | MultisigConfig;
+
+/**
+ * Returns the payload signature of a multisig witness, given the signatures
+ * ordered as the signers in its configuration with `null` for missing ones.
+ */
+export function multisigSignature(signatures: (Uint8Array | null)[]) {
+    return oasis.misc.toCBOR(signatures);
+}
+
 /**
  * Parameters for the bridge module.
  */
 export interface Parameters {
     /**
-     * A list of authorized witness public keys.
+     * A list of authorized witnesses. The witness set may mix signature
+     * schemes and include multisig witnesses.
      */
-    witnesses: oasisRT.types.PublicKey[];
+    witnesses: WitnessKey[];
     /**
-     * Number of witnesses that needs to sign off.
+     * Number of witnesses that needs to sign off. When a threshold policy is
+     * set, this is the minimum threshold.
      */
     threshold: oasis.types.longnum;
+    /**
+     * Policy for deriving the threshold from the number of active witnesses.
+     */
+    threshold_policy?: ThresholdPolicy;
     /**
      * Denominations local to this side of the bridge.
      */
@@ -83,39 +378,1418 @@ export interface Parameters {
      * Denominations that exist on the remote side of the bridge.
      */
     remote_denominations: Map<Uint8Array, Uint8Array>;
+    /**
+     * Identifier of the runtime the bridge is deployed in.
+     */
+    runtime_id?: Uint8Array;
+    /**
+     * Identifier of the remote chain.
+     */
+    remote_chain_id?: oasis.types.longnum;
+    /**
+     * Address of the bridge contract on the remote chain, if any. Witness
+     * signatures commit to it.
+     */
+    remote_contract?: Uint8Array;
+    /**
+     * Encoding of the messages that witnesses sign for outgoing operations.
+     * The ABI encoding requires a remote contract.
+     */
+    message_encoding?: 'cbor' | 'abi';
+    /**
+     * EIP-712 domain used for hashing outgoing operations. When set, the
+     * message encoding must be CBOR.
+     */
+    eip712_domain?: EIP712Domain;
+    /**
+     * Address that is allowed to add and remove witnesses and to change the
+     * fee schedule, if any.
+     */
+    admin?: Uint8Array;
+    /**
+     * Addresses that are allowed to pause and resume the bridge.
+     */
+    guardians?: Uint8Array[];
+    /**
+     * Number of most recent outgoing operations over which witness
+     * participation is reported, at most 64.
+     */
+    liveness_window?: oasis.types.longnum;
+    /**
+     * Number of consecutive outgoing operations that a witness can miss before
+     * it is marked inactive. Zero disables automatic demotion.
+     */
+    max_missed_operations?: oasis.types.longnum;
+    /**
+     * Number of rounds after a witness set change during which pending
+     * outgoing operations also accept signatures from the new witness set.
+     */
+    handover_window?: oasis.types.longnum;
+    /**
+     * Amount of a local denomination that witnesses need to bond before their
+     * signatures and votes are accepted, if any.
+     */
+    witness_bond?: oasisRT.types.BaseUnits;
+    /**
+     * Number of rounds after which unbonded amounts can be withdrawn.
+     */
+    unbonding_delay?: oasis.types.longnum;
+    /**
+     * Portion of the bond, in basis points, that is slashed from witnesses
+     * that vote for an incoming operation other than the finalized one.
+     */
+    divergence_penalty?: oasis.types.longnum;
+    /**
+     * Portion of the bond, in basis points, that is slashed from witnesses for
+     * which evidence of signing two different operations for the same
+     * sequence number is submitted.
+     */
+    equivocation_penalty?: oasis.types.longnum;
+    /**
+     * Number of rounds during which a witness for which evidence of
+     * misbehavior is submitted is inactive and cannot reactivate itself.
+     */
+    jail_duration?: oasis.types.longnum;
+    /**
+     * Number of rounds between distributions of the reward pool to the
+     * witnesses. Zero disables rewards.
+     */
+    reward_period?: oasis.types.longnum;
+    /**
+     * Fee in basis points of the locked amount that is deducted from each
+     * lock and credited to the bridge fee account.
+     */
+    lock_fee?: oasis.types.longnum;
+    /**
+     * Minimum lock fee in base units of the locked denomination.
+     */
+    min_lock_fee?: Uint8Array;
+    /**
+     * Maximum lock fee in base units of the locked denomination. Zero means no
+     * maximum.
+     */
+    max_lock_fee?: Uint8Array;
+    /**
+     * Flat fee in the native denomination that is charged on each lock and
+     * deposited into the subsidy pool, from which witnesses are reimbursed
+     * for the fees of their witness and release transactions.
+     */
+    subsidy_fee?: Uint8Array;
+    /**
+     * Minimum amount per denomination that can be locked.
+     */
+    min_lock_amounts?: Map<Uint8Array, Uint8Array>;
+    /**
+     * Maximum amount per denomination that can be locked or released in a
+     * single operation.
+     */
+    max_transfer_amounts?: Map<Uint8Array, Uint8Array>;
+    /**
+     * Number of local and remote decimals per denomination whose amounts are
+     * scaled when crossing the bridge.
+     */
+    decimal_scales?: Map<Uint8Array, DecimalScale>;
+    /**
+     * Number of rounds in a rate limit window. Zero disables rate limits.
+     */
+    rate_limit_window?: oasis.types.longnum;
+    /**
+     * Maximum amount per denomination that can be locked and released in
+     * total during a rate limit window.
+     */
+    rate_limits?: Map<Uint8Array, Uint8Array>;
+    /**
+     * Maximum amount per denomination that a single account can lock, or
+     * receive through releases, in total during a rate limit window.
+     */
+    account_limits?: Map<Uint8Array, Uint8Array>;
+    /**
+     * Expected amount per denomination that is released during a rate limit
+     * window.
+     */
+    release_baselines?: Map<Uint8Array, Uint8Array>;
+    /**
+     * Multiple of the release baseline at which the bridge is paused
+     * automatically until a guardian resumes it. Zero disables the circuit
+     * breaker.
+     */
+    circuit_breaker_multiplier?: oasis.types.longnum;
+    /**
+     * Number of rounds during which guardians can cancel large releases
+     * before they are executed. Zero disables the challenge period.
+     */
+    challenge_period?: oasis.types.longnum;
+    /**
+     * Amount per denomination above which releases are subject to the
+     * challenge period.
+     */
+    challenge_thresholds?: Map<Uint8Array, Uint8Array>;
+    /**
+     * Local addresses that cannot lock funds or receive released funds.
+     */
+    blocked_addresses?: Uint8Array[];
+    /**
+     * Remote addresses that funds cannot be locked for.
+     */
+    blocked_remote_addresses?: Uint8Array[];
+    /**
+     * Whether only addresses on the allowlist managed by the admin can lock
+     * funds.
+     */
+    lock_allowlist?: boolean;
+    /**
+     * Key that must attest to the caller address of each lock, if any.
+     */
+    lock_attester?: oasisRT.types.PublicKey;
+    /**
+     * Number of rounds for which the hashes of processed remote transactions
+     * are remembered at least. Zero remembers them forever.
+     */
+    processed_deposit_retention?: oasis.types.longnum;
+    /**
+     * Number of rounds after which funds that the admin recovers from the
+     * locked funds account are transferred, during which guardians can cancel
+     * the recovery. Zero disables recoveries.
+     */
+    recovery_delay?: oasis.types.longnum;
+    /**
+     * Maximum number of `Witness` and `Release` votes that a single account
+     * can submit per block. Zero means no limit.
+     */
+    max_votes_per_block?: oasis.types.longnum;
+    /**
+     * Number of incoming sequence numbers ahead of the next expected one that
+     * witnesses can vote on. Zero only accepts the next one.
+     */
+    release_window?: oasis.types.longnum;
+    /**
+     * Maximum number of further transfers that a single release can batch
+     * together with its primary one. Zero disables batch releases.
+     */
+    max_release_batch_size?: oasis.types.longnum;
+    /**
+     * Number of rounds after which outgoing operations that did not gather
+     * enough witness signatures expire. Their funds stay locked. Zero disables
+     * expiry.
+     */
+    operation_expiry?: oasis.types.longnum;
+    /**
+     * Number of rounds after the first vote for an incoming sequence number
+     * after which its votes are cleared if the operation has not been
+     * finalized. Zero disables clearing stale votes.
+     */
+    stale_vote_age?: oasis.types.longnum;
+    /**
+     * Whether cleared stale votes are treated as retracted, so that witnesses
+     * can vote for a different operation without it counting as equivocation.
+     */
+    reset_stale_votes?: boolean;
+    /**
+     * Maximum number of outgoing operations that can be pending finalization
+     * at the same time. Zero means no limit.
+     */
+    max_pending_locks?: oasis.types.longnum;
+    /**
+     * Whether finalized releases credit the claimable balances of their
+     * targets, which they withdraw with `bridge.ClaimBalance`, instead of
+     * transferring the funds to them. Releases committing to claims are not
+     * affected.
+     */
+    pull_releases?: boolean;
+    /**
+     * Number of rounds for which finalized operations are kept in the
+     * operation history at least. Zero disables the operation history.
+     */
+    history_retention?: oasis.types.longnum;
+    /**
+     * Size in bytes of addresses on the remote chain, which lock targets must
+     * have. Zero means 20-byte Ethereum addresses.
+     */
+    remote_address_size?: oasis.types.longnum;
 }
 
 /**
- * Release call.
+ * Threshold given as a fraction of the number of active witnesses, computed as
+ * `floor(n * numerator / denominator) + extra`.
  */
-export interface Release {
-    id: oasis.types.longnum;
-    target: Uint8Array;
+export interface ThresholdPolicy {
+    numerator: oasis.types.longnum;
+    denominator: oasis.types.longnum;
+    extra?: oasis.types.longnum;
+}
+
+/**
+ * Metadata published by a witness. Setting empty metadata clears it.
+ */
+export interface WitnessMetadata {
+    name?: string;
+    /**
+     * Contact endpoint, e.g. an e-mail address or URL.
+     */
+    endpoint?: string;
+    /**
+     * Address on the remote chain that the witness is paid out to.
+     */
+    payout_address?: Uint8Array;
+}
+
+export interface WitnessInfo {
+    /**
+     * Index of the witness in the witness set of the parameters. Signatures of
+     * operations created with a witness set snapshot are indexed by their
+     * position in that snapshot instead.
+     */
+    index?: number;
+    witness: WitnessKey;
+    /**
+     * Whether the witness has not been marked inactive.
+     */
+    active?: boolean;
+    metadata?: WitnessMetadata;
+}
+
+/**
+ * Participation of a witness in recent outgoing operations.
+ */
+export interface WitnessLiveness {
+    /**
+     * One bit per operation with the least significant bit being the most
+     * recent operation. Set bits mark signed operations.
+     */
+    recent?: oasis.types.longnum;
+    /**
+     * Number of consecutive most recent operations that the witness missed.
+     */
+    missed?: oasis.types.longnum;
+    inactive?: boolean;
+    /**
+     * Round until which the witness is jailed.
+     */
+    jailed_until?: oasis.types.longnum;
+}
+
+/**
+ * Attest remote locked call. Attests to the total amount of a remote
+ * denomination that is locked in the remote bridge contract.
+ */
+export interface AttestRemoteLocked {
     amount: oasisRT.types.BaseUnits;
 }
 
-export interface ReleaseEvent {
-    id: oasis.types.longnum;
-    target: Uint8Array;
+/**
+ * Reserve backing a remote denomination.
+ */
+export interface RemoteReserve {
+    /**
+     * Amount locked in the remote bridge contract that a threshold of
+     * witnesses agreed upon. Releases that would mint more than this amount
+     * are refused.
+     */
+    locked?: Uint8Array;
+    /**
+     * Amount minted on this side of the bridge.
+     */
+    minted?: Uint8Array;
+    /**
+     * Latest attested locked amount of each witness, keyed by witness
+     * identifier.
+     */
+    attestations?: Map<Uint8Array, Uint8Array>;
+}
+
+export interface RemoteLockedAttestedEvent {
     amount: oasisRT.types.BaseUnits;
 }
 
 /**
- * Witness event call.
+ * Bridge statistics, counted since they were introduced.
  */
-export interface Witness {
-    id: oasis.types.longnum;
-    sig: Uint8Array;
+export interface Stats {
+    locks?: oasis.types.longnum;
+    finalized_locks?: oasis.types.longnum;
+    pending_locks?: oasis.types.longnum;
+    expired_locks?: oasis.types.longnum;
+    releases?: oasis.types.longnum;
+    /**
+     * Cumulative locked amount per denomination, net of lock fees.
+     */
+    locked?: Map<Uint8Array, Uint8Array>;
+    /**
+     * Cumulative released amount per denomination.
+     */
+    released?: Map<Uint8Array, Uint8Array>;
+    /**
+     * Cumulative amount recovered from the locked funds per denomination.
+     */
+    recovered?: Map<Uint8Array, Uint8Array>;
 }
 
 /**
- * Outgoing witness signatures.
+ * Lock estimation query.
  */
-export interface WitnessSignatures {
+export interface EstimateLockQuery {
+    /**
+     * Amount to lock, including the lock fee.
+     */
+    amount: oasisRT.types.BaseUnits;
+    /**
+     * Account that would lock the amount. Account limits are only checked
+     * when set.
+     */
+    owner?: Uint8Array;
+}
+
+/**
+ * Estimated outcome of a lock.
+ */
+export interface LockEstimate {
+    /**
+     * Sequence number that the next lock of the denomination is assigned.
+     */
     id: oasis.types.longnum;
-    op: Operation;
-    wits?: number[];
-    sigs?: Uint8Array[];
+    /**
+     * Amount that would be bridged, net of the lock fee.
+     */
+    amount: oasisRT.types.BaseUnits;
+    /**
+     * Fee that would be deducted from the locked amount.
+     */
+    fee: oasisRT.types.BaseUnits;
+    /**
+     * Code of the error that the lock would currently fail with, if any.
+     */
+    error_code?: number;
+}
+
+/**
+ * Rate limit quota query.
+ */
+export interface RateLimitQuotaQuery {
+    /**
+     * Account to also return the remaining account limit quota of.
+     */
+    account?: Uint8Array;
+}
+
+/**
+ * Remaining rate limit quota available to operations in the next round.
+ */
+export interface RateLimitQuota {
+    /**
+     * Round at the end of which the current rate limit window ends and the
+     * quota is reset.
+     */
+    window_end?: oasis.types.longnum;
+    /**
+     * Remaining amount that can be locked per rate limited denomination.
+     */
+    remaining?: Map<Uint8Array, Uint8Array>;
+    /**
+     * Remaining amount that the account can lock or receive per denomination
+     * with an account limit.
+     */
+    account_remaining?: Map<Uint8Array, Uint8Array>;
+}
+
+/**
+ * Value held by the bridge per denomination.
+ */
+export interface TotalLocked {
+    /**
+     * Amount of each local denomination held in the locked funds account that
+     * has not been released or recovered again.
+     */
+    locked?: Map<Uint8Array, Uint8Array>;
+    /**
+     * Outstanding minted supply of each remote denomination.
+     */
+    minted?: Map<Uint8Array, Uint8Array>;
+}
+
+/**
+ * Accounting invariant of the bridge that does not hold for a denomination.
+ */
+export interface InvariantViolation {
+    /**
+     * Invariant that does not hold.
+     */
+    invariant: 'locked_funds' | 'minted_supply' | 'burned_supply' | 'total_supply';
+    denomination: Uint8Array;
+    /**
+     * Balance of the locked funds account, minted supply counter, burned
+     * amount or total supply, depending on the invariant.
+     */
+    actual: Uint8Array;
+    /**
+     * Outstanding remote claims, released amount including queued releases net
+     * of the burned amount, released amount or released amount net of the
+     * burned amount, depending on the invariant.
+     */
+    expected: Uint8Array;
+}
+
+/**
+ * Next expected vote nonces of a witness.
+ */
+export interface VoteNonces {
+    out?: oasis.types.longnum;
+    in?: oasis.types.longnum;
+}
+
+/**
+ * Set witness signing key call.
+ */
+export interface SetWitnessSigningKey {
+    /**
+     * Key used to sign witness payloads. When not set, payloads must again be
+     * signed using the witness' own key.
+     */
+    key?: oasisRT.types.PublicKey;
+}
+
+/**
+ * Release call.
+ */
+export interface Release {
+    id: oasis.types.longnum;
+    target: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+    /**
+     * Hash of the remote transaction that made the deposit. Required in
+     * Release calls, as releases of a remote deposit are only ever processed
+     * once.
+     */
+    tx_hash?: Uint8Array;
+    /**
+     * Index of the deposit's log entry within the remote transaction.
+     * Required in Release calls. Deposits of a remote transaction are told
+     * apart by it.
+     */
+    log_index?: oasis.types.longnum;
+    /**
+     * Further transfers released atomically together with the primary one
+     * under the same sequence number, all of the primary denomination.
+     */
+    batch?: ReleaseEntry[];
+    /**
+     * Root of a Merkle tree of claims that the release commits to. Such
+     * releases pay into the pending claims account, from which each claim is
+     * paid out through a `Claim` call.
+     */
+    claims_root?: Uint8Array;
+    /**
+     * Remote chain that the deposit was made on, which must be the configured
+     * remote chain if given. Not part of the signed release.
+     */
+    remote_chain_id?: oasis.types.longnum;
+    /**
+     * Witness signature over the release, required when submitting it.
+     */
+    sig?: Uint8Array;
+    /**
+     * Next incoming vote nonce of the witness, required when submitting the
+     * release. Not part of the signed release.
+     */
+    nonce?: oasis.types.longnum;
+}
+
+/**
+ * Single transfer of a batch release.
+ */
+export interface ReleaseEntry {
+    target: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+}
+
+/**
+ * Retract vote call. Withdraws the caller's vote for the pending incoming
+ * operation so that the witness can vote again.
+ */
+export interface RetractVote {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+}
+
+/**
+ * Update witnesses call. The update is applied once enough witnesses of the
+ * current witness set have submitted it.
+ */
+export interface UpdateWitnesses {
+    id: oasis.types.longnum;
+    witnesses: WitnessKey[];
+    threshold: oasis.types.longnum;
+    /**
+     * Witness signature over the update, required when submitting it.
+     */
+    sig?: Uint8Array;
+}
+
+export interface WitnessSetUpdatedEvent {
+    id: oasis.types.longnum;
+    witnesses: WitnessKey[];
+    threshold: oasis.types.longnum;
+}
+
+/**
+ * Bridge fee schedule.
+ */
+export interface FeeSchedule {
+    lock_fee?: oasis.types.longnum;
+    min_lock_fee?: Uint8Array;
+    max_lock_fee?: Uint8Array;
+    subsidy_fee?: Uint8Array;
+}
+
+/**
+ * Set fee schedule call. The schedule is applied immediately when submitted by
+ * the admin, otherwise once enough witnesses of the current witness set have
+ * submitted it.
+ */
+export interface SetFeeSchedule {
+    id: oasis.types.longnum;
+    schedule: FeeSchedule;
+}
+
+export interface FeeScheduleChangedEvent {
+    id: oasis.types.longnum;
+    schedule: FeeSchedule;
+}
+
+/**
+ * Set remote contract call. Applied immediately when submitted by the admin,
+ * otherwise once enough witnesses of the current witness set have relayed it
+ * from the remote chain's governance.
+ */
+export interface SetRemoteContract {
+    id: oasis.types.longnum;
+    contract: Uint8Array;
+}
+
+export interface RemoteContractChangedEvent {
+    id: oasis.types.longnum;
+    contract: Uint8Array;
+}
+
+/**
+ * Register denomination call adding a new remote denomination. Applied
+ * immediately when submitted by the admin, otherwise once enough witnesses of
+ * the current witness set have submitted it.
+ */
+export interface RegisterDenomination {
+    id: oasis.types.longnum;
+    /**
+     * Local denomination of the wrapped asset.
+     */
+    denomination: Uint8Array;
+    /**
+     * Identifier of the asset on the remote side of the bridge.
+     */
+    remote: Uint8Array;
+    /**
+     * Number of local and remote decimals if amounts of the denomination are
+     * scaled.
+     */
+    decimal_scale?: DecimalScale;
+}
+
+export interface DenominationRegisteredEvent {
+    id: oasis.types.longnum;
+    denomination: Uint8Array;
+    remote: Uint8Array;
+}
+
+export interface PendingReleaseFailedEvent {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+    /**
+     * Error code of the failure.
+     */
+    code: number;
+}
+
+/**
+ * Add witness call, restricted to the admin.
+ */
+export interface AddWitness {
+    witness: WitnessKey;
+}
+
+/**
+ * Remove witness call, restricted to the admin.
+ */
+export interface RemoveWitness {
+    witness: WitnessKey;
+}
+
+/**
+ * Update blocklist call, restricted to the admin.
+ */
+export interface UpdateBlocklist {
+    block?: Uint8Array[];
+    unblock?: Uint8Array[];
+    block_remote?: Uint8Array[];
+    unblock_remote?: Uint8Array[];
+}
+
+/**
+ * Allow or disallow address call, restricted to the admin.
+ */
+export interface AllowAddress {
+    address: Uint8Array;
+}
+
+export interface AddressAllowedEvent {
+    address: Uint8Array;
+}
+
+export interface PausedEvent {
+    guardian: Uint8Array;
+}
+
+export interface CircuitBreakerTrippedEvent {
+    volume: oasisRT.types.BaseUnits;
+    baseline: Uint8Array;
+}
+
+/**
+ * Freeze or unfreeze denomination call, restricted to the guardians and the
+ * admin.
+ */
+export interface FreezeDenomination {
+    denomination: Uint8Array;
+}
+
+/**
+ * Display metadata of a denomination.
+ */
+export interface DenominationMetadata {
+    decimals?: number;
+    symbol?: string;
+}
+
+/**
+ * Set denomination metadata call, restricted to the admin. Setting empty
+ * metadata clears it.
+ */
+export interface SetDenominationMetadata {
+    denomination: Uint8Array;
+    metadata?: DenominationMetadata;
+}
+
+/**
+ * Bridge contract on the remote chain.
+ */
+export interface RemoteContract {
+    /**
+     * Identifier of the remote chain.
+     */
+    remote_chain_id: oasis.types.longnum;
+    /**
+     * Address of the bridge contract on the remote chain, if configured.
+     */
+    address?: Uint8Array;
+}
+
+/**
+ * Local denomination query.
+ */
+export interface LocalDenominationQuery {
+    /**
+     * Identifier of the denomination on the remote side of the bridge.
+     */
+    remote: Uint8Array;
+}
+
+/**
+ * Whether the bridge is paused and which denominations are frozen.
+ */
+export interface PauseStatus {
+    /**
+     * Whether the bridge is paused, halting all locks and releases.
+     */
+    paused?: boolean;
+    /**
+     * Denominations in which locks and releases are halted.
+     */
+    frozen?: Uint8Array[];
+}
+
+/**
+ * Configured denomination together with its metadata.
+ */
+export interface DenominationInfo {
+    denomination: Uint8Array;
+    /**
+     * Identifier of the denomination on the remote side of the bridge. Not set
+     * for local denominations.
+     */
+    remote?: Uint8Array;
+    metadata?: DenominationMetadata;
+}
+
+/**
+ * Conflicting incoming operations proposed for the same sequence number.
+ */
+export interface Conflict {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+    /**
+     * Witnesses that voted for each of the conflicting operations, by
+     * operation hash.
+     */
+    votes: Map<Uint8Array, WitnessKey[]>;
+}
+
+/**
+ * Pending outgoing operations query.
+ */
+export interface PendingOutgoingQuery {
+    /**
+     * Number of pending operations to skip.
+     */
+    offset?: oasis.types.longnum;
+    /**
+     * Maximum number of pending operations to return. Zero returns the maximum
+     * page size.
+     */
+    limit?: oasis.types.longnum;
+}
+
+/**
+ * Summary of an outgoing operation that has not been finalized yet.
+ */
+export interface PendingOutgoing {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+    amount: oasisRT.types.BaseUnits;
+    /**
+     * Number of witness signatures collected so far.
+     */
+    votes: oasis.types.longnum;
+}
+
+/**
+ * Pending incoming operations query.
+ */
+export interface PendingIncomingQuery {
+    /**
+     * Number of pending operations to skip.
+     */
+    offset?: oasis.types.longnum;
+    /**
+     * Maximum number of pending operations to return. Zero returns the maximum
+     * page size.
+     */
+    limit?: oasis.types.longnum;
+}
+
+/**
+ * Summary of an incoming sequence number that has votes but has not been
+ * finalized yet.
+ */
+export interface PendingIncoming {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+    /**
+     * Number of witnesses that voted.
+     */
+    votes: oasis.types.longnum;
+    /**
+     * Number of different operations that the witnesses voted for.
+     */
+    proposals: oasis.types.longnum;
+    /**
+     * Round of the first vote, if known.
+     */
+    created?: oasis.types.longnum;
+}
+
+/**
+ * Operation history query.
+ */
+export interface HistoryQuery {
+    /**
+     * Number of matching operations to skip.
+     */
+    offset?: oasis.types.longnum;
+    /**
+     * Maximum number of operations to return. Zero returns the maximum page
+     * size.
+     */
+    limit?: oasis.types.longnum;
+    /**
+     * Only return operations in the given direction.
+     */
+    direction?: 'outgoing' | 'incoming';
+    /**
+     * Only return operations of the given denomination.
+     */
+    denomination?: Uint8Array;
+}
+
+/**
+ * Finalized operation in the operation history.
+ */
+export interface HistoryEntry {
+    direction: 'outgoing' | 'incoming';
+    id: oasis.types.longnum;
+    /**
+     * Finalized operation. Releases are stored without their signature and
+     * vote nonce.
+     */
+    op: Operation;
+    /**
+     * Round in which the operation was finalized.
+     */
+    round: oasis.types.longnum;
+}
+
+/**
+ * Completed releases query.
+ */
+export interface ReleasesForQuery {
+    address: Uint8Array;
+    /**
+     * Number of completed releases to skip.
+     */
+    offset?: oasis.types.longnum;
+    /**
+     * Maximum number of completed releases to return. Zero returns the maximum
+     * page size.
+     */
+    limit?: oasis.types.longnum;
+}
+
+/**
+ * Release that has been completed to a target address.
+ */
+export interface CompletedRelease {
+    id: oasis.types.longnum;
+    /**
+     * Amount released to the target.
+     */
+    amount: oasisRT.types.BaseUnits;
+    /**
+     * Round in which the release was completed.
+     */
+    round: oasis.types.longnum;
+}
+
+/**
+ * Finalized release that is waiting for its challenge period to pass.
+ */
+export interface PendingRelease {
+    id: oasis.types.longnum;
+    target: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+    tx_hash?: Uint8Array;
+    log_index?: oasis.types.longnum;
+    batch?: ReleaseEntry[];
+    claims_root?: Uint8Array;
+    /**
+     * Round after which the release is executed unless a guardian cancels it.
+     */
+    deadline: oasis.types.longnum;
+}
+
+/**
+ * Claim call. Pays out a single claim committed to by the claims root of a
+ * finalized release to its target. Anyone can submit it.
+ */
+export interface Claim {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+    index: oasis.types.longnum;
+    target: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+    /**
+     * Sibling hashes on the path from the claim to the claims root, starting
+     * at the leaf.
+     */
+    proof?: Uint8Array[];
+}
+
+/**
+ * Claims committed to by a finalized release.
+ */
+export interface ClaimsRoot {
+    root: Uint8Array;
+    /**
+     * Amount that has not been claimed yet.
+     */
+    remaining: oasisRT.types.BaseUnits;
+}
+
+/**
+ * Claims root query.
+ */
+export interface ClaimsRootQuery {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+}
+
+/**
+ * Claim balance call. Withdraws the given amount from the balance that pull
+ * releases credited to the caller.
+ */
+export interface ClaimBalance {
+    amount: oasisRT.types.BaseUnits;
+}
+
+/**
+ * Claimable balances query.
+ */
+export interface ClaimableBalancesQuery {
+    address: Uint8Array;
+}
+
+export interface BalanceClaimedEvent {
+    target: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+}
+
+export interface OperationExpiredEvent {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+}
+
+export interface StaleVotesClearedEvent {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+}
+
+export interface ClaimedEvent {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+    index: oasis.types.longnum;
+    target: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+}
+
+/**
+ * Cancel pending release call, restricted to the guardians and the admin.
+ */
+export interface CancelRelease {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+}
+
+export interface ReleaseQueuedEvent {
+    id: oasis.types.longnum;
+    target: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+    deadline: oasis.types.longnum;
+    batch?: ReleaseEntry[];
+}
+
+export interface ReleaseCancelledEvent {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+    by: Uint8Array;
+}
+
+/**
+ * Recover call, restricted to the admin. Moves funds out of the locked funds
+ * account after the recovery delay.
+ */
+export interface Recover {
+    target: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+}
+
+/**
+ * Recovery that is waiting for the recovery delay to pass.
+ */
+export interface PendingRecovery {
+    id: oasis.types.longnum;
+    target: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+    /**
+     * Round after which the funds are transferred unless a guardian cancels
+     * the recovery.
+     */
+    deadline: oasis.types.longnum;
+}
+
+/**
+ * Cancel recovery call, restricted to the guardians and the admin.
+ */
+export interface CancelRecovery {
+    id: oasis.types.longnum;
+}
+
+export interface RecoveryScheduledEvent {
+    id: oasis.types.longnum;
+    target: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+    deadline: oasis.types.longnum;
+}
+
+export interface FundsRecoveredEvent {
+    id: oasis.types.longnum;
+    target: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+}
+
+export interface RecoveryCancelledEvent {
+    id: oasis.types.longnum;
+    by: Uint8Array;
+}
+
+export interface DenominationFrozenEvent {
+    denomination: Uint8Array;
+    by: Uint8Array;
+}
+
+export interface WitnessChangedEvent {
+    index: number;
+    witness: WitnessKey;
+}
+
+/**
+ * Rotate witness key call, replacing the caller's own witness key.
+ */
+export interface RotateWitnessKey {
+    key: oasisRT.types.PublicKey;
+}
+
+export interface WitnessKeyRotatedEvent {
+    index: number;
+    old: oasisRT.types.PublicKey;
+    new: oasisRT.types.PublicKey;
+}
+
+/**
+ * Bond call, adding to the bond of the caller's witness.
+ */
+export interface Bond {
+    amount: oasisRT.types.BaseUnits;
+}
+
+/**
+ * Unbond call. Starts unbonding the given amount, if any, and withdraws all
+ * of the caller's unbonding amounts whose unbonding delay has passed.
+ */
+export interface Unbond {
+    amount?: oasisRT.types.BaseUnits;
+}
+
+/**
+ * Bond of a witness.
+ */
+export interface WitnessBond {
+    /**
+     * Account that posted the bond and that unbonded amounts are returned to.
+     */
+    owner: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+}
+
+export interface WitnessBondedEvent {
+    index: number;
+    witness: WitnessKey;
+    amount: oasisRT.types.BaseUnits;
+}
+
+export interface WitnessUnbondingEvent {
+    witness: WitnessKey;
+    owner: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+    /**
+     * Round after which the amount can be withdrawn.
+     */
+    ends: oasis.types.longnum;
+}
+
+/**
+ * Operation together with a witness signature over it.
+ */
+export interface SignedOperation {
+    op: Operation;
+    sig: Uint8Array;
+}
+
+/**
+ * Submit evidence call, proving that a witness signed two different
+ * operations for the same sequence number. Signatures are verified as
+ * `bridge.Witness` signatures for the `witness` kind and as `bridge.Release`
+ * signatures for the `release` kind.
+ */
+export interface SubmitEvidence {
+    witness: WitnessKey;
+    kind: 'witness' | 'release';
+    id: oasis.types.longnum;
+    first: SignedOperation;
+    second: SignedOperation;
+}
+
+export interface WitnessJailedEvent {
+    index: number;
+    witness: WitnessKey;
+    until: oasis.types.longnum;
+}
+
+export interface WitnessRewardedEvent {
+    index: number;
+    witness: WitnessKey;
+    amount: oasisRT.types.BaseUnits;
+}
+
+export interface VoteRetractedEvent {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+    index: number;
+    witness: WitnessKey;
+}
+
+export interface WitnessFeePaidEvent {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+    index: number;
+    witness: WitnessKey;
+    amount: oasisRT.types.BaseUnits;
+}
+
+export interface SlashedEvent {
+    index: number;
+    witness: WitnessKey;
+    amount: oasisRT.types.BaseUnits;
+}
+
+export interface ReleaseEvent {
+    id: oasis.types.longnum;
+    remote_chain_id: oasis.types.longnum;
+    target: Uint8Array;
+    amount: oasisRT.types.BaseUnits;
+    /**
+     * Hash of the remote transaction that made the deposit, if known.
+     */
+    tx_hash?: Uint8Array;
+    /**
+     * Index of the deposit's log entry within the remote transaction, if
+     * known.
+     */
+    log_index?: oasis.types.longnum;
+}
+
+/**
+ * Witness event call.
+ */
+export interface Witness {
+    /**
+     * Denomination of the outgoing operation, which determines its sequence
+     * space.
+     */
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+    sig: Uint8Array;
+    /**
+     * Next outgoing vote nonce of the witness.
+     */
+    nonce: oasis.types.longnum;
+}
+
+/**
+ * Witness batch call. Submits several witness votes in one transaction, with
+ * consecutive nonces. The whole batch fails if any of its votes fails.
+ */
+export interface WitnessBatch {
+    votes: Witness[];
+}
+
+/**
+ * Quorum certificate for an outgoing operation.
+ */
+export interface QuorumCertificate {
+    id: oasis.types.longnum;
+    op: Operation;
+    /**
+     * Commitment to the witness set and threshold that produced the
+     * certificate.
+     */
+    witness_set: Uint8Array;
+    /**
+     * Bitmap of signer indices, bit `i` of byte `i / 8` is set when witness
+     * `i` signed.
+     */
+    signers: Uint8Array;
+    /**
+     * Signatures ordered by signer index.
+     */
+    sigs: Uint8Array[];
+    /**
+     * Signatures by the successor witness set if the operation was signed
+     * during a witness set handover.
+     */
+    handover?: HandoverSignatures;
+}
+
+export interface HandoverSignatures {
+    /**
+     * Commitment to the successor witness set and threshold.
+     */
+    witness_set: Uint8Array;
+    signers?: Uint8Array;
+    sigs?: Uint8Array[];
+}
+
+/**
+ * Quorum certificate query.
+ */
+export interface QuorumCertificateQuery {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+}
+
+/**
+ * Outgoing operation query.
+ */
+export interface OutOperationQuery {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+}
+
+/**
+ * Outgoing operation that has not been finalized yet, with the witness
+ * signatures collected so far.
+ */
+export interface WitnessSignatures {
+    id: oasis.types.longnum;
+    op: Operation;
+    /**
+     * Bitmap of signer indices, bit `i` of byte `i / 8` is set when witness
+     * `i` signed.
+     */
+    wits?: Uint8Array;
+    /**
+     * Signatures ordered by signer index.
+     */
+    sigs?: Uint8Array[];
+}
+
+/**
+ * Incoming operation query.
+ */
+export interface InOperationQuery {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+}
+
+/**
+ * Votes for an incoming operation that has not been finalized yet.
+ */
+export interface IncomingWitnessSignatures {
+    /**
+     * Proposed operations and the witnesses that voted for each of them, keyed
+     * by operation hash.
+     */
+    ops?: Map<Uint8Array, WitnessSignatures>;
+    /**
+     * Bitmap of the indices of all witnesses that voted.
+     */
+    wits?: Uint8Array;
+    /**
+     * Round of the first vote.
+     */
+    created?: oasis.types.longnum;
+}
+
+/**
+ * Operation status query.
+ */
+export interface OperationStatusQuery {
+    direction: 'outgoing' | 'incoming';
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+}
+
+/**
+ * Status of an operation. Incoming operations are `challenged` while they wait
+ * for their challenge period to pass.
+ */
+export type OperationStatus = 'unknown' | 'collecting' | 'finalized' | 'expired' | 'challenged';
+
+/**
+ * Signing progress query.
+ */
+export interface SigningProgressQuery {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+}
+
+/**
+ * Signing progress of a pending outgoing operation.
+ */
+export interface SigningProgress {
+    collected: oasis.types.longnum;
+    threshold: oasis.types.longnum;
+    /**
+     * Indices of the witnesses that have not signed yet.
+     */
+    missing?: number[];
+}
+
+export interface ProcessedDepositQuery {
+    tx_hash: Uint8Array;
+    log_index?: oasis.types.longnum;
+}
+
+/**
+ * Witness set and threshold that an outgoing operation is witnessed by.
+ */
+export interface WitnessSet {
+    witnesses: WitnessKey[];
+    threshold: oasis.types.longnum;
+}
+
+export interface WitnessSetQuery {
+    denomination: Uint8Array;
+    id: oasis.types.longnum;
+}
+
+/**
+ * Signature context used by witnesses when signing outgoing operations.
+ */
+export const WITNESS_SIGNATURE_CONTEXT = 'oasis-bridge/witness: outgoing operation';
+/**
+ * Signature context used by witnesses when signing incoming operations.
+ */
+export const RELEASE_SIGNATURE_CONTEXT = 'oasis-bridge/witness: incoming operation';
+/**
+ * Signature context used by witnesses when signing witness set updates.
+ */
+export const WITNESS_SET_UPDATE_SIGNATURE_CONTEXT = 'oasis-bridge/witness: witness set update';
+
+/**
+ * Returns the message that witnesses sign to approve a witness set update.
+ */
+export function witnessSetUpdateMessage(params: Parameters, update: UpdateWitnesses) {
+    return oasis.misc.toCBOR({
+        runtime_id: params.runtime_id ?? new Uint8Array(32),
+        chain_id: params.remote_chain_id ?? 0,
+        id: update.id,
+        witnesses: update.witnesses,
+        threshold: update.threshold,
+    });
+}
+
+/**
+ * Returns the message that witnesses sign to attest to an operation.
+ */
+export function witnessMessage(params: Parameters, id: oasis.types.longnum, op: Operation) {
+    const denomination = oasis.misc.toHex((op.lock ?? op.release).amount[1]);
+    const message: {[key: string]: unknown} = {
+        runtime_id: params.runtime_id ?? new Uint8Array(32),
+        chain_id: params.remote_chain_id ?? 0,
+        id,
+        op,
+    };
+    for (const [local, remote] of params.remote_denominations) {
+        if (oasis.misc.toHex(local) === denomination) {
+            message.remote_denomination = remote;
+        }
+    }
+    return oasis.misc.toCBOR(message);
 }
 
 export class Wrapper extends oasisRT.wrapper.Base {
@@ -126,17 +1800,119 @@ export class Wrapper extends oasisRT.wrapper.Base {
 
     callLock() { return this.call<Lock, LockResult>(METHOD_LOCK); }
     callWitness() { return this.call<Witness, void>(METHOD_WITNESS); }
+    callWitnessBatch() { return this.call<WitnessBatch, void>(METHOD_WITNESS_BATCH); }
     callRelease() { return this.call<Release, void>(METHOD_RELEASE); }
+    callRetractVote() { return this.call<RetractVote, void>(METHOD_RETRACT_VOTE); }
+    callUpdateWitnesses() { return this.call<UpdateWitnesses, void>(METHOD_UPDATE_WITNESSES); }
+    callAddWitness() { return this.call<AddWitness, void>(METHOD_ADD_WITNESS); }
+    callRemoveWitness() { return this.call<RemoveWitness, void>(METHOD_REMOVE_WITNESS); }
+    callUpdateBlocklist() { return this.call<UpdateBlocklist, void>(METHOD_UPDATE_BLOCKLIST); }
+    callAllow() { return this.call<AllowAddress, void>(METHOD_ALLOW); }
+    callDisallow() { return this.call<AllowAddress, void>(METHOD_DISALLOW); }
+    callRotateWitnessKey() { return this.call<RotateWitnessKey, void>(METHOD_ROTATE_WITNESS_KEY); }
+    callReactivateWitness() { return this.call<void, void>(METHOD_REACTIVATE_WITNESS); }
+    callSetWitnessMetadata() { return this.call<WitnessMetadata, void>(METHOD_SET_WITNESS_METADATA); }
+    callSubmitEvidence() { return this.call<SubmitEvidence, void>(METHOD_SUBMIT_EVIDENCE); }
+    callBond() { return this.call<Bond, void>(METHOD_BOND); }
+    callUnbond() { return this.call<Unbond, void>(METHOD_UNBOND); }
+    callPause() { return this.call<void, void>(METHOD_PAUSE); }
+    callResume() { return this.call<void, void>(METHOD_RESUME); }
+    callFreezeDenomination() { return this.call<FreezeDenomination, void>(METHOD_FREEZE_DENOMINATION); }
+    callUnfreezeDenomination() { return this.call<FreezeDenomination, void>(METHOD_UNFREEZE_DENOMINATION); }
+    callSetDenominationMetadata() { return this.call<SetDenominationMetadata, void>(METHOD_SET_DENOMINATION_METADATA); }
+    callClaim() { return this.call<Claim, void>(METHOD_CLAIM); }
+    callClaimBalance() { return this.call<ClaimBalance, void>(METHOD_CLAIM_BALANCE); }
+    callCancelRelease() { return this.call<CancelRelease, void>(METHOD_CANCEL_RELEASE); }
+    callRecover() { return this.call<Recover, void>(METHOD_RECOVER); }
+    callCancelRecovery() { return this.call<CancelRecovery, void>(METHOD_CANCEL_RECOVERY); }
+    callAttestRemoteLocked() { return this.call<AttestRemoteLocked, void>(METHOD_ATTEST_REMOTE_LOCKED); }
+    callSetFeeSchedule() { return this.call<SetFeeSchedule, void>(METHOD_SET_FEE_SCHEDULE); }
+    callSetRemoteContract() { return this.call<SetRemoteContract, void>(METHOD_SET_REMOTE_CONTRACT); }
+    callRegisterDenomination() { return this.call<RegisterDenomination, void>(METHOD_REGISTER_DENOMINATION); }
+    callSetWitnessSigningKey() { return this.call<SetWitnessSigningKey, void>(METHOD_SET_WITNESS_SIGNING_KEY); }
 
-    queryNextSequenceNumbers() { return this.query<void, NextSequenceNumbers>(METHOD_NEXT_SEQUENCE_NUMBERS); }
+    queryNextSequenceNumbers() { return this.query<NextSequenceNumbersQuery | null, NextSequenceNumbers>(METHOD_NEXT_SEQUENCE_NUMBERS); }
     queryParameters() { return this.query<void, Parameters>(METHOD_PARAMETERS); }
+    queryQuorumCertificate() { return this.query<QuorumCertificateQuery, QuorumCertificate>(METHOD_QUORUM_CERTIFICATE); }
+    queryOutOperation() { return this.query<OutOperationQuery, WitnessSignatures>(METHOD_OUT_OPERATION); }
+    queryInOperation() { return this.query<InOperationQuery, IncomingWitnessSignatures>(METHOD_IN_OPERATION); }
+    queryOperationStatus() { return this.query<OperationStatusQuery, OperationStatus>(METHOD_OPERATION_STATUS); }
+    querySigningProgress() { return this.query<SigningProgressQuery, SigningProgress>(METHOD_SIGNING_PROGRESS); }
+    queryRemoteContract() { return this.query<void, RemoteContract>(METHOD_REMOTE_CONTRACT); }
+    queryWitnessSigningKeys() { return this.query<void, WitnessKey[]>(METHOD_WITNESS_SIGNING_KEYS); }
+    queryWitnessSetAt() { return this.query<WitnessSetQuery, WitnessSet>(METHOD_WITNESS_SET_AT); }
+    queryWitnessLiveness() { return this.query<void, WitnessLiveness[]>(METHOD_WITNESS_LIVENESS); }
+    queryWitnesses() { return this.query<void, WitnessInfo[]>(METHOD_WITNESSES); }
+    queryWitnessBonds() { return this.query<void, (WitnessBond | null)[]>(METHOD_WITNESS_BONDS); }
+    queryVoteNonces() { return this.query<void, VoteNonces[]>(METHOD_VOTE_NONCES); }
+    querySubsidyPool() { return this.query<void, oasisRT.types.BaseUnits>(METHOD_SUBSIDY_POOL); }
+    queryPaused() { return this.query<void, boolean>(METHOD_PAUSED); }
+    queryFrozenDenominations() { return this.query<void, Uint8Array[]>(METHOD_FROZEN_DENOMINATIONS); }
+    queryPauseStatus() { return this.query<void, PauseStatus>(METHOD_PAUSE_STATUS); }
+    queryDenominationInfo() { return this.query<void, DenominationInfo[]>(METHOD_DENOMINATION_INFO); }
+    queryLocalDenomination() { return this.query<LocalDenominationQuery, Uint8Array>(METHOD_LOCAL_DENOMINATION); }
+    querySequenceStorage() { return this.query<SequenceStorageQuery | null, SequenceStorage>(METHOD_SEQUENCE_STORAGE); }
+    queryExportState() { return this.query<ExportStateQuery, StateChunk>(METHOD_EXPORT_STATE); }
+    queryRateLimitVolumes() { return this.query<void, Map<Uint8Array, Uint8Array>>(METHOD_RATE_LIMIT_VOLUMES); }
+    queryRateLimitQuota() { return this.query<RateLimitQuotaQuery | null, RateLimitQuota>(METHOD_RATE_LIMIT_QUOTA); }
+    queryClaimsRoot() { return this.query<ClaimsRootQuery, ClaimsRoot>(METHOD_CLAIMS_ROOT); }
+    queryClaimableBalances() { return this.query<ClaimableBalancesQuery, Map<Uint8Array, Uint8Array>>(METHOD_CLAIMABLE_BALANCES); }
+    queryPendingOutgoing() { return this.query<PendingOutgoingQuery, PendingOutgoing[]>(METHOD_PENDING_OUTGOING); }
+    queryPendingIncoming() { return this.query<PendingIncomingQuery, PendingIncoming[]>(METHOD_PENDING_INCOMING); }
+    queryHistory() { return this.query<HistoryQuery, HistoryEntry[]>(METHOD_HISTORY); }
+    queryReleasesFor() { return this.query<ReleasesForQuery, CompletedRelease[]>(METHOD_RELEASES_FOR); }
+    queryPendingReleases() { return this.query<void, PendingRelease[]>(METHOD_PENDING_RELEASES); }
+    queryPendingRecoveries() { return this.query<void, PendingRecovery[]>(METHOD_PENDING_RECOVERIES); }
+    queryAllowedAddresses() { return this.query<void, Uint8Array[]>(METHOD_ALLOWED_ADDRESSES); }
+    queryConflicts() { return this.query<void, Conflict[]>(METHOD_CONFLICTS); }
+    queryProcessedDeposit() { return this.query<ProcessedDepositQuery, boolean>(METHOD_PROCESSED_DEPOSIT); }
+    queryStats() { return this.query<void, Stats>(METHOD_STATS); }
+    queryTotalLocked() { return this.query<void, TotalLocked>(METHOD_TOTAL_LOCKED); }
+    queryEstimateLock() { return this.query<EstimateLockQuery, LockEstimate>(METHOD_ESTIMATE_LOCK); }
+    queryInvariantViolations() { return this.query<void, InvariantViolation[]>(METHOD_INVARIANT_VIOLATIONS); }
+    queryRemoteReserves() { return this.query<void, Map<Uint8Array, RemoteReserve>>(METHOD_REMOTE_RESERVES); }
 
 }
 
 export function moduleEventHandler(codes: {
     [EVENT_LOCK_CODE]?: oasisRT.event.Handler<LockEvent>;
     [EVENT_RELEASE_CODE]?: oasisRT.event.Handler<ReleaseEvent>;
-    [EVENT_WITNESSES_SIGNED_CODE]?: oasisRT.event.Handler<WitnessSignatures>;
+    [EVENT_WITNESSES_SIGNED_CODE]?: oasisRT.event.Handler<QuorumCertificate>;
+    [EVENT_WITNESS_SET_UPDATED_CODE]?: oasisRT.event.Handler<WitnessSetUpdatedEvent>;
+    [EVENT_WITNESS_ADDED_CODE]?: oasisRT.event.Handler<WitnessChangedEvent>;
+    [EVENT_WITNESS_REMOVED_CODE]?: oasisRT.event.Handler<WitnessChangedEvent>;
+    [EVENT_WITNESS_KEY_ROTATED_CODE]?: oasisRT.event.Handler<WitnessKeyRotatedEvent>;
+    [EVENT_WITNESS_INACTIVE_CODE]?: oasisRT.event.Handler<WitnessChangedEvent>;
+    [EVENT_WITNESS_REACTIVATED_CODE]?: oasisRT.event.Handler<WitnessChangedEvent>;
+    [EVENT_WITNESS_BONDED_CODE]?: oasisRT.event.Handler<WitnessBondedEvent>;
+    [EVENT_WITNESS_UNBONDING_CODE]?: oasisRT.event.Handler<WitnessUnbondingEvent>;
+    [EVENT_SLASHED_CODE]?: oasisRT.event.Handler<SlashedEvent>;
+    [EVENT_WITNESS_JAILED_CODE]?: oasisRT.event.Handler<WitnessJailedEvent>;
+    [EVENT_WITNESS_REWARDED_CODE]?: oasisRT.event.Handler<WitnessRewardedEvent>;
+    [EVENT_VOTE_RETRACTED_CODE]?: oasisRT.event.Handler<VoteRetractedEvent>;
+    [EVENT_WITNESS_FEE_PAID_CODE]?: oasisRT.event.Handler<WitnessFeePaidEvent>;
+    [EVENT_FEE_SCHEDULE_CHANGED_CODE]?: oasisRT.event.Handler<FeeScheduleChangedEvent>;
+    [EVENT_REMOTE_LOCKED_ATTESTED_CODE]?: oasisRT.event.Handler<RemoteLockedAttestedEvent>;
+    [EVENT_PAUSED_CODE]?: oasisRT.event.Handler<PausedEvent>;
+    [EVENT_RESUMED_CODE]?: oasisRT.event.Handler<PausedEvent>;
+    [EVENT_DENOMINATION_FROZEN_CODE]?: oasisRT.event.Handler<DenominationFrozenEvent>;
+    [EVENT_DENOMINATION_UNFROZEN_CODE]?: oasisRT.event.Handler<DenominationFrozenEvent>;
+    [EVENT_CIRCUIT_BREAKER_TRIPPED_CODE]?: oasisRT.event.Handler<CircuitBreakerTrippedEvent>;
+    [EVENT_RELEASE_QUEUED_CODE]?: oasisRT.event.Handler<ReleaseQueuedEvent>;
+    [EVENT_RELEASE_CANCELLED_CODE]?: oasisRT.event.Handler<ReleaseCancelledEvent>;
+    [EVENT_ADDRESS_ALLOWED_CODE]?: oasisRT.event.Handler<AddressAllowedEvent>;
+    [EVENT_ADDRESS_DISALLOWED_CODE]?: oasisRT.event.Handler<AddressAllowedEvent>;
+    [EVENT_DIVERGENCE_DETECTED_CODE]?: oasisRT.event.Handler<Conflict>;
+    [EVENT_RECOVERY_SCHEDULED_CODE]?: oasisRT.event.Handler<RecoveryScheduledEvent>;
+    [EVENT_FUNDS_RECOVERED_CODE]?: oasisRT.event.Handler<FundsRecoveredEvent>;
+    [EVENT_RECOVERY_CANCELLED_CODE]?: oasisRT.event.Handler<RecoveryCancelledEvent>;
+    [EVENT_CLAIMED_CODE]?: oasisRT.event.Handler<ClaimedEvent>;
+    [EVENT_OPERATION_EXPIRED_CODE]?: oasisRT.event.Handler<OperationExpiredEvent>;
+    [EVENT_STALE_VOTES_CLEARED_CODE]?: oasisRT.event.Handler<StaleVotesClearedEvent>;
+    [EVENT_BALANCE_CLAIMED_CODE]?: oasisRT.event.Handler<BalanceClaimedEvent>;
+    [EVENT_REMOTE_CONTRACT_CHANGED_CODE]?: oasisRT.event.Handler<RemoteContractChangedEvent>;
+    [EVENT_DENOMINATION_REGISTERED_CODE]?: oasisRT.event.Handler<DenominationRegisteredEvent>;
+    [EVENT_PENDING_RELEASE_FAILED_CODE]?: oasisRT.event.Handler<PendingReleaseFailedEvent>;
 }) {
     return [MODULE_NAME, codes] as oasisRT.event.ModuleHandler;
 }
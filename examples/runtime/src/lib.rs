@@ -13,8 +13,12 @@ impl sdk::Runtime for Runtime {
 
     type Modules = (
         modules::core::Module,
-        modules::accounts::Module,
+        // The bridge comes before the accounts module so that it can cover the fees of witness
+        // transactions before they are charged.
         oasis_module_bridge::Module<modules::accounts::Module>,
+        modules::accounts::Module,
+        modules::consensus::Module,
+        modules::consensus_accounts::Module<modules::accounts::Module, modules::consensus::Module>,
     );
 
     fn genesis_state() -> <Self::Modules as sdk::module::MigrationHandler>::Genesis {
@@ -27,6 +31,31 @@ impl sdk::Runtime for Runtime {
                     ..Default::default()
                 },
             },
+            oasis_module_bridge::Genesis {
+                parameters: oasis_module_bridge::Parameters {
+                    local_denominations: {
+                        let mut ld = BTreeSet::new();
+                        ld.insert(Denomination::NATIVE);
+                        ld
+                    },
+                    remote_denominations: {
+                        let mut rd = BTreeMap::new();
+                        rd.insert(
+                            "oETH".parse().unwrap(),
+                            "0000000000000000000000000000000000000000000000000000000000000000"
+                                .into(),
+                        );
+                        rd
+                    },
+                    witnesses: vec![
+                        sdk::testing::keys::bob::pk().into(),
+                        sdk::testing::keys::charlie::pk().into(),
+                        sdk::testing::keys::dave::pk().into(),
+                    ],
+                    threshold: 2,
+                    ..Default::default()
+                },
+            },
             modules::accounts::Genesis {
                 balances: {
                     let mut balances = BTreeMap::new();
@@ -57,30 +86,14 @@ impl sdk::Runtime for Runtime {
                 },
                 ..Default::default()
             },
-            oasis_module_bridge::Genesis {
-                parameters: oasis_module_bridge::Parameters {
-                    local_denominations: {
-                        let mut ld = BTreeSet::new();
-                        ld.insert(Denomination::NATIVE);
-                        ld
-                    },
-                    remote_denominations: {
-                        let mut rd = BTreeMap::new();
-                        rd.insert(
-                            "oETH".parse().unwrap(),
-                            "0000000000000000000000000000000000000000000000000000000000000000"
-                                .into(),
-                        );
-                        rd
-                    },
-                    witnesses: vec![
-                        sdk::testing::keys::bob::pk(),
-                        sdk::testing::keys::charlie::pk(),
-                        sdk::testing::keys::dave::pk(),
-                    ],
-                    threshold: 2,
+            modules::consensus::Genesis {
+                parameters: modules::consensus::Parameters {
+                    // Consensus layer tokens are deposited as the native denomination which is
+                    // also a local denomination of the bridge.
+                    consensus_denomination: Denomination::NATIVE,
                 },
             },
+            Default::default(),
         )
     }
 }
@@ -0,0 +1,113 @@
+package main
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/oasisprotocol/oasis-core/go/common"
+	coreSignature "github.com/oasisprotocol/oasis-core/go/common/crypto/signature"
+	"github.com/oasisprotocol/oasis-core/go/common/quantity"
+	consensus "github.com/oasisprotocol/oasis-core/go/consensus/api"
+	staking "github.com/oasisprotocol/oasis-core/go/staking/api"
+
+	"github.com/oasisprotocol/oasis-sdk/client-sdk/go/client"
+	"github.com/oasisprotocol/oasis-sdk/client-sdk/go/crypto/signature"
+	"github.com/oasisprotocol/oasis-sdk/client-sdk/go/types"
+)
+
+// Deposit is the body of the consensus.Deposit call.
+type Deposit struct {
+	Amount types.BaseUnits `json:"amount"`
+}
+
+// depositFromConsensus moves the given amount of consensus layer tokens into the runtime
+// account of the signer, so that they can afterwards be locked through the bridge.
+func depositFromConsensus(
+	ctx context.Context,
+	rc *Client,
+	cons consensus.ClientBackend,
+	runtimeID common.Namespace,
+	chainContext signature.Context,
+	consensusSigner coreSignature.Signer,
+	signer signature.Signer,
+	amount uint64,
+) error {
+	logger := logger.With("side", "user")
+	address := types.NewAddress(signer.Public())
+
+	// Subscribe to blocks.
+	blkCh, blkSub, err := rc.WatchBlocks(ctx)
+	if err != nil {
+		return fmt.Errorf("failed to subscribe to runtime blocks: %w", err)
+	}
+	defer blkSub.Close()
+
+	// Get the initial runtime balance.
+	balances, err := rc.Accounts.Balances(ctx, client.RoundLatest, address)
+	if err != nil {
+		return fmt.Errorf("failed to fetch account balances: %w", err)
+	}
+	initial := balances.Balances[types.NativeDenomination]
+
+	// Allow the runtime to withdraw from the consensus account.
+	logger.Info("submitting consensus allow transaction")
+	tx := staking.NewAllowTx(0, nil, &staking.Allow{
+		Beneficiary:  staking.NewRuntimeAddress(runtimeID),
+		AmountChange: *quantity.NewFromUint64(amount),
+	})
+	if err = consensus.SignAndSubmitTx(ctx, cons, consensusSigner, tx); err != nil {
+		return fmt.Errorf("failed to submit allow transaction: %w", err)
+	}
+
+	// Get nonce.
+	nonce, err := rc.Accounts.Nonce(ctx, client.RoundLatest, address)
+	if err != nil {
+		return fmt.Errorf("failed to fetch account nonce: %w", err)
+	}
+
+	// Submit Deposit.
+	logger.Info("submitting deposit transaction")
+	rtx := types.NewTransaction(nil, "consensus.Deposit", Deposit{
+		Amount: types.NewBaseUnits(*quantity.NewFromUint64(amount), types.NativeDenomination),
+	})
+	rtx.AppendAuthSignature(signer.Public(), nonce)
+	tb := rtx.PrepareForSigning()
+	if err = tb.AppendSign(chainContext, signer); err != nil {
+		return fmt.Errorf("failed to sign deposit transaction: %w", err)
+	}
+	if _, err = rc.SubmitTx(ctx, tb.UnverifiedTransaction()); err != nil {
+		return fmt.Errorf("failed to submit deposit transaction: %w", err)
+	}
+
+	// The deposit only completes after the consensus layer has processed the withdrawal, so
+	// wait for the runtime balance to reflect it.
+	expected := initial.Clone()
+	if err = expected.Add(quantity.NewFromUint64(amount)); err != nil {
+		return fmt.Errorf("failed to compute expected balance: %w", err)
+	}
+	for {
+		select {
+		case <-ctx.Done():
+			return ctx.Err()
+		case blk, ok := <-blkCh:
+			if !ok {
+				return fmt.Errorf("block subscription closed")
+			}
+			logger.Debug("seen new block",
+				"round", blk.Block.Header.Round,
+			)
+
+			balances, err = rc.Accounts.Balances(ctx, blk.Block.Header.Round, address)
+			if err != nil {
+				return fmt.Errorf("failed to fetch account balances: %w", err)
+			}
+			balance := balances.Balances[types.NativeDenomination]
+			if balance.Cmp(expected) >= 0 {
+				logger.Info("deposit successful",
+					"balance", balance,
+				)
+				return nil
+			}
+		}
+	}
+}
@@ -12,13 +12,18 @@ import (
 
 	"github.com/oasisprotocol/oasis-core/go/common"
 	"github.com/oasisprotocol/oasis-core/go/common/cbor"
+	"github.com/oasisprotocol/oasis-core/go/common/crypto/hash"
+	coreSignature "github.com/oasisprotocol/oasis-core/go/common/crypto/signature"
+	"github.com/oasisprotocol/oasis-core/go/common/entity"
 	cmnGrpc "github.com/oasisprotocol/oasis-core/go/common/grpc"
 	"github.com/oasisprotocol/oasis-core/go/common/logging"
 	"github.com/oasisprotocol/oasis-core/go/common/quantity"
+	consensus "github.com/oasisprotocol/oasis-core/go/consensus/api"
 
 	sdk "github.com/oasisprotocol/oasis-sdk/client-sdk/go"
 	"github.com/oasisprotocol/oasis-sdk/client-sdk/go/client"
 	"github.com/oasisprotocol/oasis-sdk/client-sdk/go/crypto/signature"
+	"github.com/oasisprotocol/oasis-sdk/client-sdk/go/crypto/signature/ed25519"
 	"github.com/oasisprotocol/oasis-sdk/client-sdk/go/modules/accounts"
 	"github.com/oasisprotocol/oasis-sdk/client-sdk/go/testing"
 	"github.com/oasisprotocol/oasis-sdk/client-sdk/go/types"
@@ -47,6 +52,10 @@ func getEnvVarOrExit(name string) string {
 	return value
 }
 
+// lockAmount is the amount of native tokens that the user deposits from the consensus layer and
+// then locks through the bridge.
+const lockAmount = 10
+
 // TODO: Move these somewhere bridge-module specific.
 
 const remoteAddressSize = 20
@@ -75,8 +84,16 @@ func NewRemoteAddressFromHex(text string) RemoteAddress {
 
 // Lock is the body of the Lock call.
 type Lock struct {
-	Target RemoteAddress   `json:"target"`
-	Amount types.BaseUnits `json:"amount"`
+	Target        RemoteAddress    `json:"target"`
+	Amount        types.BaseUnits  `json:"amount"`
+	RemoteChainID *uint64          `json:"remote_chain_id,omitempty"`
+	Attestation   *LockAttestation `json:"attestation,omitempty"`
+}
+
+// LockAttestation is an attestation by the lock attester that an address may lock funds.
+type LockAttestation struct {
+	Expiration uint64 `json:"expiration"`
+	Signature  []byte `json:"sig"`
 }
 
 // LockResult is the result of a Lock method call.
@@ -86,23 +103,46 @@ type LockResult struct {
 
 // Witness is the body of a Witness call.
 type Witness struct {
-	ID        uint64 `json:"id"`
-	Signature []byte `json:"sig"`
+	Denomination types.Denomination `json:"denomination"`
+	ID           uint64             `json:"id"`
+	Signature    []byte             `json:"sig"`
+	Nonce        uint64             `json:"nonce"`
 }
 
 // Release is the body of a Release call.
 type Release struct {
-	ID     uint64          `json:"id"`
+	ID            uint64          `json:"id"`
+	Target        types.Address   `json:"target"`
+	Amount        types.BaseUnits `json:"amount"`
+	TxHash        *hash.Hash      `json:"tx_hash,omitempty"`
+	LogIndex      *uint64         `json:"log_index,omitempty"`
+	Batch         []ReleaseEntry  `json:"batch,omitempty"`
+	ClaimsRoot    *hash.Hash      `json:"claims_root,omitempty"`
+	RemoteChainID *uint64         `json:"remote_chain_id,omitempty"`
+	Signature     []byte          `json:"sig,omitempty"`
+	Nonce         *uint64         `json:"nonce,omitempty"`
+}
+
+// ReleaseEntry is a single transfer of a batch release.
+type ReleaseEntry struct {
 	Target types.Address   `json:"target"`
 	Amount types.BaseUnits `json:"amount"`
 }
 
+// VoteNonces are the next expected vote nonces of a witness.
+type VoteNonces struct {
+	Outgoing uint64 `json:"out"`
+	Incoming uint64 `json:"in"`
+}
+
 // LockEvent is a lock event.
 type LockEvent struct {
-	ID     uint64          `json:"id"`
-	Owner  types.Address   `json:"owner"`
-	Target RemoteAddress   `json:"target"`
-	Amount types.BaseUnits `json:"amount"`
+	ID            uint64          `json:"id"`
+	RemoteChainID uint64          `json:"remote_chain_id"`
+	Owner         types.Address   `json:"owner"`
+	Target        RemoteAddress   `json:"target"`
+	Amount        types.BaseUnits `json:"amount"`
+	Fee           types.BaseUnits `json:"fee"`
 }
 
 // LockEventKey is the key used for lock events.
@@ -110,9 +150,12 @@ var LockEventKey = sdk.NewEventKey("bridge", 1)
 
 // ReleaseEvent is the release event.
 type ReleaseEvent struct {
-	ID     uint64          `json:"id"`
-	Target types.Address   `json:"target"`
-	Amount types.BaseUnits `json:"amount"`
+	ID            uint64          `json:"id"`
+	RemoteChainID uint64          `json:"remote_chain_id"`
+	Target        types.Address   `json:"target"`
+	Amount        types.BaseUnits `json:"amount"`
+	TxHash        *hash.Hash      `json:"tx_hash,omitempty"`
+	LogIndex      *uint64         `json:"log_index,omitempty"`
 }
 
 // ReleaseEventKey is the key used for release events.
@@ -124,21 +167,77 @@ type Operation struct {
 	Release *Release `json:"release,omitempty"`
 }
 
-// WitnessesSignedEvent is the witnesses signed event.
+// witnessSignatureContext is the signature context used by witnesses when signing outgoing
+// operations.
+const witnessSignatureContext = "oasis-bridge/witness: outgoing operation"
+
+// witnessMessage is the message that witnesses sign to attest to an outgoing operation.
+type witnessMessage struct {
+	RuntimeID          common.Namespace   `json:"runtime_id"`
+	ChainID            uint64             `json:"chain_id"`
+	RemoteContract     *RemoteAddress     `json:"remote_contract,omitempty"`
+	RemoteDenomination RemoteDenomination `json:"remote_denomination,omitempty"`
+	ID                 uint64             `json:"id"`
+	Op                 Operation          `json:"op"`
+}
+
+// releaseSignatureContext is the signature context used by witnesses when signing incoming
+// operations.
+const releaseSignatureContext = "oasis-bridge/witness: incoming operation"
+
+// signRelease signs the given release using the witness signer.
+func signRelease(params *Parameters, signer signature.Signer, release *Release) error {
+	release.Signature = nil
+	// The nonce is not part of the signed operation.
+	op := *release
+	op.Nonce = nil
+	msg := cbor.Marshal(witnessMessage{
+		RuntimeID:          params.RuntimeID,
+		ChainID:            params.RemoteChainID,
+		RemoteContract:     params.RemoteContract,
+		RemoteDenomination: params.RemoteDenominations[release.Amount.Denomination],
+		ID:                 release.ID,
+		Op:                 Operation{Release: &op},
+	})
+	sig, err := signer.ContextSign(signature.Context(releaseSignatureContext), msg)
+	if err != nil {
+		return err
+	}
+	release.Signature = sig
+	return nil
+}
+
+// WitnessesSignedEvent is the witnesses signed event, carrying the quorum
+// certificate of an outgoing operation.
 type WitnessesSignedEvent struct {
-	ID         uint64    `json:"id"`
-	Op         Operation `json:"op"`
-	Witnesses  []uint16  `json:"wits,omitempty"`
+	ID         uint64              `json:"id"`
+	Op         Operation           `json:"op"`
+	WitnessSet hash.Hash           `json:"witness_set"`
+	Signers    []byte              `json:"signers"`
+	Signatures [][]byte            `json:"sigs"`
+	Handover   *HandoverSignatures `json:"handover,omitempty"`
+}
+
+// HandoverSignatures are signatures by the successor witness set during a witness set handover.
+type HandoverSignatures struct {
+	WitnessSet hash.Hash `json:"witness_set"`
+	Signers    []byte    `json:"signers,omitempty"`
 	Signatures [][]byte  `json:"sigs,omitempty"`
 }
 
 // WitnessesSignedEventKey is the key used for witnesses signed events.
 var WitnessesSignedEventKey = sdk.NewEventKey("bridge", 3)
 
-// NextSequenceNumbers are the next sequence numbers.
+// NextSequenceNumbers are the next sequence numbers. Incoming and outgoing operations are
+// numbered independently for each remote chain and denomination.
 type NextSequenceNumbers struct {
-	Incoming uint64 `json:"in"`
-	Outgoing uint64 `json:"out"`
+	RemoteChainID             uint64                        `json:"remote_chain_id"`
+	Incoming                  map[types.Denomination]uint64 `json:"in,omitempty"`
+	Outgoing                  map[types.Denomination]uint64 `json:"out,omitempty"`
+	WitnessSetUpdates         uint64                        `json:"witness_set_updates"`
+	FeeScheduleUpdates        uint64                        `json:"fee_schedule_updates"`
+	RemoteContractUpdates     uint64                        `json:"remote_contract_updates"`
+	DenominationRegistrations uint64                        `json:"denomination_registrations"`
 }
 
 // RemoteDenomination is a remote denomination.
@@ -149,19 +248,267 @@ func (rd RemoteDenomination) String() string {
 	return hex.EncodeToString([]byte(rd))
 }
 
+// MultisigSigner is a signer of a multisig witness.
+type MultisigSigner struct {
+	PublicKey types.PublicKey `json:"public_key"`
+	Weight    uint64          `json:"weight"`
+}
+
+// MultisigConfig is the configuration of a multisig witness.
+type MultisigConfig struct {
+	Signers   []MultisigSigner `json:"signers"`
+	Threshold uint64           `json:"threshold"`
+}
+
+// WitnessKey is a witness in the witness set, either a single public key or a
+// multisig configuration.
+type WitnessKey struct {
+	PublicKey *types.PublicKey
+	Multisig  *MultisigConfig
+}
+
+// MarshalCBOR encodes the witness the same way as the bridge module.
+func (w WitnessKey) MarshalCBOR() ([]byte, error) {
+	if w.Multisig != nil {
+		return cbor.Marshal(w.Multisig), nil
+	}
+	return cbor.Marshal(w.PublicKey), nil
+}
+
+// UnmarshalCBOR decodes either a single public key or a multisig configuration.
+func (w *WitnessKey) UnmarshalCBOR(data []byte) error {
+	var cfg MultisigConfig
+	if err := cbor.Unmarshal(data, &cfg); err == nil && cfg.Signers != nil {
+		w.Multisig = &cfg
+		return nil
+	}
+	var pk types.PublicKey
+	if err := cbor.Unmarshal(data, &pk); err != nil {
+		return err
+	}
+	w.PublicKey = &pk
+	return nil
+}
+
+// String returns a string representation of the witness.
+func (w WitnessKey) String() string {
+	if w.Multisig != nil {
+		return fmt.Sprintf("multisig with %d signers and threshold %d", len(w.Multisig.Signers), w.Multisig.Threshold)
+	}
+	return fmt.Sprintf("%s", w.PublicKey)
+}
+
 // Parameters are the bridge module parameters.
 type Parameters struct {
-	// Witnesses is a list of authorized witness public keys.
-	Witnesses []types.PublicKey `json:"witnesses"`
+	// Witnesses is a list of authorized witnesses.
+	Witnesses []WitnessKey `json:"witnesses"`
 
-	// Threshold is the number of witnesses that needs to sign off.
+	// Threshold is the number of witnesses that needs to sign off. When a threshold policy is
+	// set, this is the minimum threshold.
 	Threshold uint64 `json:"threshold"`
 
+	// ThresholdPolicy is the policy for deriving the threshold from the number of active
+	// witnesses, if any.
+	ThresholdPolicy *ThresholdPolicy `json:"threshold_policy,omitempty"`
+
 	// LocalDenominations are the denominations local to this side of the bridge.
 	LocalDenominations []types.Denomination `json:"local_denominations"`
 
 	// RemoteDenominations are the denominations that exist on the remote side of the bridge.
 	RemoteDenominations map[types.Denomination]RemoteDenomination `json:"remote_denominations"`
+
+	// RuntimeID is the identifier of the runtime the bridge is deployed in.
+	RuntimeID common.Namespace `json:"runtime_id"`
+
+	// RemoteChainID is the identifier of the remote chain.
+	RemoteChainID uint64 `json:"remote_chain_id"`
+
+	// RemoteContract is the address of the bridge contract on the remote chain, if any.
+	RemoteContract *RemoteAddress `json:"remote_contract,omitempty"`
+
+	// MessageEncoding is the encoding of the messages that witnesses sign for outgoing operations.
+	MessageEncoding string `json:"message_encoding"`
+
+	// EIP712Domain is the EIP-712 domain used for hashing outgoing operations.
+	EIP712Domain *EIP712Domain `json:"eip712_domain,omitempty"`
+
+	// Admin is the address that is allowed to add and remove witnesses and to change the fee
+	// schedule, if any.
+	Admin *types.Address `json:"admin,omitempty"`
+
+	// Guardians are the addresses that are allowed to pause and resume the bridge.
+	Guardians []types.Address `json:"guardians,omitempty"`
+
+	// LivenessWindow is the number of most recent outgoing operations over which witness
+	// participation is reported.
+	LivenessWindow uint64 `json:"liveness_window"`
+
+	// MaxMissedOperations is the number of consecutive outgoing operations that a witness can miss
+	// before it is marked inactive.
+	MaxMissedOperations uint64 `json:"max_missed_operations"`
+
+	// HandoverWindow is the number of rounds after a witness set change during which pending
+	// outgoing operations also accept signatures from the new witness set.
+	HandoverWindow uint64 `json:"handover_window"`
+
+	// WitnessBond is the amount that witnesses need to bond before their signatures and votes
+	// are accepted, if any.
+	WitnessBond *types.BaseUnits `json:"witness_bond,omitempty"`
+
+	// UnbondingDelay is the number of rounds after which unbonded amounts can be withdrawn.
+	UnbondingDelay uint64 `json:"unbonding_delay"`
+
+	// DivergencePenalty is the portion of the bond, in basis points, that is slashed from
+	// witnesses that vote for an incoming operation other than the finalized one.
+	DivergencePenalty uint64 `json:"divergence_penalty"`
+
+	// EquivocationPenalty is the portion of the bond, in basis points, that is slashed from
+	// witnesses for which evidence of signing two different operations for the same sequence
+	// number is submitted.
+	EquivocationPenalty uint64 `json:"equivocation_penalty"`
+
+	// JailDuration is the number of rounds during which a witness for which evidence of
+	// misbehavior is submitted cannot reactivate itself.
+	JailDuration uint64 `json:"jail_duration"`
+
+	// RewardPeriod is the number of rounds between distributions of the reward pool to the
+	// witnesses. Zero disables rewards.
+	RewardPeriod uint64 `json:"reward_period"`
+
+	// LockFee is the fee, in basis points of the locked amount, that is deducted from each lock
+	// and credited to the bridge fee account.
+	LockFee uint64 `json:"lock_fee"`
+
+	// MinLockFee is the minimum lock fee in base units of the locked denomination.
+	MinLockFee quantity.Quantity `json:"min_lock_fee"`
+
+	// MaxLockFee is the maximum lock fee in base units of the locked denomination. Zero means no
+	// maximum.
+	MaxLockFee quantity.Quantity `json:"max_lock_fee"`
+
+	// SubsidyFee is the flat fee in the native denomination that is charged on each lock and
+	// deposited into the subsidy pool, from which witnesses are reimbursed for transaction fees.
+	SubsidyFee quantity.Quantity `json:"subsidy_fee"`
+
+	// MinLockAmounts are the minimum amounts per denomination that can be locked.
+	MinLockAmounts map[types.Denomination]quantity.Quantity `json:"min_lock_amounts,omitempty"`
+
+	// MaxTransferAmounts are the maximum amounts per denomination that can be locked or released
+	// in a single operation.
+	MaxTransferAmounts map[types.Denomination]quantity.Quantity `json:"max_transfer_amounts,omitempty"`
+
+	// DecimalScales are the numbers of local and remote decimals per denomination whose amounts
+	// are scaled when crossing the bridge.
+	DecimalScales map[types.Denomination]DecimalScale `json:"decimal_scales,omitempty"`
+
+	// RateLimitWindow is the number of rounds in a rate limit window. Zero disables rate limits.
+	RateLimitWindow uint64 `json:"rate_limit_window"`
+
+	// RateLimits are the maximum amounts per denomination that can be locked and released in
+	// total during a rate limit window.
+	RateLimits map[types.Denomination]quantity.Quantity `json:"rate_limits,omitempty"`
+
+	// AccountLimits are the maximum amounts per denomination that a single account can lock, or
+	// receive through releases, in total during a rate limit window.
+	AccountLimits map[types.Denomination]quantity.Quantity `json:"account_limits,omitempty"`
+
+	// ReleaseBaselines are the expected amounts per denomination that are released during a
+	// rate limit window.
+	ReleaseBaselines map[types.Denomination]quantity.Quantity `json:"release_baselines,omitempty"`
+
+	// CircuitBreakerMultiplier is the multiple of the release baseline at which the bridge is
+	// paused automatically until a guardian resumes it. Zero disables the circuit breaker.
+	CircuitBreakerMultiplier uint64 `json:"circuit_breaker_multiplier"`
+
+	// ChallengePeriod is the number of rounds during which guardians can cancel large releases
+	// before they are executed. Zero disables the challenge period.
+	ChallengePeriod uint64 `json:"challenge_period"`
+
+	// ChallengeThresholds are the amounts per denomination above which releases are subject to
+	// the challenge period.
+	ChallengeThresholds map[types.Denomination]quantity.Quantity `json:"challenge_thresholds,omitempty"`
+
+	// BlockedAddresses are the local addresses that cannot lock funds or receive released funds.
+	BlockedAddresses []types.Address `json:"blocked_addresses,omitempty"`
+
+	// BlockedRemoteAddresses are the remote addresses that funds cannot be locked for.
+	BlockedRemoteAddresses []RemoteAddress `json:"blocked_remote_addresses,omitempty"`
+
+	// LockAllowlist specifies whether only addresses on the allowlist managed by the admin can
+	// lock funds.
+	LockAllowlist bool `json:"lock_allowlist"`
+
+	// LockAttester is the key that must attest to the caller address of each lock, if any.
+	LockAttester *types.PublicKey `json:"lock_attester,omitempty"`
+
+	// ProcessedDepositRetention is the number of rounds for which the hashes of processed remote
+	// transactions are remembered at least. Zero remembers them forever.
+	ProcessedDepositRetention uint64 `json:"processed_deposit_retention"`
+
+	// RecoveryDelay is the number of rounds after which funds that the admin recovers from the
+	// locked funds account are transferred, during which guardians can cancel the recovery. Zero
+	// disables recoveries.
+	RecoveryDelay uint64 `json:"recovery_delay"`
+
+	// MaxVotesPerBlock is the maximum number of Witness and Release votes that a single account
+	// can submit per block. Zero means no limit.
+	MaxVotesPerBlock uint64 `json:"max_votes_per_block"`
+
+	// ReleaseWindow is the number of incoming sequence numbers ahead of the next expected one
+	// that witnesses can vote on. Zero only accepts the next one.
+	ReleaseWindow uint64 `json:"release_window"`
+
+	// MaxReleaseBatchSize is the maximum number of further transfers that a single release can
+	// batch together with its primary one. Zero disables batch releases.
+	MaxReleaseBatchSize uint64 `json:"max_release_batch_size"`
+
+	// OperationExpiry is the number of rounds after which outgoing operations that did not
+	// gather enough witness signatures expire. Their funds stay locked. Zero disables expiry.
+	OperationExpiry uint64 `json:"operation_expiry"`
+
+	// StaleVoteAge is the number of rounds after the first vote for an incoming sequence number
+	// after which its votes are cleared if the operation has not been finalized. Zero disables
+	// clearing stale votes.
+	StaleVoteAge uint64 `json:"stale_vote_age"`
+
+	// ResetStaleVotes is whether cleared stale votes are treated as retracted, so that witnesses
+	// can vote for a different operation without it counting as equivocation.
+	ResetStaleVotes bool `json:"reset_stale_votes"`
+
+	// MaxPendingLocks is the maximum number of outgoing operations that can be pending
+	// finalization at the same time. Zero means no limit.
+	MaxPendingLocks uint64 `json:"max_pending_locks"`
+
+	// PullReleases is whether finalized releases credit the claimable balances of their targets,
+	// which they withdraw with bridge.ClaimBalance, instead of transferring the funds to them.
+	PullReleases bool `json:"pull_releases"`
+
+	// HistoryRetention is the number of rounds for which finalized operations are kept in the
+	// operation history at least. Zero disables the operation history.
+	HistoryRetention uint64 `json:"history_retention"`
+
+	// RemoteAddressSize is the size in bytes of addresses on the remote chain, which lock targets
+	// must have. Zero means 20-byte Ethereum addresses.
+	RemoteAddressSize uint64 `json:"remote_address_size"`
+}
+
+// ThresholdPolicy is a threshold given as a fraction of the number of active witnesses.
+type ThresholdPolicy struct {
+	Numerator   uint64 `json:"numerator"`
+	Denominator uint64 `json:"denominator"`
+	Extra       uint64 `json:"extra,omitempty"`
+}
+
+// DecimalScale is the number of decimals that local and remote amounts of a denomination are
+// expressed with.
+type DecimalScale struct {
+	Local  uint8 `json:"local,omitempty"`
+	Remote uint8 `json:"remote,omitempty"`
+}
+
+// EIP712Domain is the EIP-712 signing domain of the remote bridge contract.
+type EIP712Domain struct {
+	VerifyingContract RemoteAddress `json:"verifying_contract"`
 }
 
 // Client is a bridge runtime client.
@@ -176,7 +523,10 @@ func user(
 	ctx context.Context,
 	wg *sync.WaitGroup,
 	rc *Client,
+	cons consensus.ClientBackend,
+	runtimeID common.Namespace,
 	chainContext signature.Context,
+	consensusSigner coreSignature.Signer,
 	signer signature.Signer,
 ) {
 	logger := logger.With("side", "user")
@@ -186,6 +536,14 @@ func user(
 		wg.Done()
 	}()
 
+	// Deposit funds from the consensus layer.
+	if err := depositFromConsensus(ctx, rc, cons, runtimeID, chainContext, consensusSigner, signer, lockAmount); err != nil {
+		logger.Error("failed to deposit from consensus",
+			"err", err,
+		)
+		return
+	}
+
 	// Subscribe to blocks.
 	blkCh, blkSub, err := rc.WatchBlocks(ctx)
 	if err != nil {
@@ -209,7 +567,7 @@ func user(
 	logger.Info("submitting lock transaction")
 	tx := types.NewTransaction(nil, "bridge.Lock", Lock{
 		Target: NewRemoteAddressFromHex("0000000000000000000000000000000000000000"),
-		Amount: types.NewBaseUnits(*quantity.NewFromUint64(10), types.NativeDenomination),
+		Amount: types.NewBaseUnits(*quantity.NewFromUint64(lockAmount), types.NativeDenomination),
 	})
 	tx.AppendAuthSignature(signer.Public(), nonce)
 	tb := tx.PrepareForSigning()
@@ -354,6 +712,19 @@ func witness(
 		wg.Done()
 	}()
 
+	// Fetch parameters as witness signatures commit to the bridge instance.
+	var params Parameters
+	if err := rc.Query(ctx, client.RoundLatest, "bridge.Parameters", nil, &params); err != nil {
+		logger.Error("failed to query bridge parameters",
+			"err", err,
+		)
+		return
+	}
+	if params.EIP712Domain != nil || params.MessageEncoding != "cbor" {
+		logger.Error("only CBOR-encoded witness messages are supported by this example")
+		return
+	}
+
 	// Subscribe to blocks.
 	blkCh, blkSub, err := rc.WatchBlocks(ctx)
 	if err != nil {
@@ -366,6 +737,9 @@ func witness(
 
 	var lastUser types.Address
 
+	// Vote nonces start at zero, as the witness has not voted on anything yet.
+	var voteNonces VoteNonces
+
 	// TODO: Logic for persisting at which block we left off and back-processing any missed events.
 WitnessAnEvent:
 	for {
@@ -426,8 +800,21 @@ WitnessAnEvent:
 
 			// Submit bridge.Witness transactions.
 			for _, ev := range lockEvents {
-				// TODO: Sign the event using witness key.
-				evSignature := []byte("signature:" + signer.Public().String())
+				msg := cbor.Marshal(witnessMessage{
+					RuntimeID:          params.RuntimeID,
+					ChainID:            params.RemoteChainID,
+					RemoteContract:     params.RemoteContract,
+					RemoteDenomination: params.RemoteDenominations[ev.Amount.Denomination],
+					ID:                 ev.ID,
+					Op:                 Operation{Lock: &Lock{Target: ev.Target, Amount: ev.Amount}},
+				})
+				evSignature, err := signer.ContextSign(signature.Context(witnessSignatureContext), msg)
+				if err != nil {
+					logger.Error("failed to sign lock event",
+						"err", err,
+					)
+					return
+				}
 
 				logger.Info("submitting witness transaction",
 					"id", ev.ID,
@@ -443,8 +830,10 @@ WitnessAnEvent:
 				}
 
 				tx := types.NewTransaction(nil, "bridge.Witness", Witness{
-					ID:        ev.ID,
-					Signature: evSignature,
+					Denomination: ev.Amount.Denomination,
+					ID:           ev.ID,
+					Signature:    evSignature,
+					Nonce:        voteNonces.Outgoing,
 				})
 				tx.AppendAuthSignature(signer.Public(), nonce)
 				tb := tx.PrepareForSigning()
@@ -463,6 +852,7 @@ WitnessAnEvent:
 				}
 
 				lastUser = ev.Owner
+				voteNonces.Outgoing++
 			}
 
 			logger.Info("successfully witnessed events")
@@ -495,11 +885,20 @@ WitnessAnEvent:
 		return
 	}
 
-	tx := types.NewTransaction(nil, "bridge.Release", Release{
-		ID:     sequences.Incoming,
+	incomingNonce := voteNonces.Incoming
+	release := Release{
+		ID:     sequences.Incoming[types.NativeDenomination],
 		Target: lastUser,
 		Amount: types.NewBaseUnits(*quantity.NewFromUint64(10), types.NativeDenomination),
-	})
+		Nonce:  &incomingNonce,
+	}
+	if err = signRelease(&params, signer, &release); err != nil {
+		logger.Error("failed to sign release",
+			"err", err,
+		)
+		return
+	}
+	tx := types.NewTransaction(nil, "bridge.Release", release)
 	tx.AppendAuthSignature(signer.Public(), nonce)
 	tb := tx.PrepareForSigning()
 	if err = tb.AppendSign(chainContext, signer); err != nil {
@@ -517,6 +916,7 @@ WitnessAnEvent:
 	}
 
 	logger.Info("release successful")
+	voteNonces.Incoming++
 
 	// Make sure all witnesses release before proceeding to make sure the bridge is ready for the
 	// next release (e.g., the sequence number is incremented).
@@ -535,11 +935,20 @@ WitnessAnEvent:
 		return
 	}
 
-	tx = types.NewTransaction(nil, "bridge.Release", Release{
-		ID:     sequences.Incoming + 1,
+	incomingNonce = voteNonces.Incoming
+	release = Release{
+		ID:     sequences.Incoming[types.Denomination("oETH")],
 		Target: lastUser,
 		Amount: types.NewBaseUnits(*quantity.NewFromUint64(10), types.Denomination("oETH")),
-	})
+		Nonce:  &incomingNonce,
+	}
+	if err = signRelease(&params, signer, &release); err != nil {
+		logger.Error("failed to sign release",
+			"err", err,
+		)
+		return
+	}
+	tx = types.NewTransaction(nil, "bridge.Release", release)
 	tx.AppendAuthSignature(signer.Public(), nonce)
 	tb = tx.PrepareForSigning()
 	if err = tb.AppendSign(chainContext, signer); err != nil {
@@ -590,7 +999,8 @@ func main() {
 	}
 	defer conn.Close()
 
-	// Create the runtime client.
+	// Create the consensus and runtime clients.
+	cons := consensus.NewConsensusClient(conn)
 	c := client.New(conn, runtimeID)
 	rc := &Client{
 		RuntimeClient: c,
@@ -614,13 +1024,22 @@ func main() {
 	// Start two witnesses.
 	go witness(ctx, &wg, &releaseWg, rc, info.ChainContext, testing.Bob.Signer)
 	go witness(ctx, &wg, &releaseWg, rc, info.ChainContext, testing.Dave.Signer)
-	// Start one user.
-	go user(ctx, &wg, rc, info.ChainContext, testing.Alice.Signer)
+	// Start one user. The user is the test entity as it has funds in the consensus layer that it
+	// can deposit into the runtime.
+	_, entitySigner, err := entity.TestEntity()
+	if err != nil {
+		logger.Error("failed to load test entity",
+			"err", err,
+		)
+		os.Exit(1)
+	}
+	userSigner := ed25519.WrapSigner(entitySigner)
+	go user(ctx, &wg, rc, cons, runtimeID, info.ChainContext, entitySigner, userSigner)
 
 	wg.Wait()
 
 	// Show closing balances.
-	showBalances(ctx, rc, testing.Alice.Address)
+	showBalances(ctx, rc, types.NewAddress(userSigner.Public()))
 	// Show runtime parameters.
 	showParameters(ctx, rc)
 
@@ -0,0 +1,599 @@
+//! Witness signatures.
+use std::convert::TryFrom;
+
+use k256::ecdsa::{self, recoverable};
+use serde::Serialize;
+use tiny_keccak::{Hasher, Keccak};
+
+use oasis_runtime_sdk::{
+    core::common::{cbor, crypto::hash::Hash, namespace::Namespace, quantity::Quantity},
+    crypto::signature::{PublicKey, Signature},
+    types::{address::Address, token},
+};
+
+use crate::{types, Error, Parameters};
+
+/// Signature context used by witnesses when signing outgoing operations.
+pub const WITNESS_SIGNATURE_CONTEXT: &[u8] = b"oasis-bridge/witness: outgoing operation";
+/// Signature context used by witnesses when signing incoming operations.
+pub const RELEASE_SIGNATURE_CONTEXT: &[u8] = b"oasis-bridge/witness: incoming operation";
+/// Signature context used by witnesses when signing witness set updates.
+pub const WITNESS_SET_UPDATE_SIGNATURE_CONTEXT: &[u8] = b"oasis-bridge/witness: witness set update";
+/// Signature context used by the lock attester when attesting to addresses.
+pub const LOCK_ATTESTATION_SIGNATURE_CONTEXT: &[u8] = b"oasis-bridge/attester: lock";
+
+/// Name of the EIP-712 signing domain.
+pub const EIP712_DOMAIN_NAME: &str = "Oasis Bridge";
+/// Version of the EIP-712 signing domain.
+pub const EIP712_DOMAIN_VERSION: &str = "1";
+
+/// EIP-712 type of the signing domain.
+const EIP712_DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract,bytes32 salt)";
+/// EIP-712 type of outgoing lock operations.
+const EIP712_LOCK_TYPE: &str =
+    "Lock(uint64 id,address target,uint256 amount,string denomination,bytes token)";
+
+/// Message that witnesses sign for an operation.
+#[derive(Serialize)]
+struct OperationMessage<'a> {
+    #[serde(rename = "runtime_id")]
+    runtime_id: &'a Namespace,
+
+    #[serde(rename = "chain_id")]
+    chain_id: u64,
+
+    #[serde(rename = "remote_contract")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote_contract: Option<&'a types::RemoteAddress>,
+
+    #[serde(rename = "remote_denomination")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote_denomination: Option<&'a types::RemoteDenomination>,
+
+    #[serde(rename = "id")]
+    id: u64,
+
+    #[serde(rename = "op")]
+    op: &'a types::Operation,
+
+    #[serde(rename = "remote_amount")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote_amount: Option<Quantity>,
+}
+
+/// Message that witnesses sign for a witness set update.
+#[derive(Serialize)]
+struct WitnessSetUpdateMessage<'a> {
+    #[serde(rename = "runtime_id")]
+    runtime_id: &'a Namespace,
+
+    #[serde(rename = "chain_id")]
+    chain_id: u64,
+
+    #[serde(rename = "id")]
+    id: u64,
+
+    #[serde(rename = "witnesses")]
+    witnesses: &'a [types::WitnessKey],
+
+    #[serde(rename = "threshold")]
+    threshold: u64,
+}
+
+/// Message that the lock attester signs for an address.
+#[derive(Serialize)]
+struct LockAttestationMessage<'a> {
+    #[serde(rename = "runtime_id")]
+    runtime_id: &'a Namespace,
+
+    #[serde(rename = "address")]
+    address: &'a Address,
+
+    #[serde(rename = "expiration")]
+    expiration: u64,
+}
+
+/// Claim that claims trees commit to.
+#[derive(Serialize)]
+struct ClaimLeaf<'a> {
+    #[serde(rename = "index")]
+    index: u64,
+
+    #[serde(rename = "target")]
+    target: &'a Address,
+
+    #[serde(rename = "amount")]
+    amount: &'a token::BaseUnits,
+}
+
+/// Witness set that quorum certificates commit to.
+#[derive(Serialize)]
+struct WitnessSet<'a> {
+    #[serde(rename = "witnesses")]
+    witnesses: &'a [types::WitnessKey],
+
+    #[serde(rename = "threshold")]
+    threshold: u64,
+}
+
+/// Remote denomination of the given operation's amount, if any.
+fn remote_denomination<'a>(
+    params: &'a Parameters,
+    op: &types::Operation,
+) -> Option<&'a types::RemoteDenomination> {
+    params.remote_denominations.get(op.amount().denomination())
+}
+
+/// Amount of the given operation on the remote chain, if it is scaled.
+fn scaled_remote_amount(params: &Parameters, op: &types::Operation) -> Option<Quantity> {
+    params
+        .decimal_scales
+        .get(op.amount().denomination())
+        .and_then(|scale| scale.to_remote(op.amount().amount()))
+}
+
+/// Amount of the given lock on the remote chain.
+fn remote_amount(params: &Parameters, lock: &types::Lock) -> Result<Quantity, Error> {
+    params
+        .remote_amount(&lock.amount)
+        .ok_or(Error::InvalidArgument)
+}
+
+/// Compute the Keccak-256 digest (as used by Ethereum) of the concatenation of the given chunks.
+pub fn keccak256(chunks: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    let mut digest = [0; 32];
+    hasher.finalize(&mut digest);
+    digest
+}
+
+/// Message that witnesses need to sign to attest to the given operation.
+///
+/// The message commits to the runtime and remote chain identifiers from the parameters so that
+/// signatures cannot be replayed on other bridge instances, and to the remote bridge contract (if
+/// configured) so that they cannot be replayed against other contracts on the same chain. It
+/// also commits to the remote denomination of the transferred amount (if any) so that signatures
+/// cannot be replayed for other assets, and to the remote amount if the denomination is scaled.
+pub fn witness_message(params: &Parameters, id: u64, op: &types::Operation) -> Vec<u8> {
+    // Go through a CBOR value so that map keys end up in canonical order and the message can be
+    // reproduced by witnesses that are not implemented in Rust.
+    cbor::to_vec(&cbor::to_value(&OperationMessage {
+        runtime_id: &params.runtime_id,
+        chain_id: params.remote_chain_id,
+        remote_contract: params.remote_contract.as_ref(),
+        remote_denomination: remote_denomination(params, op),
+        id,
+        op,
+        remote_amount: scaled_remote_amount(params, op),
+    }))
+}
+
+/// Message that witnesses need to sign to approve the given witness set update.
+///
+/// Like the witness message, it commits to the runtime and remote chain identifiers.
+pub fn witness_set_update_message(params: &Parameters, update: &types::UpdateWitnesses) -> Vec<u8> {
+    cbor::to_vec(&cbor::to_value(&WitnessSetUpdateMessage {
+        runtime_id: &params.runtime_id,
+        chain_id: params.remote_chain_id,
+        id: update.id,
+        witnesses: &update.witnesses,
+        threshold: update.threshold,
+    }))
+}
+
+/// Commitment to a witness set, given by the signing keys of the witnesses ordered by witness
+/// index, and the threshold.
+///
+/// Quorum certificates include the commitment so that external verifiers can check that a
+/// certificate was produced by the witness set they know about.
+pub fn witness_set_commitment(signing_keys: &[types::WitnessKey], threshold: u64) -> Hash {
+    Hash::digest_bytes(&cbor::to_vec(&cbor::to_value(&WitnessSet {
+        witnesses: signing_keys,
+        threshold,
+    })))
+}
+
+/// Leaf of the claims tree for the claim with the given index in the tree.
+///
+/// Leaves and inner nodes are hashed with different prefixes so that one cannot be passed off as
+/// the other.
+pub fn claim_leaf(index: u64, target: &Address, amount: &token::BaseUnits) -> Hash {
+    let leaf = cbor::to_vec(&cbor::to_value(&ClaimLeaf {
+        index,
+        target,
+        amount,
+    }));
+    Hash::digest_bytes(&[&[0x00][..], &leaf].concat())
+}
+
+/// Inner node of the claims tree with the given children.
+fn claim_node(left: &Hash, right: &Hash) -> Hash {
+    Hash::digest_bytes(&[&[0x01][..], left.as_ref(), right.as_ref()].concat())
+}
+
+/// Root of the claims tree with the given leaves, where the leaf of each claim sits at the
+/// position given by its index.
+///
+/// The leaves are padded with default hashes up to the next power of two.
+pub fn claims_root(leaves: &[Hash]) -> Hash {
+    let mut level = leaves.to_vec();
+    level.resize(leaves.len().next_power_of_two(), Hash::default());
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| claim_node(&pair[0], &pair[1]))
+            .collect();
+    }
+    level[0]
+}
+
+/// Proof of inclusion of the leaf with the given index in the claims tree with the given leaves.
+///
+/// Panics if the index is not within the leaves.
+pub fn claims_proof(leaves: &[Hash], index: usize) -> Vec<Hash> {
+    let mut level = leaves.to_vec();
+    level.resize(leaves.len().next_power_of_two(), Hash::default());
+    let mut index = index;
+    let mut proof = vec![];
+    while level.len() > 1 {
+        proof.push(level[index ^ 1]);
+        level = level
+            .chunks(2)
+            .map(|pair| claim_node(&pair[0], &pair[1]))
+            .collect();
+        index /= 2;
+    }
+    proof
+}
+
+/// Root of the claims tree that the given proof of inclusion of the leaf with the given index
+/// leads to, or `None` if the index does not fit in a tree of the proof's depth.
+pub fn claims_root_from_proof(leaf: Hash, index: u64, proof: &[Hash]) -> Option<Hash> {
+    if proof.len() > 64 || (proof.len() < 64 && index >> proof.len() != 0) {
+        return None;
+    }
+    Some(
+        proof
+            .iter()
+            .enumerate()
+            .fold(leaf, |node, (level, sibling)| {
+                if (index >> level) & 1 == 0 {
+                    claim_node(&node, sibling)
+                } else {
+                    claim_node(sibling, &node)
+                }
+            }),
+    )
+}
+
+/// Encode an unsigned integer given as big-endian bytes as a 256-bit ABI word.
+fn abi_uint(value: &[u8]) -> Result<[u8; 32], Error> {
+    // Skip any leading zeros.
+    let value = &value[value.iter().take_while(|b| **b == 0).count()..];
+    if value.len() > 32 {
+        return Err(Error::InvalidArgument);
+    }
+    let mut word = [0; 32];
+    word[32 - value.len()..].copy_from_slice(value);
+    Ok(word)
+}
+
+/// Encode a quantity as a 256-bit ABI word.
+fn abi_quantity(quantity: &Quantity) -> Result<[u8; 32], Error> {
+    // Quantities are serialized as big-endian byte strings.
+    match cbor::to_value(quantity) {
+        cbor::Value::Bytes(value) => abi_uint(&value),
+        _ => Err(Error::InvalidArgument),
+    }
+}
+
+/// ABI-encoded `(uint256 id, address token, address recipient, uint256 amount)` message that
+/// witnesses sign for the given outgoing operation when using the ABI message encoding.
+///
+/// The token is the remote denomination for remote denominations and the Keccak-256 digest of
+/// the denomination for local denominations. The amount is scaled to the remote decimals of the
+/// denomination, if configured. The message is prefixed with the address of the remote contract,
+/// which must be configured, as an additional `address` word. The message does not commit to the
+/// runtime and remote chain identifiers, so the remote contract must not accept it on any other
+/// deployment.
+pub fn abi_message(params: &Parameters, id: u64, op: &types::Operation) -> Result<Vec<u8>, Error> {
+    let lock = match op {
+        types::Operation::Lock(lock) => lock,
+        _ => return Err(Error::InvalidArgument),
+    };
+    let contract = params
+        .remote_contract
+        .as_ref()
+        .ok_or(Error::InvalidArgument)?;
+    let token = match remote_denomination(params, op) {
+        Some(remote) => abi_uint(remote.as_ref())?,
+        None => keccak256(&[lock.amount.denomination().to_string().as_bytes()]),
+    };
+
+    let mut message = Vec::with_capacity(5 * 32);
+    for word in &[
+        abi_uint(contract.as_ref())?,
+        abi_uint(&id.to_be_bytes())?,
+        token,
+        abi_uint(lock.target.as_ref())?,
+        abi_quantity(&remote_amount(params, lock)?)?,
+    ] {
+        message.extend_from_slice(word);
+    }
+    Ok(message)
+}
+
+/// Compute the EIP-712 separator of the given signing domain.
+pub fn eip712_domain_separator(
+    params: &Parameters,
+    domain: &types::Eip712Domain,
+) -> Result<[u8; 32], Error> {
+    Ok(keccak256(&[
+        &keccak256(&[EIP712_DOMAIN_TYPE.as_bytes()]),
+        &keccak256(&[EIP712_DOMAIN_NAME.as_bytes()]),
+        &keccak256(&[EIP712_DOMAIN_VERSION.as_bytes()]),
+        &abi_uint(&params.remote_chain_id.to_be_bytes())?,
+        &abi_uint(domain.verifying_contract.as_ref())?,
+        &params.runtime_id.0,
+    ]))
+}
+
+/// Compute the EIP-712 digest that witnesses need to sign to attest to the given outgoing
+/// operation.
+pub fn eip712_digest(
+    params: &Parameters,
+    domain: &types::Eip712Domain,
+    id: u64,
+    op: &types::Operation,
+) -> Result<[u8; 32], Error> {
+    let lock = match op {
+        types::Operation::Lock(lock) => lock,
+        _ => return Err(Error::InvalidArgument),
+    };
+    let struct_hash = keccak256(&[
+        &keccak256(&[EIP712_LOCK_TYPE.as_bytes()]),
+        &abi_uint(&id.to_be_bytes())?,
+        &abi_uint(lock.target.as_ref())?,
+        &abi_quantity(&remote_amount(params, lock)?)?,
+        &keccak256(&[lock.amount.denomination().to_string().as_bytes()]),
+        &keccak256(&[remote_denomination(params, op)
+            .map(AsRef::as_ref)
+            .unwrap_or_default()]),
+    ]);
+
+    Ok(keccak256(&[
+        b"\x19\x01",
+        &eip712_domain_separator(params, domain)?,
+        &struct_hash,
+    ]))
+}
+
+/// Whether the given public key can be used by a witness.
+pub fn is_valid_witness_key(pk: &PublicKey) -> bool {
+    match pk {
+        PublicKey::Secp256k1(pk) => ecdsa::VerifyingKey::from_sec1_bytes(pk.as_bytes()).is_ok(),
+        _ => true,
+    }
+}
+
+/// Whether the given witness can be part of the witness set.
+///
+/// Multisig witnesses need at least one signer, distinct valid signer keys and a non-zero
+/// threshold that the signers can reach.
+pub fn is_valid_witness(witness: &types::WitnessKey) -> bool {
+    let config = match witness {
+        types::WitnessKey::PublicKey(pk) => return is_valid_witness_key(pk),
+        types::WitnessKey::Multisig(config) => config,
+    };
+    let total_weight = config
+        .signers
+        .iter()
+        .try_fold(0u64, |total, signer| total.checked_add(signer.weight));
+    let distinct = config.signers.iter().enumerate().all(|(i, signer)| {
+        config.signers[..i]
+            .iter()
+            .all(|other| other.public_key != signer.public_key)
+    });
+
+    config.threshold > 0
+        && total_weight.map(|w| w >= config.threshold).unwrap_or(false)
+        && distinct
+        && config
+            .signers
+            .iter()
+            .all(|signer| is_valid_witness_key(&signer.public_key))
+}
+
+/// Verify a witness signature over the given message.
+///
+/// Ed25519 witnesses use the regular domain-separated signature scheme. Secp256k1 witnesses
+/// produce Ethereum-compatible recoverable signatures (`r || s || v`) over the Keccak-256 digest
+/// of the context and message, so that the remote contract can verify them using `ecrecover`.
+pub fn verify(
+    pk: &PublicKey,
+    context: &[u8],
+    message: &[u8],
+    signature: &Signature,
+) -> Result<(), Error> {
+    match pk {
+        PublicKey::Secp256k1(pk) => verify_recoverable(
+            pk.as_bytes(),
+            &keccak256(&[context, message]),
+            signature.as_ref(),
+        ),
+        _ => pk
+            .verify(context, message, signature)
+            .map_err(|_| Error::InvalidSignature),
+    }
+}
+
+/// Verify a witness signature over a pre-hashed digest.
+///
+/// Secp256k1 witnesses sign the digest directly so that it can be verified using `ecrecover`,
+/// other witnesses sign it as a message under the witness signature context.
+pub fn verify_digest(
+    pk: &PublicKey,
+    digest: &[u8; 32],
+    signature: &Signature,
+) -> Result<(), Error> {
+    match pk {
+        PublicKey::Secp256k1(pk) => verify_recoverable(pk.as_bytes(), digest, signature.as_ref()),
+        _ => pk
+            .verify(WITNESS_SIGNATURE_CONTEXT, digest, signature)
+            .map_err(|_| Error::InvalidSignature),
+    }
+}
+
+/// Verify a witness signature attesting to the given outgoing operation.
+///
+/// When an EIP-712 domain is configured, witnesses sign the EIP-712 digest of the operation. With
+/// the ABI message encoding they sign the Keccak-256 digest of the ABI message, as computed by
+/// the remote contract. Otherwise they sign the witness message of the operation.
+pub fn verify_witness(
+    witness: &types::WitnessKey,
+    params: &Parameters,
+    id: u64,
+    op: &types::Operation,
+    signature: &Signature,
+) -> Result<(), Error> {
+    verify_signers(witness, signature, |pk, signature| {
+        match (&params.eip712_domain, params.message_encoding) {
+            (Some(domain), _) => {
+                verify_digest(pk, &eip712_digest(params, domain, id, op)?, signature)
+            }
+            (None, types::MessageEncoding::Abi) => {
+                verify_digest(pk, &keccak256(&[&abi_message(params, id, op)?]), signature)
+            }
+            (None, types::MessageEncoding::Cbor) => verify(
+                pk,
+                WITNESS_SIGNATURE_CONTEXT,
+                &witness_message(params, id, op),
+                signature,
+            ),
+        }
+    })
+}
+
+/// Verify a witness signature over the given incoming operation.
+pub fn verify_release(
+    witness: &types::WitnessKey,
+    params: &Parameters,
+    id: u64,
+    op: &types::Operation,
+    signature: &Signature,
+) -> Result<(), Error> {
+    let message = witness_message(params, id, op);
+    verify_signers(witness, signature, |pk, signature| {
+        verify(pk, RELEASE_SIGNATURE_CONTEXT, &message, signature)
+    })
+}
+
+/// Message that the lock attester needs to sign to let the given address lock funds until the
+/// given round.
+pub fn lock_attestation_message(
+    params: &Parameters,
+    address: &Address,
+    expiration: u64,
+) -> Vec<u8> {
+    cbor::to_vec(&cbor::to_value(&LockAttestationMessage {
+        runtime_id: &params.runtime_id,
+        address,
+        expiration,
+    }))
+}
+
+/// Verify a lock attestation over the given address.
+pub fn verify_lock_attestation(
+    attester: &PublicKey,
+    params: &Parameters,
+    address: &Address,
+    attestation: &types::LockAttestation,
+) -> Result<(), Error> {
+    verify(
+        attester,
+        LOCK_ATTESTATION_SIGNATURE_CONTEXT,
+        &lock_attestation_message(params, address, attestation.expiration),
+        &attestation.signature,
+    )
+}
+
+/// Verify a witness signature over the given witness set update.
+pub fn verify_witness_set_update(
+    witness: &types::WitnessKey,
+    params: &Parameters,
+    update: &types::UpdateWitnesses,
+    signature: &Signature,
+) -> Result<(), Error> {
+    let message = witness_set_update_message(params, update);
+    verify_signers(witness, signature, |pk, signature| {
+        verify(
+            pk,
+            WITNESS_SET_UPDATE_SIGNATURE_CONTEXT,
+            &message,
+            signature,
+        )
+    })
+}
+
+/// Verify a witness signature using the given verification function for individual keys.
+///
+/// Multisig witnesses need valid signatures from signers whose weights reach the threshold.
+fn verify_signers<F>(
+    witness: &types::WitnessKey,
+    signature: &Signature,
+    verify: F,
+) -> Result<(), Error>
+where
+    F: Fn(&PublicKey, &Signature) -> Result<(), Error>,
+{
+    let config = match witness {
+        types::WitnessKey::PublicKey(pk) => return verify(pk, signature),
+        types::WitnessKey::Multisig(config) => config,
+    };
+    let signatures =
+        types::MultisigSignature::from_signature(signature).ok_or(Error::InvalidSignature)?;
+    if signatures.0.len() != config.signers.len() {
+        return Err(Error::InvalidSignature);
+    }
+
+    let mut weight: u64 = 0;
+    for (signer, signature) in config.signers.iter().zip(signatures.0.iter()) {
+        if let Some(signature) = signature {
+            verify(&signer.public_key, signature)?;
+            weight = weight.saturating_add(signer.weight);
+        }
+    }
+    if weight < config.threshold {
+        return Err(Error::InvalidSignature);
+    }
+
+    Ok(())
+}
+
+fn verify_recoverable(pk: &[u8], digest: &[u8; 32], signature: &[u8]) -> Result<(), Error> {
+    if signature.len() != 65 {
+        return Err(Error::InvalidSignature);
+    }
+    // Ethereum uses 27 and 28 as recovery identifiers, accept both forms.
+    let v = match signature[64] {
+        v @ 0..=1 => v,
+        v @ 27..=28 => v - 27,
+        _ => return Err(Error::InvalidSignature),
+    };
+    let id = recoverable::Id::new(v).map_err(|_| Error::InvalidSignature)?;
+    let sig = ecdsa::Signature::try_from(&signature[..64]).map_err(|_| Error::InvalidSignature)?;
+    let sig = recoverable::Signature::new(&sig, id).map_err(|_| Error::InvalidSignature)?;
+
+    let recovered = sig
+        .recover_verify_key_from_digest_bytes(k256::FieldBytes::from_slice(digest))
+        .map_err(|_| Error::InvalidSignature)?;
+    let expected = ecdsa::VerifyingKey::from_sec1_bytes(pk).map_err(|_| Error::InvalidSignature)?;
+    if recovered != expected {
+        return Err(Error::InvalidSignature);
+    }
+
+    Ok(())
+}
@@ -10,21 +10,48 @@ use thiserror::Error;
 use oasis_runtime_sdk::{
     self as sdk,
     context::{Context, TxContext},
-    core::common::cbor,
+    core::common::{cbor, crypto::hash::Hash, namespace::Namespace, quantity::Quantity},
     crypto::signature::PublicKey,
     error::{self, Error as _},
     module::{self, Module as _},
     modules, storage,
-    types::{address::Address, token, transaction::CallResult},
+    types::{
+        address::Address,
+        token,
+        transaction::{self, CallResult},
+    },
 };
 
+pub mod crypto;
 #[cfg(test)]
 mod test;
 pub mod types;
+pub mod verifier;
+
+use verifier::IncomingVerifier;
 
 /// Unique module name.
 const MODULE_NAME: &str = "bridge";
 
+/// Maximum length of the witness display name in bytes.
+const MAX_WITNESS_NAME_LENGTH: usize = 64;
+/// Maximum length of the witness contact endpoint in bytes.
+const MAX_WITNESS_ENDPOINT_LENGTH: usize = 256;
+/// Maximum length of a denomination ticker symbol in bytes.
+const MAX_DENOMINATION_SYMBOL_LENGTH: usize = 16;
+/// Maximum number of votes in a single `bridge.WitnessBatch` call.
+const MAX_WITNESS_BATCH_SIZE: usize = 64;
+/// Maximum number of entries returned by a single paginated query.
+const MAX_PAGE_SIZE: u64 = 100;
+/// Maximum size in bytes of the keys and values returned by a single `bridge.ExportState` query.
+const MAX_EXPORT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Number of basis points that make up a whole.
+const BASIS_POINTS: u64 = 10_000;
+
+/// Methods whose transaction fees are covered by the subsidy pool when called by witnesses.
+const SPONSORED_METHODS: &[&str] = &["bridge.Witness", "bridge.Release"];
+
 /// Errors emitted by the accounts module.
 #[derive(Error, Debug, sdk::Error)]
 pub enum Error {
@@ -51,6 +78,94 @@ pub enum Error {
     #[error("unsupported denomination")]
     #[sdk_error(code = 6)]
     UnsupportedDenomination,
+
+    #[error("invalid target")]
+    #[sdk_error(code = 7)]
+    InvalidTarget,
+
+    #[error("invalid signature")]
+    #[sdk_error(code = 8)]
+    InvalidSignature,
+
+    #[error("insufficient bond")]
+    #[sdk_error(code = 9)]
+    InsufficientBond,
+
+    #[error("evidence already submitted")]
+    #[sdk_error(code = 10)]
+    DuplicateEvidence,
+
+    #[error("invalid vote nonce")]
+    #[sdk_error(code = 11)]
+    InvalidNonce,
+
+    #[error("remote reserve exceeded")]
+    #[sdk_error(code = 12)]
+    ReserveExceeded,
+
+    #[error("amount too small")]
+    #[sdk_error(code = 13)]
+    AmountTooSmall,
+
+    #[error("amount too large")]
+    #[sdk_error(code = 14)]
+    AmountTooLarge,
+
+    #[error("bridge is paused")]
+    #[sdk_error(code = 15)]
+    Paused,
+
+    #[error("denomination is frozen")]
+    #[sdk_error(code = 16)]
+    DenominationFrozen,
+
+    #[error("rate limit exceeded")]
+    #[sdk_error(code = 17)]
+    RateLimitExceeded,
+
+    #[error("account limit exceeded")]
+    #[sdk_error(code = 18)]
+    AccountLimitExceeded,
+
+    #[error("address is blocked")]
+    #[sdk_error(code = 19)]
+    AddressBlocked,
+
+    #[error("address is not allowed")]
+    #[sdk_error(code = 20)]
+    AddressNotAllowed,
+
+    #[error("attestation expired")]
+    #[sdk_error(code = 21)]
+    AttestationExpired,
+
+    #[error("deposit already processed")]
+    #[sdk_error(code = 22)]
+    DepositAlreadyProcessed,
+
+    #[error("too many votes in block")]
+    #[sdk_error(code = 23)]
+    TooManyVotes,
+
+    #[error("release batch too large")]
+    #[sdk_error(code = 24)]
+    BatchTooLarge,
+
+    #[error("invalid claim proof")]
+    #[sdk_error(code = 25)]
+    InvalidClaimProof,
+
+    #[error("already claimed")]
+    #[sdk_error(code = 26)]
+    AlreadyClaimed,
+
+    #[error("too many pending operations")]
+    #[sdk_error(code = 27)]
+    TooManyPending,
+
+    #[error("unsupported remote chain")]
+    #[sdk_error(code = 28)]
+    UnsupportedRemoteChain,
 }
 
 impl From<modules::accounts::Error> for Error {
@@ -69,34 +184,271 @@ pub enum Event {
     #[sdk_event(code = 1)]
     Lock {
         id: u64,
+        remote_chain_id: u64,
         owner: Address,
         target: types::RemoteAddress,
         amount: token::BaseUnits,
+        fee: token::BaseUnits,
     },
 
     #[sdk_event(code = 2)]
     Release {
         id: u64,
+        remote_chain_id: u64,
         target: Address,
         amount: token::BaseUnits,
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tx_hash: Option<Hash>,
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        log_index: Option<u64>,
     },
 
     #[sdk_event(code = 3)]
-    WitnessesSigned(types::WitnessSignatures),
+    WitnessesSigned(types::QuorumCertificate),
+
+    #[sdk_event(code = 4)]
+    WitnessSetUpdated {
+        id: u64,
+        witnesses: Vec<types::WitnessKey>,
+        threshold: u64,
+    },
+
+    #[sdk_event(code = 5)]
+    WitnessAdded {
+        index: u16,
+        witness: types::WitnessKey,
+    },
+
+    #[sdk_event(code = 6)]
+    WitnessRemoved {
+        index: u16,
+        witness: types::WitnessKey,
+    },
+
+    #[sdk_event(code = 7)]
+    WitnessKeyRotated {
+        index: u16,
+        old: PublicKey,
+        new: PublicKey,
+    },
+
+    #[sdk_event(code = 8)]
+    WitnessInactive {
+        index: u16,
+        witness: types::WitnessKey,
+    },
+
+    #[sdk_event(code = 9)]
+    WitnessReactivated {
+        index: u16,
+        witness: types::WitnessKey,
+    },
+
+    #[sdk_event(code = 10)]
+    WitnessBonded {
+        index: u16,
+        witness: types::WitnessKey,
+        amount: token::BaseUnits,
+    },
+
+    #[sdk_event(code = 11)]
+    WitnessUnbonding {
+        witness: types::WitnessKey,
+        owner: Address,
+        amount: token::BaseUnits,
+        ends: u64,
+    },
+
+    #[sdk_event(code = 12)]
+    Slashed {
+        index: u16,
+        witness: types::WitnessKey,
+        amount: token::BaseUnits,
+    },
+
+    #[sdk_event(code = 13)]
+    WitnessJailed {
+        index: u16,
+        witness: types::WitnessKey,
+        until: u64,
+    },
+
+    #[sdk_event(code = 14)]
+    WitnessRewarded {
+        index: u16,
+        witness: types::WitnessKey,
+        amount: token::BaseUnits,
+    },
+
+    #[sdk_event(code = 15)]
+    VoteRetracted {
+        denomination: token::Denomination,
+        id: u64,
+        index: u16,
+        witness: types::WitnessKey,
+    },
+
+    #[sdk_event(code = 16)]
+    WitnessFeePaid {
+        denomination: token::Denomination,
+        id: u64,
+        index: u16,
+        witness: types::WitnessKey,
+        amount: token::BaseUnits,
+    },
+
+    #[sdk_event(code = 17)]
+    FeeScheduleChanged {
+        id: u64,
+        schedule: types::FeeSchedule,
+    },
+
+    #[sdk_event(code = 18)]
+    RemoteLockedAttested { amount: token::BaseUnits },
+
+    #[sdk_event(code = 19)]
+    Paused { guardian: Address },
+
+    #[sdk_event(code = 20)]
+    Resumed { guardian: Address },
+
+    #[sdk_event(code = 21)]
+    DenominationFrozen {
+        denomination: token::Denomination,
+        by: Address,
+    },
+
+    #[sdk_event(code = 22)]
+    DenominationUnfrozen {
+        denomination: token::Denomination,
+        by: Address,
+    },
+
+    #[sdk_event(code = 23)]
+    CircuitBreakerTripped {
+        volume: token::BaseUnits,
+        baseline: Quantity,
+    },
+
+    #[sdk_event(code = 24)]
+    ReleaseQueued {
+        id: u64,
+        target: Address,
+        amount: token::BaseUnits,
+        deadline: u64,
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        batch: Vec<types::ReleaseEntry>,
+    },
+
+    #[sdk_event(code = 25)]
+    ReleaseCancelled {
+        denomination: token::Denomination,
+        id: u64,
+        by: Address,
+    },
+
+    #[sdk_event(code = 26)]
+    AddressAllowed { address: Address },
+
+    #[sdk_event(code = 27)]
+    AddressDisallowed { address: Address },
+
+    #[sdk_event(code = 28)]
+    DivergenceDetected(types::Conflict),
+
+    #[sdk_event(code = 29)]
+    RecoveryScheduled {
+        id: u64,
+        target: Address,
+        amount: token::BaseUnits,
+        deadline: u64,
+    },
+
+    #[sdk_event(code = 30)]
+    FundsRecovered {
+        id: u64,
+        target: Address,
+        amount: token::BaseUnits,
+    },
+
+    #[sdk_event(code = 31)]
+    RecoveryCancelled { id: u64, by: Address },
+
+    #[sdk_event(code = 32)]
+    Claimed {
+        denomination: token::Denomination,
+        id: u64,
+        index: u64,
+        target: Address,
+        amount: token::BaseUnits,
+    },
+
+    #[sdk_event(code = 33)]
+    OperationExpired {
+        denomination: token::Denomination,
+        id: u64,
+    },
+
+    #[sdk_event(code = 34)]
+    StaleVotesCleared {
+        denomination: token::Denomination,
+        id: u64,
+    },
+
+    #[sdk_event(code = 35)]
+    BalanceClaimed {
+        target: Address,
+        amount: token::BaseUnits,
+    },
+
+    #[sdk_event(code = 36)]
+    RemoteContractChanged {
+        id: u64,
+        contract: types::RemoteAddress,
+    },
+
+    #[sdk_event(code = 37)]
+    DenominationRegistered {
+        id: u64,
+        denomination: token::Denomination,
+        remote: types::RemoteDenomination,
+    },
+
+    #[sdk_event(code = 38)]
+    PendingReleaseFailed {
+        denomination: token::Denomination,
+        id: u64,
+        code: u32,
+    },
 }
 
 /// Parameters for the bridge module.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Parameters {
-    /// A list of authorized witness public keys.
+    /// A list of authorized witnesses.
+    ///
+    /// Each public key is tagged with its signature scheme, so the witness set may mix Ed25519
+    /// and Secp256k1 witnesses. Signatures are always verified using the witness' own scheme.
+    /// A witness may also be a multisig configuration, in which case any of its signers can submit
+    /// transactions and payload signatures must satisfy the multisig threshold.
     #[serde(rename = "witnesses")]
-    pub witnesses: Vec<PublicKey>,
+    pub witnesses: Vec<types::WitnessKey>,
 
-    /// Number of witnesses that needs to sign off.
+    /// Number of witnesses that needs to sign off. When a threshold policy is set, this is the
+    /// minimum threshold.
     #[serde(rename = "threshold")]
     pub threshold: u64,
 
+    /// Policy for deriving the threshold from the number of active witnesses, if any.
+    #[serde(rename = "threshold_policy")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold_policy: Option<types::ThresholdPolicy>,
+
     /// Denominations local to this side of the bridge.
     #[serde(rename = "local_denominations")]
     pub local_denominations: BTreeSet<token::Denomination>,
@@ -104,6 +456,289 @@ pub struct Parameters {
     /// Denominations that exist on the remote side of the bridge.
     #[serde(rename = "remote_denominations")]
     pub remote_denominations: BTreeMap<token::Denomination, types::RemoteDenomination>,
+
+    /// Identifier of the runtime the bridge is deployed in. Witness signatures commit to it so
+    /// they cannot be replayed on other bridge instances.
+    #[serde(rename = "runtime_id")]
+    #[serde(default)]
+    pub runtime_id: Namespace,
+
+    /// Identifier of the remote chain. Witness signatures commit to it so they cannot be replayed
+    /// on other remote chains.
+    #[serde(rename = "remote_chain_id")]
+    #[serde(default)]
+    pub remote_chain_id: u64,
+
+    /// Address of the bridge contract on the remote chain, if any. Witness signatures commit to
+    /// it so they cannot be replayed against other contracts deployed on the same remote chain.
+    #[serde(rename = "remote_contract")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_contract: Option<types::RemoteAddress>,
+
+    /// Encoding of the messages that witnesses sign for outgoing operations. The ABI encoding
+    /// requires a remote contract.
+    #[serde(rename = "message_encoding")]
+    #[serde(default)]
+    pub message_encoding: types::MessageEncoding,
+
+    /// EIP-712 domain used for hashing outgoing operations. When set, witnesses sign the EIP-712
+    /// digests of outgoing operations and the message encoding must be CBOR.
+    #[serde(rename = "eip712_domain")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eip712_domain: Option<types::Eip712Domain>,
+
+    /// Address that is allowed to add and remove witnesses and to change the fee schedule, if any.
+    #[serde(rename = "admin")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin: Option<Address>,
+
+    /// Addresses that are allowed to pause and resume the bridge in an emergency.
+    #[serde(rename = "guardians")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub guardians: Vec<Address>,
+
+    /// Number of most recent outgoing operations over which witness participation is reported,
+    /// at most 64.
+    #[serde(rename = "liveness_window")]
+    #[serde(default)]
+    pub liveness_window: u64,
+
+    /// Number of consecutive outgoing operations that a witness can miss before it is marked
+    /// inactive. Inactive witnesses are left out of the witness sets of new outgoing operations
+    /// until they reactivate themselves. Zero disables automatic demotion.
+    #[serde(rename = "max_missed_operations")]
+    #[serde(default)]
+    pub max_missed_operations: u64,
+
+    /// Number of rounds after a witness set change during which pending outgoing operations also
+    /// accept signatures from the new witness set. Zero disables the handover.
+    #[serde(rename = "handover_window")]
+    #[serde(default)]
+    pub handover_window: u64,
+
+    /// Amount of a local denomination that witnesses need to bond before their signatures and
+    /// votes are accepted, if any.
+    #[serde(rename = "witness_bond")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness_bond: Option<token::BaseUnits>,
+
+    /// Number of rounds after which unbonded amounts can be withdrawn.
+    #[serde(rename = "unbonding_delay")]
+    #[serde(default)]
+    pub unbonding_delay: u64,
+
+    /// Portion of the bond, in basis points, that is slashed from witnesses that vote for an
+    /// incoming operation other than the one that is finalized for the same sequence number.
+    #[serde(rename = "divergence_penalty")]
+    #[serde(default)]
+    pub divergence_penalty: u64,
+
+    /// Portion of the bond, in basis points, that is slashed from witnesses for which evidence
+    /// of signing two different operations for the same sequence number is submitted.
+    #[serde(rename = "equivocation_penalty")]
+    #[serde(default)]
+    pub equivocation_penalty: u64,
+
+    /// Number of rounds during which a witness for which evidence of misbehavior is submitted is
+    /// marked inactive and cannot reactivate itself.
+    #[serde(rename = "jail_duration")]
+    #[serde(default)]
+    pub jail_duration: u64,
+
+    /// Number of rounds between distributions of the reward pool to the witnesses, in proportion
+    /// to the number of finalized operations that they signed. Zero disables rewards.
+    #[serde(rename = "reward_period")]
+    #[serde(default)]
+    pub reward_period: u64,
+
+    /// Fee, in basis points of the locked amount, that is deducted from each lock and credited to
+    /// the bridge fee account.
+    #[serde(rename = "lock_fee")]
+    #[serde(default)]
+    pub lock_fee: u64,
+
+    /// Minimum lock fee in base units of the locked denomination.
+    #[serde(rename = "min_lock_fee")]
+    #[serde(default)]
+    pub min_lock_fee: Quantity,
+
+    /// Maximum lock fee in base units of the locked denomination. Zero means no maximum.
+    #[serde(rename = "max_lock_fee")]
+    #[serde(default)]
+    pub max_lock_fee: Quantity,
+
+    /// Flat fee in the native denomination that is charged on each lock and deposited into the
+    /// subsidy pool, which covers the fees of witness and release transactions by witnesses.
+    #[serde(rename = "subsidy_fee")]
+    #[serde(default)]
+    pub subsidy_fee: Quantity,
+
+    /// Minimum amount per denomination that can be locked. Smaller locks cost witnesses more to
+    /// relay than they transfer.
+    #[serde(rename = "min_lock_amounts")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub min_lock_amounts: BTreeMap<token::Denomination, Quantity>,
+
+    /// Maximum amount per denomination that can be locked or released in a single operation, so
+    /// that a single compromised operation cannot drain the locked funds.
+    #[serde(rename = "max_transfer_amounts")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub max_transfer_amounts: BTreeMap<token::Denomination, Quantity>,
+
+    /// Number of local and remote decimals per denomination whose amounts are scaled when
+    /// crossing the bridge. Denominations without an entry are not scaled.
+    #[serde(rename = "decimal_scales")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub decimal_scales: BTreeMap<token::Denomination, types::DecimalScale>,
+
+    /// Number of rounds in a rate limit window. Zero disables rate limits.
+    #[serde(rename = "rate_limit_window")]
+    #[serde(default)]
+    pub rate_limit_window: u64,
+
+    /// Maximum amount per denomination that can be locked and released in total during a rate
+    /// limit window.
+    #[serde(rename = "rate_limits")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub rate_limits: BTreeMap<token::Denomination, Quantity>,
+
+    /// Maximum amount per denomination that a single account can lock, or receive through
+    /// releases, in total during a rate limit window.
+    #[serde(rename = "account_limits")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub account_limits: BTreeMap<token::Denomination, Quantity>,
+
+    /// Expected amount per denomination that is released during a rate limit window.
+    #[serde(rename = "release_baselines")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub release_baselines: BTreeMap<token::Denomination, Quantity>,
+
+    /// Multiple of the release baseline at which the bridge is paused automatically until a
+    /// guardian resumes it. Zero disables the circuit breaker.
+    #[serde(rename = "circuit_breaker_multiplier")]
+    #[serde(default)]
+    pub circuit_breaker_multiplier: u64,
+
+    /// Number of rounds during which guardians can cancel large releases before they are
+    /// executed. Zero disables the challenge period.
+    #[serde(rename = "challenge_period")]
+    #[serde(default)]
+    pub challenge_period: u64,
+
+    /// Amount per denomination above which releases are subject to the challenge period.
+    #[serde(rename = "challenge_thresholds")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub challenge_thresholds: BTreeMap<token::Denomination, Quantity>,
+
+    /// Local addresses that cannot lock funds or receive released funds.
+    #[serde(rename = "blocked_addresses")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub blocked_addresses: BTreeSet<Address>,
+
+    /// Remote addresses that funds cannot be locked for.
+    #[serde(rename = "blocked_remote_addresses")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub blocked_remote_addresses: BTreeSet<types::RemoteAddress>,
+
+    /// Whether only addresses on the allowlist managed by the admin can lock funds.
+    #[serde(rename = "lock_allowlist")]
+    #[serde(default)]
+    pub lock_allowlist: bool,
+
+    /// Key that must attest to the caller address of each lock, if any.
+    #[serde(rename = "lock_attester")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_attester: Option<PublicKey>,
+
+    /// Number of rounds for which the hashes of processed remote transactions are remembered at
+    /// least. Zero remembers them forever.
+    #[serde(rename = "processed_deposit_retention")]
+    #[serde(default)]
+    pub processed_deposit_retention: u64,
+
+    /// Number of rounds after which funds that the admin recovers from the locked funds account
+    /// are transferred, during which guardians can cancel the recovery. Zero disables recoveries.
+    #[serde(rename = "recovery_delay")]
+    #[serde(default)]
+    pub recovery_delay: u64,
+
+    /// Maximum number of `bridge.Witness` and `bridge.Release` votes that a single account can
+    /// submit per block. Zero means no limit.
+    #[serde(rename = "max_votes_per_block")]
+    #[serde(default)]
+    pub max_votes_per_block: u64,
+
+    /// Number of incoming sequence numbers ahead of the next expected one that witnesses can vote
+    /// on. Operations are still finalized in sequence. Zero only accepts the next one.
+    #[serde(rename = "release_window")]
+    #[serde(default)]
+    pub release_window: u64,
+
+    /// Maximum number of further transfers that a single release can batch together with its
+    /// primary one. Zero disables batch releases.
+    #[serde(rename = "max_release_batch_size")]
+    #[serde(default)]
+    pub max_release_batch_size: u64,
+
+    /// Number of rounds after which outgoing operations that did not gather enough witness
+    /// signatures expire and are removed. The funds of expired operations stay locked. Zero
+    /// disables expiry.
+    #[serde(rename = "operation_expiry")]
+    #[serde(default)]
+    pub operation_expiry: u64,
+
+    /// Number of rounds after the first vote for an incoming sequence number after which its votes
+    /// are cleared if the operation has not been finalized, for example because the witnesses
+    /// split. Zero disables clearing stale votes.
+    #[serde(rename = "stale_vote_age")]
+    #[serde(default)]
+    pub stale_vote_age: u64,
+
+    /// Whether cleared stale votes are treated as retracted, so that witnesses can vote for a
+    /// different operation without it counting as equivocation.
+    #[serde(rename = "reset_stale_votes")]
+    #[serde(default)]
+    pub reset_stale_votes: bool,
+
+    /// Maximum number of outgoing operations that can be pending finalization at the same time.
+    /// Zero means no limit.
+    #[serde(rename = "max_pending_locks")]
+    #[serde(default)]
+    pub max_pending_locks: u64,
+
+    /// Whether finalized releases credit the claimable balances of their targets, which they
+    /// withdraw with `bridge.ClaimBalance`, instead of transferring the funds to them. Releases
+    /// committing to claims are not affected.
+    #[serde(rename = "pull_releases")]
+    #[serde(default)]
+    pub pull_releases: bool,
+
+    /// Number of rounds for which finalized operations are kept in the operation history at
+    /// least. Zero disables the operation history.
+    #[serde(rename = "history_retention")]
+    #[serde(default)]
+    pub history_retention: u64,
+
+    /// Size in bytes of addresses on the remote chain, which lock targets must have. Zero means
+    /// 20-byte Ethereum addresses.
+    #[serde(rename = "remote_address_size")]
+    #[serde(default)]
+    pub remote_address_size: u64,
 }
 
 impl Default for Parameters {
@@ -111,10 +746,125 @@ impl Default for Parameters {
         Self {
             witnesses: vec![],
             threshold: 1,
+            threshold_policy: None,
             local_denominations: BTreeSet::new(),
             remote_denominations: BTreeMap::new(),
+            runtime_id: Namespace::default(),
+            remote_chain_id: 0,
+            remote_contract: None,
+            message_encoding: Default::default(),
+            eip712_domain: None,
+            admin: None,
+            guardians: vec![],
+            liveness_window: 0,
+            max_missed_operations: 0,
+            handover_window: 0,
+            witness_bond: None,
+            unbonding_delay: 0,
+            divergence_penalty: 0,
+            equivocation_penalty: 0,
+            jail_duration: 0,
+            reward_period: 0,
+            lock_fee: 0,
+            min_lock_fee: Quantity::default(),
+            max_lock_fee: Quantity::default(),
+            subsidy_fee: Quantity::default(),
+            min_lock_amounts: BTreeMap::new(),
+            max_transfer_amounts: BTreeMap::new(),
+            decimal_scales: BTreeMap::new(),
+            rate_limit_window: 0,
+            rate_limits: BTreeMap::new(),
+            account_limits: BTreeMap::new(),
+            release_baselines: BTreeMap::new(),
+            circuit_breaker_multiplier: 0,
+            challenge_period: 0,
+            challenge_thresholds: BTreeMap::new(),
+            blocked_addresses: BTreeSet::new(),
+            blocked_remote_addresses: BTreeSet::new(),
+            lock_allowlist: false,
+            lock_attester: None,
+            processed_deposit_retention: 0,
+            recovery_delay: 0,
+            max_votes_per_block: 0,
+            release_window: 0,
+            max_release_batch_size: 0,
+            operation_expiry: 0,
+            stale_vote_age: 0,
+            reset_stale_votes: false,
+            max_pending_locks: 0,
+            pull_releases: false,
+            history_retention: 0,
+            remote_address_size: 0,
+        }
+    }
+}
+
+impl Parameters {
+    /// Number of witnesses that needs to sign off given the number of active witnesses.
+    pub fn threshold_for(&self, active: u64) -> u64 {
+        match &self.threshold_policy {
+            Some(policy) => self.threshold.max(policy.threshold(active)),
+            None => self.threshold,
+        }
+    }
+
+    /// Current fee schedule.
+    pub fn fee_schedule(&self) -> types::FeeSchedule {
+        types::FeeSchedule {
+            lock_fee: self.lock_fee,
+            min_lock_fee: self.min_lock_fee.clone(),
+            max_lock_fee: self.max_lock_fee.clone(),
+            subsidy_fee: self.subsidy_fee.clone(),
+        }
+    }
+
+    /// Denomination that the given remote denomination identifier is configured for, if any.
+    pub fn local_denomination(
+        &self,
+        remote: &types::RemoteDenomination,
+    ) -> Option<&token::Denomination> {
+        self.remote_denominations
+            .iter()
+            .find(|(_, rd)| *rd == remote)
+            .map(|(denomination, _)| denomination)
+    }
+
+    /// Size in bytes of addresses on the remote chain.
+    pub fn remote_address_size(&self) -> usize {
+        match self.remote_address_size {
+            0 => types::RemoteAddress::DEFAULT_SIZE,
+            size => size as usize,
         }
     }
+
+    /// Amount that the given local amount corresponds to on the remote chain, or `None` if the
+    /// amount cannot be represented there without dust.
+    pub fn remote_amount(&self, amount: &token::BaseUnits) -> Option<Quantity> {
+        match self.decimal_scales.get(amount.denomination()) {
+            Some(scale) => scale.to_remote(amount.amount()),
+            None => Some(amount.amount().clone()),
+        }
+    }
+
+    /// Address of the bridge contract on the remote chain, if known.
+    ///
+    /// Falls back to the verifying contract of the EIP-712 domain when no remote contract is
+    /// configured.
+    pub fn remote_contract(&self) -> Option<types::RemoteAddress> {
+        self.remote_contract.or_else(|| {
+            self.eip712_domain
+                .as_ref()
+                .map(|domain| domain.verifying_contract)
+        })
+    }
+
+    /// Replace the fee parameters with the given fee schedule.
+    pub fn set_fee_schedule(&mut self, schedule: types::FeeSchedule) {
+        self.lock_fee = schedule.lock_fee;
+        self.min_lock_fee = schedule.min_lock_fee;
+        self.max_lock_fee = schedule.max_lock_fee;
+        self.subsidy_fee = schedule.subsidy_fee;
+    }
 }
 
 /// Errors emitted by the accounts module.
@@ -124,6 +874,36 @@ pub enum ParameterValidationError {
     TooManyWitnesses,
     #[error("a denomination cannot be both local and remote")]
     DenominationLocalAndRemote,
+    #[error("invalid witness public key or multisig configuration")]
+    InvalidWitnessKey,
+    #[error("duplicate witness")]
+    DuplicateWitness,
+    #[error("threshold must be between one and the number of witnesses")]
+    InvalidThreshold,
+    #[error("EIP-712 hashing requires the CBOR message encoding")]
+    ConflictingMessageEncoding,
+    #[error("ABI message encoding requires a remote contract")]
+    MissingRemoteContract,
+    #[error("liveness window too large")]
+    LivenessWindowTooLarge,
+    #[error("invalid threshold policy")]
+    InvalidThresholdPolicy,
+    #[error("witness bond denomination must be local")]
+    BondDenominationNotLocal,
+    #[error("invalid slashing penalty")]
+    InvalidPenalty,
+    #[error("invalid lock fee")]
+    InvalidLockFee,
+    #[error("minimum lock amount above maximum transfer amount")]
+    InvalidTransferLimits,
+    #[error("a remote denomination identifier cannot be used by more than one denomination")]
+    DuplicateRemoteDenomination,
+    #[error("remote address size too large")]
+    InvalidRemoteAddressSize,
+    #[error("invalid remote contract address")]
+    InvalidRemoteContract,
+    #[error("invalid decimal scale")]
+    InvalidDecimalScale,
 }
 
 impl module::Parameters for Parameters {
@@ -133,6 +913,70 @@ impl module::Parameters for Parameters {
         if self.witnesses.len() > (u16::MAX as usize) {
             return Err(ParameterValidationError::TooManyWitnesses);
         }
+        if !self.witnesses.iter().all(crypto::is_valid_witness) {
+            return Err(ParameterValidationError::InvalidWitnessKey);
+        }
+        let mut witness_ids = BTreeSet::new();
+        if !self
+            .witnesses
+            .iter()
+            .all(|witness| witness_ids.insert(witness.id()))
+        {
+            return Err(ParameterValidationError::DuplicateWitness);
+        }
+        if self.threshold == 0
+            || self.threshold_for(self.witnesses.len() as u64) > self.witnesses.len() as u64
+        {
+            return Err(ParameterValidationError::InvalidThreshold);
+        }
+        if self.eip712_domain.is_some() && self.message_encoding != types::MessageEncoding::Cbor {
+            return Err(ParameterValidationError::ConflictingMessageEncoding);
+        }
+        if self.message_encoding == types::MessageEncoding::Abi && self.remote_contract.is_none() {
+            return Err(ParameterValidationError::MissingRemoteContract);
+        }
+        if self.liveness_window > 64 {
+            return Err(ParameterValidationError::LivenessWindowTooLarge);
+        }
+        if self.remote_address_size > types::RemoteAddress::MAX_SIZE as u64 {
+            return Err(ParameterValidationError::InvalidRemoteAddressSize);
+        }
+        if let Some(contract) = &self.remote_contract {
+            if contract.size() != self.remote_address_size() {
+                return Err(ParameterValidationError::InvalidRemoteContract);
+            }
+            if matches!(&self.eip712_domain, Some(domain) if domain.verifying_contract != *contract)
+            {
+                return Err(ParameterValidationError::InvalidRemoteContract);
+            }
+        }
+        if let Some(policy) = &self.threshold_policy {
+            if policy.denominator == 0 || policy.numerator > policy.denominator {
+                return Err(ParameterValidationError::InvalidThresholdPolicy);
+            }
+        }
+
+        if let Some(bond) = &self.witness_bond {
+            if !self.local_denominations.contains(bond.denomination()) {
+                return Err(ParameterValidationError::BondDenominationNotLocal);
+            }
+        }
+
+        if self.divergence_penalty > BASIS_POINTS || self.equivocation_penalty > BASIS_POINTS {
+            return Err(ParameterValidationError::InvalidPenalty);
+        }
+        if self.lock_fee > BASIS_POINTS
+            || (self.max_lock_fee != Quantity::default() && self.min_lock_fee > self.max_lock_fee)
+        {
+            return Err(ParameterValidationError::InvalidLockFee);
+        }
+        for (denomination, max_amount) in &self.max_transfer_amounts {
+            if let Some(min_amount) = self.min_lock_amounts.get(denomination) {
+                if min_amount > max_amount {
+                    return Err(ParameterValidationError::InvalidTransferLimits);
+                }
+            }
+        }
 
         // Make sure a denomination is either local or remote, but not both.
         for rd in self.remote_denominations.keys() {
@@ -140,6 +984,27 @@ impl module::Parameters for Parameters {
                 return Err(ParameterValidationError::DenominationLocalAndRemote);
             }
         }
+        // Make sure remote denomination identifiers map back to a single denomination.
+        let mut remote_ids = BTreeSet::new();
+        if !self
+            .remote_denominations
+            .values()
+            .all(|remote| remote_ids.insert(remote))
+        {
+            return Err(ParameterValidationError::DuplicateRemoteDenomination);
+        }
+        for (denomination, scale) in &self.decimal_scales {
+            if !self.local_denominations.contains(denomination)
+                && !self.remote_denominations.contains_key(denomination)
+            {
+                return Err(ParameterValidationError::InvalidDecimalScale);
+            }
+            if scale.local.max(scale.remote) - scale.local.min(scale.remote)
+                > types::DecimalScale::MAX_DIFFERENCE
+            {
+                return Err(ParameterValidationError::InvalidDecimalScale);
+            }
+        }
 
         Ok(())
     }
@@ -162,28 +1027,154 @@ impl Default for Genesis {
 }
 
 /// State schema constants.
+///
+/// Incoming and outgoing operations are numbered independently for each denomination, so maps of
+/// sequence numbers are keyed by both the denomination and the sequence number.
 pub mod state {
-    /// Next outgoing (to external chain) sequence number.
-    pub const NEXT_OUT_SEQUENCE: &[u8] = &[0x01];
-    /// Next incoming (from external chain) sequence number.
-    pub const NEXT_IN_SEQUENCE: &[u8] = &[0x02];
+    /// Next outgoing (to external chain) sequence number shared by all denominations.
+    ///
+    /// Only read when migrating state from version 1.
+    pub const LEGACY_NEXT_OUT_SEQUENCE: &[u8] = &[0x01];
+    /// Next incoming (from external chain) sequence number shared by all denominations.
+    ///
+    /// Only read when migrating state from version 1.
+    pub const LEGACY_NEXT_IN_SEQUENCE: &[u8] = &[0x02];
 
     /// Map of outgoing sequence number to list of witness signatures.
+    ///
+    /// Only read when migrating state from version 1, which converts the entries to signature sets.
     pub const OUT_WITNESS_SIGNATURES: &[u8] = &[0x03];
     /// Map of incoming sequence number to list of witness signatures.
     pub const IN_WITNESS_SIGNATURES: &[u8] = &[0x04];
+    /// Map of outgoing sequence number to compact set of witness signatures.
+    pub const OUT_WITNESS_SIGNATURE_SETS: &[u8] = &[0x05];
+    /// Map of outgoing sequence number to quorum certificate.
+    pub const QUORUM_CERTIFICATES: &[u8] = &[0x06];
+    /// Map of witness address to the key it uses for signing witness payloads.
+    pub const WITNESS_SIGNING_KEYS: &[u8] = &[0x07];
+    /// Next witness set update sequence number.
+    pub const NEXT_WITNESS_SET_UPDATE: &[u8] = &[0x08];
+    /// Map of witness set update sequence number to witness votes.
+    pub const WITNESS_SET_UPDATE_VOTES: &[u8] = &[0x09];
+    /// Map of witness address to the witness key it was rotated from.
+    pub const ROTATED_WITNESS_KEYS: &[u8] = &[0x0a];
+    /// Map of witness set commitment to witness set snapshot.
+    pub const WITNESS_SETS: &[u8] = &[0x0b];
+    /// Map of outgoing sequence number to the commitment of its witness set snapshot.
+    pub const OUT_WITNESS_SETS: &[u8] = &[0x0c];
+    /// Map of witness identifier to witness liveness.
+    pub const WITNESS_LIVENESS: &[u8] = &[0x0d];
+    /// Map of witness identifier to witness metadata.
+    pub const WITNESS_METADATA: &[u8] = &[0x0e];
+    /// Map of witness set commitment to the handover to its successor.
+    pub const WITNESS_SET_HANDOVERS: &[u8] = &[0x0f];
+    /// Map of witness identifier to witness bond.
+    pub const WITNESS_BONDS: &[u8] = &[0x10];
+    /// Map of owner address to amounts being unbonded.
+    pub const UNBONDINGS: &[u8] = &[0x11];
+    /// Set of hashes of misbehavior that evidence has been submitted for.
+    pub const EVIDENCE: &[u8] = &[0x12];
+    /// Map of witness identifier to the number of finalized operations it signed in the current
+    /// reward period.
+    pub const REWARD_POINTS: &[u8] = &[0x13];
+    /// Map of witness identifier to its next expected vote nonces.
+    pub const VOTE_NONCES: &[u8] = &[0x14];
+    /// Map of hashes of witness identifier and incoming sequence number to the operations that
+    /// the witness retracted its votes for.
+    pub const RETRACTED_VOTES: &[u8] = &[0x15];
+    /// Next fee schedule update sequence number.
+    pub const NEXT_FEE_SCHEDULE_UPDATE: &[u8] = &[0x16];
+    /// Map of fee schedule update sequence number to witness votes.
+    pub const FEE_SCHEDULE_VOTES: &[u8] = &[0x17];
+    /// Map of remote denomination to its attested remote reserve and minted supply.
+    pub const REMOTE_RESERVES: &[u8] = &[0x18];
+    /// Bridge statistics.
+    pub const STATS: &[u8] = &[0x19];
+    /// Whether the bridge is paused.
+    pub const PAUSED: &[u8] = &[0x1a];
+    /// Set of frozen denominations.
+    pub const FROZEN_DENOMINATIONS: &[u8] = &[0x1b];
+    /// Map of denomination to the amount locked and released during the current rate limit window.
+    pub const RATE_LIMIT_VOLUMES: &[u8] = &[0x1c];
+    /// Map of account address to the amounts it locked and received during its latest rate limit
+    /// window.
+    pub const ACCOUNT_VOLUMES: &[u8] = &[0x1d];
+    /// Map of denomination to the amount released during the current rate limit window.
+    pub const RELEASE_VOLUMES: &[u8] = &[0x1e];
+    /// Map of incoming sequence number to releases waiting for their challenge period to pass.
+    pub const PENDING_RELEASES: &[u8] = &[0x1f];
+    /// Set of addresses that can lock funds when the lock allowlist is enabled.
+    pub const ALLOWED_ADDRESSES: &[u8] = &[0x20];
+    /// Map of incoming sequence number to conflicting operations proposed for it.
+    pub const CONFLICTS: &[u8] = &[0x21];
+    /// Map of remote transaction hash and log index to the round in which the deposit was
+    /// released.
+    pub const PROCESSED_DEPOSITS: &[u8] = &[0x22];
+    /// Map of recovery identifier to recoveries waiting for their delay to pass.
+    pub const RECOVERIES: &[u8] = &[0x23];
+    /// Next recovery identifier.
+    pub const NEXT_RECOVERY: &[u8] = &[0x24];
+    /// Map of account address to the votes it submitted in the latest block it voted in.
+    pub const BLOCK_VOTES: &[u8] = &[0x25];
+    /// Map of remote chain identifier to the next outgoing (to external chain) sequence number of
+    /// each denomination.
+    pub const NEXT_OUT_SEQUENCES: &[u8] = &[0x26];
+    /// Map of remote chain identifier to the next incoming (from external chain) sequence number
+    /// of each denomination.
+    pub const NEXT_IN_SEQUENCES: &[u8] = &[0x27];
+    /// Map of incoming sequence number to the claims committed to by the finalized release.
+    pub const CLAIMS_ROOTS: &[u8] = &[0x28];
+    /// Map of incoming sequence number and claim index to the round in which the claim was paid
+    /// out.
+    pub const CLAIMED: &[u8] = &[0x29];
+    /// Map of address to the amounts that pull releases credited to it and that it has not
+    /// claimed yet.
+    pub const CLAIMABLE_BALANCES: &[u8] = &[0x2a];
+    /// Map of denomination to its display metadata.
+    pub const DENOMINATION_METADATA: &[u8] = &[0x2b];
+    /// Map of history index to finalized operation, in order of finalization.
+    pub const HISTORY: &[u8] = &[0x2c];
+    /// Next history index.
+    pub const NEXT_HISTORY_INDEX: &[u8] = &[0x2d];
+    /// Map of target address and incoming sequence number to the completed release to the
+    /// address.
+    pub const RELEASES_BY_TARGET: &[u8] = &[0x2e];
+    /// Next remote contract update sequence number.
+    pub const NEXT_REMOTE_CONTRACT_UPDATE: &[u8] = &[0x2f];
+    /// Map of remote contract update sequence number to witness votes.
+    pub const REMOTE_CONTRACT_VOTES: &[u8] = &[0x30];
+    /// Next denomination registration sequence number.
+    pub const NEXT_DENOMINATION_REGISTRATION: &[u8] = &[0x31];
+    /// Map of denomination registration sequence number to witness votes.
+    pub const DENOMINATION_REGISTRATION_VOTES: &[u8] = &[0x32];
 }
 
-pub struct Module<Accounts: modules::accounts::API> {
+pub struct Module<
+    Accounts: modules::accounts::API,
+    Verifier: IncomingVerifier<Accounts> = verifier::WitnessQuorum,
+> {
     _accounts: std::marker::PhantomData<Accounts>,
+    _verifier: std::marker::PhantomData<Verifier>,
 }
 
 lazy_static! {
     /// Module's address where all locked funds are stored.
     pub static ref ADDRESS_LOCKED_FUNDS: Address = Address::from_module(MODULE_NAME, "locked-funds");
+    /// Module's address where all witness bonds are stored.
+    pub static ref ADDRESS_WITNESS_BONDS: Address = Address::from_module(MODULE_NAME, "witness-bonds");
+    /// Module's address where witness rewards are accumulated until they are distributed.
+    pub static ref ADDRESS_REWARD_POOL: Address = Address::from_module(MODULE_NAME, "reward-pool");
+    /// Module's address where bridge fees are accumulated.
+    pub static ref ADDRESS_BRIDGE_FEES: Address = Address::from_module(MODULE_NAME, "bridge-fees");
+    /// Module's address from which witnesses are reimbursed for transaction fees.
+    pub static ref ADDRESS_SUBSIDY_POOL: Address = Address::from_module(MODULE_NAME, "subsidy-pool");
+    /// Module's address where released funds are held until they are claimed.
+    pub static ref ADDRESS_PENDING_CLAIMS: Address = Address::from_module(MODULE_NAME, "pending-claims");
 }
 
-impl<Accounts: modules::accounts::API> Module<Accounts> {
+impl<Accounts: modules::accounts::API, Verifier: IncomingVerifier<Accounts>>
+    Module<Accounts, Verifier>
+{
     fn ensure_local_or_remote<C: Context>(
         ctx: &mut C,
         denomination: &token::Denomination,
@@ -202,249 +1193,5129 @@ impl<Accounts: modules::accounts::API> Module<Accounts> {
         Err(Error::UnsupportedDenomination)
     }
 
-    fn tx_lock<C: TxContext>(ctx: &mut C, body: types::Lock) -> Result<types::LockResult, Error> {
-        let remote = Self::ensure_local_or_remote(ctx, body.amount.denomination())?;
-        let caller_address = ctx.tx_caller_address();
+    /// Whether the given address is an account internal to the bridge or to fee accounting.
+    ///
+    /// Funds must never be released into such accounts as that would corrupt the reserve
+    /// accounting of the bridge.
+    fn is_internal_address(address: &Address) -> bool {
+        [
+            *ADDRESS_LOCKED_FUNDS,
+            *ADDRESS_WITNESS_BONDS,
+            *ADDRESS_REWARD_POOL,
+            *ADDRESS_BRIDGE_FEES,
+            *ADDRESS_SUBSIDY_POOL,
+            *ADDRESS_PENDING_CLAIMS,
+            *modules::accounts::ADDRESS_FEE_ACCUMULATOR,
+            *modules::accounts::ADDRESS_COMMON_POOL,
+        ]
+        .contains(address)
+    }
 
-        if ctx.is_check_only() {
-            return Ok(types::LockResult { id: 0 });
+    /// Find the index of the witness in the given witness set that the given address acts for,
+    /// together with the witness key that its payloads must be verified against.
+    ///
+    /// Witnesses that rotated their key can still act using the previous key for operations that
+    /// were in flight at the time of the rotation, as determined by `in_flight`.
+    fn find_witness<S, F>(
+        mut store: S,
+        witnesses: &[types::WitnessKey],
+        address: &Address,
+        in_flight: F,
+    ) -> Result<(u16, types::WitnessKey), Error>
+    where
+        S: storage::Store,
+        F: Fn(&types::RotatedWitnessKey) -> bool,
+    {
+        if let Some((index, witness)) = witnesses
+            .iter()
+            .enumerate()
+            .find(|(_, witness)| witness.is_authorized(address))
+        {
+            return Ok((index as u16, witness.clone()));
         }
 
-        // Transfer funds from user's account into the bridge-owned account.
-        Accounts::transfer(ctx, caller_address, *ADDRESS_LOCKED_FUNDS, &body.amount)?;
-
-        // Assign a unique identifier to the event.
-        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
-        let mut tstore = storage::TypedStore::new(&mut store);
-        let id: u64 = tstore.get(state::NEXT_OUT_SEQUENCE).unwrap_or_default();
-        tstore.insert(state::NEXT_OUT_SEQUENCE, &(id + 1));
-
-        // Create an entry in outgoing witness signatures map.
-        let amount = body.amount.clone();
-        let target = body.target;
-        let mut out_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+        let rotated_keys = storage::TypedStore::new(storage::PrefixStore::new(
             &mut store,
-            &state::OUT_WITNESS_SIGNATURES,
+            &state::ROTATED_WITNESS_KEYS,
         ));
-        out_witness_signatures.insert(
-            id.to_storage_key(),
-            &types::WitnessSignatures::new(id, types::Operation::Lock(body)),
-        );
-
-        // If this is a remote denomination burn the amount from the bridge-owned account. If this
-        // is a local denomination, then the amount just stays locked in the account.
-        if remote.is_some() {
-            Accounts::burn(ctx, *ADDRESS_LOCKED_FUNDS, &amount)?;
+        let rotated: types::RotatedWitnessKey =
+            rotated_keys.get(address).ok_or(Error::NotAuthorized)?;
+        if !in_flight(&rotated) {
+            return Err(Error::NotAuthorized);
+        }
+        // Follow the chain of rotations to the current key. Keys are never reused, so the chain
+        // can not contain cycles.
+        let mut successor = rotated.successor.clone();
+        loop {
+            if let Some(index) = witnesses
+                .iter()
+                .position(|witness| witness.public_key() == Some(&successor))
+            {
+                return Ok((index as u16, rotated.key.into()));
+            }
+            let next: types::RotatedWitnessKey = rotated_keys
+                .get(Address::from_pk(&successor))
+                .ok_or(Error::NotAuthorized)?;
+            successor = next.successor;
         }
+    }
 
-        // Emit a lock event.
-        ctx.emit_event(Event::Lock {
-            id,
-            owner: caller_address,
-            target,
-            amount,
-        });
+    /// Key that the given witness uses for signing witness payloads.
+    ///
+    /// This is the key registered via `bridge.SetWitnessSigningKey` if any, otherwise it is the
+    /// witness' own key. Multisig witnesses always use their own configuration.
+    fn signing_key<S: storage::Store>(
+        mut store: S,
+        witness: &types::WitnessKey,
+    ) -> types::WitnessKey {
+        let pk = match witness {
+            types::WitnessKey::PublicKey(pk) => pk,
+            types::WitnessKey::Multisig(_) => return witness.clone(),
+        };
+        let signing_keys = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_SIGNING_KEYS,
+        ));
+        signing_keys
+            .get::<_, PublicKey>(Address::from_pk(pk))
+            .map(Into::into)
+            .unwrap_or_else(|| witness.clone())
+    }
 
-        Ok(types::LockResult { id })
+    /// Signing keys of all given witnesses, ordered by witness index.
+    fn signing_keys<S: storage::Store>(
+        mut store: S,
+        witnesses: &[types::WitnessKey],
+    ) -> Vec<types::WitnessKey> {
+        witnesses
+            .iter()
+            .map(|witness| Self::signing_key(&mut store, witness))
+            .collect()
     }
 
-    fn tx_witness<C: TxContext>(ctx: &mut C, body: types::Witness) -> Result<(), Error> {
-        if ctx.is_check_only() {
-            return Ok(());
+    /// Snapshot the current witness set, returning its commitment.
+    ///
+    /// Inactive witnesses are left out as long as the remaining witnesses can reach the threshold.
+    fn snapshot_witness_set<S: storage::Store>(mut store: S, params: &Parameters) -> Hash {
+        let liveness = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_LIVENESS,
+        ));
+        let mut witnesses: Vec<types::WitnessKey> = params
+            .witnesses
+            .iter()
+            .filter(|witness| {
+                !liveness
+                    .get::<_, types::WitnessLiveness>(witness.id())
+                    .unwrap_or_default()
+                    .inactive
+            })
+            .cloned()
+            .collect();
+        let mut threshold = params.threshold_for(witnesses.len() as u64);
+        if (witnesses.len() as u64) < threshold {
+            witnesses = params.witnesses.clone();
+            threshold = params.threshold_for(witnesses.len() as u64);
         }
 
-        let caller_address = ctx.tx_caller_address();
-        let params = Self::params(ctx.runtime_state());
-        // Make sure the caller is an authorized witness.
-        let (index, _pk) = params
+        let commitment = crypto::witness_set_commitment(&witnesses, threshold);
+        let mut witness_sets =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::WITNESS_SETS));
+        if witness_sets
+            .get::<_, types::WitnessSet>(&commitment)
+            .is_none()
+        {
+            witness_sets.insert(
+                &commitment,
+                &types::WitnessSet {
+                    witnesses,
+                    threshold,
+                },
+            );
+        }
+        commitment
+    }
+
+    /// Successor of the witness set with the given commitment if a handover is in progress in the
+    /// given round.
+    fn handover_witness_set<S: storage::Store>(
+        mut store: S,
+        commitment: &Hash,
+        round: u64,
+    ) -> Option<(Hash, types::WitnessSet)> {
+        let handovers = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_SET_HANDOVERS,
+        ));
+        let handover: types::WitnessSetHandover = handovers.get(commitment)?;
+        if round > handover.ends {
+            return None;
+        }
+        let witness_sets =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::WITNESS_SETS));
+        let witness_set = witness_sets.get(&handover.to)?;
+        Some((handover.to, witness_set))
+    }
+
+    /// Replace the witness set in the parameters, starting a handover from the previous witness set
+    /// if a handover window is configured.
+    fn set_witness_set<C: Context>(ctx: &mut C, params: &Parameters) {
+        let previous = Self::params(ctx.runtime_state());
+        if params.handover_window > 0 {
+            let round = ctx.runtime_header().round;
+            let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+            let from = Self::snapshot_witness_set(&mut store, &previous);
+            let to = Self::snapshot_witness_set(&mut store, params);
+            if from != to {
+                let mut handovers = storage::TypedStore::new(storage::PrefixStore::new(
+                    &mut store,
+                    &state::WITNESS_SET_HANDOVERS,
+                ));
+                handovers.insert(
+                    &from,
+                    &types::WitnessSetHandover {
+                        to,
+                        ends: round.saturating_add(params.handover_window),
+                    },
+                );
+            }
+        }
+
+        Self::set_params(ctx.runtime_state(), params);
+    }
+
+    /// Number of witnesses in the witness set that have not been marked inactive.
+    fn active_witness_count<S: storage::Store>(mut store: S, params: &Parameters) -> u64 {
+        let liveness = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_LIVENESS,
+        ));
+        params
             .witnesses
             .iter()
-            .enumerate()
-            .find(|(_, pk)| Address::from_pk(pk) == caller_address)
-            .ok_or(Error::NotAuthorized)?;
+            .filter(|witness| {
+                !liveness
+                    .get::<_, types::WitnessLiveness>(witness.id())
+                    .unwrap_or_default()
+                    .inactive
+            })
+            .count() as u64
+    }
 
-        // Check if sequence number is correct.
-        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
-        let mut out_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+    /// Number of witnesses that currently needs to sign off.
+    fn current_threshold<S: storage::Store>(store: S, params: &Parameters) -> u64 {
+        params.threshold_for(Self::active_witness_count(store, params))
+    }
+
+    /// Record which witnesses of the given witness set signed a finalized outgoing operation,
+    /// returning the witnesses that have been marked inactive as a result.
+    ///
+    /// A witness is never marked inactive if that would leave too few active witnesses to reach
+    /// the threshold.
+    fn record_participation<S: storage::Store>(
+        mut store: S,
+        params: &Parameters,
+        witness_set: &types::WitnessSet,
+        signers: &types::WitnessBitmap,
+    ) -> Vec<(u16, types::WitnessKey)> {
+        if params.liveness_window == 0 && params.max_missed_operations == 0 {
+            return vec![];
+        }
+
+        let mut active = Self::active_witness_count(&mut store, params);
+        let mut liveness = storage::TypedStore::new(storage::PrefixStore::new(
             &mut store,
-            &state::OUT_WITNESS_SIGNATURES,
+            &state::WITNESS_LIVENESS,
         ));
-        let mut info: types::WitnessSignatures = out_witness_signatures
-            .get(body.id.to_storage_key())
-            .ok_or(Error::InvalidSequenceNumber)?;
+        let mut demoted = vec![];
+        for (index, witness) in witness_set.witnesses.iter().enumerate() {
+            let mut info: types::WitnessLiveness = liveness.get(witness.id()).unwrap_or_default();
+            info.record(signers.contains(index as u16));
 
-        // Make sure it didn't already submit a signature.
-        if info.witnesses.iter().any(|i| *i as usize == index) {
-            return Err(Error::AlreadySubmittedSignature);
+            if !info.inactive
+                && params.max_missed_operations > 0
+                && info.missed >= params.max_missed_operations
+                && active.checked_sub(1).map_or(false, |remaining| {
+                    remaining >= params.threshold_for(remaining)
+                })
+            {
+                // Only witnesses that are still part of the witness set can be marked inactive.
+                if let Some(index) = params.witnesses.iter().position(|w| w == witness) {
+                    info.inactive = true;
+                    active -= 1;
+                    demoted.push((index as u16, witness.clone()));
+                }
+            }
+
+            liveness.insert(witness.id(), &info);
         }
-        // TODO: Validate witness signature.
-        // TODO: Verify signature against the remote denomination.
+        demoted
+    }
 
-        // Store signature in storage.
-        info.witnesses.push(index as u16);
-        info.signatures.push(body.signature);
-        // Check if there's enough signatures.
-        if (info.witnesses.len() as u64) < params.threshold {
-            // Not enough signatures yet.
-            out_witness_signatures.insert(body.id.to_storage_key(), &info);
-            return Ok(());
+    /// Credit a reward point to each of the given witnesses for signing a finalized operation.
+    fn add_reward_points<'a, S, I>(mut store: S, params: &Parameters, witnesses: I)
+    where
+        S: storage::Store,
+        I: IntoIterator<Item = &'a types::WitnessKey>,
+    {
+        if params.reward_period == 0 {
+            return;
+        }
+        let mut points =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::REWARD_POINTS));
+        for witness in witnesses {
+            let current: u64 = points.get(witness.id()).unwrap_or_default();
+            points.insert(witness.id(), &current.saturating_add(1));
         }
+    }
 
-        // Clear entry in storage.
-        out_witness_signatures.remove(body.id.to_storage_key());
+    /// Account that rewards of the given witness are paid out to, if any.
+    ///
+    /// This is the owner of the witness' bond, or the witness' own account for single-key
+    /// witnesses without a bond.
+    fn reward_address<S: storage::Store>(
+        mut store: S,
+        witness: &types::WitnessKey,
+    ) -> Option<Address> {
+        let bonds =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::WITNESS_BONDS));
+        bonds
+            .get::<_, types::WitnessBond>(witness.id())
+            .map(|bond| bond.owner)
+            .or_else(|| witness.public_key().map(Address::from_pk))
+    }
 
-        // Emit the collected signatures.
-        ctx.emit_event(Event::WitnessesSigned(info));
+    /// Split the given fee evenly among the given witnesses, paying each share to the witness'
+    /// reward address.
+    ///
+    /// Witnesses without a reward address forfeit their share and any remainder of the division
+    /// stays in the bridge fee account.
+    fn split_fee<S: storage::Store>(
+        mut store: S,
+        fee: &token::BaseUnits,
+        witnesses: &[(u16, types::WitnessKey)],
+    ) -> Vec<(u16, types::WitnessKey, Address, token::BaseUnits)> {
+        let share = fee
+            .amount()
+            .checked_div(&Quantity::from(witnesses.len() as u64))
+            .unwrap_or_default();
+        if share == Quantity::default() {
+            return vec![];
+        }
+        witnesses
+            .iter()
+            .filter_map(|(index, witness)| {
+                let address = Self::reward_address(&mut store, witness)?;
+                let amount = token::BaseUnits::new(share.clone(), fee.denomination().clone());
+                Some((*index, witness.clone(), address, amount))
+            })
+            .collect()
+    }
 
-        Ok(())
+    /// Distribute the reward pool to the witnesses in proportion to their reward points and start
+    /// a new reward period.
+    ///
+    /// Any remainder of the division stays in the reward pool for the next reward period. Points
+    /// of witnesses that were removed during the reward period are forfeited.
+    fn distribute_rewards<C: Context>(ctx: &mut C, params: &Parameters) {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut recipients = vec![];
+        for (index, witness) in params.witnesses.iter().enumerate() {
+            let address = Self::reward_address(&mut store, witness);
+            let points = storage::TypedStore::new(storage::PrefixStore::new(
+                &mut store,
+                &state::REWARD_POINTS,
+            ));
+            let witness_points: u64 = points.get(witness.id()).unwrap_or_default();
+            if let (Some(address), true) = (address, witness_points > 0) {
+                recipients.push((index as u16, witness, address, witness_points));
+            }
+        }
+        // Every reward period starts from scratch.
+        let mut points =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::REWARD_POINTS));
+        let entries: Vec<(Vec<u8>, u64)> = points.iter().collect();
+        for (key, _) in entries {
+            points.remove(key);
+        }
+        let total: u64 = recipients.iter().map(|(_, _, _, points)| points).sum();
+        if total == 0 {
+            return;
+        }
+
+        let balances = Accounts::get_balances(ctx.runtime_state(), *ADDRESS_REWARD_POOL)
+            .map(|balances| balances.balances)
+            .unwrap_or_default();
+        for (denomination, balance) in balances {
+            for (index, witness, address, points) in &recipients {
+                let share = balance
+                    .checked_mul(&Quantity::from(*points))
+                    .and_then(|amount| amount.checked_div(&Quantity::from(total)))
+                    .unwrap_or_default();
+                if share == Quantity::default() {
+                    continue;
+                }
+                let amount = token::BaseUnits::new(share, denomination.clone());
+                if let Err(err) = Accounts::transfer(ctx, *ADDRESS_REWARD_POOL, *address, &amount) {
+                    // Skip the share, it stays in the reward pool for the next reward period.
+                    slog::warn!(
+                        ctx.get_logger(MODULE_NAME),
+                        "failed to pay witness reward";
+                        "index" => *index,
+                        "denomination" => %denomination,
+                        "err" => %err,
+                    );
+                    continue;
+                }
+                ctx.emit_event(Event::WitnessRewarded {
+                    index: *index,
+                    witness: (*witness).clone(),
+                    amount,
+                });
+            }
+        }
     }
 
-    fn tx_release<C: TxContext>(ctx: &mut C, body: types::Release) -> Result<(), Error> {
-        let remote = Self::ensure_local_or_remote(ctx, body.amount.denomination())?;
-        let caller_address = ctx.tx_caller_address();
+    /// Witness set with the given commitment, or the current witness set if there is none.
+    fn witness_set<S: storage::Store>(
+        mut store: S,
+        params: &Parameters,
+        commitment: Option<Hash>,
+    ) -> types::WitnessSet {
+        let witness_sets =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::WITNESS_SETS));
+        commitment
+            .and_then(|commitment| witness_sets.get(commitment))
+            .unwrap_or_else(|| types::WitnessSet {
+                witnesses: params.witnesses.clone(),
+                threshold: params.threshold_for(params.witnesses.len() as u64),
+            })
+    }
 
-        if ctx.is_check_only() {
-            return Ok(());
+    /// Outgoing operation with the given sequence number that has not been finalized yet.
+    fn pending_out_operation<S: storage::Store>(
+        store: S,
+        denomination: &token::Denomination,
+        id: u64,
+    ) -> Option<types::WitnessSignatureSet> {
+        let out_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            store,
+            &state::OUT_WITNESS_SIGNATURE_SETS,
+        ));
+        out_witness_signatures.get(sequence_key(denomination, id))
+    }
+
+    /// Remove the witness with the given index from all pending operations and witness set update
+    /// votes, shifting the indices of all following witnesses down by one.
+    ///
+    /// Outgoing operations with a witness set snapshot are left untouched.
+    fn remove_pending_witness<S: storage::Store>(mut store: S, index: u16) {
+        // Outgoing operations.
+        let mut out_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::OUT_WITNESS_SIGNATURE_SETS,
+        ));
+        let entries: Vec<(Vec<u8>, types::WitnessSignatureSet)> =
+            out_witness_signatures.iter().collect();
+        for (key, mut info) in entries {
+            if info.witness_set.is_some() {
+                continue;
+            }
+            info.remove_witness(index);
+            out_witness_signatures.insert(key, &info);
         }
 
-        let params = Self::params(ctx.runtime_state());
-        // Make sure the caller is an authorized witness.
-        let (index, _pk) = params
-            .witnesses
-            .iter()
-            .enumerate()
-            .find(|(_, pk)| Address::from_pk(pk) == caller_address)
-            .ok_or(Error::NotAuthorized)?;
-        let index = index as u16;
+        // Incoming operations.
+        let mut in_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::IN_WITNESS_SIGNATURES,
+        ));
+        let entries: Vec<(Vec<u8>, types::IncomingWitnessSignatures)> =
+            in_witness_signatures.iter().collect();
+        for (key, mut info) in entries {
+            info.remove_witness(index);
+            in_witness_signatures.insert(key, &info);
+        }
 
-        // Check if sequence number is correct. This requires that all events are processed in
-        // sequence by the witnesses and no events are missed.
-        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
-        let tstore = storage::TypedStore::new(&mut store);
-        let expected_id: u64 = tstore.get(state::NEXT_IN_SEQUENCE).unwrap_or_default();
-        if body.id != expected_id {
-            return Err(Error::InvalidSequenceNumber);
+        // Witness set update votes.
+        let mut update_votes = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_SET_UPDATE_VOTES,
+        ));
+        let entries: Vec<([u8; 8], types::UpdateVotes)> = update_votes.iter().collect();
+        for (key, mut votes) in entries {
+            votes.remove_witness(index);
+            update_votes.insert(key, &votes);
         }
 
-        // Fetch existing signatures.
+        // Fee schedule update votes.
+        let mut fee_votes = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::FEE_SCHEDULE_VOTES,
+        ));
+        let entries: Vec<([u8; 8], types::UpdateVotes)> = fee_votes.iter().collect();
+        for (key, mut votes) in entries {
+            votes.remove_witness(index);
+            fee_votes.insert(key, &votes);
+        }
+
+        // Remote contract update votes.
+        let mut contract_votes = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::REMOTE_CONTRACT_VOTES,
+        ));
+        let entries: Vec<([u8; 8], types::UpdateVotes)> = contract_votes.iter().collect();
+        for (key, mut votes) in entries {
+            votes.remove_witness(index);
+            contract_votes.insert(key, &votes);
+        }
+
+        // Denomination registration votes.
+        let mut registration_votes = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::DENOMINATION_REGISTRATION_VOTES,
+        ));
+        let entries: Vec<([u8; 8], types::UpdateVotes)> = registration_votes.iter().collect();
+        for (key, mut votes) in entries {
+            votes.remove_witness(index);
+            registration_votes.insert(key, &votes);
+        }
+    }
+
+    /// Clear the witness votes on all pending operations and updates, as their witness indices
+    /// refer to a witness set that has been replaced.
+    ///
+    /// Outgoing operations with a witness set snapshot are left untouched.
+    fn clear_pending_votes<S: storage::Store>(mut store: S) {
+        // Outgoing operations.
+        let mut out_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::OUT_WITNESS_SIGNATURE_SETS,
+        ));
+        let entries: Vec<(Vec<u8>, types::WitnessSignatureSet)> =
+            out_witness_signatures.iter().collect();
+        for (key, mut info) in entries {
+            if info.witness_set.is_some() {
+                continue;
+            }
+            info.signers = Default::default();
+            info.signatures.clear();
+            out_witness_signatures.insert(key, &info);
+        }
+
+        // Incoming operations.
         let mut in_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
             &mut store,
             &state::IN_WITNESS_SIGNATURES,
         ));
-        let mut info: types::IncomingWitnessSignatures = in_witness_signatures
-            .get(body.id.to_storage_key())
-            .unwrap_or_default();
+        let keys: Vec<Vec<u8>> = in_witness_signatures
+            .iter()
+            .map(|(key, _): (Vec<u8>, types::IncomingWitnessSignatures)| key)
+            .collect();
+        for key in keys {
+            in_witness_signatures.remove(key);
+        }
 
-        // Make sure it didn't already submit a signature.
-        if info.witnesses.iter().any(|i| i == &index) {
-            return Err(Error::AlreadySubmittedSignature);
+        // Witness set update, fee schedule update, remote contract update and denomination
+        // registration votes.
+        for prefix in &[
+            state::WITNESS_SET_UPDATE_VOTES,
+            state::FEE_SCHEDULE_VOTES,
+            state::REMOTE_CONTRACT_VOTES,
+            state::DENOMINATION_REGISTRATION_VOTES,
+        ] {
+            let mut votes = storage::TypedStore::new(storage::PrefixStore::new(&mut store, prefix));
+            let keys: Vec<[u8; 8]> = votes
+                .iter()
+                .map(|(key, _): ([u8; 8], types::UpdateVotes)| key)
+                .collect();
+            for key in keys {
+                votes.remove(key);
+            }
         }
+    }
 
-        // There can be multiple different operations proposed for the sequence (in case some
-        // witnesses are corrupted). We handle these by hashing the operation and using that as the
-        // discriminator.
-        let op = types::Operation::Release(body.clone());
-        let op_id = types::OperationId::from(&op);
-        let op_sigs = info
-            .ops
-            .entry(op_id)
-            .or_insert_with(|| types::WitnessSignatures::new(body.id, op));
-        // TODO: Validate witness signature.
-        // TODO: Verify signature against the remote denomination.
+    /// Ensure that the given member of a witness set has bonded at least the amount required by
+    /// the parameters.
+    ///
+    /// Bonds are held by the current key of a witness, so members that rotated their key since
+    /// the witness set was recorded are covered by the bond of their successor key.
+    fn ensure_bonded<S: storage::Store>(
+        mut store: S,
+        params: &Parameters,
+        witness: &types::WitnessKey,
+    ) -> Result<(), Error> {
+        let required = match &params.witness_bond {
+            Some(required) => required,
+            None => return Ok(()),
+        };
+        let mut witness = witness.clone();
+        let bond = loop {
+            let bonds = storage::TypedStore::new(storage::PrefixStore::new(
+                &mut store,
+                &state::WITNESS_BONDS,
+            ));
+            if let Some(bond) = bonds.get::<_, types::WitnessBond>(witness.id()) {
+                break Some(bond);
+            }
+            // Keys are never reused, so the chain of rotations can not contain cycles.
+            let rotated_keys = storage::TypedStore::new(storage::PrefixStore::new(
+                &mut store,
+                &state::ROTATED_WITNESS_KEYS,
+            ));
+            match witness.public_key().and_then(|pk| {
+                rotated_keys.get::<_, types::RotatedWitnessKey>(Address::from_pk(pk))
+            }) {
+                Some(rotated) => witness = rotated.successor.into(),
+                None => break None,
+            }
+        };
+        match bond {
+            Some(bond)
+                if bond.amount.denomination() == required.denomination()
+                    && bond.amount.amount() >= required.amount() =>
+            {
+                Ok(())
+            }
+            _ => Err(Error::InsufficientBond),
+        }
+    }
 
-        // Store which witnesses signed in storage. Note that in the incoming case we don't need to
-        // store the actual signatures as we verify them here and no longer need them.
-        info.witnesses.push(index);
-        op_sigs.witnesses.push(index);
-        // Check if there's enough signatures.
-        if (op_sigs.witnesses.len() as u64) < params.threshold {
-            // Not enough signatures yet.
-            in_witness_signatures.insert(body.id.to_storage_key(), &info);
-            return Ok(());
+    /// Start unbonding the given amount, returning the round after which it can be withdrawn.
+    fn start_unbonding<S: storage::Store>(
+        mut store: S,
+        params: &Parameters,
+        owner: Address,
+        amount: token::BaseUnits,
+        round: u64,
+    ) -> u64 {
+        let ends = round.saturating_add(params.unbonding_delay);
+        let mut unbondings =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::UNBONDINGS));
+        let mut pending: Vec<types::Unbonding> = unbondings.get(owner).unwrap_or_default();
+        pending.push(types::Unbonding { amount, ends });
+        unbondings.insert(owner, &pending);
+        ends
+    }
+
+    /// Slash the given portion, in basis points, of the bond of the given witness, returning the
+    /// slashed amount if any.
+    ///
+    /// The slashed amount stays in the bond account until it is moved out by the caller.
+    fn slash_bond<S: storage::Store>(
+        mut store: S,
+        witness: &types::WitnessKey,
+        penalty: u64,
+    ) -> Option<token::BaseUnits> {
+        let mut bonds =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::WITNESS_BONDS));
+        let bond: types::WitnessBond = bonds.get(witness.id())?;
+        let slashed = bond
+            .amount
+            .amount()
+            .checked_mul(&Quantity::from(penalty.min(BASIS_POINTS)))?
+            .checked_div(&Quantity::from(BASIS_POINTS))?;
+        if slashed == Quantity::default() {
+            return None;
+        }
+        let remaining = bond.amount.amount().checked_sub(&slashed)?;
+        let denomination = bond.amount.denomination().clone();
+        if remaining == Quantity::default() {
+            bonds.remove(witness.id());
+        } else {
+            bonds.insert(
+                witness.id(),
+                &types::WitnessBond {
+                    amount: token::BaseUnits::new(remaining, denomination.clone()),
+                    ..bond
+                },
+            );
+        }
+        Some(token::BaseUnits::new(slashed, denomination))
+    }
+
+    /// Next incoming or outgoing sequence numbers of all denominations for traffic with the given
+    /// remote chain.
+    fn next_sequences<S: storage::Store>(
+        mut store: S,
+        remote_chain_id: u64,
+        outgoing: bool,
+    ) -> BTreeMap<token::Denomination, u64> {
+        let sequences = storage::TypedStore::new(&mut store);
+        sequences
+            .get(next_sequences_key(remote_chain_id, outgoing))
+            .unwrap_or_default()
+    }
+
+    /// Next incoming or outgoing sequence number of the given denomination for traffic with the
+    /// given remote chain.
+    fn next_sequence<S: storage::Store>(
+        store: S,
+        remote_chain_id: u64,
+        outgoing: bool,
+        denomination: &token::Denomination,
+    ) -> u64 {
+        Self::next_sequences(store, remote_chain_id, outgoing)
+            .get(denomination)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Advance the next incoming or outgoing sequence number of the given denomination for traffic
+    /// with the given remote chain, returning the sequence number before the advance.
+    fn advance_sequence<S: storage::Store>(
+        mut store: S,
+        remote_chain_id: u64,
+        outgoing: bool,
+        denomination: &token::Denomination,
+    ) -> u64 {
+        let mut next_sequences = Self::next_sequences(&mut store, remote_chain_id, outgoing);
+        let next = next_sequences.entry(denomination.clone()).or_default();
+        let id = *next;
+        *next += 1;
+
+        let mut sequences = storage::TypedStore::new(&mut store);
+        sequences.insert(
+            next_sequences_key(remote_chain_id, outgoing),
+            &next_sequences,
+        );
+        id
+    }
+
+    /// Next sequence number of the given denomination recorded at the time of a key rotation.
+    fn rotated_sequence(
+        sequences: &BTreeMap<token::Denomination, u64>,
+        denomination: &token::Denomination,
+    ) -> u64 {
+        sequences.get(denomination).copied().unwrap_or_default()
+    }
+
+    /// Ensure that the given nonce is the next vote nonce of the witness that the given address
+    /// acts for in the given direction, and advance it.
+    ///
+    /// Vote nonces are kept for the current key of a witness, so they carry over key rotations.
+    /// Witnesses that are no longer part of the witness set use the given witness key.
+    fn use_vote_nonce<S: storage::Store>(
+        mut store: S,
+        params: &Parameters,
+        address: &Address,
+        witness: &types::WitnessKey,
+        outgoing: bool,
+        nonce: u64,
+    ) -> Result<(), Error> {
+        let id = match Self::find_witness(&mut store, &params.witnesses, address, |_| true) {
+            Ok((index, _)) => params.witnesses[index as usize].id(),
+            Err(_) => witness.id(),
+        };
+        let mut vote_nonces =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::VOTE_NONCES));
+        let mut nonces: types::VoteNonces = vote_nonces.get(&id).unwrap_or_default();
+        let next = if outgoing {
+            &mut nonces.outgoing
+        } else {
+            &mut nonces.incoming
+        };
+        if nonce != *next {
+            return Err(Error::InvalidNonce);
+        }
+        *next += 1;
+        vote_nonces.insert(&id, &nonces);
+        Ok(())
+    }
+
+    /// Witness index that the given address votes as on updates that the admin can make directly,
+    /// or `None` for the admin.
+    fn update_voter<S: storage::Store>(
+        store: S,
+        params: &Parameters,
+        address: &Address,
+    ) -> Result<Option<u16>, Error> {
+        if params.admin == Some(*address) {
+            return Ok(None);
+        }
+        let (index, _) = Self::find_witness(store, &params.witnesses, address, |_| true)?;
+        Ok(Some(index))
+    }
+
+    /// Record the vote of the given voter for the given proposal as the update with the given
+    /// sequence number, returning whether the update was approved.
+    ///
+    /// Votes are kept under the given state prefix and the next sequence number under the given
+    /// key. Updates by the admin are approved directly. Approving an update clears its votes and
+    /// advances the sequence number.
+    fn vote_update<S: storage::Store>(
+        mut store: S,
+        params: &Parameters,
+        votes_prefix: &[u8],
+        sequence_key: &[u8],
+        voter: Option<u16>,
+        id: u64,
+        proposal: Hash,
+    ) -> Result<bool, Error> {
+        // Check if sequence number is correct.
+        if let Some(index) = voter {
+            Self::ensure_bonded(&mut store, params, &params.witnesses[index as usize])?;
+        }
+        let threshold = Self::current_threshold(&mut store, params);
+        let tstore = storage::TypedStore::new(&mut store);
+        let expected_id: u64 = tstore.get(sequence_key).unwrap_or_default();
+        if id != expected_id {
+            return Err(Error::InvalidSequenceNumber);
+        }
+
+        let mut update_votes =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &votes_prefix));
+        if let Some(index) = voter {
+            let mut votes: types::UpdateVotes =
+                update_votes.get(id.to_storage_key()).unwrap_or_default();
+
+            // Make sure it didn't already vote.
+            if votes.witnesses.contains(&index) {
+                return Err(Error::AlreadySubmittedSignature);
+            }
+
+            // Different proposals may be made for the same sequence number, so votes are counted
+            // separately for each of them.
+            let voters = votes.updates.entry(proposal).or_default();
+            voters.push(index);
+            let approved = (voters.len() as u64) >= threshold;
+            votes.witnesses.push(index);
+            if !approved {
+                // Not enough votes yet.
+                update_votes.insert(id.to_storage_key(), &votes);
+                return Ok(false);
+            }
         }
 
         // Clear entry in storage.
-        in_witness_signatures.remove(body.id.to_storage_key());
+        update_votes.remove(id.to_storage_key());
 
         // Increment sequence number.
         let mut tstore = storage::TypedStore::new(&mut store);
-        tstore.insert(state::NEXT_IN_SEQUENCE, &(expected_id + 1));
+        tstore.insert(sequence_key, &(expected_id + 1));
+
+        Ok(true)
+    }
 
-        // If this is a remote denomination mint the amount in the bridge-owned account. If this is
-        // a local denomination, then the amount is just unlocked from the account.
-        if let Some(_remote) = remote {
-            Accounts::mint(ctx, *ADDRESS_LOCKED_FUNDS, &body.amount)?;
+    /// Account for the given amount of a remote denomination being minted or burned on this side
+    /// of the bridge.
+    ///
+    /// Minting fails if it would push the minted supply above the remote reserve agreed upon by
+    /// the witnesses, if any.
+    fn update_minted_supply<S: storage::Store>(
+        store: S,
+        amount: &token::BaseUnits,
+        mint: bool,
+    ) -> Result<(), Error> {
+        let mut tstore = storage::TypedStore::new(store);
+        let mut reserves: BTreeMap<token::Denomination, types::RemoteReserve> =
+            tstore.get(state::REMOTE_RESERVES).unwrap_or_default();
+        let reserve = reserves.entry(amount.denomination().clone()).or_default();
+        if mint {
+            reserve.minted = reserve
+                .minted
+                .checked_add(amount.amount())
+                .ok_or(Error::InvalidArgument)?;
+            if matches!(&reserve.locked, Some(locked) if &reserve.minted > locked) {
+                return Err(Error::ReserveExceeded);
+            }
+        } else {
+            // Amounts minted before the supply was tracked are not accounted for.
+            reserve.minted = reserve
+                .minted
+                .checked_sub(amount.amount())
+                .unwrap_or_default();
         }
+        tstore.insert(state::REMOTE_RESERVES, &reserves);
+        Ok(())
+    }
 
-        // Transfer funds from bridge-owned account into user's account.
-        Accounts::transfer(ctx, *ADDRESS_LOCKED_FUNDS, body.target, &body.amount)?;
+    /// Count a vote submitted by the given account in the given round, making sure that the
+    /// account does not exceed the per-block vote cap.
+    fn use_block_vote<S: storage::Store>(
+        store: S,
+        params: &Parameters,
+        address: Address,
+        round: u64,
+    ) -> Result<(), Error> {
+        if params.max_votes_per_block == 0 {
+            return Ok(());
+        }
+        let mut block_votes =
+            storage::TypedStore::new(storage::PrefixStore::new(store, &state::BLOCK_VOTES));
+        let mut votes: types::BlockVotes = block_votes.get(address).unwrap_or_default();
+        if votes.round != round {
+            votes = types::BlockVotes { round, count: 0 };
+        }
+        if votes.count >= params.max_votes_per_block {
+            return Err(Error::TooManyVotes);
+        }
+        votes.count += 1;
+        block_votes.insert(address, &votes);
+        Ok(())
+    }
 
-        // Emit release event.
-        ctx.emit_event(Event::Release {
-            id: body.id,
-            target: body.target,
-            amount: body.amount,
-        });
+    /// Update the bridge statistics.
+    fn update_stats<S: storage::Store, F: FnOnce(&mut types::Stats)>(store: S, f: F) {
+        let mut tstore = storage::TypedStore::new(store);
+        let mut stats: types::Stats = tstore.get(state::STATS).unwrap_or_default();
+        f(&mut stats);
+        tstore.insert(state::STATS, &stats);
+    }
 
-        Ok(())
+    /// Ensure that the given amount does not exceed the maximum transfer amount.
+    fn ensure_max_transfer_amount(
+        params: &Parameters,
+        amount: &token::BaseUnits,
+    ) -> Result<(), Error> {
+        match params.max_transfer_amounts.get(amount.denomination()) {
+            Some(max_amount) if amount.amount() > max_amount => Err(Error::AmountTooLarge),
+            _ => Ok(()),
+        }
     }
 
-    fn query_next_sequence_numbers<C: Context>(
-        ctx: &mut C,
-        _args: (),
-    ) -> Result<types::NextSequenceNumbers, Error> {
+    /// Bridge statistics.
+    fn stats<S: storage::Store>(state: S) -> types::Stats {
+        let store = storage::PrefixStore::new(state, &MODULE_NAME);
+        let tstore = storage::TypedStore::new(store);
+        tstore.get(state::STATS).unwrap_or_default()
+    }
+
+    /// Value held by the bridge per denomination, computed from the statistics and minted supply
+    /// counters maintained by the module.
+    fn total_locked<S: storage::Store>(state: S, params: &Parameters) -> types::TotalLocked {
+        let store = storage::PrefixStore::new(state, &MODULE_NAME);
+        let tstore = storage::TypedStore::new(store);
+        let stats: types::Stats = tstore.get(state::STATS).unwrap_or_default();
+        let reserves: BTreeMap<token::Denomination, types::RemoteReserve> =
+            tstore.get(state::REMOTE_RESERVES).unwrap_or_default();
+        let total = |volume: &BTreeMap<token::Denomination, Quantity>,
+                     denomination: &token::Denomination| {
+            volume.get(denomination).cloned().unwrap_or_default()
+        };
+
+        let locked = params
+            .local_denominations
+            .iter()
+            .map(|denomination| {
+                // Releases of funds locked before the statistics were introduced are not
+                // accounted for.
+                let outstanding = total(&stats.locked, denomination)
+                    .checked_sub(&total(&stats.released, denomination))
+                    .and_then(|outstanding| {
+                        outstanding.checked_sub(&total(&stats.recovered, denomination))
+                    })
+                    .unwrap_or_default();
+                (denomination.clone(), outstanding)
+            })
+            .collect();
+        let minted = params
+            .remote_denominations
+            .keys()
+            .map(|denomination| {
+                let minted = reserves
+                    .get(denomination)
+                    .map(|reserve| reserve.minted.clone())
+                    .unwrap_or_default();
+                (denomination.clone(), minted)
+            })
+            .collect();
+        types::TotalLocked { locked, minted }
+    }
+
+    /// Whether the bridge has been paused by a guardian.
+    fn is_paused<S: storage::Store>(state: S) -> bool {
+        let store = storage::PrefixStore::new(state, &MODULE_NAME);
+        let tstore = storage::TypedStore::new(store);
+        tstore.get(state::PAUSED).unwrap_or_default()
+    }
+
+    /// Set of denominations that have been frozen.
+    fn frozen_denominations<S: storage::Store>(state: S) -> BTreeSet<token::Denomination> {
+        let store = storage::PrefixStore::new(state, &MODULE_NAME);
+        let tstore = storage::TypedStore::new(store);
+        tstore.get(state::FROZEN_DENOMINATIONS).unwrap_or_default()
+    }
+
+    /// Display metadata of the denominations that have any set.
+    fn denomination_metadata<S: storage::Store>(
+        state: S,
+    ) -> BTreeMap<token::Denomination, types::DenominationMetadata> {
+        let store = storage::PrefixStore::new(state, &MODULE_NAME);
+        let tstore = storage::TypedStore::new(store);
+        tstore.get(state::DENOMINATION_METADATA).unwrap_or_default()
+    }
+
+    /// Key of the deposit made by the given remote transaction and log entry in the processed
+    /// deposits map.
+    fn processed_deposit_key(tx_hash: &Hash, log_index: Option<u64>) -> Vec<u8> {
+        let mut key = tx_hash.as_ref().to_vec();
+        if let Some(log_index) = log_index {
+            key.extend_from_slice(&log_index.to_be_bytes());
+        }
+        key
+    }
+
+    /// Round in which the deposit made by the given remote transaction and log entry was
+    /// released, if it has been processed.
+    ///
+    /// Deposits released without a log entry cover the whole remote transaction, so they are also
+    /// considered when looking up any of its log entries.
+    fn processed_deposit<S: storage::Store>(
+        state: S,
+        tx_hash: &Hash,
+        log_index: Option<u64>,
+    ) -> Option<u64> {
+        let store = storage::PrefixStore::new(state, &MODULE_NAME);
+        let processed_deposits =
+            storage::TypedStore::new(storage::PrefixStore::new(store, &state::PROCESSED_DEPOSITS));
+        processed_deposits
+            .get(Self::processed_deposit_key(tx_hash, None))
+            .or_else(|| {
+                log_index.and_then(|log_index| {
+                    processed_deposits.get(Self::processed_deposit_key(tx_hash, Some(log_index)))
+                })
+            })
+    }
+
+    /// Forget the processed remote transactions that are older than the retention period.
+    fn prune_processed_deposits<C: Context>(ctx: &mut C, params: &Parameters, round: u64) {
         let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
-        let store = storage::TypedStore::new(store);
+        let mut processed_deposits =
+            storage::TypedStore::new(storage::PrefixStore::new(store, &state::PROCESSED_DEPOSITS));
+        let expired: Vec<Vec<u8>> = processed_deposits
+            .iter()
+            .filter(|(_, processed): &(Vec<u8>, u64)| {
+                processed.saturating_add(params.processed_deposit_retention) <= round
+            })
+            .map(|(key, _)| key)
+            .collect();
+        for key in expired {
+            processed_deposits.remove(key);
+        }
+    }
 
-        Ok(types::NextSequenceNumbers {
-            incoming: store.get(state::NEXT_IN_SEQUENCE).unwrap_or_default(),
-            outgoing: store.get(state::NEXT_OUT_SEQUENCE).unwrap_or_default(),
-        })
+    /// Remove the outgoing operations that did not gather enough witness signatures within the
+    /// expiry period.
+    fn expire_operations<C: Context>(ctx: &mut C, params: &Parameters, round: u64) {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut out_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::OUT_WITNESS_SIGNATURE_SETS,
+        ));
+        let expired: Vec<(Vec<u8>, types::WitnessSignatureSet)> = out_witness_signatures
+            .iter()
+            .filter(|(_, info): &(Vec<u8>, types::WitnessSignatureSet)| {
+                info.created.map_or(false, |created| {
+                    created.saturating_add(params.operation_expiry) <= round
+                })
+            })
+            .collect();
+        for (key, _) in &expired {
+            out_witness_signatures.remove(key);
+        }
+        Self::update_stats(&mut store, |stats| {
+            stats.pending_locks = stats.pending_locks.saturating_sub(expired.len() as u64);
+            stats.expired_locks += expired.len() as u64;
+        });
+
+        for (_, info) in expired {
+            ctx.emit_event(Event::OperationExpired {
+                denomination: info.op.amount().denomination().clone(),
+                id: info.id,
+            });
+        }
     }
 
-    fn query_parameters<C: Context>(ctx: &mut C, _args: ()) -> Result<Parameters, Error> {
-        Ok(Self::params(ctx.runtime_state()))
+    /// Clear the votes for incoming sequence numbers that did not finalize within the stale vote
+    /// age.
+    fn clear_stale_votes<C: Context>(ctx: &mut C, params: &Parameters, round: u64) {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut in_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::IN_WITNESS_SIGNATURES,
+        ));
+        let stale: Vec<(Vec<u8>, types::IncomingWitnessSignatures)> = in_witness_signatures
+            .iter()
+            .filter(|(_, info): &(Vec<u8>, types::IncomingWitnessSignatures)| {
+                info.created.map_or(false, |created| {
+                    created.saturating_add(params.stale_vote_age) <= round
+                })
+            })
+            .collect();
+        for (key, _) in &stale {
+            in_witness_signatures.remove(key);
+        }
+
+        let mut cleared = vec![];
+        for (_, info) in stale {
+            let (denomination, id) = match info.summary() {
+                Some(summary) => (summary.denomination, summary.id),
+                None => continue,
+            };
+            if params.reset_stale_votes {
+                // Treat the cleared votes as retracted so that voting again is not equivocation.
+                let mut retracted_votes = storage::TypedStore::new(storage::PrefixStore::new(
+                    &mut store,
+                    &state::RETRACTED_VOTES,
+                ));
+                for (op_id, op_sigs) in &info.ops {
+                    for witness in op_sigs
+                        .signers
+                        .iter()
+                        .filter_map(|index| params.witnesses.get(index as usize))
+                    {
+                        let retraction_id = Self::retraction_id(witness, &denomination, id);
+                        let mut ops: Vec<types::OperationId> =
+                            retracted_votes.get(&retraction_id).unwrap_or_default();
+                        ops.push(op_id.clone());
+                        retracted_votes.insert(&retraction_id, &ops);
+                    }
+                }
+            }
+            cleared.push((denomination, id));
+        }
+
+        for (denomination, id) in cleared {
+            ctx.emit_event(Event::StaleVotesCleared { denomination, id });
+        }
     }
-}
 
-impl<Accounts: modules::accounts::API> module::Module for Module<Accounts> {
-    const NAME: &'static str = MODULE_NAME;
-    type Error = Error;
-    type Event = Event;
-    type Parameters = Parameters;
-}
+    /// Set of addresses on the lock allowlist.
+    fn allowed_addresses<S: storage::Store>(state: S) -> BTreeSet<Address> {
+        let store = storage::PrefixStore::new(state, &MODULE_NAME);
+        let tstore = storage::TypedStore::new(store);
+        tstore.get(state::ALLOWED_ADDRESSES).unwrap_or_default()
+    }
 
-impl<Accounts: modules::accounts::API> module::MethodHandler for Module<Accounts> {
-    fn dispatch_call<C: TxContext>(
-        ctx: &mut C,
-        method: &str,
+    /// Add the given amount to the volume of the current rate limit window, making sure that it
+    /// does not exceed the rate limit of the denomination.
+    fn use_rate_limit<S: storage::Store>(
+        store: S,
+        params: &Parameters,
+        amount: &token::BaseUnits,
+    ) -> Result<(), Error> {
+        if params.rate_limit_window == 0 {
+            return Ok(());
+        }
+        let limit = match params.rate_limits.get(amount.denomination()) {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+
+        let mut tstore = storage::TypedStore::new(store);
+        let mut volumes: BTreeMap<token::Denomination, Quantity> =
+            tstore.get(state::RATE_LIMIT_VOLUMES).unwrap_or_default();
+        let volume = volumes.entry(amount.denomination().clone()).or_default();
+        *volume = volume
+            .checked_add(amount.amount())
+            .ok_or(Error::InvalidArgument)?;
+        if &*volume > limit {
+            return Err(Error::RateLimitExceeded);
+        }
+        tstore.insert(state::RATE_LIMIT_VOLUMES, &volumes);
+        Ok(())
+    }
+
+    /// Add the given amount to the volume of the given account in the current rate limit window,
+    /// making sure that it does not exceed the account limit of the denomination.
+    fn use_account_limit<S: storage::Store>(
+        store: S,
+        params: &Parameters,
+        round: u64,
+        account: Address,
+        amount: &token::BaseUnits,
+    ) -> Result<(), Error> {
+        if params.rate_limit_window == 0 {
+            return Ok(());
+        }
+        let limit = match params.account_limits.get(amount.denomination()) {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+        // Windows end at rounds that are multiples of the window length, as rate limit volumes
+        // are reset at the end of those rounds.
+        let window = round.saturating_sub(1) / params.rate_limit_window;
+
+        let mut account_volumes =
+            storage::TypedStore::new(storage::PrefixStore::new(store, &state::ACCOUNT_VOLUMES));
+        let mut volume: types::AccountVolume = account_volumes
+            .get(account)
+            .filter(|volume: &types::AccountVolume| volume.window == window)
+            .unwrap_or_else(|| types::AccountVolume {
+                window,
+                ..Default::default()
+            });
+        let total = volume
+            .volumes
+            .entry(amount.denomination().clone())
+            .or_default();
+        *total = total
+            .checked_add(amount.amount())
+            .ok_or(Error::InvalidArgument)?;
+        if &*total > limit {
+            return Err(Error::AccountLimitExceeded);
+        }
+        account_volumes.insert(account, &volume);
+        Ok(())
+    }
+
+    /// Add the given amount to the released volume of the current rate limit window.
+    fn add_release_volume<S: storage::Store>(
+        store: S,
+        params: &Parameters,
+        amount: &token::BaseUnits,
+    ) {
+        if params.rate_limit_window == 0 || params.circuit_breaker_multiplier == 0 {
+            return;
+        }
+
+        let mut tstore = storage::TypedStore::new(store);
+        let mut volumes: BTreeMap<token::Denomination, Quantity> =
+            tstore.get(state::RELEASE_VOLUMES).unwrap_or_default();
+        let volume = volumes.entry(amount.denomination().clone()).or_default();
+        if let Some(sum) = volume.checked_add(amount.amount()) {
+            *volume = sum;
+        }
+        tstore.insert(state::RELEASE_VOLUMES, &volumes);
+    }
+
+    /// Pause the bridge if the released volume of any denomination during the current rate limit
+    /// window exceeds its baseline by more than the circuit breaker multiplier.
+    fn check_circuit_breaker<C: Context>(ctx: &mut C, params: &Parameters) {
+        if params.circuit_breaker_multiplier == 0 || Self::is_paused(ctx.runtime_state()) {
+            return;
+        }
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut tstore = storage::TypedStore::new(&mut store);
+        let volumes: BTreeMap<token::Denomination, Quantity> =
+            tstore.get(state::RELEASE_VOLUMES).unwrap_or_default();
+        let tripped = params
+            .release_baselines
+            .iter()
+            .find_map(|(denomination, baseline)| {
+                let volume = volumes.get(denomination)?;
+                let threshold =
+                    baseline.checked_mul(&Quantity::from(params.circuit_breaker_multiplier))?;
+                if volume > &threshold {
+                    Some((
+                        token::BaseUnits::new(volume.clone(), denomination.clone()),
+                        baseline.clone(),
+                    ))
+                } else {
+                    None
+                }
+            });
+        let (volume, baseline) = match tripped {
+            Some(tripped) => tripped,
+            None => return,
+        };
+        tstore.insert(state::PAUSED, &true);
+        // Start counting anew so that the circuit breaker does not trip again as soon as a
+        // guardian resumes the bridge.
+        tstore.remove(state::RELEASE_VOLUMES);
+
+        ctx.emit_event(Event::CircuitBreakerTripped { volume, baseline });
+    }
+
+    /// Ensure that the caller is one of the guardians configured in the parameters.
+    fn ensure_guardian<C: TxContext>(ctx: &mut C, params: &Parameters) -> Result<(), Error> {
+        if !params.guardians.contains(&ctx.tx_caller_address()) {
+            return Err(Error::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    /// Ensure that the caller is the admin configured in the parameters.
+    fn ensure_admin<C: TxContext>(ctx: &mut C, params: &Parameters) -> Result<(), Error> {
+        if params.admin != Some(ctx.tx_caller_address()) {
+            return Err(Error::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    /// Ensure that the given amount can be locked with respect to the minimum and maximum amounts
+    /// and the limit on pending outgoing operations.
+    fn check_lock_amount<S: storage::Store>(
+        state: S,
+        params: &Parameters,
+        amount: &token::BaseUnits,
+    ) -> Result<(), Error> {
+        if let Some(min_amount) = params.min_lock_amounts.get(amount.denomination()) {
+            if amount.amount() < min_amount {
+                return Err(Error::AmountTooSmall);
+            }
+        }
+        Self::ensure_max_transfer_amount(params, amount)?;
+        if params.max_pending_locks != 0
+            && Self::stats(state).pending_locks >= params.max_pending_locks
+        {
+            return Err(Error::TooManyPending);
+        }
+        Ok(())
+    }
+
+    /// Split the given locked amount into the amount that is bridged and the lock fee.
+    fn split_lock_fee(
+        params: &Parameters,
+        amount: &token::BaseUnits,
+    ) -> Result<(token::BaseUnits, token::BaseUnits), Error> {
+        let mut fee = amount
+            .amount()
+            .checked_mul(&Quantity::from(params.lock_fee.min(BASIS_POINTS)))
+            .and_then(|fee| fee.checked_div(&Quantity::from(BASIS_POINTS)))
+            .ok_or(Error::InvalidArgument)?;
+        if fee < params.min_lock_fee {
+            fee = params.min_lock_fee.clone();
+        }
+        if params.max_lock_fee != Quantity::default() && fee > params.max_lock_fee {
+            fee = params.max_lock_fee.clone();
+        }
+        let net = amount
+            .amount()
+            .checked_sub(&fee)
+            .ok_or(Error::AmountTooSmall)?;
+        let denomination = amount.denomination().clone();
+        Ok((
+            token::BaseUnits::new(net, denomination.clone()),
+            token::BaseUnits::new(fee, denomination),
+        ))
+    }
+
+    /// Pay the given transaction fee to the given payer from the subsidy pool, as far as the pool
+    /// allows.
+    fn sponsor_fee<C: Context>(ctx: &mut C, payer: Address, fee: &token::BaseUnits) {
+        if fee.denomination() != &token::Denomination::NATIVE {
+            return;
+        }
+        let available = Accounts::get_balances(ctx.runtime_state(), *ADDRESS_SUBSIDY_POOL)
+            .map(|balances| balances.balances)
+            .unwrap_or_default()
+            .remove(&token::Denomination::NATIVE)
+            .unwrap_or_default();
+        let amount = std::cmp::min(fee.amount().clone(), available);
+        if amount == Quantity::default() {
+            return;
+        }
+        if let Err(err) = Accounts::transfer(
+            ctx,
+            *ADDRESS_SUBSIDY_POOL,
+            payer,
+            &token::BaseUnits::new(amount, token::Denomination::NATIVE),
+        ) {
+            // The fee is then paid by the payer as usual.
+            slog::warn!(
+                ctx.get_logger(MODULE_NAME),
+                "failed to sponsor transaction fee";
+                "payer" => %payer,
+                "err" => %err,
+            );
+        }
+    }
+
+    fn tx_lock<C: TxContext>(
+        ctx: &mut C,
+        mut body: types::Lock,
+    ) -> Result<types::LockResult, Error> {
+        if Self::is_paused(ctx.runtime_state()) {
+            return Err(Error::Paused);
+        }
+        let remote = Self::ensure_local_or_remote(ctx, body.amount.denomination())?;
+        if Self::frozen_denominations(ctx.runtime_state()).contains(body.amount.denomination()) {
+            return Err(Error::DenominationFrozen);
+        }
+        let caller_address = ctx.tx_caller_address();
+        let params = Self::params(ctx.runtime_state());
+        // The remote chain is not part of the operation.
+        if matches!(body.remote_chain_id.take(), Some(id) if id != params.remote_chain_id) {
+            return Err(Error::UnsupportedRemoteChain);
+        }
+        if body.target.size() != params.remote_address_size() {
+            return Err(Error::InvalidTarget);
+        }
+        if params.blocked_addresses.contains(&caller_address)
+            || params.blocked_remote_addresses.contains(&body.target)
+        {
+            return Err(Error::AddressBlocked);
+        }
+        if params.lock_allowlist
+            && !Self::allowed_addresses(ctx.runtime_state()).contains(&caller_address)
+        {
+            return Err(Error::AddressNotAllowed);
+        }
+        // The attestation is not part of the operation.
+        let attestation = body.attestation.take();
+        if let Some(attester) = &params.lock_attester {
+            let attestation = attestation.ok_or(Error::InvalidSignature)?;
+            if ctx.runtime_header().round > attestation.expiration {
+                return Err(Error::AttestationExpired);
+            }
+            crypto::verify_lock_attestation(attester, &params, &caller_address, &attestation)?;
+        }
+        Self::check_lock_amount(ctx.runtime_state(), &params, &body.amount)?;
+        let (amount, fee) = Self::split_lock_fee(&params, &body.amount)?;
+        // Dust that has no remote equivalent stays with the caller.
+        let amount = match params.decimal_scales.get(amount.denomination()) {
+            Some(scale) => {
+                let (bridged, _dust) = scale
+                    .split_dust(amount.amount())
+                    .ok_or(Error::InvalidArgument)?;
+                if bridged == Quantity::default() {
+                    return Err(Error::AmountTooSmall);
+                }
+                token::BaseUnits::new(bridged, amount.denomination().clone())
+            }
+            None => amount,
+        };
+
+        if ctx.is_check_only() {
+            return Ok(types::LockResult { id: 0, fee });
+        }
+
+        let round = ctx.runtime_header().round;
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        Self::use_rate_limit(&mut store, &params, &amount)?;
+        Self::use_account_limit(&mut store, &params, round, caller_address, &amount)?;
+
+        // Transfer the fee into the bridge fee account and the rest of the funds from user's
+        // account into the bridge-owned account.
+        if fee.amount() != &Quantity::default() {
+            Accounts::transfer(ctx, caller_address, *ADDRESS_BRIDGE_FEES, &fee)?;
+        }
+        // Charge the flat subsidy fee.
+        if params.subsidy_fee != Quantity::default() {
+            let subsidy_fee =
+                token::BaseUnits::new(params.subsidy_fee.clone(), token::Denomination::NATIVE);
+            Accounts::transfer(ctx, caller_address, *ADDRESS_SUBSIDY_POOL, &subsidy_fee)?;
+        }
+        Accounts::transfer(ctx, caller_address, *ADDRESS_LOCKED_FUNDS, &amount)?;
+        // Only the amount net of the fee is bridged.
+        body.amount = amount;
+
+        // Assign a unique identifier to the event within the sequence space of its denomination.
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let id = Self::advance_sequence(
+            &mut store,
+            params.remote_chain_id,
+            true,
+            body.amount.denomination(),
+        );
+        let key = sequence_key(body.amount.denomination(), id);
+
+        // Create an entry in outgoing witness signatures map. Signatures are verified against the
+        // witness set at the time the operation was created.
+        let amount = body.amount.clone();
+        let target = body.target;
+        let witness_set = Self::snapshot_witness_set(&mut store, &params);
+        let mut out_witness_sets = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::OUT_WITNESS_SETS,
+        ));
+        out_witness_sets.insert(&key, &witness_set);
+        let mut out_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::OUT_WITNESS_SIGNATURE_SETS,
+        ));
+        out_witness_signatures.insert(
+            &key,
+            &types::WitnessSignatureSet {
+                witness_set: Some(witness_set),
+                fee: Some(fee.clone()).filter(|fee| fee.amount() != &Quantity::default()),
+                created: Some(round),
+                ..types::WitnessSignatureSet::new(id, types::Operation::Lock(body))
+            },
+        );
+        if remote.is_some() {
+            Self::update_minted_supply(&mut store, &amount, false)?;
+        }
+        Self::update_stats(&mut store, |stats| {
+            stats.locks += 1;
+            stats.pending_locks += 1;
+            stats.add_locked(&amount);
+        });
+
+        // If this is a remote denomination burn the amount from the bridge-owned account. If this
+        // is a local denomination, then the amount just stays locked in the account.
+        if remote.is_some() {
+            Accounts::burn(ctx, *ADDRESS_LOCKED_FUNDS, &amount)?;
+        }
+
+        // Emit a lock event.
+        ctx.emit_event(Event::Lock {
+            id,
+            remote_chain_id: params.remote_chain_id,
+            owner: caller_address,
+            target,
+            amount,
+            fee: fee.clone(),
+        });
+
+        Ok(types::LockResult { id, fee })
+    }
+
+    fn tx_witness<C: TxContext>(ctx: &mut C, body: types::Witness) -> Result<(), Error> {
+        let caller_address = ctx.tx_caller_address();
+        let round = ctx.runtime_header().round;
+        let params = Self::params(ctx.runtime_state());
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        Self::use_block_vote(&mut store, &params, caller_address, round)?;
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        Self::record_witness_signature(ctx, &params, round, caller_address, body)
+    }
+
+    fn tx_witness_batch<C: TxContext>(ctx: &mut C, body: types::WitnessBatch) -> Result<(), Error> {
+        if body.votes.is_empty() {
+            return Err(Error::InvalidArgument);
+        }
+        if body.votes.len() > MAX_WITNESS_BATCH_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
+        let caller_address = ctx.tx_caller_address();
+        let round = ctx.runtime_header().round;
+        let params = Self::params(ctx.runtime_state());
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        // Each vote of the batch counts towards the per-block vote limit.
+        for _ in &body.votes {
+            Self::use_block_vote(&mut store, &params, caller_address, round)?;
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        // Votes are processed in order and the whole batch fails if any of them fails.
+        for vote in body.votes {
+            Self::record_witness_signature(ctx, &params, round, caller_address, vote)?;
+        }
+
+        Ok(())
+    }
+
+    /// Record the caller's witness signature over an outgoing operation, finalizing the operation
+    /// once enough witnesses signed it.
+    fn record_witness_signature<C: Context>(
+        ctx: &mut C,
+        params: &Parameters,
+        round: u64,
+        caller_address: Address,
+        body: types::Witness,
+    ) -> Result<(), Error> {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+
+        // Check if sequence number is correct.
+        let key = sequence_key(&body.denomination, body.id);
+        let out_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::OUT_WITNESS_SIGNATURE_SETS,
+        ));
+        let mut info: types::WitnessSignatureSet = out_witness_signatures
+            .get(&key)
+            .ok_or(Error::InvalidSequenceNumber)?;
+
+        // Make sure the caller is an authorized witness in the operation's witness set or, during a
+        // witness set handover, in the successor witness set.
+        let witness_set = Self::witness_set(&mut store, params, info.witness_set.clone());
+        let in_flight = |rotated: &types::RotatedWitnessKey| {
+            body.id < Self::rotated_sequence(&rotated.outgoing, &body.denomination)
+        };
+        let (index, witness, handover) = match Self::find_witness(
+            &mut store,
+            &witness_set.witnesses,
+            &caller_address,
+            &in_flight,
+        ) {
+            Ok((index, witness)) => (index, witness, None),
+            Err(err) => {
+                let (commitment, successor) = info
+                    .witness_set
+                    .as_ref()
+                    .and_then(|commitment| {
+                        Self::handover_witness_set(&mut store, commitment, round)
+                    })
+                    .ok_or(err)?;
+                let (index, witness) = Self::find_witness(
+                    &mut store,
+                    &successor.witnesses,
+                    &caller_address,
+                    &in_flight,
+                )?;
+                (index, witness, Some((commitment, successor)))
+            }
+        };
+        let signing_key = Self::signing_key(&mut store, &witness);
+        let member = match &handover {
+            Some((_, successor)) => &successor.witnesses[index as usize],
+            None => &witness_set.witnesses[index as usize],
+        };
+        Self::ensure_bonded(&mut store, params, member)?;
+        if let Some((commitment, _)) = &handover {
+            // Signatures by an earlier successor are discarded when the successor changes.
+            if info
+                .handover
+                .as_ref()
+                .map(|signatures| &signatures.witness_set)
+                != Some(commitment)
+            {
+                info.handover = Some(types::HandoverSignatures::new(commitment.clone()));
+            }
+        }
+
+        // Make sure it didn't already submit a signature.
+        let already_signed = match &handover {
+            Some(_) => info
+                .handover
+                .iter()
+                .any(|signatures| signatures.signers.contains(index)),
+            None => info.signers.contains(index),
+        };
+        if already_signed {
+            return Err(Error::AlreadySubmittedSignature);
+        }
+        // Make sure the signature is valid for the operation.
+        crypto::verify_witness(&signing_key, params, body.id, &info.op, &body.signature)?;
+        Self::use_vote_nonce(
+            &mut store,
+            params,
+            &caller_address,
+            &witness,
+            true,
+            body.nonce,
+        )?;
+
+        // Store signature in storage.
+        match (&handover, info.handover.as_mut()) {
+            (Some(_), Some(signatures)) => {
+                signatures.insert(index, body.signature);
+            }
+            _ => {
+                info.insert(index, body.signature);
+            }
+        }
+        // Check if there's enough signatures from either witness set.
+        let complete = (info.signers.len() as u64) >= witness_set.threshold
+            || match (&handover, &info.handover) {
+                (Some((_, successor)), Some(signatures)) => {
+                    (signatures.signers.len() as u64) >= successor.threshold
+                }
+                _ => false,
+            };
+        let mut out_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::OUT_WITNESS_SIGNATURE_SETS,
+        ));
+        if !complete {
+            // Not enough signatures yet.
+            out_witness_signatures.insert(&key, &info);
+            return Ok(());
+        }
+
+        // Clear entry in storage.
+        out_witness_signatures.remove(&key);
+
+        Self::update_stats(&mut store, |stats| {
+            stats.finalized_locks += 1;
+            // Locks made before statistics were tracked are not counted as pending.
+            stats.pending_locks = stats.pending_locks.saturating_sub(1);
+        });
+        Self::record_history(
+            &mut store,
+            params,
+            types::HistoryEntry {
+                direction: types::Direction::Outgoing,
+                id: body.id,
+                op: info.op.clone(),
+                round,
+            },
+        );
+
+        // Track witness participation.
+        let demoted = Self::record_participation(&mut store, params, &witness_set, &info.signers);
+        // Witnesses whose signatures finalized the operation. These are the successor witnesses
+        // when the handover signatures reached the successor's threshold.
+        let (signers, signer_set) = match (&handover, &info.handover) {
+            (Some((_, successor)), Some(signatures))
+                if (signatures.signers.len() as u64) >= successor.threshold =>
+            {
+                (&signatures.signers, successor)
+            }
+            _ => (&info.signers, &witness_set),
+        };
+        let signers: Vec<(u16, types::WitnessKey)> = signers
+            .iter()
+            .filter_map(|index| Some((index, signer_set.witnesses.get(index as usize)?.clone())))
+            .collect();
+        Self::add_reward_points(
+            &mut store,
+            params,
+            signers.iter().map(|(_, witness)| witness),
+        );
+
+        // Split the fee attached to the operation among the signers.
+        let fee_shares = match info.fee.take() {
+            Some(fee) => Self::split_fee(&mut store, &fee, &signers),
+            None => vec![],
+        };
+
+        // Store the quorum certificate so that it can be queried later.
+        let commitment = crypto::witness_set_commitment(
+            &Self::signing_keys(&mut store, &witness_set.witnesses),
+            witness_set.threshold,
+        );
+        let mut certificate = types::QuorumCertificate::new(info, commitment);
+        if let Some(signatures) = certificate.handover.as_mut() {
+            let successor =
+                Self::witness_set(&mut store, params, Some(signatures.witness_set.clone()));
+            signatures.witness_set = crypto::witness_set_commitment(
+                &Self::signing_keys(&mut store, &successor.witnesses),
+                successor.threshold,
+            );
+        }
+        let mut certificates = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::QUORUM_CERTIFICATES,
+        ));
+        certificates.insert(&key, &certificate);
+
+        // Emit the quorum certificate.
+        ctx.emit_event(Event::WitnessesSigned(certificate));
+        for (index, witness) in demoted {
+            ctx.emit_event(Event::WitnessInactive { index, witness });
+        }
+
+        for (index, witness, address, amount) in fee_shares {
+            Accounts::transfer(ctx, *ADDRESS_BRIDGE_FEES, address, &amount)?;
+            ctx.emit_event(Event::WitnessFeePaid {
+                denomination: body.denomination.clone(),
+                id: body.id,
+                index,
+                witness,
+                amount,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn tx_release<C: TxContext>(ctx: &mut C, mut body: types::Release) -> Result<(), Error> {
+        if Self::is_paused(ctx.runtime_state()) {
+            return Err(Error::Paused);
+        }
+        Self::ensure_local_or_remote(ctx, body.amount.denomination())?;
+        if Self::frozen_denominations(ctx.runtime_state()).contains(body.amount.denomination()) {
+            return Err(Error::DenominationFrozen);
+        }
+        let caller_address = ctx.tx_caller_address();
+        let params = Self::params(ctx.runtime_state());
+        if body.batch.len() as u64 > params.max_release_batch_size {
+            return Err(Error::BatchTooLarge);
+        }
+        // The remote chain is not part of the operation.
+        if matches!(body.remote_chain_id.take(), Some(id) if id != params.remote_chain_id) {
+            return Err(Error::UnsupportedRemoteChain);
+        }
+        // All transfers of a batch are in the sequence space of the primary denomination.
+        if body.total_amount().is_none() {
+            return Err(Error::InvalidArgument);
+        }
+        // Released amounts must correspond to whole remote amounts.
+        if body
+            .entries()
+            .iter()
+            .any(|entry| params.remote_amount(&entry.amount).is_none())
+        {
+            return Err(Error::InvalidArgument);
+        }
+
+        if body.claims_root.is_some() {
+            // Releases committing to claims pay into the pending claims account in a single
+            // transfer. Limits on individual transfers apply once the claims are paid out.
+            if body.target != *ADDRESS_PENDING_CLAIMS || !body.batch.is_empty() {
+                return Err(Error::InvalidTarget);
+            }
+        } else {
+            for entry in body.entries() {
+                // Make sure funds are not released into any of the internal accounts.
+                if Self::is_internal_address(&entry.target) {
+                    return Err(Error::InvalidTarget);
+                }
+                if params.blocked_addresses.contains(&entry.target) {
+                    return Err(Error::AddressBlocked);
+                }
+                Self::ensure_max_transfer_amount(&params, &entry.amount)?;
+            }
+        }
+        if let Some(tx_hash) = &body.tx_hash {
+            if Self::processed_deposit(ctx.runtime_state(), tx_hash, body.log_index).is_some() {
+                return Err(Error::DepositAlreadyProcessed);
+            }
+        }
+        let round = ctx.runtime_header().round;
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        Self::use_block_vote(store, &params, caller_address, round)?;
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        Verifier::verify_release(ctx, &params, body)
+    }
+
+    /// Record the vote of the calling witness for the given release, finalizing it once enough
+    /// witnesses voted for it and all earlier releases have been finalized.
+    ///
+    /// Following releases that already gathered enough votes are finalized along with it.
+    fn vote_release<C: TxContext>(
+        ctx: &mut C,
+        params: &Parameters,
+        mut body: types::Release,
+    ) -> Result<(), Error> {
+        let caller_address = ctx.tx_caller_address();
+        let round = ctx.runtime_header().round;
+        let denomination = body.amount.denomination().clone();
+        // Releases must identify the remote deposit, so that it cannot be released twice.
+        if body.tx_hash.is_none() || body.log_index.is_none() {
+            return Err(Error::InvalidArgument);
+        }
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        // Make sure the caller is an authorized witness.
+        let (index, witness) =
+            Self::find_witness(&mut store, &params.witnesses, &caller_address, |rotated| {
+                body.id <= Self::rotated_sequence(&rotated.incoming, &denomination)
+            })?;
+        let signing_key = Self::signing_key(&mut store, &witness);
+        Self::ensure_bonded(&mut store, params, &params.witnesses[index as usize])?;
+        let threshold = Self::current_threshold(&mut store, params);
+        // The signature and nonce are not part of the operation.
+        let signature = body.signature.take().ok_or(Error::InvalidSignature)?;
+        let nonce = body.nonce.take().ok_or(Error::InvalidNonce)?;
+
+        // Check if sequence number is correct. Witnesses can vote on operations ahead of the next
+        // expected one, but operations are finalized in sequence.
+        let expected_id =
+            Self::ensure_in_release_window(&mut store, params, &denomination, body.id)?;
+
+        // Fetch existing signatures.
+        let key = sequence_key(&denomination, body.id);
+        let mut in_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::IN_WITNESS_SIGNATURES,
+        ));
+        let mut info: types::IncomingWitnessSignatures = in_witness_signatures
+            .get(&key)
+            .unwrap_or_else(|| types::IncomingWitnessSignatures {
+                created: Some(round),
+                ..Default::default()
+            });
+
+        // Make sure it didn't already submit a signature.
+        if info.signers.contains(index) {
+            return Err(Error::AlreadySubmittedSignature);
+        }
+
+        // There can be multiple different operations proposed for the sequence (in case some
+        // witnesses are corrupted). We handle these by hashing the operation and using that as the
+        // discriminator.
+        let op = types::Operation::Release(body.clone());
+        // Make sure the witness signed the proposed operation, so that relaying transactions alone
+        // is not enough to propose arbitrary releases.
+        crypto::verify_release(&signing_key, params, body.id, &op, &signature)?;
+        Self::use_vote_nonce(&mut store, params, &caller_address, &witness, false, nonce)?;
+        let op_id = types::OperationId::from(&op);
+        let new_op = !info.ops.contains_key(&op_id);
+        let op_sigs = info
+            .ops
+            .entry(op_id.clone())
+            .or_insert_with(|| types::WitnessSignatures::new(body.id, op));
+
+        // Store which witnesses signed in storage. Note that in the incoming case we don't need to
+        // store the actual signatures as we verify them here and no longer need them.
+        info.signers.insert(index);
+        op_sigs.signers.insert(index);
+        // Check if there's enough signatures and all earlier operations have been finalized.
+        let complete = (op_sigs.signers.len() as u64) >= threshold && body.id == expected_id;
+        // The vote is stored before finalizing, so that it is kept even if the operation cannot
+        // be finalized yet.
+        in_witness_signatures.insert(&key, &info);
+        let finalized = complete
+            && match Self::finalize_release(
+                ctx,
+                params,
+                round,
+                body.id,
+                info.clone(),
+                &op_id,
+                new_op,
+            ) {
+                Ok(()) => true,
+                Err(err) => {
+                    // Operations that cannot be finalized, e.g. duplicate deposits or releases
+                    // exceeding the limits, are left pending. Witnesses can retract their votes and
+                    // vote again to retry.
+                    slog::warn!(
+                        ctx.get_logger(MODULE_NAME),
+                        "failed to finalize release, keeping it pending";
+                        "denomination" => %denomination,
+                        "id" => body.id,
+                        "err" => %err,
+                    );
+                    false
+                }
+            };
+        if !finalized {
+            let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+            if let Some(conflict) =
+                Self::record_conflict(store, params, &denomination, body.id, &info, new_op)
+            {
+                ctx.emit_event(Event::DivergenceDetected(conflict));
+            }
+            return Ok(());
+        }
+
+        // Finalize the following operations that already gathered enough signatures, in order.
+        let mut id = body.id;
+        loop {
+            id += 1;
+            let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+            let in_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+                store,
+                &state::IN_WITNESS_SIGNATURES,
+            ));
+            let info: types::IncomingWitnessSignatures =
+                match in_witness_signatures.get(sequence_key(&denomination, id)) {
+                    Some(info) => info,
+                    None => break,
+                };
+            let op_id = match info
+                .ops
+                .iter()
+                .find(|(_, op_sigs)| (op_sigs.signers.len() as u64) >= threshold)
+            {
+                Some((op_id, _)) => op_id.clone(),
+                None => break,
+            };
+            // Operations that cannot be finalized, e.g. duplicate deposits or releases exceeding
+            // the limits, are left pending without affecting the vote. Finalizing fails without
+            // any changes, so nothing of the failed operation is recorded.
+            if Self::finalize_release(ctx, params, round, id, info, &op_id, false).is_err() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Make sure that witnesses can vote on the incoming operation with the given sequence number
+    /// in the sequence space of the given denomination, returning the next expected incoming
+    /// sequence number.
+    ///
+    /// Witnesses can vote on any sequence number within the release window ahead of the next
+    /// expected one.
+    fn ensure_in_release_window<S: storage::Store>(
+        store: S,
+        params: &Parameters,
+        denomination: &token::Denomination,
+        id: u64,
+    ) -> Result<u64, Error> {
+        let expected_id = Self::next_sequence(store, params.remote_chain_id, false, denomination);
+        if id < expected_id || id > expected_id.saturating_add(params.release_window) {
+            return Err(Error::InvalidSequenceNumber);
+        }
+        Ok(expected_id)
+    }
+
+    /// Finalize the incoming operation with the given sequence number, for which the given
+    /// operation gathered enough witness signatures.
+    ///
+    /// The sequence number must be the next expected one. Fails without any changes if the
+    /// operation releases an already processed deposit, exceeds any of the limits or is not
+    /// covered by the locked funds.
+    fn finalize_release<C: Context>(
+        ctx: &mut C,
+        params: &Parameters,
+        round: u64,
+        id: u64,
+        info: types::IncomingWitnessSignatures,
+        op_id: &types::OperationId,
+        new_op: bool,
+    ) -> Result<(), Error> {
+        let body = match info.ops.get(op_id).map(|op_sigs| &op_sigs.op) {
+            Some(types::Operation::Release(release)) => release.clone(),
+            _ => return Err(Error::InvalidArgument),
+        };
+        let denomination = body.amount.denomination().clone();
+        Self::complete_release(ctx, params, round, body)?;
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+
+        // Clear entry in storage.
+        let mut in_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::IN_WITNESS_SIGNATURES,
+        ));
+        in_witness_signatures.remove(sequence_key(&denomination, id));
+        let conflict = Self::record_conflict(&mut store, params, &denomination, id, &info, new_op);
+
+        // Witnesses that voted for the finalized operation earn rewards.
+        if let Some(op_sigs) = info.ops.get(op_id) {
+            Self::add_reward_points(
+                &mut store,
+                params,
+                op_sigs
+                    .signers
+                    .iter()
+                    .filter_map(|index| params.witnesses.get(index as usize)),
+            );
+        }
+
+        // Slash witnesses that voted for a conflicting operation. Signers that are no longer part
+        // of the witness set are skipped.
+        let mut slashed = vec![];
+        if params.divergence_penalty > 0 {
+            for (_, op_sigs) in info.ops.iter().filter(|(id, _)| *id != op_id) {
+                for index in op_sigs.signers.iter() {
+                    let witness = match params.witnesses.get(index as usize) {
+                        Some(witness) => witness,
+                        None => continue,
+                    };
+                    if let Some(amount) =
+                        Self::slash_bond(&mut store, witness, params.divergence_penalty)
+                    {
+                        slashed.push((index, witness.clone(), amount));
+                    }
+                }
+            }
+        }
+
+        if let Some(conflict) = conflict {
+            ctx.emit_event(Event::DivergenceDetected(conflict));
+        }
+
+        // Slashed amounts go to the common pool.
+        for (index, witness, amount) in slashed {
+            Accounts::transfer(
+                ctx,
+                *ADDRESS_WITNESS_BONDS,
+                *modules::accounts::ADDRESS_COMMON_POOL,
+                &amount,
+            )?;
+            ctx.emit_event(Event::Slashed {
+                index,
+                witness,
+                amount,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Complete the verified incoming release, which must be the next expected one in the
+    /// sequence space of its denomination, by executing it or queueing it for the challenge
+    /// period.
+    ///
+    /// Fails without any changes if the release is of an already processed deposit, exceeds any of
+    /// the limits or is not covered by the locked funds.
+    fn complete_release<C: Context>(
+        ctx: &mut C,
+        params: &Parameters,
+        round: u64,
+        body: types::Release,
+    ) -> Result<(), Error> {
+        if let Some(tx_hash) = &body.tx_hash {
+            if Self::processed_deposit(ctx.runtime_state(), tx_hash, body.log_index).is_some() {
+                return Err(Error::DepositAlreadyProcessed);
+            }
+        }
+        let denomination = body.amount.denomination().clone();
+        let total = body.total_amount().ok_or(Error::InvalidArgument)?;
+        let remote = params.remote_denominations.contains_key(&denomination);
+
+        // Large releases only execute after the challenge period has passed.
+        let challenged = params.challenge_period != 0
+            && matches!(
+                params.challenge_thresholds.get(&denomination),
+                Some(threshold) if total.amount() > threshold
+            );
+        if !remote && !challenged && !Self::locked_funds_cover(ctx, &total) {
+            return Err(Error::InsufficientBalance);
+        }
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        // Releases are completed in sequence.
+        if Self::next_sequence(&mut store, params.remote_chain_id, false, &denomination) != body.id
+        {
+            return Err(Error::InvalidSequenceNumber);
+        }
+
+        // Recording the completion is first tried in an overlay that is discarded, as it must only
+        // be recorded once the release is executed or queued.
+        Self::record_completion(
+            storage::OverlayStore::new(&mut store),
+            params,
+            round,
+            &body,
+            &total,
+            remote,
+        )?;
+
+        if challenged {
+            Self::record_completion(&mut store, params, round, &body, &total, remote)?;
+            let pending = types::PendingRelease {
+                id: body.id,
+                target: body.target,
+                amount: body.amount,
+                tx_hash: body.tx_hash,
+                log_index: body.log_index,
+                batch: body.batch,
+                claims_root: body.claims_root,
+                deadline: round.saturating_add(params.challenge_period),
+            };
+            let mut pending_releases = storage::TypedStore::new(storage::PrefixStore::new(
+                &mut store,
+                &state::PENDING_RELEASES,
+            ));
+            pending_releases.insert(sequence_key(&denomination, pending.id), &pending);
+
+            ctx.emit_event(Event::ReleaseQueued {
+                id: pending.id,
+                target: pending.target,
+                amount: pending.amount,
+                deadline: pending.deadline,
+                batch: pending.batch,
+            });
+        } else {
+            Self::execute_release(ctx, params, remote, body.clone())?;
+            let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+            Self::record_completion(store, params, round, &body, &total, remote)?;
+        }
+
+        Ok(())
+    }
+
+    /// Record the completion of the given release by advancing the incoming sequence number,
+    /// accounting for it in the minted supply and the limits and registering its deposit as
+    /// processed.
+    ///
+    /// Fails if the release exceeds the remote reserve or any of the limits.
+    fn record_completion<S: storage::Store>(
+        mut store: S,
+        params: &Parameters,
+        round: u64,
+        body: &types::Release,
+        total: &token::BaseUnits,
+        remote: bool,
+    ) -> Result<(), Error> {
+        // Increment sequence number.
+        Self::advance_sequence(
+            &mut store,
+            params.remote_chain_id,
+            false,
+            total.denomination(),
+        );
+
+        // Make sure the minted supply stays covered by the remote reserve.
+        if remote {
+            Self::update_minted_supply(&mut store, total, true)?;
+        }
+        Self::use_rate_limit(&mut store, params, total)?;
+        if body.claims_root.is_none() {
+            for entry in body.entries() {
+                Self::use_account_limit(&mut store, params, round, entry.target, &entry.amount)?;
+            }
+        }
+        Self::add_release_volume(&mut store, params, total);
+        if let Some(tx_hash) = &body.tx_hash {
+            let mut processed_deposits = storage::TypedStore::new(storage::PrefixStore::new(
+                &mut store,
+                &state::PROCESSED_DEPOSITS,
+            ));
+            processed_deposits.insert(Self::processed_deposit_key(tx_hash, body.log_index), &round);
+        }
+
+        Ok(())
+    }
+
+    /// Record the conflicting operations proposed for the given incoming sequence number of the
+    /// given denomination, if there are any, for later audit and slashing.
+    ///
+    /// Returns the conflict if the latest vote was for a new conflicting operation.
+    fn record_conflict<S: storage::Store>(
+        mut store: S,
+        params: &Parameters,
+        denomination: &token::Denomination,
+        id: u64,
+        info: &types::IncomingWitnessSignatures,
+        new_op: bool,
+    ) -> Option<types::Conflict> {
+        if info.ops.len() < 2 {
+            return None;
+        }
+
+        let conflict = types::Conflict {
+            denomination: denomination.clone(),
+            id,
+            votes: info
+                .ops
+                .iter()
+                .map(|(op_id, op_sigs)| {
+                    let witnesses = op_sigs
+                        .signers
+                        .iter()
+                        .filter_map(|index| params.witnesses.get(index as usize))
+                        .cloned()
+                        .collect();
+                    (op_id.clone(), witnesses)
+                })
+                .collect(),
+        };
+        let mut conflicts =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::CONFLICTS));
+        conflicts.insert(sequence_key(denomination, id), &conflict);
+
+        Some(conflict).filter(|_| new_op)
+    }
+
+    /// Mint or unlock the released amount and transfer it to the targets.
+    ///
+    /// Each transfer of a batch release emits its own release event. Releases committing to
+    /// claims make their claims available for paying out. With pull releases the targets are
+    /// credited claimable balances instead of receiving the funds.
+    ///
+    /// Fails without any changes if the locked funds do not cover all transfers of the release or
+    /// recording it fails.
+    fn execute_release<C: Context>(
+        ctx: &mut C,
+        params: &Parameters,
+        remote: bool,
+        release: types::Release,
+    ) -> Result<(), Error> {
+        let total = release.total_amount().ok_or(Error::InvalidArgument)?;
+        let round = ctx.runtime_header().round;
+        let pull = params.pull_releases && release.claims_root.is_none();
+
+        // Recording the release is first tried in an overlay that is discarded, as the funds can
+        // only be moved once it is known to succeed.
+        {
+            let mut overlay = storage::OverlayStore::new(ctx.runtime_state());
+            let store = storage::PrefixStore::new(&mut overlay, &MODULE_NAME);
+            Self::record_execution(store, params, &release, &total, pull, round)?;
+        }
+
+        // All transfers are made from the bridge-owned account, so it must cover their total
+        // before any of them is made. If this is a remote denomination mint the amount in the
+        // bridge-owned account. If this is a local denomination, then the amount is just unlocked
+        // from the account.
+        if remote {
+            Accounts::mint(ctx, *ADDRESS_LOCKED_FUNDS, &total)?;
+        } else if !Self::locked_funds_cover(ctx, &total) {
+            return Err(Error::InsufficientBalance);
+        }
+
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        Self::record_execution(store, params, &release, &total, pull, round)?;
+
+        for entry in release.entries() {
+            // With pull releases the funds are kept in the pending claims account until the
+            // target claims them, otherwise they are transferred into the user's account.
+            let to = if pull {
+                *ADDRESS_PENDING_CLAIMS
+            } else {
+                entry.target
+            };
+            Accounts::transfer(ctx, *ADDRESS_LOCKED_FUNDS, to, &entry.amount)?;
+
+            // Emit release event.
+            ctx.emit_event(Event::Release {
+                id: release.id,
+                remote_chain_id: params.remote_chain_id,
+                target: entry.target,
+                amount: entry.amount,
+                tx_hash: release.tx_hash,
+                log_index: release.log_index,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Record the execution of the given release in the statistics, the operation history, the
+    /// claims roots, the index of completed releases and the claimable balances of pull releases.
+    fn record_execution<S: storage::Store>(
+        mut store: S,
+        params: &Parameters,
+        release: &types::Release,
+        total: &token::BaseUnits,
+        pull: bool,
+        round: u64,
+    ) -> Result<(), Error> {
+        Self::update_stats(&mut store, |stats| {
+            stats.releases += 1;
+            stats.add_released(total);
+        });
+        Self::record_history(
+            &mut store,
+            params,
+            types::HistoryEntry {
+                direction: types::Direction::Incoming,
+                id: release.id,
+                op: types::Operation::Release(types::Release {
+                    signature: None,
+                    nonce: None,
+                    ..release.clone()
+                }),
+                round,
+            },
+        );
+        if let Some(root) = release.claims_root {
+            let mut claims_roots = storage::TypedStore::new(storage::PrefixStore::new(
+                &mut store,
+                &state::CLAIMS_ROOTS,
+            ));
+            claims_roots.insert(
+                sequence_key(total.denomination(), release.id),
+                &types::ClaimsRoot {
+                    root,
+                    remaining: total.clone(),
+                },
+            );
+        }
+
+        for entry in release.entries() {
+            Self::record_completed_release(
+                &mut store,
+                entry.target,
+                release.id,
+                &entry.amount,
+                round,
+            )?;
+            if pull {
+                Self::update_claimable_balance(&mut store, entry.target, &entry.amount, false)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add the given amount released to the given target to the index of completed releases.
+    ///
+    /// Multiple transfers of a batch release to the same target are recorded as a single
+    /// completed release.
+    fn record_completed_release<S: storage::Store>(
+        store: S,
+        target: Address,
+        id: u64,
+        amount: &token::BaseUnits,
+        round: u64,
+    ) -> Result<(), Error> {
+        let mut releases = storage::TypedStore::new(storage::PrefixStore::new(
+            storage::PrefixStore::new(store, &state::RELEASES_BY_TARGET),
+            target,
+        ));
+        let key = sequence_key(amount.denomination(), id);
+        let release = match releases.get::<_, types::CompletedRelease>(&key) {
+            Some(release) => types::CompletedRelease {
+                amount: token::BaseUnits::new(
+                    release
+                        .amount
+                        .amount()
+                        .checked_add(amount.amount())
+                        .ok_or(Error::InvalidArgument)?,
+                    amount.denomination().clone(),
+                ),
+                ..release
+            },
+            None => types::CompletedRelease {
+                id,
+                amount: amount.clone(),
+                round,
+            },
+        };
+        releases.insert(&key, &release);
+        Ok(())
+    }
+
+    /// Add the given finalized operation to the operation history, if the history is kept.
+    fn record_history<S: storage::Store>(
+        mut store: S,
+        params: &Parameters,
+        entry: types::HistoryEntry,
+    ) {
+        if params.history_retention == 0 {
+            return;
+        }
+
+        let mut tstore = storage::TypedStore::new(&mut store);
+        let index: u64 = tstore.get(state::NEXT_HISTORY_INDEX).unwrap_or_default();
+        tstore.insert(state::NEXT_HISTORY_INDEX, &(index + 1));
+        let mut history =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::HISTORY));
+        history.insert(index.to_storage_key(), &entry);
+    }
+
+    /// Remove the finalized operations that are older than the retention period from the
+    /// operation history.
+    fn prune_history<C: Context>(ctx: &mut C, params: &Parameters, round: u64) {
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut history =
+            storage::TypedStore::new(storage::PrefixStore::new(store, &state::HISTORY));
+        // Entries are ordered by finalization, so only a prefix of them can be expired.
+        let expired: Vec<[u8; 8]> = history
+            .iter()
+            .take_while(|(_, entry): &([u8; 8], types::HistoryEntry)| {
+                entry.round.saturating_add(params.history_retention) <= round
+            })
+            .map(|(key, _)| key)
+            .collect();
+        for key in expired {
+            history.remove(key);
+        }
+    }
+
+    /// Add the given amount to (or subtract it from) the claimable balance of the given address.
+    fn update_claimable_balance<S: storage::Store>(
+        store: S,
+        address: Address,
+        amount: &token::BaseUnits,
+        subtract: bool,
+    ) -> Result<(), Error> {
+        let mut claimable_balances =
+            storage::TypedStore::new(storage::PrefixStore::new(store, &state::CLAIMABLE_BALANCES));
+        let mut balances: BTreeMap<token::Denomination, Quantity> =
+            claimable_balances.get(address).unwrap_or_default();
+        let balance = balances.entry(amount.denomination().clone()).or_default();
+        *balance = if subtract {
+            balance.checked_sub(amount.amount())
+        } else {
+            balance.checked_add(amount.amount())
+        }
+        .ok_or(Error::InsufficientBalance)?;
+        balances.retain(|_, balance| *balance != Quantity::default());
+        if balances.is_empty() {
+            claimable_balances.remove(address);
+        } else {
+            claimable_balances.insert(address, &balances);
+        }
+        Ok(())
+    }
+
+    /// Releases waiting for their challenge period to pass, in sequence order of each
+    /// denomination.
+    fn pending_releases<S: storage::Store>(state: S) -> Vec<types::PendingRelease> {
+        let store = storage::PrefixStore::new(state, &MODULE_NAME);
+        let pending_releases =
+            storage::TypedStore::new(storage::PrefixStore::new(store, &state::PENDING_RELEASES));
+        pending_releases
+            .iter()
+            .map(|(_, pending): (Vec<u8>, types::PendingRelease)| pending)
+            .collect()
+    }
+
+    /// Check the accounting invariants of the bridge, returning the violations found.
+    ///
+    /// The locked funds must cover the outstanding remote claims on each local denomination, that
+    /// is everything locked that has not been released or recovered again. The minted supply
+    /// counter of each remote denomination must match the released amount, including queued
+    /// releases, net of the locked (burned) amount, and the total supply in the accounts module
+    /// must match the released amount net of the burned amount.
+    fn check_invariants<C: Context>(
+        ctx: &mut C,
+        params: &Parameters,
+    ) -> Result<Vec<types::InvariantViolation>, Error> {
+        let locked_funds =
+            Accounts::get_balances(ctx.runtime_state(), *ADDRESS_LOCKED_FUNDS)?.balances;
+        let total_supplies = Accounts::get_total_supplies(ctx.runtime_state())?;
+        let pending_releases = Self::pending_releases(ctx.runtime_state());
+        let stats = Self::stats(ctx.runtime_state());
+        let totals = Self::total_locked(ctx.runtime_state(), params);
+        let total = |volume: &BTreeMap<token::Denomination, Quantity>,
+                     denomination: &token::Denomination| {
+            volume.get(denomination).cloned().unwrap_or_default()
+        };
+
+        let mut violations = Vec::new();
+        for denomination in &params.local_denominations {
+            let balance = total(&locked_funds, denomination);
+            let outstanding = total(&totals.locked, denomination);
+            if balance < outstanding {
+                violations.push(types::InvariantViolation {
+                    invariant: types::Invariant::LockedFunds,
+                    denomination: denomination.clone(),
+                    actual: balance,
+                    expected: outstanding,
+                });
+            }
+        }
+        for denomination in params.remote_denominations.keys() {
+            let queued = pending_releases
+                .iter()
+                .filter(|pending| pending.amount.denomination() == denomination)
+                .try_fold(Quantity::default(), |sum, pending| {
+                    sum.checked_add(pending.total_amount()?.amount())
+                })
+                .ok_or(Error::InvalidArgument)?;
+            let released = total(&stats.released, denomination);
+            let burned = total(&stats.locked, denomination);
+            // Only released amounts can be burned again.
+            let circulating = match released.checked_sub(&burned) {
+                Some(circulating) => circulating,
+                None => {
+                    violations.push(types::InvariantViolation {
+                        invariant: types::Invariant::BurnedSupply,
+                        denomination: denomination.clone(),
+                        actual: burned,
+                        expected: released,
+                    });
+                    continue;
+                }
+            };
+            let expected = circulating
+                .checked_add(&queued)
+                .ok_or(Error::InvalidArgument)?;
+            let minted = total(&totals.minted, denomination);
+            if minted != expected {
+                violations.push(types::InvariantViolation {
+                    invariant: types::Invariant::MintedSupply,
+                    denomination: denomination.clone(),
+                    actual: minted,
+                    expected,
+                });
+            }
+            let supply = total(&total_supplies, denomination);
+            if supply != circulating {
+                violations.push(types::InvariantViolation {
+                    invariant: types::Invariant::TotalSupply,
+                    denomination: denomination.clone(),
+                    actual: supply,
+                    expected: circulating,
+                });
+            }
+        }
+        Ok(violations)
+    }
+
+    /// Execute the pending releases whose challenge period has passed.
+    ///
+    /// Nothing is executed while the bridge is paused. Releases of frozen denominations and to
+    /// blocked addresses stay queued until the denomination is unfrozen or the address unblocked.
+    /// Releases that fail to execute, for example because the locked funds do not cover them, stay
+    /// queued and are retried in the following blocks, emitting a `PendingReleaseFailed` event
+    /// each time.
+    fn execute_pending_releases<C: Context>(ctx: &mut C, params: &Parameters, round: u64) {
+        if Self::is_paused(ctx.runtime_state()) {
+            return;
+        }
+        let frozen = Self::frozen_denominations(ctx.runtime_state());
+
+        let due: Vec<_> = Self::pending_releases(ctx.runtime_state())
+            .into_iter()
+            .filter(|pending| {
+                pending.deadline <= round
+                    && !frozen.contains(pending.amount.denomination())
+                    && !pending
+                        .entries()
+                        .iter()
+                        .any(|entry| params.blocked_addresses.contains(&entry.target))
+            })
+            .collect();
+
+        for pending in due {
+            let denomination = pending.amount.denomination().clone();
+            let remote = params.remote_denominations.contains_key(&denomination);
+            let id = pending.id;
+            let release = types::Release {
+                id: pending.id,
+                target: pending.target,
+                amount: pending.amount,
+                tx_hash: pending.tx_hash,
+                log_index: pending.log_index,
+                batch: pending.batch,
+                claims_root: pending.claims_root,
+                remote_chain_id: None,
+                signature: None,
+                nonce: None,
+            };
+            if let Err(err) = Self::execute_release(ctx, params, remote, release) {
+                slog::warn!(
+                    ctx.get_logger(MODULE_NAME),
+                    "failed to execute pending release, keeping it queued";
+                    "denomination" => %denomination,
+                    "id" => id,
+                    "err" => %err,
+                );
+                ctx.emit_event(Event::PendingReleaseFailed {
+                    denomination,
+                    id,
+                    code: err.code(),
+                });
+                continue;
+            }
+
+            let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+            let mut pending_releases = storage::TypedStore::new(storage::PrefixStore::new(
+                store,
+                &state::PENDING_RELEASES,
+            ));
+            pending_releases.remove(sequence_key(&denomination, id));
+        }
+    }
+
+    /// Whether the locked funds cover the given amount.
+    fn locked_funds_cover<C: Context>(ctx: &mut C, amount: &token::BaseUnits) -> bool {
+        let available = Accounts::get_balances(ctx.runtime_state(), *ADDRESS_LOCKED_FUNDS)
+            .ok()
+            .and_then(|balances| balances.balances.get(amount.denomination()).cloned())
+            .unwrap_or_default();
+        &available >= amount.amount()
+    }
+
+    /// Recoveries waiting for their delay to pass.
+    fn pending_recoveries<S: storage::Store>(state: S) -> Vec<types::PendingRecovery> {
+        let store = storage::PrefixStore::new(state, &MODULE_NAME);
+        let recoveries =
+            storage::TypedStore::new(storage::PrefixStore::new(store, &state::RECOVERIES));
+        recoveries
+            .iter()
+            .map(|(_, recovery): ([u8; 8], types::PendingRecovery)| recovery)
+            .collect()
+    }
+
+    /// Execute the recoveries whose delay has passed.
+    ///
+    /// Recoveries that the locked funds cannot cover stay scheduled until they can be covered or
+    /// are cancelled.
+    fn execute_recoveries<C: Context>(ctx: &mut C, round: u64) {
+        let due: Vec<_> = Self::pending_recoveries(ctx.runtime_state())
+            .into_iter()
+            .filter(|recovery| recovery.deadline <= round)
+            .collect();
+
+        for recovery in due {
+            if Accounts::transfer(
+                ctx,
+                *ADDRESS_LOCKED_FUNDS,
+                recovery.target,
+                &recovery.amount,
+            )
+            .is_err()
+            {
+                continue;
+            }
+
+            let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+            Self::update_stats(&mut store, |stats| stats.add_recovered(&recovery.amount));
+            let mut recoveries =
+                storage::TypedStore::new(storage::PrefixStore::new(store, &state::RECOVERIES));
+            recoveries.remove(recovery.id.to_storage_key());
+
+            ctx.emit_event(Event::FundsRecovered {
+                id: recovery.id,
+                target: recovery.target,
+                amount: recovery.amount,
+            });
+        }
+    }
+
+    fn tx_retract_vote<C: TxContext>(ctx: &mut C, body: types::RetractVote) -> Result<(), Error> {
+        let caller_address = ctx.tx_caller_address();
+        let params = Self::params(ctx.runtime_state());
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        // Make sure the caller is an authorized witness.
+        let (index, witness) =
+            Self::find_witness(&mut store, &params.witnesses, &caller_address, |rotated| {
+                body.id <= Self::rotated_sequence(&rotated.incoming, &body.denomination)
+            })?;
+
+        // Only votes for pending incoming operations can be retracted, finalized operations are
+        // final.
+        Self::ensure_in_release_window(&mut store, &params, &body.denomination, body.id)?;
+
+        let key = sequence_key(&body.denomination, body.id);
+        let in_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::IN_WITNESS_SIGNATURES,
+        ));
+        let mut info: types::IncomingWitnessSignatures =
+            in_witness_signatures.get(&key).unwrap_or_default();
+        if !info.signers.contains(index) {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        // Remove the vote so that the witness can vote again.
+        info.signers.remove(index);
+        let mut retracted = vec![];
+        for (op_id, op_sigs) in info.ops.iter_mut() {
+            if op_sigs.signers.remove(index) {
+                retracted.push(op_id.clone());
+            }
+        }
+        info.ops.retain(|_, op_sigs| !op_sigs.signers.is_empty());
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut in_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::IN_WITNESS_SIGNATURES,
+        ));
+        if info.signers.is_empty() {
+            in_witness_signatures.remove(&key);
+        } else {
+            in_witness_signatures.insert(&key, &info);
+        }
+
+        // Remember the retracted operations so that voting again is not treated as equivocation.
+        let retraction_id = Self::retraction_id(&witness, &body.denomination, body.id);
+        let mut retracted_votes = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::RETRACTED_VOTES,
+        ));
+        let mut ops: Vec<types::OperationId> =
+            retracted_votes.get(&retraction_id).unwrap_or_default();
+        ops.extend(retracted);
+        retracted_votes.insert(&retraction_id, &ops);
+
+        ctx.emit_event(Event::VoteRetracted {
+            denomination: body.denomination,
+            id: body.id,
+            index,
+            witness,
+        });
+
+        Ok(())
+    }
+
+    /// Storage key of the votes that the given witness retracted for the given incoming sequence
+    /// number of the given denomination.
+    fn retraction_id(
+        witness: &types::WitnessKey,
+        denomination: &token::Denomination,
+        id: u64,
+    ) -> Hash {
+        Hash::digest_bytes(&cbor::to_vec(&cbor::to_value(&(
+            witness.id(),
+            denomination,
+            id,
+        ))))
+    }
+
+    fn tx_set_witness_signing_key<C: TxContext>(
+        ctx: &mut C,
+        body: types::SetWitnessSigningKey,
+    ) -> Result<(), Error> {
+        if !body.key.iter().all(crypto::is_valid_witness_key) {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let caller_address = ctx.tx_caller_address();
+        let params = Self::params(ctx.runtime_state());
+        // Make sure the caller is an authorized witness.
+        // Multisig witnesses cannot delegate, so only single-key witnesses are authorized.
+        if !params
+            .witnesses
+            .iter()
+            .any(|witness| witness.public_key().map(Address::from_pk) == Some(caller_address))
+        {
+            return Err(Error::NotAuthorized);
+        }
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut signing_keys = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_SIGNING_KEYS,
+        ));
+        match body.key {
+            Some(key) => signing_keys.insert(caller_address, &key),
+            None => signing_keys.remove(caller_address),
+        }
+
+        Ok(())
+    }
+
+    fn tx_update_witnesses<C: TxContext>(
+        ctx: &mut C,
+        mut body: types::UpdateWitnesses,
+    ) -> Result<(), Error> {
+        let params = Self::params(ctx.runtime_state());
+        let caller_address = ctx.tx_caller_address();
+        // Make sure the caller is an authorized witness.
+        let (index, witness) = params
+            .witnesses
+            .iter()
+            .enumerate()
+            .find(|(_, witness)| witness.is_authorized(&caller_address))
+            .ok_or(Error::NotAuthorized)?;
+        let index = index as u16;
+        // Make sure the proposed witness set is valid.
+        let updated = Parameters {
+            witnesses: body.witnesses.clone(),
+            threshold: body.threshold,
+            ..params.clone()
+        };
+        if module::Parameters::validate_basic(&updated).is_err() {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        // The signature is not part of the update.
+        let signature = body.signature.take().ok_or(Error::InvalidSignature)?;
+
+        // Check if sequence number is correct.
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let signing_key = Self::signing_key(&mut store, witness);
+        Self::ensure_bonded(&mut store, &params, witness)?;
+        let threshold = Self::current_threshold(&mut store, &params);
+        let tstore = storage::TypedStore::new(&mut store);
+        let expected_id: u64 = tstore
+            .get(state::NEXT_WITNESS_SET_UPDATE)
+            .unwrap_or_default();
+        if body.id != expected_id {
+            return Err(Error::InvalidSequenceNumber);
+        }
+
+        // Fetch existing votes.
+        let mut update_votes = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_SET_UPDATE_VOTES,
+        ));
+        let mut votes: types::UpdateVotes = update_votes
+            .get(body.id.to_storage_key())
+            .unwrap_or_default();
+
+        // Make sure it didn't already vote.
+        if votes.witnesses.contains(&index) {
+            return Err(Error::AlreadySubmittedSignature);
+        }
+        // Make sure the witness signed the proposed update.
+        crypto::verify_witness_set_update(&signing_key, &params, &body, &signature)?;
+
+        // Different updates may be proposed for the same sequence number, so votes are counted
+        // separately for each of them.
+        let update_id = Hash::digest_bytes(&crypto::witness_set_update_message(&params, &body));
+        let voters = votes.updates.entry(update_id).or_default();
+        voters.push(index);
+        let approved = (voters.len() as u64) >= threshold;
+        votes.witnesses.push(index);
+        if !approved {
+            // Not enough votes yet.
+            update_votes.insert(body.id.to_storage_key(), &votes);
+            return Ok(());
+        }
+
+        // Clear entry in storage.
+        update_votes.remove(body.id.to_storage_key());
+
+        // Increment sequence number.
+        let mut tstore = storage::TypedStore::new(&mut store);
+        tstore.insert(state::NEXT_WITNESS_SET_UPDATE, &(expected_id + 1));
+
+        // Votes cast so far refer to witnesses by their index in the replaced witness set.
+        if updated.witnesses != params.witnesses {
+            Self::clear_pending_votes(&mut store);
+        }
+
+        // Apply the update.
+        Self::set_witness_set(ctx, &updated);
+
+        ctx.emit_event(Event::WitnessSetUpdated {
+            id: body.id,
+            witnesses: body.witnesses,
+            threshold: body.threshold,
+        });
+
+        Ok(())
+    }
+
+    fn tx_set_fee_schedule<C: TxContext>(
+        ctx: &mut C,
+        body: types::SetFeeSchedule,
+    ) -> Result<(), Error> {
+        let mut params = Self::params(ctx.runtime_state());
+        let caller_address = ctx.tx_caller_address();
+        // The admin can change the fee schedule directly, otherwise the caller must be an
+        // authorized witness voting for the change.
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let voter = Self::update_voter(store, &params, &caller_address)?;
+        // Make sure the proposed fee schedule is valid.
+        let current = params.clone();
+        params.set_fee_schedule(body.schedule.clone());
+        if module::Parameters::validate_basic(&params).is_err() {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let proposal = Hash::digest_bytes(&cbor::to_vec(&body.schedule));
+        if !Self::vote_update(
+            store,
+            &current,
+            state::FEE_SCHEDULE_VOTES,
+            state::NEXT_FEE_SCHEDULE_UPDATE,
+            voter,
+            body.id,
+            proposal,
+        )? {
+            // Not enough votes yet.
+            return Ok(());
+        }
+
+        // Apply the update.
+        Self::set_params(ctx.runtime_state(), &params);
+
+        ctx.emit_event(Event::FeeScheduleChanged {
+            id: body.id,
+            schedule: body.schedule,
+        });
+
+        Ok(())
+    }
+
+    fn tx_set_remote_contract<C: TxContext>(
+        ctx: &mut C,
+        body: types::SetRemoteContract,
+    ) -> Result<(), Error> {
+        let mut params = Self::params(ctx.runtime_state());
+        let caller_address = ctx.tx_caller_address();
+        // The admin can change the remote contract directly, otherwise the caller must be an
+        // authorized witness relaying the change from the remote chain's governance.
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let voter = Self::update_voter(store, &params, &caller_address)?;
+        // Make sure the proposed remote contract is valid. An EIP-712 domain always verifies
+        // signatures against the remote contract, so it follows the update.
+        let current = params.clone();
+        params.remote_contract = Some(body.contract);
+        if let Some(domain) = &mut params.eip712_domain {
+            domain.verifying_contract = body.contract;
+        }
+        if module::Parameters::validate_basic(&params).is_err() {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let proposal = Hash::digest_bytes(&cbor::to_vec(&body.contract));
+        if !Self::vote_update(
+            store,
+            &current,
+            state::REMOTE_CONTRACT_VOTES,
+            state::NEXT_REMOTE_CONTRACT_UPDATE,
+            voter,
+            body.id,
+            proposal,
+        )? {
+            // Not enough votes yet.
+            return Ok(());
+        }
+
+        // Apply the update.
+        Self::set_params(ctx.runtime_state(), &params);
+
+        ctx.emit_event(Event::RemoteContractChanged {
+            id: body.id,
+            contract: body.contract,
+        });
+
+        Ok(())
+    }
+
+    fn tx_register_denomination<C: TxContext>(
+        ctx: &mut C,
+        body: types::RegisterDenomination,
+    ) -> Result<(), Error> {
+        let mut params = Self::params(ctx.runtime_state());
+        let caller_address = ctx.tx_caller_address();
+        // The admin can register a denomination directly, otherwise the caller must be an
+        // authorized witness voting for the registration.
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let voter = Self::update_voter(store, &params, &caller_address)?;
+        // Make sure the denomination is new and the resulting parameters are valid.
+        if params.local_denominations.contains(&body.denomination)
+            || params.remote_denominations.contains_key(&body.denomination)
+        {
+            return Err(Error::InvalidArgument);
+        }
+        let current = params.clone();
+        params
+            .remote_denominations
+            .insert(body.denomination.clone(), body.remote.clone());
+        if let Some(scale) = body.decimal_scale {
+            params
+                .decimal_scales
+                .insert(body.denomination.clone(), scale);
+        }
+        if module::Parameters::validate_basic(&params).is_err() {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let proposal = Hash::digest_bytes(&cbor::to_vec(&body));
+        if !Self::vote_update(
+            store,
+            &current,
+            state::DENOMINATION_REGISTRATION_VOTES,
+            state::NEXT_DENOMINATION_REGISTRATION,
+            voter,
+            body.id,
+            proposal,
+        )? {
+            // Not enough votes yet.
+            return Ok(());
+        }
+
+        // Apply the registration.
+        Self::set_params(ctx.runtime_state(), &params);
+
+        ctx.emit_event(Event::DenominationRegistered {
+            id: body.id,
+            denomination: body.denomination,
+            remote: body.remote,
+        });
+
+        Ok(())
+    }
+
+    fn tx_attest_remote_locked<C: TxContext>(
+        ctx: &mut C,
+        body: types::AttestRemoteLocked,
+    ) -> Result<(), Error> {
+        let params = Self::params(ctx.runtime_state());
+        // Only remote denominations are locked in the remote contract.
+        if !params
+            .remote_denominations
+            .contains_key(body.amount.denomination())
+        {
+            return Err(Error::UnsupportedDenomination);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let caller_address = ctx.tx_caller_address();
+        // Make sure the caller is an authorized witness.
+        let witness = params
+            .witnesses
+            .iter()
+            .find(|witness| witness.is_authorized(&caller_address))
+            .ok_or(Error::NotAuthorized)?;
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        Self::ensure_bonded(&mut store, &params, witness)?;
+        let threshold = Self::current_threshold(&mut store, &params);
+
+        let mut tstore = storage::TypedStore::new(&mut store);
+        let mut reserves: BTreeMap<token::Denomination, types::RemoteReserve> =
+            tstore.get(state::REMOTE_RESERVES).unwrap_or_default();
+        let reserve = reserves
+            .entry(body.amount.denomination().clone())
+            .or_default();
+        // Only the latest attestation of each witness counts.
+        reserve
+            .attestations
+            .insert(witness.id(), body.amount.amount().clone());
+
+        // The agreed reserve is the largest amount that a threshold of current witnesses attested
+        // to, as each of them attested to at least that amount.
+        let mut attested: Vec<&Quantity> = params
+            .witnesses
+            .iter()
+            .filter_map(|witness| reserve.attestations.get(&witness.id()))
+            .collect();
+        attested.sort_unstable_by(|a, b| b.cmp(a));
+        let updated = match threshold
+            .checked_sub(1)
+            .and_then(|index| attested.get(index as usize))
+        {
+            Some(&locked) if reserve.locked.as_ref() != Some(locked) => {
+                reserve.locked = Some(locked.clone());
+                reserve.locked.clone()
+            }
+            _ => None,
+        };
+        tstore.insert(state::REMOTE_RESERVES, &reserves);
+
+        if let Some(locked) = updated {
+            ctx.emit_event(Event::RemoteLockedAttested {
+                amount: token::BaseUnits::new(locked, body.amount.denomination().clone()),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn tx_set_witness_metadata<C: TxContext>(
+        ctx: &mut C,
+        body: types::WitnessMetadata,
+    ) -> Result<(), Error> {
+        if body.name.len() > MAX_WITNESS_NAME_LENGTH
+            || body.endpoint.len() > MAX_WITNESS_ENDPOINT_LENGTH
+        {
+            return Err(Error::InvalidArgument);
+        }
+
+        let caller_address = ctx.tx_caller_address();
+        let params = Self::params(ctx.runtime_state());
+        // Make sure the caller is an authorized witness.
+        let witness = params
+            .witnesses
+            .iter()
+            .find(|witness| witness.is_authorized(&caller_address))
+            .ok_or(Error::NotAuthorized)?;
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut metadata = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_METADATA,
+        ));
+        if body.is_empty() {
+            metadata.remove(witness.id());
+        } else {
+            metadata.insert(witness.id(), &body);
+        }
+
+        Ok(())
+    }
+
+    fn tx_rotate_witness_key<C: TxContext>(
+        ctx: &mut C,
+        body: types::RotateWitnessKey,
+    ) -> Result<(), Error> {
+        if !crypto::is_valid_witness_key(&body.key) {
+            return Err(Error::InvalidArgument);
+        }
+
+        let caller_address = ctx.tx_caller_address();
+        let mut params = Self::params(ctx.runtime_state());
+        // Make sure the caller is a single-key witness.
+        let (index, old) = params
+            .witnesses
+            .iter()
+            .enumerate()
+            .find_map(|(index, witness)| match witness.public_key() {
+                Some(pk) if Address::from_pk(pk) == caller_address => Some((index, pk.clone())),
+                _ => None,
+            })
+            .ok_or(Error::NotAuthorized)?;
+        // Make sure the new key is not already used by any witness, now or in the past.
+        let new_address = Address::from_pk(&body.key);
+        if params
+            .witnesses
+            .iter()
+            .any(|witness| witness.is_authorized(&new_address))
+        {
+            return Err(Error::InvalidArgument);
+        }
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let rotated_keys = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::ROTATED_WITNESS_KEYS,
+        ));
+        if rotated_keys
+            .get::<_, types::RotatedWitnessKey>(new_address)
+            .is_some()
+        {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        // Remember the previous key so that it can still be used for operations in flight.
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let rotated = types::RotatedWitnessKey {
+            key: old.clone(),
+            successor: body.key.clone(),
+            incoming: Self::next_sequences(&mut store, params.remote_chain_id, false),
+            outgoing: Self::next_sequences(&mut store, params.remote_chain_id, true),
+        };
+        let mut rotated_keys = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::ROTATED_WITNESS_KEYS,
+        ));
+        rotated_keys.insert(caller_address, &rotated);
+
+        // Metadata is carried over to the new key.
+        let old_id = types::WitnessKey::from(old.clone()).id();
+        let mut metadata = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_METADATA,
+        ));
+        if let Some(info) = metadata.get::<_, types::WitnessMetadata>(&old_id) {
+            metadata.remove(&old_id);
+            metadata.insert(types::WitnessKey::from(body.key.clone()).id(), &info);
+        }
+        // And so are the bond and reward points.
+        let mut bonds =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::WITNESS_BONDS));
+        if let Some(bond) = bonds.get::<_, types::WitnessBond>(&old_id) {
+            bonds.remove(&old_id);
+            bonds.insert(types::WitnessKey::from(body.key.clone()).id(), &bond);
+        }
+        let mut points =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::REWARD_POINTS));
+        if let Some(count) = points.get::<_, u64>(&old_id) {
+            points.remove(&old_id);
+            points.insert(types::WitnessKey::from(body.key.clone()).id(), &count);
+        }
+        // Vote nonces continue from where the previous key left off so that votes submitted
+        // with the previous key cannot be replayed.
+        let mut vote_nonces =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::VOTE_NONCES));
+        if let Some(nonces) = vote_nonces.get::<_, types::VoteNonces>(&old_id) {
+            vote_nonces.remove(&old_id);
+            vote_nonces.insert(types::WitnessKey::from(body.key.clone()).id(), &nonces);
+        }
+
+        // Replace the witness key in place so that its index is unchanged.
+        params.witnesses[index] = body.key.clone().into();
+        Self::set_witness_set(ctx, &params);
+
+        ctx.emit_event(Event::WitnessKeyRotated {
+            index: index as u16,
+            old,
+            new: body.key,
+        });
+
+        Ok(())
+    }
+
+    fn tx_pause<C: TxContext>(ctx: &mut C, _body: ()) -> Result<(), Error> {
+        Self::set_paused(ctx, true)
+    }
+
+    fn tx_resume<C: TxContext>(ctx: &mut C, _body: ()) -> Result<(), Error> {
+        Self::set_paused(ctx, false)
+    }
+
+    /// Pause or resume the bridge on behalf of a guardian.
+    fn set_paused<C: TxContext>(ctx: &mut C, paused: bool) -> Result<(), Error> {
+        let params = Self::params(ctx.runtime_state());
+        Self::ensure_guardian(ctx, &params)?;
+        if Self::is_paused(ctx.runtime_state()) == paused {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut tstore = storage::TypedStore::new(store);
+        tstore.insert(state::PAUSED, &paused);
+
+        let guardian = ctx.tx_caller_address();
+        if paused {
+            ctx.emit_event(Event::Paused { guardian });
+        } else {
+            ctx.emit_event(Event::Resumed { guardian });
+        }
+
+        Ok(())
+    }
+
+    fn tx_freeze_denomination<C: TxContext>(
+        ctx: &mut C,
+        body: types::FreezeDenomination,
+    ) -> Result<(), Error> {
+        Self::set_frozen(ctx, body.denomination, true)
+    }
+
+    fn tx_unfreeze_denomination<C: TxContext>(
+        ctx: &mut C,
+        body: types::FreezeDenomination,
+    ) -> Result<(), Error> {
+        Self::set_frozen(ctx, body.denomination, false)
+    }
+
+    fn tx_set_denomination_metadata<C: TxContext>(
+        ctx: &mut C,
+        body: types::SetDenominationMetadata,
+    ) -> Result<(), Error> {
+        if body.metadata.symbol.len() > MAX_DENOMINATION_SYMBOL_LENGTH {
+            return Err(Error::InvalidArgument);
+        }
+
+        let params = Self::params(ctx.runtime_state());
+        Self::ensure_admin(ctx, &params)?;
+        Self::ensure_local_or_remote(ctx, &body.denomination)?;
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let mut metadata = Self::denomination_metadata(ctx.runtime_state());
+        if body.metadata.is_empty() {
+            metadata.remove(&body.denomination);
+        } else {
+            metadata.insert(body.denomination, body.metadata);
+        }
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut tstore = storage::TypedStore::new(store);
+        tstore.insert(state::DENOMINATION_METADATA, &metadata);
+
+        Ok(())
+    }
+
+    /// Freeze or unfreeze a denomination on behalf of a guardian or the admin.
+    fn set_frozen<C: TxContext>(
+        ctx: &mut C,
+        denomination: token::Denomination,
+        frozen: bool,
+    ) -> Result<(), Error> {
+        let params = Self::params(ctx.runtime_state());
+        if params.admin != Some(ctx.tx_caller_address()) {
+            Self::ensure_guardian(ctx, &params)?;
+        }
+        Self::ensure_local_or_remote(ctx, &denomination)?;
+        let mut frozen_denominations = Self::frozen_denominations(ctx.runtime_state());
+        let changed = if frozen {
+            frozen_denominations.insert(denomination.clone())
+        } else {
+            frozen_denominations.remove(&denomination)
+        };
+        if !changed {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut tstore = storage::TypedStore::new(store);
+        tstore.insert(state::FROZEN_DENOMINATIONS, &frozen_denominations);
+
+        let by = ctx.tx_caller_address();
+        if frozen {
+            ctx.emit_event(Event::DenominationFrozen { denomination, by });
+        } else {
+            ctx.emit_event(Event::DenominationUnfrozen { denomination, by });
+        }
+
+        Ok(())
+    }
+
+    fn tx_cancel_release<C: TxContext>(
+        ctx: &mut C,
+        body: types::CancelRelease,
+    ) -> Result<(), Error> {
+        let params = Self::params(ctx.runtime_state());
+        if params.admin != Some(ctx.tx_caller_address()) {
+            Self::ensure_guardian(ctx, &params)?;
+        }
+        let by = ctx.tx_caller_address();
+        let pending = Self::pending_releases(ctx.runtime_state())
+            .into_iter()
+            .find(|pending| {
+                pending.id == body.id && pending.amount.denomination() == &body.denomination
+            })
+            .ok_or(Error::InvalidArgument)?;
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut pending_releases = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::PENDING_RELEASES,
+        ));
+        pending_releases.remove(sequence_key(&body.denomination, body.id));
+        // The cancelled amount is never minted.
+        if params
+            .remote_denominations
+            .contains_key(pending.amount.denomination())
+        {
+            let total = pending.total_amount().ok_or(Error::InvalidArgument)?;
+            Self::update_minted_supply(&mut store, &total, false)?;
+        }
+
+        ctx.emit_event(Event::ReleaseCancelled {
+            denomination: body.denomination,
+            id: body.id,
+            by,
+        });
+
+        Ok(())
+    }
+
+    fn tx_claim<C: TxContext>(ctx: &mut C, body: types::Claim) -> Result<(), Error> {
+        if Self::is_paused(ctx.runtime_state()) {
+            return Err(Error::Paused);
+        }
+        if Self::frozen_denominations(ctx.runtime_state()).contains(&body.denomination) {
+            return Err(Error::DenominationFrozen);
+        }
+        if body.amount.denomination() != &body.denomination {
+            return Err(Error::InvalidArgument);
+        }
+        // Make sure funds are not claimed into any of the internal accounts.
+        if Self::is_internal_address(&body.target) {
+            return Err(Error::InvalidTarget);
+        }
+        let params = Self::params(ctx.runtime_state());
+        if params.blocked_addresses.contains(&body.target) {
+            return Err(Error::AddressBlocked);
+        }
+        Self::ensure_max_transfer_amount(&params, &body.amount)?;
+        let round = ctx.runtime_header().round;
+
+        let key = sequence_key(&body.denomination, body.id);
+        let claimed_key = [&key[..], &body.index.to_storage_key()].concat();
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let claims_roots =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::CLAIMS_ROOTS));
+        let mut claims_root: types::ClaimsRoot =
+            claims_roots.get(&key).ok_or(Error::InvalidSequenceNumber)?;
+        let leaf = crypto::claim_leaf(body.index, &body.target, &body.amount);
+        if crypto::claims_root_from_proof(leaf, body.index, &body.proof) != Some(claims_root.root) {
+            return Err(Error::InvalidClaimProof);
+        }
+        let claimed =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::CLAIMED));
+        if claimed.get::<_, u64>(&claimed_key).is_some() {
+            return Err(Error::AlreadyClaimed);
+        }
+        // Claims can never pay out more than was released for them.
+        let remaining = claims_root
+            .remaining
+            .amount()
+            .checked_sub(body.amount.amount())
+            .ok_or(Error::InsufficientBalance)?;
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        Self::use_account_limit(&mut store, &params, round, body.target, &body.amount)?;
+        let mut claimed =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::CLAIMED));
+        claimed.insert(&claimed_key, &round);
+        claims_root.remaining = token::BaseUnits::new(remaining, body.denomination.clone());
+        let mut claims_roots =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::CLAIMS_ROOTS));
+        claims_roots.insert(&key, &claims_root);
+
+        Accounts::transfer(ctx, *ADDRESS_PENDING_CLAIMS, body.target, &body.amount)?;
+
+        ctx.emit_event(Event::Claimed {
+            denomination: body.denomination,
+            id: body.id,
+            index: body.index,
+            target: body.target,
+            amount: body.amount,
+        });
+
+        Ok(())
+    }
+
+    fn tx_claim_balance<C: TxContext>(ctx: &mut C, body: types::ClaimBalance) -> Result<(), Error> {
+        if Self::is_paused(ctx.runtime_state()) {
+            return Err(Error::Paused);
+        }
+        if Self::frozen_denominations(ctx.runtime_state()).contains(body.amount.denomination()) {
+            return Err(Error::DenominationFrozen);
+        }
+        if body.amount.amount() == &Quantity::default() {
+            return Err(Error::InvalidArgument);
+        }
+        let caller_address = ctx.tx_caller_address();
+        let params = Self::params(ctx.runtime_state());
+        if params.blocked_addresses.contains(&caller_address) {
+            return Err(Error::AddressBlocked);
+        }
+        let claimable = Self::claimable_balances(ctx.runtime_state(), caller_address)
+            .get(body.amount.denomination())
+            .cloned()
+            .unwrap_or_default();
+        if &claimable < body.amount.amount() {
+            return Err(Error::InsufficientBalance);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        Self::update_claimable_balance(&mut store, caller_address, &body.amount, true)?;
+        Accounts::transfer(ctx, *ADDRESS_PENDING_CLAIMS, caller_address, &body.amount)?;
+
+        ctx.emit_event(Event::BalanceClaimed {
+            target: caller_address,
+            amount: body.amount,
+        });
+
+        Ok(())
+    }
+
+    /// Amounts that pull releases credited to the given address and that it has not claimed yet.
+    fn claimable_balances<S: storage::Store>(
+        state: S,
+        address: Address,
+    ) -> BTreeMap<token::Denomination, Quantity> {
+        let store = storage::PrefixStore::new(state, &MODULE_NAME);
+        let claimable_balances =
+            storage::TypedStore::new(storage::PrefixStore::new(store, &state::CLAIMABLE_BALANCES));
+        claimable_balances.get(address).unwrap_or_default()
+    }
+
+    fn tx_recover<C: TxContext>(ctx: &mut C, body: types::Recover) -> Result<(), Error> {
+        let params = Self::params(ctx.runtime_state());
+        Self::ensure_admin(ctx, &params)?;
+        if params.recovery_delay == 0 || body.amount.amount() == &Quantity::default() {
+            return Err(Error::InvalidArgument);
+        }
+        // Make sure funds are not recovered into any of the internal accounts.
+        if Self::is_internal_address(&body.target) {
+            return Err(Error::InvalidTarget);
+        }
+        let available = Accounts::get_balances(ctx.runtime_state(), *ADDRESS_LOCKED_FUNDS)?
+            .balances
+            .get(body.amount.denomination())
+            .cloned()
+            .unwrap_or_default();
+        if &available < body.amount.amount() {
+            return Err(Error::InsufficientBalance);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let round = ctx.runtime_header().round;
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut tstore = storage::TypedStore::new(&mut store);
+        let id: u64 = tstore.get(state::NEXT_RECOVERY).unwrap_or_default();
+        tstore.insert(state::NEXT_RECOVERY, &(id + 1));
+
+        let recovery = types::PendingRecovery {
+            id,
+            target: body.target,
+            amount: body.amount,
+            deadline: round.saturating_add(params.recovery_delay),
+        };
+        let mut recoveries =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::RECOVERIES));
+        recoveries.insert(id.to_storage_key(), &recovery);
+
+        ctx.emit_event(Event::RecoveryScheduled {
+            id,
+            target: recovery.target,
+            amount: recovery.amount,
+            deadline: recovery.deadline,
+        });
+
+        Ok(())
+    }
+
+    fn tx_cancel_recovery<C: TxContext>(
+        ctx: &mut C,
+        body: types::CancelRecovery,
+    ) -> Result<(), Error> {
+        let params = Self::params(ctx.runtime_state());
+        if params.admin != Some(ctx.tx_caller_address()) {
+            Self::ensure_guardian(ctx, &params)?;
+        }
+        let by = ctx.tx_caller_address();
+        if !Self::pending_recoveries(ctx.runtime_state())
+            .iter()
+            .any(|recovery| recovery.id == body.id)
+        {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut recoveries =
+            storage::TypedStore::new(storage::PrefixStore::new(store, &state::RECOVERIES));
+        recoveries.remove(body.id.to_storage_key());
+
+        ctx.emit_event(Event::RecoveryCancelled { id: body.id, by });
+
+        Ok(())
+    }
+
+    fn tx_reactivate_witness<C: TxContext>(ctx: &mut C, _body: ()) -> Result<(), Error> {
+        let caller_address = ctx.tx_caller_address();
+        let round = ctx.runtime_header().round;
+        let params = Self::params(ctx.runtime_state());
+        // Make sure the caller is an authorized witness.
+        let (index, witness) = params
+            .witnesses
+            .iter()
+            .enumerate()
+            .find(|(_, witness)| witness.is_authorized(&caller_address))
+            .ok_or(Error::NotAuthorized)?;
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let liveness = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_LIVENESS,
+        ));
+        let mut info: types::WitnessLiveness = liveness.get(witness.id()).unwrap_or_default();
+        if !info.inactive {
+            return Err(Error::InvalidArgument);
+        }
+        // Jailed witnesses need to wait for the jail to end.
+        if round <= info.jailed_until {
+            return Err(Error::NotAuthorized);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut liveness = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_LIVENESS,
+        ));
+        info.inactive = false;
+        info.missed = 0;
+        liveness.insert(witness.id(), &info);
+
+        ctx.emit_event(Event::WitnessReactivated {
+            index: index as u16,
+            witness: witness.clone(),
+        });
+
+        Ok(())
+    }
+
+    fn tx_bond<C: TxContext>(ctx: &mut C, body: types::Bond) -> Result<(), Error> {
+        let caller_address = ctx.tx_caller_address();
+        let params = Self::params(ctx.runtime_state());
+        let required = params.witness_bond.as_ref().ok_or(Error::InvalidArgument)?;
+        if body.amount.denomination() != required.denomination() {
+            return Err(Error::UnsupportedDenomination);
+        }
+        // Make sure the caller is an authorized witness.
+        let (index, witness) = params
+            .witnesses
+            .iter()
+            .enumerate()
+            .find(|(_, witness)| witness.is_authorized(&caller_address))
+            .ok_or(Error::NotAuthorized)?;
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let bonds =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::WITNESS_BONDS));
+        let bond = match bonds.get::<_, types::WitnessBond>(witness.id()) {
+            // Only the account that posted the bond can add to it.
+            Some(bond) if bond.owner != caller_address => return Err(Error::NotAuthorized),
+            Some(bond) => types::WitnessBond {
+                amount: token::BaseUnits::new(
+                    bond.amount
+                        .amount()
+                        .checked_add(body.amount.amount())
+                        .ok_or(Error::InvalidArgument)?,
+                    bond.amount.denomination().clone(),
+                ),
+                ..bond
+            },
+            None => types::WitnessBond {
+                owner: caller_address,
+                amount: body.amount.clone(),
+            },
+        };
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        // Transfer funds from the witness' account into the bridge-owned bond account.
+        Accounts::transfer(ctx, caller_address, *ADDRESS_WITNESS_BONDS, &body.amount)?;
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut bonds =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::WITNESS_BONDS));
+        bonds.insert(witness.id(), &bond);
+
+        ctx.emit_event(Event::WitnessBonded {
+            index: index as u16,
+            witness: witness.clone(),
+            amount: body.amount,
+        });
+
+        Ok(())
+    }
+
+    fn tx_unbond<C: TxContext>(ctx: &mut C, body: types::Unbond) -> Result<(), Error> {
+        let caller_address = ctx.tx_caller_address();
+        let round = ctx.runtime_header().round;
+        let params = Self::params(ctx.runtime_state());
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let unbonding = match body.amount {
+            Some(amount) => {
+                // Make sure the caller is an authorized witness that posted the bond.
+                let witness = params
+                    .witnesses
+                    .iter()
+                    .find(|witness| witness.is_authorized(&caller_address))
+                    .ok_or(Error::NotAuthorized)?;
+                let bonds = storage::TypedStore::new(storage::PrefixStore::new(
+                    &mut store,
+                    &state::WITNESS_BONDS,
+                ));
+                let bond: types::WitnessBond =
+                    bonds.get(witness.id()).ok_or(Error::InsufficientBond)?;
+                if bond.owner != caller_address {
+                    return Err(Error::NotAuthorized);
+                }
+                if amount.denomination() != bond.amount.denomination() {
+                    return Err(Error::UnsupportedDenomination);
+                }
+                let remaining = bond
+                    .amount
+                    .amount()
+                    .checked_sub(amount.amount())
+                    .ok_or(Error::InsufficientBond)?;
+                Some((witness.clone(), bond, remaining, amount))
+            }
+            None => None,
+        };
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut unbonded = None;
+        if let Some((witness, bond, remaining, amount)) = unbonding {
+            let mut bonds = storage::TypedStore::new(storage::PrefixStore::new(
+                &mut store,
+                &state::WITNESS_BONDS,
+            ));
+            if remaining == Quantity::default() {
+                bonds.remove(witness.id());
+            } else {
+                bonds.insert(
+                    witness.id(),
+                    &types::WitnessBond {
+                        amount: token::BaseUnits::new(
+                            remaining,
+                            bond.amount.denomination().clone(),
+                        ),
+                        ..bond
+                    },
+                );
+            }
+            let ends =
+                Self::start_unbonding(&mut store, &params, caller_address, amount.clone(), round);
+            unbonded = Some((witness, amount, ends));
+        }
+
+        // Withdraw all amounts whose unbonding delay has passed.
+        let mut unbondings =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::UNBONDINGS));
+        let (withdrawn, pending): (Vec<types::Unbonding>, Vec<types::Unbonding>) = unbondings
+            .get::<_, Vec<types::Unbonding>>(caller_address)
+            .unwrap_or_default()
+            .into_iter()
+            .partition(|unbonding| unbonding.ends <= round);
+        if pending.is_empty() {
+            unbondings.remove(caller_address);
+        } else {
+            unbondings.insert(caller_address, &pending);
+        }
+        for unbonding in withdrawn {
+            Accounts::transfer(
+                ctx,
+                *ADDRESS_WITNESS_BONDS,
+                caller_address,
+                &unbonding.amount,
+            )?;
+        }
+
+        if let Some((witness, amount, ends)) = unbonded {
+            ctx.emit_event(Event::WitnessUnbonding {
+                witness,
+                owner: caller_address,
+                amount,
+                ends,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn tx_submit_evidence<C: TxContext>(
+        ctx: &mut C,
+        body: types::SubmitEvidence,
+    ) -> Result<(), Error> {
+        // The evidence must cover two different operations in the same sequence space.
+        if types::OperationId::from(&body.first.op) == types::OperationId::from(&body.second.op) {
+            return Err(Error::InvalidArgument);
+        }
+        let denomination = body.first.op.amount().denomination();
+        if body.second.op.amount().denomination() != denomination {
+            return Err(Error::InvalidArgument);
+        }
+
+        let round = ctx.runtime_header().round;
+        let params = Self::params(ctx.runtime_state());
+        let index = params
+            .witnesses
+            .iter()
+            .position(|witness| witness == &body.witness)
+            .ok_or(Error::InvalidArgument)?;
+
+        // Make sure both operations were signed by the witness.
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let signing_key = Self::signing_key(&mut store, &body.witness);
+        for signed in &[&body.first, &body.second] {
+            match body.kind {
+                types::EvidenceKind::Witness => crypto::verify_witness(
+                    &signing_key,
+                    &params,
+                    body.id,
+                    &signed.op,
+                    &signed.signature,
+                )?,
+                types::EvidenceKind::Release => crypto::verify_release(
+                    &signing_key,
+                    &params,
+                    body.id,
+                    &signed.op,
+                    &signed.signature,
+                )?,
+            }
+        }
+
+        // Votes that the witness retracted before voting again are not equivocation.
+        if body.kind == types::EvidenceKind::Release {
+            let retracted_votes = storage::TypedStore::new(storage::PrefixStore::new(
+                &mut store,
+                &state::RETRACTED_VOTES,
+            ));
+            let retracted: Vec<types::OperationId> = retracted_votes
+                .get(&Self::retraction_id(&body.witness, denomination, body.id))
+                .unwrap_or_default();
+            if [&body.first, &body.second]
+                .iter()
+                .any(|signed| retracted.contains(&types::OperationId::from(&signed.op)))
+            {
+                return Err(Error::InvalidArgument);
+            }
+        }
+
+        // The witness is only punished once for each sequence number.
+        let evidence_id = Hash::digest_bytes(&cbor::to_vec(&cbor::to_value(&(
+            body.witness.id(),
+            body.kind,
+            denomination,
+            body.id,
+        ))));
+        let evidence =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::EVIDENCE));
+        if evidence.get::<_, bool>(&evidence_id).is_some() {
+            return Err(Error::DuplicateEvidence);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut evidence =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::EVIDENCE));
+        evidence.insert(&evidence_id, &true);
+        let slashed = match params.equivocation_penalty {
+            0 => None,
+            penalty => Self::slash_bond(&mut store, &body.witness, penalty),
+        };
+        let jailed_until = if params.jail_duration > 0 {
+            let until = round.saturating_add(params.jail_duration);
+            let mut liveness = storage::TypedStore::new(storage::PrefixStore::new(
+                &mut store,
+                &state::WITNESS_LIVENESS,
+            ));
+            let mut info: types::WitnessLiveness =
+                liveness.get(body.witness.id()).unwrap_or_default();
+            info.inactive = true;
+            info.jailed_until = info.jailed_until.max(until);
+            liveness.insert(body.witness.id(), &info);
+            Some(info.jailed_until)
+        } else {
+            None
+        };
+
+        // Slashed amounts go to the common pool.
+        if let Some(amount) = slashed {
+            Accounts::transfer(
+                ctx,
+                *ADDRESS_WITNESS_BONDS,
+                *modules::accounts::ADDRESS_COMMON_POOL,
+                &amount,
+            )?;
+            ctx.emit_event(Event::Slashed {
+                index: index as u16,
+                witness: body.witness.clone(),
+                amount,
+            });
+        }
+        if let Some(until) = jailed_until {
+            ctx.emit_event(Event::WitnessJailed {
+                index: index as u16,
+                witness: body.witness,
+                until,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn tx_update_blocklist<C: TxContext>(
+        ctx: &mut C,
+        body: types::UpdateBlocklist,
+    ) -> Result<(), Error> {
+        let mut params = Self::params(ctx.runtime_state());
+        Self::ensure_admin(ctx, &params)?;
+
+        for address in &body.unblock {
+            params.blocked_addresses.remove(address);
+        }
+        params.blocked_addresses.extend(body.block);
+        for address in &body.unblock_remote {
+            params.blocked_remote_addresses.remove(address);
+        }
+        params.blocked_remote_addresses.extend(body.block_remote);
+        if module::Parameters::validate_basic(&params).is_err() {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        Self::set_params(ctx.runtime_state(), &params);
+
+        Ok(())
+    }
+
+    fn tx_allow<C: TxContext>(ctx: &mut C, body: types::AllowAddress) -> Result<(), Error> {
+        Self::set_allowed(ctx, body.address, true)
+    }
+
+    fn tx_disallow<C: TxContext>(ctx: &mut C, body: types::AllowAddress) -> Result<(), Error> {
+        Self::set_allowed(ctx, body.address, false)
+    }
+
+    /// Add an address to or remove it from the lock allowlist on behalf of the admin.
+    fn set_allowed<C: TxContext>(
+        ctx: &mut C,
+        address: Address,
+        allowed: bool,
+    ) -> Result<(), Error> {
+        let params = Self::params(ctx.runtime_state());
+        Self::ensure_admin(ctx, &params)?;
+        let mut allowed_addresses = Self::allowed_addresses(ctx.runtime_state());
+        let changed = if allowed {
+            allowed_addresses.insert(address)
+        } else {
+            allowed_addresses.remove(&address)
+        };
+        if !changed {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut tstore = storage::TypedStore::new(store);
+        tstore.insert(state::ALLOWED_ADDRESSES, &allowed_addresses);
+
+        if allowed {
+            ctx.emit_event(Event::AddressAllowed { address });
+        } else {
+            ctx.emit_event(Event::AddressDisallowed { address });
+        }
+
+        Ok(())
+    }
+
+    fn tx_add_witness<C: TxContext>(ctx: &mut C, body: types::AddWitness) -> Result<(), Error> {
+        let mut params = Self::params(ctx.runtime_state());
+        Self::ensure_admin(ctx, &params)?;
+
+        // Make sure the witness is not already part of the witness set.
+        if params.witnesses.contains(&body.witness) {
+            return Err(Error::InvalidArgument);
+        }
+        params.witnesses.push(body.witness.clone());
+        if module::Parameters::validate_basic(&params).is_err() {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        // New witnesses are appended so indices in pending operations remain valid.
+        Self::set_witness_set(ctx, &params);
+
+        ctx.emit_event(Event::WitnessAdded {
+            index: (params.witnesses.len() - 1) as u16,
+            witness: body.witness,
+        });
+
+        Ok(())
+    }
+
+    fn tx_remove_witness<C: TxContext>(
+        ctx: &mut C,
+        body: types::RemoveWitness,
+    ) -> Result<(), Error> {
+        let mut params = Self::params(ctx.runtime_state());
+        Self::ensure_admin(ctx, &params)?;
+
+        let index = params
+            .witnesses
+            .iter()
+            .position(|witness| witness == &body.witness)
+            .ok_or(Error::InvalidArgument)?;
+        params.witnesses.remove(index);
+        // Make sure the threshold can still be reached.
+        if module::Parameters::validate_basic(&params).is_err() {
+            return Err(Error::InvalidArgument);
+        }
+
+        if ctx.is_check_only() {
+            return Ok(());
+        }
+
+        let round = ctx.runtime_header().round;
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        // Remove any signing key registered by the witness.
+        if let Some(pk) = body.witness.public_key() {
+            let mut signing_keys = storage::TypedStore::new(storage::PrefixStore::new(
+                &mut store,
+                &state::WITNESS_SIGNING_KEYS,
+            ));
+            signing_keys.remove(Address::from_pk(pk));
+        }
+        let mut liveness = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_LIVENESS,
+        ));
+        liveness.remove(body.witness.id());
+        let mut metadata = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_METADATA,
+        ));
+        metadata.remove(body.witness.id());
+        let mut points =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::REWARD_POINTS));
+        points.remove(body.witness.id());
+        // Start unbonding the whole bond of the witness.
+        let mut bonds =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::WITNESS_BONDS));
+        let bond: Option<types::WitnessBond> = bonds.get(body.witness.id());
+        bonds.remove(body.witness.id());
+        let unbonding = bond.map(|bond| {
+            let ends =
+                Self::start_unbonding(&mut store, &params, bond.owner, bond.amount.clone(), round);
+            (bond, ends)
+        });
+        // Drop the witness' signatures and votes from pending operations and re-index the
+        // remaining witnesses so that pending operations stay consistent with the new set.
+        Self::remove_pending_witness(&mut store, index as u16);
+
+        Self::set_witness_set(ctx, &params);
+
+        if let Some((bond, ends)) = unbonding {
+            ctx.emit_event(Event::WitnessUnbonding {
+                witness: body.witness.clone(),
+                owner: bond.owner,
+                amount: bond.amount,
+                ends,
+            });
+        }
+        ctx.emit_event(Event::WitnessRemoved {
+            index: index as u16,
+            witness: body.witness,
+        });
+
+        Ok(())
+    }
+
+    fn query_next_sequence_numbers<C: Context>(
+        ctx: &mut C,
+        args: Option<types::NextSequenceNumbersQuery>,
+    ) -> Result<types::NextSequenceNumbers, Error> {
+        let args = args.unwrap_or_default();
+        // Default to the sequence numbers of the currently configured remote chain.
+        let remote_chain_id = match args.remote_chain_id {
+            Some(remote_chain_id) => remote_chain_id,
+            None => Self::params(ctx.runtime_state()).remote_chain_id,
+        };
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut incoming = Self::next_sequences(&mut store, remote_chain_id, false);
+        let mut outgoing = Self::next_sequences(&mut store, remote_chain_id, true);
+        if let Some(denomination) = args.denomination {
+            let select = |sequences: BTreeMap<token::Denomination, u64>| {
+                let next = sequences.get(&denomination).copied().unwrap_or_default();
+                [(denomination.clone(), next)].iter().cloned().collect()
+            };
+            incoming = select(incoming);
+            outgoing = select(outgoing);
+        }
+        let store = storage::TypedStore::new(store);
+
+        Ok(types::NextSequenceNumbers {
+            remote_chain_id,
+            incoming,
+            outgoing,
+            witness_set_updates: store
+                .get(state::NEXT_WITNESS_SET_UPDATE)
+                .unwrap_or_default(),
+            fee_schedule_updates: store
+                .get(state::NEXT_FEE_SCHEDULE_UPDATE)
+                .unwrap_or_default(),
+            remote_contract_updates: store
+                .get(state::NEXT_REMOTE_CONTRACT_UPDATE)
+                .unwrap_or_default(),
+            denomination_registrations: store
+                .get(state::NEXT_DENOMINATION_REGISTRATION)
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Raw storage entries holding the next incoming and outgoing sequence numbers of traffic
+    /// with the given remote chain, together with the state root they can be proven against.
+    ///
+    /// No Merkle proof is included, as the module cannot access the runtime state as a tree. It
+    /// must be obtained for the returned keys separately, e.g. from a node's storage.
+    fn query_sequence_storage<C: Context>(
+        ctx: &mut C,
+        args: Option<types::SequenceStorageQuery>,
+    ) -> Result<types::SequenceStorage, Error> {
+        let args = args.unwrap_or_default();
+        let remote_chain_id = match args.remote_chain_id {
+            Some(remote_chain_id) => remote_chain_id,
+            None => Self::params(ctx.runtime_state()).remote_chain_id,
+        };
+        let round = ctx.runtime_header().round;
+        let state_root = ctx.runtime_header().state_root;
+        let store = ctx.runtime_state();
+        let entry = |outgoing: bool| {
+            // Module state is stored under the module name prefix.
+            let key = [
+                MODULE_NAME.as_bytes(),
+                &next_sequences_key(remote_chain_id, outgoing),
+            ]
+            .concat();
+            let value = storage::Store::get(&*store, &key);
+            types::StorageEntry { key, value }
+        };
+        let incoming = entry(false);
+        let outgoing = entry(true);
+
+        Ok(types::SequenceStorage {
+            round,
+            state_root,
+            remote_chain_id,
+            incoming,
+            outgoing,
+        })
+    }
+
+    /// Raw entries of the module state in key order, starting after the given key.
+    ///
+    /// Each response is limited in size and returns the key to continue after if there are more
+    /// entries, but always includes at least one entry.
+    fn query_export_state<C: Context>(
+        ctx: &mut C,
+        args: types::ExportStateQuery,
+    ) -> Result<types::StateChunk, Error> {
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let tstore = storage::TypedStore::new(store);
+
+        let mut chunk = types::StateChunk::default();
+        let mut size = 0;
+        let entries = tstore.iter().filter(|(key, _): &(Vec<u8>, cbor::Value)| {
+            args.after.as_ref().map_or(true, |after| key > after)
+        });
+        for (key, value) in entries {
+            let entry_size = key.len() + cbor::to_vec(&value).len();
+            if !chunk.entries.is_empty() && size + entry_size > MAX_EXPORT_CHUNK_SIZE {
+                chunk.next = chunk.entries.last().map(|entry| entry.key.clone());
+                break;
+            }
+            size += entry_size;
+            chunk.entries.push(types::StateEntry { key, value });
+        }
+        Ok(chunk)
+    }
+
+    fn query_parameters<C: Context>(ctx: &mut C, _args: ()) -> Result<Parameters, Error> {
+        Ok(Self::params(ctx.runtime_state()))
+    }
+
+    fn query_remote_contract<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<types::RemoteContract, Error> {
+        let params = Self::params(ctx.runtime_state());
+        Ok(types::RemoteContract {
+            remote_chain_id: params.remote_chain_id,
+            address: params.remote_contract(),
+        })
+    }
+
+    fn query_witness_signing_keys<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<Vec<types::WitnessKey>, Error> {
+        let params = Self::params(ctx.runtime_state());
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+
+        Ok(Self::signing_keys(store, &params.witnesses))
+    }
+
+    fn query_witnesses<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<Vec<types::WitnessInfo>, Error> {
+        let params = Self::params(ctx.runtime_state());
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let active: Vec<bool> = {
+            let liveness = storage::TypedStore::new(storage::PrefixStore::new(
+                &mut store,
+                &state::WITNESS_LIVENESS,
+            ));
+            params
+                .witnesses
+                .iter()
+                .map(|witness| {
+                    !liveness
+                        .get::<_, types::WitnessLiveness>(witness.id())
+                        .unwrap_or_default()
+                        .inactive
+                })
+                .collect()
+        };
+        let metadata = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_METADATA,
+        ));
+
+        Ok(params
+            .witnesses
+            .into_iter()
+            .enumerate()
+            .map(|(index, witness)| types::WitnessInfo {
+                index: index as u16,
+                active: active[index],
+                metadata: metadata.get(witness.id()).unwrap_or_default(),
+                witness,
+            })
+            .collect())
+    }
+
+    fn query_witness_liveness<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<Vec<types::WitnessLiveness>, Error> {
+        let params = Self::params(ctx.runtime_state());
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let liveness = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::WITNESS_LIVENESS,
+        ));
+
+        Ok(params
+            .witnesses
+            .iter()
+            .map(|witness| liveness.get(witness.id()).unwrap_or_default())
+            .collect())
+    }
+
+    fn query_paused<C: Context>(ctx: &mut C, _args: ()) -> Result<bool, Error> {
+        Ok(Self::is_paused(ctx.runtime_state()))
+    }
+
+    fn query_frozen_denominations<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<BTreeSet<token::Denomination>, Error> {
+        Ok(Self::frozen_denominations(ctx.runtime_state()))
+    }
+
+    fn query_pause_status<C: Context>(ctx: &mut C, _args: ()) -> Result<types::PauseStatus, Error> {
+        Ok(types::PauseStatus {
+            paused: Self::is_paused(ctx.runtime_state()),
+            frozen: Self::frozen_denominations(ctx.runtime_state()),
+        })
+    }
+
+    fn query_denomination_info<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<Vec<types::DenominationInfo>, Error> {
+        let params = Self::params(ctx.runtime_state());
+        let mut metadata = Self::denomination_metadata(ctx.runtime_state());
+
+        let local = params
+            .local_denominations
+            .into_iter()
+            .map(|denomination| (denomination, None));
+        let remote = params
+            .remote_denominations
+            .into_iter()
+            .map(|(denomination, remote)| (denomination, Some(remote)));
+        Ok(local
+            .chain(remote)
+            .map(|(denomination, remote)| types::DenominationInfo {
+                metadata: metadata.remove(&denomination).unwrap_or_default(),
+                denomination,
+                remote,
+            })
+            .collect())
+    }
+
+    fn query_local_denomination<C: Context>(
+        ctx: &mut C,
+        args: types::LocalDenominationQuery,
+    ) -> Result<token::Denomination, Error> {
+        let params = Self::params(ctx.runtime_state());
+        params
+            .local_denomination(&args.remote)
+            .cloned()
+            .ok_or(Error::UnsupportedDenomination)
+    }
+
+    fn query_allowed_addresses<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<BTreeSet<Address>, Error> {
+        Ok(Self::allowed_addresses(ctx.runtime_state()))
+    }
+
+    fn query_rate_limit_volumes<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<BTreeMap<token::Denomination, Quantity>, Error> {
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let tstore = storage::TypedStore::new(store);
+        Ok(tstore.get(state::RATE_LIMIT_VOLUMES).unwrap_or_default())
+    }
+
+    /// Remaining rate limit quota in the current rate limit window, optionally including the
+    /// remaining account limit quota of the given account.
+    fn query_rate_limit_quota<C: Context>(
+        ctx: &mut C,
+        args: Option<types::RateLimitQuotaQuery>,
+    ) -> Result<types::RateLimitQuota, Error> {
+        let args = args.unwrap_or_default();
+        let round = ctx.runtime_header().round;
+        let params = Self::params(ctx.runtime_state());
+        if params.rate_limit_window == 0 {
+            return Ok(Default::default());
+        }
+        // Operations in the next round fall into the window containing it.
+        let window = round / params.rate_limit_window;
+        let remaining =
+            |limits: &BTreeMap<token::Denomination, Quantity>,
+             volumes: &BTreeMap<token::Denomination, Quantity>| {
+                limits
+                    .iter()
+                    .map(|(denomination, limit)| {
+                        let volume = volumes.get(denomination).cloned().unwrap_or_default();
+                        (
+                            denomination.clone(),
+                            limit.checked_sub(&volume).unwrap_or_default(),
+                        )
+                    })
+                    .collect()
+            };
+
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let tstore = storage::TypedStore::new(&mut store);
+        let volumes = tstore.get(state::RATE_LIMIT_VOLUMES).unwrap_or_default();
+        let account_volumes = match args.account {
+            Some(account) => {
+                let account_volumes = storage::TypedStore::new(storage::PrefixStore::new(
+                    &mut store,
+                    &state::ACCOUNT_VOLUMES,
+                ));
+                account_volumes
+                    .get(account)
+                    .filter(|volume: &types::AccountVolume| volume.window == window)
+                    .unwrap_or_default()
+                    .volumes
+            }
+            None => BTreeMap::new(),
+        };
+
+        Ok(types::RateLimitQuota {
+            window_end: window
+                .saturating_add(1)
+                .saturating_mul(params.rate_limit_window),
+            remaining: remaining(&params.rate_limits, &volumes),
+            account_remaining: match args.account {
+                Some(_) => remaining(&params.account_limits, &account_volumes),
+                None => BTreeMap::new(),
+            },
+        })
+    }
+
+    fn query_conflicts<C: Context>(ctx: &mut C, _args: ()) -> Result<Vec<types::Conflict>, Error> {
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let conflicts =
+            storage::TypedStore::new(storage::PrefixStore::new(store, &state::CONFLICTS));
+        Ok(conflicts
+            .iter()
+            .map(|(_, conflict): (Vec<u8>, types::Conflict)| conflict)
+            .collect())
+    }
+
+    fn query_processed_deposit<C: Context>(
+        ctx: &mut C,
+        args: types::ProcessedDepositQuery,
+    ) -> Result<bool, Error> {
+        Ok(Self::processed_deposit(ctx.runtime_state(), &args.tx_hash, args.log_index).is_some())
+    }
+
+    fn query_invariant_violations<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<Vec<types::InvariantViolation>, Error> {
+        let params = Self::params(ctx.runtime_state());
+        Self::check_invariants(ctx, &params)
+    }
+
+    fn query_pending_recoveries<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<Vec<types::PendingRecovery>, Error> {
+        Ok(Self::pending_recoveries(ctx.runtime_state()))
+    }
+
+    fn query_pending_releases<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<Vec<types::PendingRelease>, Error> {
+        Ok(Self::pending_releases(ctx.runtime_state()))
+    }
+
+    /// Summaries of the outgoing operations that have not been finalized yet, starting at the
+    /// given offset.
+    fn query_pending_outgoing<C: Context>(
+        ctx: &mut C,
+        args: types::PendingOutgoingQuery,
+    ) -> Result<Vec<types::PendingOutgoing>, Error> {
+        let limit = match args.limit {
+            0 => MAX_PAGE_SIZE,
+            limit => limit.min(MAX_PAGE_SIZE),
+        };
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let out_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            store,
+            &state::OUT_WITNESS_SIGNATURE_SETS,
+        ));
+        Ok(out_witness_signatures
+            .iter()
+            .map(|(_, set): (Vec<u8>, types::WitnessSignatureSet)| {
+                types::PendingOutgoing::from(&set)
+            })
+            .skip(args.offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    /// Finalized operations in the operation history that match the given filters, oldest first,
+    /// starting at the given offset.
+    fn query_history<C: Context>(
+        ctx: &mut C,
+        args: types::HistoryQuery,
+    ) -> Result<Vec<types::HistoryEntry>, Error> {
+        let limit = match args.limit {
+            0 => MAX_PAGE_SIZE,
+            limit => limit.min(MAX_PAGE_SIZE),
+        };
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let history = storage::TypedStore::new(storage::PrefixStore::new(store, &state::HISTORY));
+        Ok(history
+            .iter()
+            .map(|(_, entry): ([u8; 8], types::HistoryEntry)| entry)
+            .filter(|entry| {
+                args.direction
+                    .map_or(true, |direction| entry.direction == direction)
+            })
+            .filter(|entry| {
+                args.denomination.as_ref().map_or(true, |denomination| {
+                    entry.op.amount().denomination() == denomination
+                })
+            })
+            .skip(args.offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    /// Summaries of the incoming sequence numbers that have votes but have not been finalized
+    /// yet, starting at the given offset.
+    fn query_pending_incoming<C: Context>(
+        ctx: &mut C,
+        args: types::PendingIncomingQuery,
+    ) -> Result<Vec<types::PendingIncoming>, Error> {
+        let limit = match args.limit {
+            0 => MAX_PAGE_SIZE,
+            limit => limit.min(MAX_PAGE_SIZE),
+        };
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let in_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            store,
+            &state::IN_WITNESS_SIGNATURES,
+        ));
+        Ok(in_witness_signatures
+            .iter()
+            .filter_map(|(_, info): (Vec<u8>, types::IncomingWitnessSignatures)| info.summary())
+            .skip(args.offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    fn query_subsidy_pool<C: Context>(ctx: &mut C, _args: ()) -> Result<token::BaseUnits, Error> {
+        let balance = Accounts::get_balances(ctx.runtime_state(), *ADDRESS_SUBSIDY_POOL)?
+            .balances
+            .get(&token::Denomination::NATIVE)
+            .cloned()
+            .unwrap_or_default();
+        Ok(token::BaseUnits::new(balance, token::Denomination::NATIVE))
+    }
+
+    fn query_remote_reserves<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<BTreeMap<token::Denomination, types::RemoteReserve>, Error> {
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let tstore = storage::TypedStore::new(store);
+        Ok(tstore.get(state::REMOTE_RESERVES).unwrap_or_default())
+    }
+
+    /// Outcome of locking the given amount in the current round, without executing the lock.
+    ///
+    /// Fails if the amount does not cover the lock fee.
+    fn query_estimate_lock<C: Context>(
+        ctx: &mut C,
+        args: types::EstimateLockQuery,
+    ) -> Result<types::LockEstimate, Error> {
+        Self::ensure_local_or_remote(ctx, args.amount.denomination())?;
+        let round = ctx.runtime_header().round;
+        let params = Self::params(ctx.runtime_state());
+        let (amount, fee) = Self::split_lock_fee(&params, &args.amount)?;
+
+        let result = if Self::is_paused(ctx.runtime_state()) {
+            Err(Error::Paused)
+        } else if Self::frozen_denominations(ctx.runtime_state())
+            .contains(args.amount.denomination())
+        {
+            Err(Error::DenominationFrozen)
+        } else {
+            Self::check_lock_amount(ctx.runtime_state(), &params, &args.amount).and_then(|_| {
+                // Rate limit volumes are updated in an overlay that is discarded afterwards.
+                let mut overlay = storage::OverlayStore::new(ctx.runtime_state());
+                let mut store = storage::PrefixStore::new(&mut overlay, &MODULE_NAME);
+                Self::use_rate_limit(&mut store, &params, &amount)?;
+                match args.owner {
+                    Some(owner) => {
+                        Self::use_account_limit(&mut store, &params, round, owner, &amount)
+                    }
+                    None => Ok(()),
+                }
+            })
+        };
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let id = Self::next_sequence(
+            store,
+            params.remote_chain_id,
+            true,
+            args.amount.denomination(),
+        );
+
+        Ok(types::LockEstimate {
+            id,
+            amount,
+            fee,
+            error_code: result.err().map(|err| err.code()),
+        })
+    }
+
+    fn query_stats<C: Context>(ctx: &mut C, _args: ()) -> Result<types::Stats, Error> {
+        Ok(Self::stats(ctx.runtime_state()))
+    }
+
+    fn query_total_locked<C: Context>(ctx: &mut C, _args: ()) -> Result<types::TotalLocked, Error> {
+        let params = Self::params(ctx.runtime_state());
+        Ok(Self::total_locked(ctx.runtime_state(), &params))
+    }
+
+    fn query_vote_nonces<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<Vec<types::VoteNonces>, Error> {
+        let params = Self::params(ctx.runtime_state());
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let vote_nonces =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::VOTE_NONCES));
+
+        Ok(params
+            .witnesses
+            .iter()
+            .map(|witness| vote_nonces.get(witness.id()).unwrap_or_default())
+            .collect())
+    }
+
+    fn query_witness_set_at<C: Context>(
+        ctx: &mut C,
+        args: types::WitnessSetQuery,
+    ) -> Result<types::WitnessSet, Error> {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let out_witness_sets = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::OUT_WITNESS_SETS,
+        ));
+        let commitment: Hash = out_witness_sets
+            .get(sequence_key(&args.denomination, args.id))
+            .ok_or(Error::InvalidSequenceNumber)?;
+        let witness_sets =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::WITNESS_SETS));
+        witness_sets
+            .get(commitment)
+            .ok_or(Error::InvalidSequenceNumber)
+    }
+
+    fn query_witness_bonds<C: Context>(
+        ctx: &mut C,
+        _args: (),
+    ) -> Result<Vec<Option<types::WitnessBond>>, Error> {
+        let params = Self::params(ctx.runtime_state());
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let bonds =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::WITNESS_BONDS));
+
+        Ok(params
+            .witnesses
+            .iter()
+            .map(|witness| bonds.get(witness.id()))
+            .collect())
+    }
+
+    fn query_claims_root<C: Context>(
+        ctx: &mut C,
+        args: types::ClaimsRootQuery,
+    ) -> Result<types::ClaimsRoot, Error> {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let claims_roots =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::CLAIMS_ROOTS));
+        claims_roots
+            .get(sequence_key(&args.denomination, args.id))
+            .ok_or(Error::InvalidSequenceNumber)
+    }
+
+    fn query_claimable_balances<C: Context>(
+        ctx: &mut C,
+        args: types::ClaimableBalancesQuery,
+    ) -> Result<BTreeMap<token::Denomination, Quantity>, Error> {
+        Ok(Self::claimable_balances(ctx.runtime_state(), args.address))
+    }
+
+    /// Completed releases to the given address, ordered by denomination and sequence number,
+    /// starting at the given offset.
+    fn query_releases_for<C: Context>(
+        ctx: &mut C,
+        args: types::ReleasesForQuery,
+    ) -> Result<Vec<types::CompletedRelease>, Error> {
+        let limit = match args.limit {
+            0 => MAX_PAGE_SIZE,
+            limit => limit.min(MAX_PAGE_SIZE),
+        };
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let releases = storage::TypedStore::new(storage::PrefixStore::new(
+            storage::PrefixStore::new(store, &state::RELEASES_BY_TARGET),
+            args.address,
+        ));
+        Ok(releases
+            .iter()
+            .map(|(_, release): (Vec<u8>, types::CompletedRelease)| release)
+            .skip(args.offset as usize)
+            .take(limit as usize)
+            .collect())
+    }
+
+    /// Outgoing operation that has not been finalized yet, together with the witness signatures
+    /// collected for it so far.
+    fn query_out_operation<C: Context>(
+        ctx: &mut C,
+        args: types::OutOperationQuery,
+    ) -> Result<types::WitnessSignatures, Error> {
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        Self::pending_out_operation(store, &args.denomination, args.id)
+            .map(Into::into)
+            .ok_or(Error::InvalidSequenceNumber)
+    }
+
+    /// Signatures collected for the outgoing operation that has not been finalized yet compared
+    /// to the threshold of its witness set, and the witnesses that have not signed yet.
+    fn query_signing_progress<C: Context>(
+        ctx: &mut C,
+        args: types::SigningProgressQuery,
+    ) -> Result<types::SigningProgress, Error> {
+        let params = Self::params(ctx.runtime_state());
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let info = Self::pending_out_operation(&mut store, &args.denomination, args.id)
+            .ok_or(Error::InvalidSequenceNumber)?;
+        let witness_set = Self::witness_set(&mut store, &params, info.witness_set);
+        Ok(types::SigningProgress {
+            collected: info.signers.len() as u64,
+            threshold: witness_set.threshold,
+            missing: (0..witness_set.witnesses.len() as u16)
+                .filter(|index| !info.signers.contains(*index))
+                .collect(),
+        })
+    }
+
+    /// Votes for the incoming operation that has not been finalized yet, including all proposed
+    /// operations and the witnesses that voted for each of them.
+    fn query_in_operation<C: Context>(
+        ctx: &mut C,
+        args: types::InOperationQuery,
+    ) -> Result<types::IncomingWitnessSignatures, Error> {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let in_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::IN_WITNESS_SIGNATURES,
+        ));
+        in_witness_signatures
+            .get(sequence_key(&args.denomination, args.id))
+            .ok_or(Error::InvalidSequenceNumber)
+    }
+
+    /// Status of the operation with the given sequence number, derived from the sequence counters
+    /// and the stored entries. Cancelled releases are reported as finalized.
+    fn query_operation_status<C: Context>(
+        ctx: &mut C,
+        args: types::OperationStatusQuery,
+    ) -> Result<types::OperationStatus, Error> {
+        let params = Self::params(ctx.runtime_state());
+        let key = sequence_key(&args.denomination, args.id);
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let outgoing = args.direction == types::Direction::Outgoing;
+        let next = Self::next_sequence(
+            &mut store,
+            params.remote_chain_id,
+            outgoing,
+            &args.denomination,
+        );
+
+        let status = if outgoing {
+            if has_entry(&mut store, state::OUT_WITNESS_SIGNATURE_SETS, &key) {
+                types::OperationStatus::Collecting
+            } else if args.id >= next {
+                types::OperationStatus::Unknown
+            } else if has_entry(&mut store, state::QUORUM_CERTIFICATES, &key) {
+                types::OperationStatus::Finalized
+            } else {
+                // Operations are only ever removed without a certificate when they expire.
+                types::OperationStatus::Expired
+            }
+        } else if has_entry(&mut store, state::PENDING_RELEASES, &key) {
+            types::OperationStatus::Challenged
+        } else if args.id < next {
+            types::OperationStatus::Finalized
+        } else if has_entry(&mut store, state::IN_WITNESS_SIGNATURES, &key) {
+            types::OperationStatus::Collecting
+        } else {
+            types::OperationStatus::Unknown
+        };
+        Ok(status)
+    }
+
+    fn query_quorum_certificate<C: Context>(
+        ctx: &mut C,
+        args: types::QuorumCertificateQuery,
+    ) -> Result<types::QuorumCertificate, Error> {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let certificates = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::QUORUM_CERTIFICATES,
+        ));
+        certificates
+            .get(sequence_key(&args.denomination, args.id))
+            .ok_or(Error::InvalidSequenceNumber)
+    }
+}
+
+impl<Accounts: modules::accounts::API, Verifier: IncomingVerifier<Accounts>> module::Module
+    for Module<Accounts, Verifier>
+{
+    const NAME: &'static str = MODULE_NAME;
+    const VERSION: u32 = 2;
+    type Error = Error;
+    type Event = Event;
+    type Parameters = Parameters;
+}
+
+impl<Accounts: modules::accounts::API, Verifier: IncomingVerifier<Accounts>> module::MethodHandler
+    for Module<Accounts, Verifier>
+{
+    fn dispatch_call<C: TxContext>(
+        ctx: &mut C,
+        method: &str,
         body: cbor::Value,
     ) -> module::DispatchResult<cbor::Value, CallResult> {
         match method {
             "bridge.Lock" => {
                 let result = || -> Result<cbor::Value, Error> {
                     let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
-                    Ok(cbor::to_value(&Self::tx_lock(ctx, args)?))
+                    Ok(cbor::to_value(&Self::tx_lock(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.Witness" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_witness(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.WitnessBatch" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_witness_batch(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.Release" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_release(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.RetractVote" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_retract_vote(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.UpdateWitnesses" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_update_witnesses(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.SetFeeSchedule" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_set_fee_schedule(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.SetRemoteContract" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_set_remote_contract(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.RegisterDenomination" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_register_denomination(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.AttestRemoteLocked" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_attest_remote_locked(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.SetWitnessMetadata" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_set_witness_metadata(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.RotateWitnessKey" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_rotate_witness_key(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.Pause" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_pause(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.Resume" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_resume(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.FreezeDenomination" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_freeze_denomination(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.UnfreezeDenomination" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_unfreeze_denomination(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.SetDenominationMetadata" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_set_denomination_metadata(
+                        ctx, args,
+                    )?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.Allow" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_allow(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.Disallow" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_disallow(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.UpdateBlocklist" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_update_blocklist(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.Recover" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_recover(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.CancelRecovery" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_cancel_recovery(ctx, args)?))
                 }();
                 match result {
                     Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
                     Err(err) => module::DispatchResult::Handled(err.to_call_result()),
                 }
             }
-            "bridge.Witness" => {
+            "bridge.Claim" => {
                 let result = || -> Result<cbor::Value, Error> {
                     let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
-                    Ok(cbor::to_value(&Self::tx_witness(ctx, args)?))
+                    Ok(cbor::to_value(&Self::tx_claim(ctx, args)?))
                 }();
                 match result {
                     Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
                     Err(err) => module::DispatchResult::Handled(err.to_call_result()),
                 }
             }
-            "bridge.Release" => {
+            "bridge.ClaimBalance" => {
                 let result = || -> Result<cbor::Value, Error> {
                     let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
-                    Ok(cbor::to_value(&Self::tx_release(ctx, args)?))
+                    Ok(cbor::to_value(&Self::tx_claim_balance(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.CancelRelease" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_cancel_release(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.ReactivateWitness" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_reactivate_witness(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.Bond" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_bond(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.Unbond" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_unbond(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.SubmitEvidence" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_submit_evidence(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.AddWitness" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_add_witness(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.RemoveWitness" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_remove_witness(ctx, args)?))
+                }();
+                match result {
+                    Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
+                    Err(err) => module::DispatchResult::Handled(err.to_call_result()),
+                }
+            }
+            "bridge.SetWitnessSigningKey" => {
+                let result = || -> Result<cbor::Value, Error> {
+                    let args = cbor::from_value(body).map_err(|_| Error::InvalidArgument)?;
+                    Ok(cbor::to_value(&Self::tx_set_witness_signing_key(
+                        ctx, args,
+                    )?))
                 }();
                 match result {
                     Ok(value) => module::DispatchResult::Handled(CallResult::Ok(value)),
@@ -467,28 +6338,395 @@ impl<Accounts: modules::accounts::API> module::MethodHandler for Module<Accounts
                     ctx, args,
                 )?))
             })()),
+            "bridge.SequenceStorage" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_sequence_storage(ctx, args)?))
+            })()),
+            "bridge.ExportState" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_export_state(ctx, args)?))
+            })()),
             "bridge.Parameters" => module::DispatchResult::Handled((|| {
                 let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
                 Ok(cbor::to_value(&Self::query_parameters(ctx, args)?))
             })()),
+            "bridge.RemoteContract" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_remote_contract(ctx, args)?))
+            })()),
+            "bridge.WitnessSigningKeys" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_witness_signing_keys(
+                    ctx, args,
+                )?))
+            })()),
+            "bridge.Witnesses" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_witnesses(ctx, args)?))
+            })()),
+            "bridge.WitnessLiveness" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_witness_liveness(ctx, args)?))
+            })()),
+            "bridge.Paused" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_paused(ctx, args)?))
+            })()),
+            "bridge.FrozenDenominations" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_frozen_denominations(
+                    ctx, args,
+                )?))
+            })()),
+            "bridge.PauseStatus" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_pause_status(ctx, args)?))
+            })()),
+            "bridge.DenominationInfo" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_denomination_info(ctx, args)?))
+            })()),
+            "bridge.LocalDenomination" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_local_denomination(ctx, args)?))
+            })()),
+            "bridge.AllowedAddresses" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_allowed_addresses(ctx, args)?))
+            })()),
+            "bridge.RateLimitVolumes" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_rate_limit_volumes(ctx, args)?))
+            })()),
+            "bridge.RateLimitQuota" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_rate_limit_quota(ctx, args)?))
+            })()),
+            "bridge.Conflicts" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_conflicts(ctx, args)?))
+            })()),
+            "bridge.ProcessedDeposit" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_processed_deposit(ctx, args)?))
+            })()),
+            "bridge.InvariantViolations" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_invariant_violations(
+                    ctx, args,
+                )?))
+            })()),
+            "bridge.PendingRecoveries" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_pending_recoveries(ctx, args)?))
+            })()),
+            "bridge.OutOperation" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_out_operation(ctx, args)?))
+            })()),
+            "bridge.InOperation" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_in_operation(ctx, args)?))
+            })()),
+            "bridge.OperationStatus" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_operation_status(ctx, args)?))
+            })()),
+            "bridge.SigningProgress" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_signing_progress(ctx, args)?))
+            })()),
+            "bridge.ClaimsRoot" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_claims_root(ctx, args)?))
+            })()),
+            "bridge.ClaimableBalances" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_claimable_balances(ctx, args)?))
+            })()),
+            "bridge.ReleasesFor" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_releases_for(ctx, args)?))
+            })()),
+            "bridge.PendingOutgoing" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_pending_outgoing(ctx, args)?))
+            })()),
+            "bridge.PendingIncoming" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_pending_incoming(ctx, args)?))
+            })()),
+            "bridge.History" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_history(ctx, args)?))
+            })()),
+            "bridge.PendingReleases" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_pending_releases(ctx, args)?))
+            })()),
+            "bridge.SubsidyPool" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_subsidy_pool(ctx, args)?))
+            })()),
+            "bridge.RemoteReserves" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_remote_reserves(ctx, args)?))
+            })()),
+            "bridge.EstimateLock" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_estimate_lock(ctx, args)?))
+            })()),
+            "bridge.Stats" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_stats(ctx, args)?))
+            })()),
+            "bridge.TotalLocked" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_total_locked(ctx, args)?))
+            })()),
+            "bridge.VoteNonces" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_vote_nonces(ctx, args)?))
+            })()),
+            "bridge.WitnessSetAt" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_witness_set_at(ctx, args)?))
+            })()),
+            "bridge.WitnessBonds" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_witness_bonds(ctx, args)?))
+            })()),
+            "bridge.QuorumCertificate" => module::DispatchResult::Handled((|| {
+                let args = cbor::from_value(args).map_err(|_| Error::InvalidArgument)?;
+                Ok(cbor::to_value(&Self::query_quorum_certificate(ctx, args)?))
+            })()),
             _ => module::DispatchResult::Unhandled(args),
         }
     }
 }
 
-impl<Accounts: modules::accounts::API> Module<Accounts> {
+impl<Accounts: modules::accounts::API, Verifier: IncomingVerifier<Accounts>>
+    Module<Accounts, Verifier>
+{
     fn init<C: Context>(ctx: &mut C, genesis: &Genesis) {
         // Set genesis parameters.
         Self::set_params(ctx.runtime_state(), &genesis.parameters);
     }
 
-    fn migrate<C: Context>(_ctx: &mut C, _from: u32) -> bool {
-        // No migrations currently supported.
-        false
+    fn migrate<C: Context>(ctx: &mut C, from: u32) -> bool {
+        match from {
+            1 => {
+                Self::migrate_sequence_spaces(ctx);
+                true
+            }
+            // Unknown versions are not supported.
+            _ => false,
+        }
+    }
+
+    /// Migrate state from version 1, in which all incoming and all outgoing operations shared a
+    /// single sequence space, to a sequence space for each remote chain and denomination.
+    ///
+    /// The shared sequence spaces were those of the configured remote chain. Operations are moved
+    /// to the sequence space of their denomination and every denomination of the configured remote
+    /// chain continues numbering from the shared sequence number, so that no sequence number is
+    /// reused. Conflicts of operations that are no longer pending cannot
+    /// be attributed to a denomination and are dropped, they remain available in the emitted
+    /// divergence events.
+    fn migrate_sequence_spaces<C: Context>(ctx: &mut C) {
+        let params = Self::params(ctx.runtime_state());
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+
+        // Outgoing operations, converting any signatures in the legacy representation.
+        let mut out_sets: Vec<(u64, types::WitnessSignatureSet)> =
+            take_legacy_entries(&mut store, state::OUT_WITNESS_SIGNATURES)
+                .into_iter()
+                .filter_map(|(id, info)| {
+                    let info: types::WitnessSignatures = cbor::from_value(info).ok()?;
+                    Some((id, info.into()))
+                })
+                .collect();
+        out_sets.extend(
+            take_legacy_entries(&mut store, state::OUT_WITNESS_SIGNATURE_SETS)
+                .into_iter()
+                .filter_map(|(id, info)| Some((id, cbor::from_value(info).ok()?))),
+        );
+        let mut out_denominations = BTreeMap::new();
+        let mut out_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::OUT_WITNESS_SIGNATURE_SETS,
+        ));
+        for (id, info) in out_sets {
+            let denomination = info.op.amount().denomination().clone();
+            out_witness_signatures.insert(sequence_key(&denomination, id), &info);
+            out_denominations.insert(id, denomination);
+        }
+
+        let certificates = take_legacy_entries(&mut store, state::QUORUM_CERTIFICATES);
+        let mut quorum_certificates = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::QUORUM_CERTIFICATES,
+        ));
+        for (id, certificate) in certificates {
+            let certificate: types::QuorumCertificate = match cbor::from_value(certificate) {
+                Ok(certificate) => certificate,
+                Err(_) => continue,
+            };
+            let denomination = certificate.op.amount().denomination().clone();
+            quorum_certificates.insert(sequence_key(&denomination, id), &certificate);
+            out_denominations.insert(id, denomination);
+        }
+
+        // Witness set snapshots are only needed for operations that are still around.
+        let witness_sets = take_legacy_entries(&mut store, state::OUT_WITNESS_SETS);
+        let mut out_witness_sets = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::OUT_WITNESS_SETS,
+        ));
+        for (id, witness_set) in witness_sets {
+            if let Some(denomination) = out_denominations.get(&id) {
+                out_witness_sets.insert(sequence_key(denomination, id), &witness_set);
+            }
+        }
+
+        // Incoming operations.
+        let votes = take_legacy_entries(&mut store, state::IN_WITNESS_SIGNATURES);
+        let mut in_denominations = BTreeMap::new();
+        let mut in_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::IN_WITNESS_SIGNATURES,
+        ));
+        for (id, info) in votes {
+            let info: types::IncomingWitnessSignatures = match cbor::from_value(info) {
+                Ok(info) => info,
+                Err(_) => continue,
+            };
+            let denomination = match info.ops.values().next() {
+                Some(op_sigs) => op_sigs.op.amount().denomination().clone(),
+                None => continue,
+            };
+            in_witness_signatures.insert(sequence_key(&denomination, id), &info);
+            in_denominations.insert(id, denomination);
+        }
+
+        // Retracted votes are keyed by a hash that includes the denomination.
+        let mut retracted_votes = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::RETRACTED_VOTES,
+        ));
+        for (id, denomination) in &in_denominations {
+            for witness in &params.witnesses {
+                let legacy_id =
+                    Hash::digest_bytes(&cbor::to_vec(&cbor::to_value(&(witness.id(), *id))));
+                let ops: Option<Vec<types::OperationId>> = retracted_votes.get(&legacy_id);
+                if let Some(ops) = ops {
+                    retracted_votes.remove(&legacy_id);
+                    retracted_votes.insert(&Self::retraction_id(witness, denomination, *id), &ops);
+                }
+            }
+        }
+
+        let releases = take_legacy_entries(&mut store, state::PENDING_RELEASES);
+        let mut pending_releases = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::PENDING_RELEASES,
+        ));
+        for (id, pending) in releases {
+            let pending: types::PendingRelease = match cbor::from_value(pending) {
+                Ok(pending) => pending,
+                Err(_) => continue,
+            };
+            let denomination = pending.amount.denomination().clone();
+            pending_releases.insert(sequence_key(&denomination, id), &pending);
+            in_denominations.insert(id, denomination);
+        }
+
+        let legacy_conflicts = take_legacy_entries(&mut store, state::CONFLICTS);
+        let mut conflicts =
+            storage::TypedStore::new(storage::PrefixStore::new(&mut store, &state::CONFLICTS));
+        for (id, conflict) in legacy_conflicts {
+            let conflict: LegacyConflict = match cbor::from_value(conflict) {
+                Ok(conflict) => conflict,
+                Err(_) => continue,
+            };
+            if let Some(denomination) = in_denominations.get(&id) {
+                conflicts.insert(
+                    sequence_key(denomination, id),
+                    &types::Conflict {
+                        denomination: denomination.clone(),
+                        id: conflict.id,
+                        votes: conflict.votes,
+                    },
+                );
+            }
+        }
+
+        // Every denomination continues numbering from the shared sequence numbers.
+        let mut denominations: BTreeSet<token::Denomination> = params
+            .local_denominations
+            .iter()
+            .chain(params.remote_denominations.keys())
+            .cloned()
+            .collect();
+        denominations.extend(out_denominations.into_iter().map(|(_, d)| d));
+        denominations.extend(in_denominations.into_iter().map(|(_, d)| d));
+        let shared_sequences = |next: u64| -> BTreeMap<token::Denomination, u64> {
+            denominations
+                .iter()
+                .filter(|_| next > 0)
+                .map(|denomination| (denomination.clone(), next))
+                .collect()
+        };
+
+        let mut tstore = storage::TypedStore::new(&mut store);
+        let next_out: u64 = tstore
+            .get(state::LEGACY_NEXT_OUT_SEQUENCE)
+            .unwrap_or_default();
+        let next_in: u64 = tstore
+            .get(state::LEGACY_NEXT_IN_SEQUENCE)
+            .unwrap_or_default();
+        tstore.remove(state::LEGACY_NEXT_OUT_SEQUENCE);
+        tstore.remove(state::LEGACY_NEXT_IN_SEQUENCE);
+        for (prefix, next) in [
+            (state::NEXT_OUT_SEQUENCES, next_out),
+            (state::NEXT_IN_SEQUENCES, next_in),
+        ] {
+            let mut sequences =
+                storage::TypedStore::new(storage::PrefixStore::new(&mut store, &prefix));
+            sequences.insert(
+                params.remote_chain_id.to_storage_key(),
+                &shared_sequences(next),
+            );
+        }
+
+        // Key rotations recorded the shared sequence numbers at the time of the rotation.
+        let mut rotated_keys = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::ROTATED_WITNESS_KEYS,
+        ));
+        let legacy_rotated: Vec<(Vec<u8>, LegacyRotatedWitnessKey)> = rotated_keys
+            .iter()
+            .filter_map(|(key, rotated): (Vec<u8>, cbor::Value)| {
+                Some((key, cbor::from_value(rotated).ok()?))
+            })
+            .collect();
+        for (key, rotated) in legacy_rotated {
+            rotated_keys.insert(
+                &key,
+                &types::RotatedWitnessKey {
+                    key: rotated.key,
+                    successor: rotated.successor,
+                    incoming: shared_sequences(rotated.incoming),
+                    outgoing: shared_sequences(rotated.outgoing),
+                },
+            );
+        }
     }
 }
 
-impl<Accounts: modules::accounts::API> module::MigrationHandler for Module<Accounts> {
+impl<Accounts: modules::accounts::API, Verifier: IncomingVerifier<Accounts>>
+    module::MigrationHandler for Module<Accounts, Verifier>
+{
     type Genesis = Genesis;
 
     fn init_or_migrate<C: Context>(
@@ -504,17 +6742,115 @@ impl<Accounts: modules::accounts::API> module::MigrationHandler for Module<Accou
             return true;
         }
 
-        Self::migrate(ctx, version)
+        // Perform migration.
+        if !Self::migrate(ctx, version) {
+            return false;
+        }
+        meta.versions.insert(Self::NAME.to_owned(), Self::VERSION);
+        true
+    }
+}
+
+impl<Accounts: modules::accounts::API, Verifier: IncomingVerifier<Accounts>> module::AuthHandler
+    for Module<Accounts, Verifier>
+{
+    fn authenticate_tx<C: Context>(
+        ctx: &mut C,
+        tx: &transaction::Transaction,
+    ) -> Result<(), modules::core::Error> {
+        // Witnesses are exempt from the fees of their witness and release transactions, which are
+        // covered by the subsidy pool as far as the pool allows. When the bridge module comes
+        // before the accounts module in the runtime, witnesses don't need funded accounts at all.
+        //
+        // The fee is covered before the call is made, so the fees of failing calls are covered as
+        // well. They cannot be charged back, as the changes of failing calls are discarded.
+        if !SPONSORED_METHODS.contains(&tx.call.method.as_str()) {
+            return Ok(());
+        }
+        let payer = match tx.auth_info.signer_info.first() {
+            Some(signer) => signer.address_spec.address(),
+            None => return Ok(()),
+        };
+        let params = Self::params(ctx.runtime_state());
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        if Self::find_witness(store, &params.witnesses, &payer, |_| true).is_err() {
+            return Ok(());
+        }
+
+        Self::sponsor_fee(ctx, payer, &tx.auth_info.fee.amount);
+        Ok(())
     }
 }
 
-impl<Accounts: modules::accounts::API> module::AuthHandler for Module<Accounts> {}
+impl<Accounts: modules::accounts::API, Verifier: IncomingVerifier<Accounts>> module::BlockHandler
+    for Module<Accounts, Verifier>
+{
+    fn end_block<C: Context>(ctx: &mut C) {
+        let params = Self::params(ctx.runtime_state());
+        let round = ctx.runtime_header().round;
+
+        Self::check_circuit_breaker(ctx, &params);
+        Self::execute_pending_releases(ctx, &params, round);
+        Self::execute_recoveries(ctx, round);
+
+        // Start a new rate limit window.
+        if params.rate_limit_window != 0 && round % params.rate_limit_window == 0 {
+            let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+            let mut tstore = storage::TypedStore::new(store);
+            tstore.remove(state::RATE_LIMIT_VOLUMES);
+            tstore.remove(state::RELEASE_VOLUMES);
+        }
+
+        if params.processed_deposit_retention != 0
+            && round % params.processed_deposit_retention == 0
+        {
+            Self::prune_processed_deposits(ctx, &params, round);
+        }
+
+        if params.operation_expiry != 0 && round % params.operation_expiry == 0 {
+            Self::expire_operations(ctx, &params, round);
+        }
+
+        if params.history_retention != 0 && round % params.history_retention == 0 {
+            Self::prune_history(ctx, &params, round);
+        }
+
+        if params.stale_vote_age != 0 && round % params.stale_vote_age == 0 {
+            Self::clear_stale_votes(ctx, &params, round);
+        }
+
+        if params.reward_period != 0 && round % params.reward_period == 0 {
+            Self::distribute_rewards(ctx, &params);
+        }
 
-impl<Accounts: modules::accounts::API> module::BlockHandler for Module<Accounts> {}
+        // Catch accounting bugs as early as possible in debug builds.
+        #[cfg(debug_assertions)]
+        {
+            let violations =
+                Self::check_invariants(ctx, &params).expect("invariants should be checkable");
+            assert!(
+                violations.is_empty(),
+                "bridge invariants violated: {:?}",
+                violations
+            );
+        }
+    }
+}
 
 /// A trait that exist solely to convert u64 IDs to bytes for use as a storage key.
 /// Method call syntax is easier to read than alternatives like macro/function invocations
 /// and wrapper types.
+/// Key of the next incoming or outgoing sequence numbers of traffic with the given remote chain
+/// within the module state.
+fn next_sequences_key(remote_chain_id: u64, outgoing: bool) -> Vec<u8> {
+    let prefix = if outgoing {
+        state::NEXT_OUT_SEQUENCES
+    } else {
+        state::NEXT_IN_SEQUENCES
+    };
+    [prefix, &remote_chain_id.to_storage_key()].concat()
+}
+
 trait ToStorageKey {
     fn to_storage_key(&self) -> [u8; 8];
 }
@@ -524,3 +6860,72 @@ impl ToStorageKey for u64 {
         self.to_be_bytes()
     }
 }
+
+/// Storage key of the operation with the given sequence number in the sequence space of the given
+/// denomination.
+///
+/// The denomination is length-prefixed so that keys of different denominations never collide.
+fn sequence_key(denomination: &token::Denomination, id: u64) -> Vec<u8> {
+    let denomination = denomination.to_string();
+    [
+        &[denomination.len() as u8][..],
+        denomination.as_bytes(),
+        &id.to_storage_key(),
+    ]
+    .concat()
+}
+
+/// Remove and return the entries of the map with the given prefix that are keyed by the sequence
+/// number alone, as they were stored in version 1.
+fn take_legacy_entries<S: storage::Store>(store: S, prefix: &[u8]) -> Vec<(u64, cbor::Value)> {
+    let mut entries = storage::TypedStore::new(storage::PrefixStore::new(store, &prefix));
+    let legacy: Vec<(u64, cbor::Value)> = entries
+        .iter()
+        .filter(|(key, _): &(Vec<u8>, cbor::Value)| key.len() == 8)
+        .map(|(key, value)| {
+            let mut id = [0; 8];
+            id.copy_from_slice(&key);
+            (u64::from_be_bytes(id), value)
+        })
+        .collect();
+    for (id, _) in &legacy {
+        entries.remove(id.to_storage_key());
+    }
+    legacy
+}
+
+/// Conflict as stored in version 1, before sequence numbers were assigned per denomination.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LegacyConflict {
+    #[serde(rename = "id")]
+    id: u64,
+
+    #[serde(rename = "votes")]
+    votes: BTreeMap<types::OperationId, Vec<types::WitnessKey>>,
+}
+
+/// Rotated witness key as stored in version 1, with the sequence numbers shared by all
+/// denominations.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LegacyRotatedWitnessKey {
+    #[serde(rename = "key")]
+    key: PublicKey,
+
+    #[serde(rename = "successor")]
+    successor: PublicKey,
+
+    #[serde(rename = "in")]
+    incoming: u64,
+
+    #[serde(rename = "out")]
+    outgoing: u64,
+}
+
+/// Whether the given store has an entry with the given key under the given prefix.
+fn has_entry<S: storage::Store>(store: S, prefix: &[u8], key: &[u8]) -> bool {
+    storage::TypedStore::new(storage::PrefixStore::new(store, &prefix))
+        .get::<_, cbor::Value>(key)
+        .is_some()
+}
@@ -1,23 +1,48 @@
 //! Tests for the bridge module.
 use std::collections::{BTreeMap, BTreeSet};
 
+use digest::{
+    generic_array::{
+        typenum::{U136, U32},
+        GenericArray,
+    },
+    BlockInput, Digest, FixedOutput, Reset, Update,
+};
+use k256::ecdsa::{recoverable, signature::DigestSigner, SigningKey};
+use tiny_keccak::{Hasher, Keccak};
+
 use oasis_runtime_sdk::{
-    context::{BatchContext, Context},
-    core::common::cbor,
-    crypto::signature::PublicKey,
-    module::MigrationHandler,
+    context::{BatchContext, Context, TxContext},
+    core::common::{
+        cbor,
+        crypto::{
+            hash::Hash,
+            signature::{PrivateKey, Signer as _},
+        },
+        namespace::Namespace,
+        quantity::Quantity,
+    },
+    crypto::signature::{secp256k1, PublicKey, Signature},
+    error::Error as _,
+    module::{AuthHandler, BlockHandler, MigrationHandler, Module as _, Parameters as _},
     modules::{
         accounts::{self, Module as Accounts, API as AccountsAPI},
         core,
     },
+    storage,
     testing::{keys, mock},
     types::{
+        address::Address,
         token::{BaseUnits, Denomination},
         transaction,
     },
 };
 
-use super::{types::*, Error, Genesis, Parameters, ADDRESS_LOCKED_FUNDS};
+use super::{
+    crypto, sequence_key, state, types::*, verifier::IncomingVerifier, Error, Event, Genesis,
+    Parameters, ADDRESS_BRIDGE_FEES, ADDRESS_LOCKED_FUNDS, ADDRESS_PENDING_CLAIMS,
+    ADDRESS_REWARD_POOL, ADDRESS_WITNESS_BONDS, MODULE_NAME,
+};
 
 type Bridge = super::Module<Accounts>;
 
@@ -59,10 +84,16 @@ fn init_accounts<C: Context>(ctx: &mut C) {
 }
 
 fn init_bridge<C: Context>(ctx: &mut C) -> Parameters {
-    init_bridge_ex(ctx, vec![keys::bob::pk(), keys::charlie::pk()])
+    init_bridge_ex(
+        ctx,
+        vec![
+            TestWitness::ed25519("bob").pk().into(),
+            TestWitness::ed25519("charlie").pk().into(),
+        ],
+    )
 }
 
-fn init_bridge_ex<C: Context>(ctx: &mut C, witnesses: Vec<PublicKey>) -> Parameters {
+fn init_bridge_ex<C: Context>(ctx: &mut C, witnesses: Vec<WitnessKey>) -> Parameters {
     let parameters = Parameters {
         local_denominations: {
             let mut ld = BTreeSet::new();
@@ -79,6 +110,7 @@ fn init_bridge_ex<C: Context>(ctx: &mut C, witnesses: Vec<PublicKey>) -> Paramet
         },
         witnesses,
         threshold: 2,
+        ..Default::default()
     };
 
     Bridge::init_or_migrate(
@@ -93,13 +125,211 @@ fn init_bridge_ex<C: Context>(ctx: &mut C, witnesses: Vec<PublicKey>) -> Paramet
     parameters
 }
 
+/// Hash of the remote transaction that made the deposit released under the given sequence number
+/// of the given denomination.
+fn deposit_tx_hash(denomination: &Denomination, id: u64) -> Hash {
+    Hash::digest_bytes(&sequence_key(denomination, id))
+}
+
+/// Keccak-256 digest for producing Ethereum-compatible signatures.
+#[derive(Clone)]
+struct Keccak256(Keccak);
+
+impl Default for Keccak256 {
+    fn default() -> Self {
+        Self(Keccak::v256())
+    }
+}
+
+impl Update for Keccak256 {
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.0.update(data.as_ref());
+    }
+}
+
+impl BlockInput for Keccak256 {
+    type BlockSize = U136;
+}
+
+impl FixedOutput for Keccak256 {
+    type OutputSize = U32;
+
+    fn finalize_into(self, out: &mut GenericArray<u8, U32>) {
+        self.0.finalize(out);
+    }
+
+    fn finalize_into_reset(&mut self, out: &mut GenericArray<u8, U32>) {
+        std::mem::take(self).finalize_into(out);
+    }
+}
+
+impl Reset for Keccak256 {
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Digest that has already been computed, for signing pre-hashed EIP-712 digests.
+#[derive(Clone, Default)]
+struct Prehashed(Vec<u8>);
+
+impl Update for Prehashed {
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.0.extend_from_slice(data.as_ref());
+    }
+}
+
+impl BlockInput for Prehashed {
+    type BlockSize = U136;
+}
+
+impl FixedOutput for Prehashed {
+    type OutputSize = U32;
+
+    fn finalize_into(self, out: &mut GenericArray<u8, U32>) {
+        out.copy_from_slice(&self.0);
+    }
+
+    fn finalize_into_reset(&mut self, out: &mut GenericArray<u8, U32>) {
+        std::mem::take(self).finalize_into(out);
+    }
+}
+
+impl Reset for Prehashed {
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// A witness with a known signing key.
+enum TestWitness {
+    Ed25519(PrivateKey),
+    Secp256k1(SigningKey),
+}
+
+impl TestWitness {
+    fn ed25519(name: &str) -> Self {
+        TestWitness::Ed25519(PrivateKey::from_test_seed(format!(
+            "oasis-bridge/test: {}",
+            name
+        )))
+    }
+
+    fn secp256k1(name: &str) -> Self {
+        let seed = Hash::digest_bytes(format!("oasis-bridge/test: {}", name).as_bytes());
+        TestWitness::Secp256k1(SigningKey::from_bytes(seed.as_ref()).unwrap())
+    }
+
+    fn pk(&self) -> PublicKey {
+        match self {
+            TestWitness::Ed25519(sk) => PublicKey::Ed25519(sk.public_key().into()),
+            TestWitness::Secp256k1(sk) => PublicKey::Secp256k1(
+                secp256k1::PublicKey::from_bytes(sk.verifying_key().to_bytes().as_slice()).unwrap(),
+            ),
+        }
+    }
+
+    fn sign_message(&self, context: &[u8], message: &[u8]) -> Signature {
+        match self {
+            TestWitness::Ed25519(sk) => sk.sign(context, message).unwrap().as_ref().to_vec().into(),
+            TestWitness::Secp256k1(sk) => {
+                let digest = Keccak256::default().chain(context).chain(message);
+                let signature: recoverable::Signature = sk.sign_digest(digest);
+                signature.as_ref().to_vec().into()
+            }
+        }
+    }
+
+    fn sign(&self, params: &Parameters, id: u64, op: &Operation) -> Signature {
+        self.sign_message(
+            crypto::WITNESS_SIGNATURE_CONTEXT,
+            &crypto::witness_message(params, id, op),
+        )
+    }
+
+    fn sign_release(&self, params: &Parameters, release: Release) -> Release {
+        let op = Operation::Release(Release {
+            nonce: None,
+            ..release.clone()
+        });
+        Release {
+            signature: Some(self.sign_message(
+                crypto::RELEASE_SIGNATURE_CONTEXT,
+                &crypto::witness_message(params, release.id, &op),
+            )),
+            ..release
+        }
+    }
+
+    fn sign_update(&self, params: &Parameters, update: UpdateWitnesses) -> UpdateWitnesses {
+        UpdateWitnesses {
+            signature: Some(self.sign_message(
+                crypto::WITNESS_SET_UPDATE_SIGNATURE_CONTEXT,
+                &crypto::witness_set_update_message(params, &update),
+            )),
+            ..update
+        }
+    }
+
+    fn attest_lock(
+        &self,
+        params: &Parameters,
+        address: Address,
+        expiration: u64,
+    ) -> LockAttestation {
+        LockAttestation {
+            expiration,
+            signature: self.sign_message(
+                crypto::LOCK_ATTESTATION_SIGNATURE_CONTEXT,
+                &crypto::lock_attestation_message(params, &address, expiration),
+            ),
+        }
+    }
+
+    fn sign_eip712(&self, params: &Parameters, id: u64, op: &Operation) -> Signature {
+        let domain = params.eip712_domain.as_ref().unwrap();
+        self.sign_digest(&crypto::eip712_digest(params, domain, id, op).unwrap())
+    }
+
+    fn sign_abi(&self, params: &Parameters, id: u64, op: &Operation) -> Signature {
+        let message = crypto::abi_message(params, id, op).unwrap();
+        self.sign_digest(&crypto::keccak256(&[&message]))
+    }
+
+    fn sign_digest(&self, digest: &[u8; 32]) -> Signature {
+        match self {
+            TestWitness::Ed25519(sk) => sk
+                .sign(crypto::WITNESS_SIGNATURE_CONTEXT, digest)
+                .unwrap()
+                .as_ref()
+                .to_vec()
+                .into(),
+            TestWitness::Secp256k1(sk) => {
+                let signature: recoverable::Signature =
+                    sk.sign_digest(Prehashed::default().chain(digest));
+                signature.as_ref().to_vec().into()
+            }
+        }
+    }
+}
+
 #[test]
 fn test_outgoing_basic() {
     let mut mock = mock::Mock::default();
     let mut ctx = mock.create_ctx();
 
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::secp256k1("charlie");
+
     init_accounts(&mut ctx);
-    init_bridge(&mut ctx);
+    let params = init_bridge_ex(&mut ctx, vec![bob.pk().into(), charlie.pk().into()]);
+
+    let op = Operation::Lock(Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    });
 
     // User Alice locks an amount.
     let tx = transaction::Transaction {
@@ -109,6 +339,8 @@ fn test_outgoing_basic() {
             body: cbor::to_value(Lock {
                 target: "0000000000000000000000000000000000000000".into(),
                 amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
             }),
         },
         auth_info: transaction::AuthInfo {
@@ -160,12 +392,14 @@ fn test_outgoing_basic() {
         call: transaction::Call {
             method: "bridge.Witness".to_owned(),
             body: cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
                 id: 0,
-                signature: vec![].into(),
+                signature: bob.sign(&params, 0, &op),
+                nonce: 0,
             }),
         },
         auth_info: transaction::AuthInfo {
-            signer_info: vec![transaction::SignerInfo::new(keys::bob::pk(), 0)],
+            signer_info: vec![transaction::SignerInfo::new(bob.pk(), 0)],
             fee: transaction::Fee {
                 amount: Default::default(),
                 gas: 1000,
@@ -183,18 +417,20 @@ fn test_outgoing_basic() {
         let (_tags, _messages) = tx_ctx.commit();
     });
 
-    // Witness Charlie witnesses the local event.
+    // Witness Charlie witnesses the local event using an Ethereum-compatible signature.
     let tx = transaction::Transaction {
         version: 1,
         call: transaction::Call {
             method: "bridge.Witness".to_owned(),
             body: cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
                 id: 0,
-                signature: vec![].into(),
+                signature: charlie.sign(&params, 0, &op),
+                nonce: 0,
             }),
         },
         auth_info: transaction::AuthInfo {
-            signer_info: vec![transaction::SignerInfo::new(keys::charlie::pk(), 0)],
+            signer_info: vec![transaction::SignerInfo::new(charlie.pk(), 0)],
             fee: transaction::Fee {
                 amount: Default::default(),
                 gas: 1000,
@@ -207,6 +443,41 @@ fn test_outgoing_basic() {
 
         let (_tags, _messages) = tx_ctx.commit();
     });
+
+    // The quorum certificate should be queryable and verifiable on its own.
+    let certificate = Bridge::query_quorum_certificate(
+        &mut ctx,
+        QuorumCertificateQuery {
+            denomination: Denomination::NATIVE,
+            id: 0,
+        },
+    )
+    .expect("quorum certificate should exist");
+    assert_eq!(certificate.id, 0);
+    assert_eq!(
+        certificate.witness_set,
+        crypto::witness_set_commitment(&params.witnesses, params.threshold)
+    );
+    assert_eq!(certificate.signers.iter().collect::<Vec<_>>(), vec![0, 1]);
+    for (index, signature) in certificate.signatures() {
+        crypto::verify_witness(
+            &params.witnesses[index as usize],
+            &params,
+            certificate.id,
+            &certificate.op,
+            signature,
+        )
+        .expect("quorum certificate signatures should be valid");
+    }
+
+    let result = Bridge::query_quorum_certificate(
+        &mut ctx,
+        QuorumCertificateQuery {
+            denomination: Denomination::NATIVE,
+            id: 1,
+        },
+    );
+    assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
 }
 
 #[test]
@@ -223,8 +494,10 @@ fn test_outgoing_fail_not_authorized() {
         call: transaction::Call {
             method: "bridge.Witness".to_owned(),
             body: cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
                 id: 0,
                 signature: vec![].into(),
+                nonce: 0,
             }),
         },
         auth_info: transaction::AuthInfo {
@@ -242,25 +515,31 @@ fn test_outgoing_fail_not_authorized() {
 }
 
 #[test]
-fn test_outgoing_fail_invalid_sequence() {
+fn test_outgoing_fail_invalid_signature() {
     let mut mock = mock::Mock::default();
     let mut ctx = mock.create_ctx();
 
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::secp256k1("charlie");
+
     init_accounts(&mut ctx);
-    init_bridge(&mut ctx);
+    let params = init_bridge_ex(&mut ctx, vec![bob.pk().into(), charlie.pk().into()]);
 
-    // Witness Bob witnesses an invalid event.
+    // User Alice locks an amount.
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
     let tx = transaction::Transaction {
         version: 1,
         call: transaction::Call {
-            method: "bridge.Witness".to_owned(),
-            body: cbor::to_value(Witness {
-                id: 0,
-                signature: vec![].into(),
-            }),
+            method: "bridge.Lock".to_owned(),
+            body: cbor::to_value(lock.clone()),
         },
         auth_info: transaction::AuthInfo {
-            signer_info: vec![transaction::SignerInfo::new(keys::bob::pk(), 0)],
+            signer_info: vec![transaction::SignerInfo::new(keys::alice::pk(), 0)],
             fee: transaction::Fee {
                 amount: Default::default(),
                 gas: 1000,
@@ -268,32 +547,98 @@ fn test_outgoing_fail_invalid_sequence() {
         },
     };
     ctx.with_tx(tx, |mut tx_ctx, call| {
-        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
-        assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Signatures over a different operation or for a different bridge instance should be
+    // rejected.
+    let op = Operation::Lock(lock.clone());
+    let other = Operation::Lock(Lock {
+        amount: BaseUnits::new(2_000.into(), Denomination::NATIVE),
+        ..lock
     });
+    let other_chain = Parameters {
+        remote_chain_id: params.remote_chain_id + 1,
+        ..params.clone()
+    };
+    let other_runtime = Parameters {
+        runtime_id: Namespace([0xff; 32]),
+        ..params.clone()
+    };
+    for (witness, signature) in vec![
+        (&bob, vec![].into()),
+        (&bob, bob.sign(&params, 0, &other)),
+        (&bob, charlie.sign(&params, 0, &op)),
+        (&bob, bob.sign(&other_chain, 0, &op)),
+        (&bob, bob.sign(&other_runtime, 0, &op)),
+        (&charlie, charlie.sign(&params, 0, &other)),
+        (&charlie, charlie.sign(&params, 1, &op)),
+        (&charlie, charlie.sign(&other_chain, 0, &op)),
+        (&charlie, charlie.sign(&other_runtime, 0, &op)),
+    ] {
+        let tx = transaction::Transaction {
+            version: 1,
+            call: transaction::Call {
+                method: "bridge.Witness".to_owned(),
+                body: cbor::to_value(Witness {
+                    denomination: Denomination::NATIVE,
+                    id: 0,
+                    signature,
+                    nonce: 0,
+                }),
+            },
+            auth_info: transaction::AuthInfo {
+                signer_info: vec![transaction::SignerInfo::new(witness.pk(), 0)],
+                fee: transaction::Fee {
+                    amount: Default::default(),
+                    gas: 1000,
+                },
+            },
+        };
+        ctx.with_tx(tx, |mut tx_ctx, call| {
+            let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidSignature)));
+        });
+    }
 }
 
 #[test]
-fn test_incoming_basic() {
+fn test_outgoing_eip712() {
     let mut mock = mock::Mock::default();
     let mut ctx = mock.create_ctx();
 
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::secp256k1("charlie");
+
     init_accounts(&mut ctx);
-    init_bridge(&mut ctx);
+    let params = Parameters {
+        remote_chain_id: 1,
+        eip712_domain: Some(Eip712Domain {
+            verifying_contract: "00000000000000000000000000000000000b1d9e".into(),
+        }),
+        ..init_bridge_ex(&mut ctx, vec![bob.pk().into(), charlie.pk().into()])
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
 
-    // Witness Bob witnesses the remote event.
+    // User Alice locks an amount.
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
     let tx = transaction::Transaction {
         version: 1,
         call: transaction::Call {
-            method: "bridge.Release".to_owned(),
-            body: cbor::to_value(Release {
-                id: 0,
-                target: keys::alice::address(),
-                amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
-            }),
+            method: "bridge.Lock".to_owned(),
+            body: cbor::to_value(lock),
         },
         auth_info: transaction::AuthInfo {
-            signer_info: vec![transaction::SignerInfo::new(keys::bob::pk(), 0)],
+            signer_info: vec![transaction::SignerInfo::new(keys::alice::pk(), 0)],
             fee: transaction::Fee {
                 amount: Default::default(),
                 gas: 1000,
@@ -301,51 +646,96 @@ fn test_incoming_basic() {
         },
     };
     ctx.with_tx(tx, |mut tx_ctx, call| {
-        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body.clone()).unwrap())
-            .expect("release should succeed");
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
 
-        // Check source account balances.
-        let bals = Accounts::get_balances(tx_ctx.runtime_state(), keys::alice::address())
-            .expect("get_balances should succeed");
-        assert_eq!(
-            bals.balances[&Denomination::NATIVE],
-            1_000_000.into(),
-            "balance in target account should be unchanged"
-        );
-        assert_eq!(
-            bals.balances.len(),
-            1,
-            "there should only be one denomination"
-        );
+        let (_tags, _messages) = tx_ctx.commit();
+    });
 
-        // Check bridge module account balances.
-        let bals = Accounts::get_balances(tx_ctx.runtime_state(), *ADDRESS_LOCKED_FUNDS)
-            .expect("get_balances should succeed");
-        assert!(
-            bals.balances.is_empty(),
-            "there should be no balances in the bridge module account"
-        );
+    // Witnesses sign the EIP-712 digest of the operation.
+    for witness in &[&bob, &charlie] {
+        let tx = transaction::Transaction {
+            version: 1,
+            call: transaction::Call {
+                method: "bridge.Witness".to_owned(),
+                body: cbor::to_value(Witness {
+                    denomination: Denomination::NATIVE,
+                    id: 0,
+                    signature: witness.sign(&params, 0, &op),
+                    nonce: 0,
+                }),
+            },
+            auth_info: transaction::AuthInfo {
+                signer_info: vec![transaction::SignerInfo::new(witness.pk(), 0)],
+                fee: transaction::Fee {
+                    amount: Default::default(),
+                    gas: 1000,
+                },
+            },
+        };
+        ctx.with_tx(tx, |mut tx_ctx, call| {
+            // Signatures over the CBOR-encoded operation should be rejected.
+            let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidSignature)));
 
-        // Duplicate releases should be rejected.
-        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
-        assert!(matches!(result, Err(Error::AlreadySubmittedSignature)));
+            Bridge::tx_witness(
+                &mut tx_ctx,
+                Witness {
+                    denomination: Denomination::NATIVE,
+                    id: 0,
+                    signature: witness.sign_eip712(&params, 0, &op),
+                    nonce: 0,
+                },
+            )
+            .expect("witness should succeed");
 
-        let (_tags, _messages) = tx_ctx.commit();
-    });
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+}
 
-    // Witness Charlie witnesses the remote event.
+#[test]
+fn test_outgoing_abi() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::secp256k1("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        remote_contract: Some("00000000000000000000000000000000000b1d9e".into()),
+        message_encoding: MessageEncoding::Abi,
+        ..init_bridge_ex(&mut ctx, vec![bob.pk().into(), charlie.pk().into()])
+    };
+    params.validate_basic().expect("parameters should be valid");
+    assert!(
+        Parameters {
+            remote_contract: None,
+            ..params.clone()
+        }
+        .validate_basic()
+        .is_err(),
+        "parameters with the ABI encoding but without a remote contract should be invalid"
+    );
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    // User Alice locks an amount.
+    let lock = Lock {
+        target: "00000000000000000000000000000000000a11ce".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
     let tx = transaction::Transaction {
         version: 1,
         call: transaction::Call {
-            method: "bridge.Release".to_owned(),
-            body: cbor::to_value(Release {
-                id: 0,
-                target: keys::alice::address(),
-                amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
-            }),
+            method: "bridge.Lock".to_owned(),
+            body: cbor::to_value(lock),
         },
         auth_info: transaction::AuthInfo {
-            signer_info: vec![transaction::SignerInfo::new(keys::charlie::pk(), 0)],
+            signer_info: vec![transaction::SignerInfo::new(keys::alice::pk(), 0)],
             fee: transaction::Fee {
                 amount: Default::default(),
                 gas: 1000,
@@ -353,67 +743,92 @@ fn test_incoming_basic() {
         },
     };
     ctx.with_tx(tx, |mut tx_ctx, call| {
-        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
-            .expect("release should succeed");
-
-        // Check source account balances.
-        let bals = Accounts::get_balances(tx_ctx.runtime_state(), keys::alice::address())
-            .expect("get_balances should succeed");
-        assert_eq!(
-            bals.balances[&Denomination::NATIVE],
-            1_000_000.into(),
-            "native token balance in target account should be unchanged"
-        );
-        assert_eq!(
-            bals.balances[&"oETH".parse().unwrap()],
-            1_000.into(),
-            "tokens should have been minted"
-        );
-        assert_eq!(
-            bals.balances.len(),
-            2,
-            "there should now be two denominations"
-        );
-
-        // Check bridge module account balances.
-        let bals = Accounts::get_balances(tx_ctx.runtime_state(), *ADDRESS_LOCKED_FUNDS)
-            .expect("get_balances should succeed");
-        assert_eq!(
-            bals.balances[&"oETH".parse().unwrap()],
-            0.into(),
-            "no minted tokens should remain in the bridge account"
-        );
-        assert_eq!(
-            bals.balances.len(),
-            1,
-            "there should be a single denomination"
-        );
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
 
         let (_tags, _messages) = tx_ctx.commit();
     });
+
+    // The message should be the ABI-encoded (contract, id, token, recipient, amount) tuple where
+    // the token of a local denomination is the Keccak-256 digest of its name.
+    assert_eq!(
+        hex::encode(crypto::abi_message(&params, 0, &op).unwrap()),
+        concat!(
+            "00000000000000000000000000000000000000000000000000000000000b1d9e",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            "00000000000000000000000000000000000000000000000000000000000a11ce",
+            "00000000000000000000000000000000000000000000000000000000000003e8",
+        ),
+        "ABI message should be correct"
+    );
+
+    // Witnesses sign the Keccak-256 digest of the ABI message.
+    for witness in &[&bob, &charlie] {
+        let tx = transaction::Transaction {
+            version: 1,
+            call: transaction::Call {
+                method: "bridge.Witness".to_owned(),
+                body: cbor::to_value(Witness {
+                    denomination: Denomination::NATIVE,
+                    id: 0,
+                    signature: witness.sign(&params, 0, &op),
+                    nonce: 0,
+                }),
+            },
+            auth_info: transaction::AuthInfo {
+                signer_info: vec![transaction::SignerInfo::new(witness.pk(), 0)],
+                fee: transaction::Fee {
+                    amount: Default::default(),
+                    gas: 1000,
+                },
+            },
+        };
+        ctx.with_tx(tx, |mut tx_ctx, call| {
+            // Signatures over the CBOR-encoded operation should be rejected.
+            let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidSignature)));
+
+            Bridge::tx_witness(
+                &mut tx_ctx,
+                Witness {
+                    denomination: Denomination::NATIVE,
+                    id: 0,
+                    signature: witness.sign_abi(&params, 0, &op),
+                    nonce: 0,
+                },
+            )
+            .expect("witness should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
 }
 
 #[test]
-fn test_incoming_fail_invalid_sequence() {
+fn test_outgoing_fail_invalid_sequence() {
     let mut mock = mock::Mock::default();
     let mut ctx = mock.create_ctx();
 
+    let bob = TestWitness::ed25519("bob");
+
     init_accounts(&mut ctx);
     init_bridge(&mut ctx);
 
-    // Witness Bob witnesses the remote event.
+    // Witness Bob witnesses an invalid event.
     let tx = transaction::Transaction {
         version: 1,
         call: transaction::Call {
-            method: "bridge.Release".to_owned(),
-            body: cbor::to_value(Release {
-                id: 1, // Invalid sequence as it should be 0.
-                target: keys::alice::address(),
-                amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+            method: "bridge.Witness".to_owned(),
+            body: cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 0,
+                signature: vec![].into(),
+                nonce: 0,
             }),
         },
         auth_info: transaction::AuthInfo {
-            signer_info: vec![transaction::SignerInfo::new(keys::bob::pk(), 0)],
+            signer_info: vec![transaction::SignerInfo::new(bob.pk(), 0)],
             fee: transaction::Fee {
                 amount: Default::default(),
                 gas: 1000,
@@ -421,62 +836,8530 @@ fn test_incoming_fail_invalid_sequence() {
         },
     };
     ctx.with_tx(tx, |mut tx_ctx, call| {
-        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
         assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
     });
 }
 
 #[test]
-fn test_incoming_fail_divergence() {
+fn test_remote_chain_id() {
     let mut mock = mock::Mock::default();
     let mut ctx = mock.create_ctx();
 
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
     init_accounts(&mut ctx);
-    init_bridge_ex(
-        &mut ctx,
-        vec![keys::alice::pk(), keys::bob::pk(), keys::charlie::pk()],
-    );
+    let params = Parameters {
+        remote_chain_id: 5,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
 
-    // Witness Bob witnesses the remote event.
-    let tx = transaction::Transaction {
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
         version: 1,
         call: transaction::Call {
-            method: "bridge.Release".to_owned(),
-            body: cbor::to_value(Release {
-                id: 0,
-                target: keys::alice::address(),
-                amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
-            }),
+            method: method.to_owned(),
+            body,
         },
         auth_info: transaction::AuthInfo {
-            signer_info: vec![transaction::SignerInfo::new(keys::bob::pk(), 0)],
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
             fee: transaction::Fee {
                 amount: Default::default(),
                 gas: 1000,
             },
         },
     };
-    ctx.with_tx(tx, |mut tx_ctx, call| {
-        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body.clone()).unwrap())
-            .expect("release should succeed");
+    let lock_tx = |remote_chain_id: Option<u64>| {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+                remote_chain_id,
+                attestation: None,
+            }),
+        )
+    };
 
-        let (_tags, _messages) = tx_ctx.commit();
+    // Locks targeting another remote chain are rejected.
+    ctx.with_tx(lock_tx(Some(6)), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::UnsupportedRemoteChain)));
     });
+    ctx.with_tx(lock_tx(Some(5)), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
 
-    // Witness Charlie witnesses a diverging event.
-    let tx = transaction::Transaction {
-        version: 1,
-        call: transaction::Call {
-            method: "bridge.Release".to_owned(),
-            body: cbor::to_value(Release {
-                id: 0,
-                target: keys::alice::address(),
-                amount: BaseUnits::new(2_000.into(), "oETH".parse().unwrap()),
-            }),
-        },
-        auth_info: transaction::AuthInfo {
-            signer_info: vec![transaction::SignerInfo::new(keys::charlie::pk(), 0)],
-            fee: transaction::Fee {
+        let (tags, _messages) = tx_ctx.commit();
+        let lock_key = [MODULE_NAME.as_bytes(), &1u32.to_be_bytes()].concat();
+        let tag = tags
+            .iter()
+            .find(|tag| tag.key == lock_key)
+            .expect("lock event should be emitted");
+        match cbor::from_slice(&tag.value).expect("lock event should decode") {
+            Event::Lock {
+                remote_chain_id, ..
+            } => assert_eq!(remote_chain_id, 5),
+            _ => panic!("lock event should be emitted"),
+        }
+    });
+
+    // The remote chain is not part of the operation that witnesses sign.
+    let op = Operation::Lock(Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    });
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(
+            make_tx(
+                witness.pk(),
+                "bridge.Witness",
+                cbor::to_value(Witness {
+                    denomination: Denomination::NATIVE,
+                    id: 0,
+                    signature: witness.sign(&params, 0, &op),
+                    nonce: 0,
+                }),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("witness should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+    Bridge::query_quorum_certificate(
+        &mut ctx,
+        QuorumCertificateQuery {
+            denomination: Denomination::NATIVE,
+            id: 0,
+        },
+    )
+    .expect("quorum certificate should exist");
+
+    // Releases of deposits on another remote chain are rejected.
+    let release = |remote_chain_id: Option<u64>| Release {
+        id: 0,
+        target: keys::alice::address(),
+        amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+        tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+        log_index: Some(0),
+        batch: vec![],
+        claims_root: None,
+        remote_chain_id,
+        signature: None,
+        nonce: Some(0),
+    };
+    ctx.with_tx(
+        make_tx(
+            bob.pk(),
+            "bridge.Release",
+            cbor::to_value(bob.sign_release(&params, release(Some(6)))),
+        ),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::UnsupportedRemoteChain)));
+        },
+    );
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(
+            make_tx(
+                witness.pk(),
+                "bridge.Release",
+                cbor::to_value(witness.sign_release(&params, release(None))),
+            ),
+            |mut tx_ctx, call| {
+                let mut body: Release = cbor::from_value(call.body).unwrap();
+                body.remote_chain_id = Some(5);
+                Bridge::tx_release(&mut tx_ctx, body).expect("release should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert_eq!(
+        bals.balances[&"oETH".parse::<Denomination>().unwrap()],
+        1_000.into()
+    );
+}
+
+/// Witness signatures in the legacy representation with signers as a list of witness indices.
+#[derive(serde::Serialize)]
+struct LegacyWitnessSignatures {
+    id: u64,
+    op: Operation,
+    wits: Vec<u16>,
+    sigs: Vec<Signature>,
+}
+
+#[test]
+fn test_outgoing_legacy_signatures() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let op = Operation::Lock(Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    });
+
+    // Store an operation already witnessed by Charlie in the legacy representation and migrate
+    // it from version 1.
+    {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut tstore = storage::TypedStore::new(&mut store);
+        tstore.insert(state::LEGACY_NEXT_OUT_SEQUENCE, &1u64);
+        let mut legacy = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::OUT_WITNESS_SIGNATURES,
+        ));
+        legacy.insert(
+            0u64.to_be_bytes(),
+            &LegacyWitnessSignatures {
+                id: 0,
+                op: op.clone(),
+                wits: vec![1],
+                sigs: vec![charlie.sign(&params, 0, &op)],
+            },
+        );
+    }
+    let mut meta = core::types::Metadata::default();
+    meta.versions.insert(MODULE_NAME.to_owned(), 1);
+    Bridge::init_or_migrate(&mut ctx, &mut meta, &Default::default());
+
+    let witness_tx = |witness: &TestWitness| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Witness".to_owned(),
+            body: cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 0,
+                signature: witness.sign(&params, 0, &op),
+                nonce: 0,
+            }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(witness.pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // Charlie's legacy signature should be taken into account.
+    ctx.with_tx(witness_tx(&charlie), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AlreadySubmittedSignature)));
+    });
+
+    // Witness Bob witnesses the local event which completes the signature set.
+    ctx.with_tx(witness_tx(&bob), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Both representations should have been cleared.
+    let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+    for (prefix, key) in &[
+        (state::OUT_WITNESS_SIGNATURES, 0u64.to_be_bytes().to_vec()),
+        (
+            state::OUT_WITNESS_SIGNATURE_SETS,
+            sequence_key(&Denomination::NATIVE, 0),
+        ),
+    ] {
+        let entries = storage::TypedStore::new(storage::PrefixStore::new(&mut store, prefix));
+        let entry: Option<cbor::Value> = entries.get(key);
+        assert!(entry.is_none(), "outgoing signatures should be cleared");
+    }
+}
+
+#[test]
+fn test_witness_signature_set() {
+    let op = Operation::Lock(Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    });
+
+    let mut set = WitnessSignatureSet::new(0, op.clone());
+    assert!(set.insert(9, vec![9].into()));
+    assert!(set.insert(0, vec![0].into()));
+    assert!(set.insert(3, vec![3].into()));
+    assert!(
+        !set.insert(3, vec![4].into()),
+        "duplicate signer should be rejected"
+    );
+
+    assert_eq!(set.signers.len(), 3);
+    assert!(set.signers.contains(9));
+    assert!(!set.signers.contains(8));
+    assert!(!set.signers.contains(u16::MAX));
+    assert_eq!(set.signers.iter().collect::<Vec<_>>(), vec![0, 3, 9]);
+
+    // Signers are stored as a bitmap.
+    assert_eq!(
+        cbor::to_vec(&set.signers),
+        cbor::to_vec(&serde_bytes::Bytes::new(&[0b1001, 0b10]))
+    );
+
+    let info: WitnessSignatures = set.into();
+    assert_eq!(info.signers.iter().collect::<Vec<_>>(), vec![0, 3, 9]);
+    assert_eq!(
+        info.signatures
+            .iter()
+            .map(|sig| sig.as_ref().to_vec())
+            .collect::<Vec<_>>(),
+        vec![vec![0], vec![3], vec![9]],
+        "signatures should be ordered by signer index"
+    );
+
+    // Signers in the legacy representation are converted into a bitmap.
+    let legacy = LegacyWitnessSignatures {
+        id: 0,
+        op,
+        wits: vec![9, 0, 3],
+        sigs: vec![vec![9].into(), vec![0].into(), vec![3].into()],
+    };
+    let info: WitnessSignatures = cbor::from_slice(&cbor::to_vec(&legacy)).unwrap();
+    assert_eq!(info.signers.iter().collect::<Vec<_>>(), vec![0, 3, 9]);
+    assert_eq!(
+        info.signatures
+            .iter()
+            .map(|sig| sig.as_ref().to_vec())
+            .collect::<Vec<_>>(),
+        vec![vec![0], vec![3], vec![9]],
+        "legacy signatures should be reordered by signer index"
+    );
+    let reencoded: WitnessSignatures = cbor::from_slice(&cbor::to_vec(&info)).unwrap();
+    assert_eq!(reencoded.signers, info.signers);
+}
+
+#[test]
+fn test_witness_signing_key() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let bob_hot = TestWitness::secp256k1("bob-hot");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let set_key_tx = |signer: PublicKey, key: Option<PublicKey>| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.SetWitnessSigningKey".to_owned(),
+            body: cbor::to_value(SetWitnessSigningKey { key }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // Only witnesses can register signing keys.
+    ctx.with_tx(
+        set_key_tx(keys::alice::pk(), Some(bob_hot.pk())),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_set_witness_signing_key(
+                &mut tx_ctx,
+                cbor::from_value(call.body).unwrap(),
+            );
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+
+    // Witness Bob registers a hot signing key.
+    ctx.with_tx(
+        set_key_tx(bob.pk(), Some(bob_hot.pk())),
+        |mut tx_ctx, call| {
+            Bridge::tx_set_witness_signing_key(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("setting the signing key should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    let signing_keys = Bridge::query_witness_signing_keys(&mut ctx, ())
+        .expect("querying signing keys should succeed");
+    assert_eq!(
+        signing_keys,
+        vec![WitnessKey::from(bob_hot.pk()), charlie.pk().into()]
+    );
+
+    // User Alice locks an amount.
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    let tx = transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Lock".to_owned(),
+            body: cbor::to_value(lock),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(keys::alice::pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    ctx.with_tx(tx, |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    let witness_tx = |signer: PublicKey, signature: Signature| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Witness".to_owned(),
+            body: cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 0,
+                signature,
+                nonce: 0,
+            }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // Payloads signed with Bob's own key should be rejected.
+    ctx.with_tx(
+        witness_tx(bob.pk(), bob.sign(&params, 0, &op)),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidSignature)));
+        },
+    );
+
+    // Payloads signed with the hot key but submitted by anyone else should be rejected.
+    ctx.with_tx(
+        witness_tx(charlie.pk(), bob_hot.sign(&params, 0, &op)),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidSignature)));
+        },
+    );
+
+    // Payloads signed with the hot key and submitted by Bob should be accepted.
+    ctx.with_tx(
+        witness_tx(bob.pk(), bob_hot.sign(&params, 0, &op)),
+        |mut tx_ctx, call| {
+            Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("witness should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(
+        witness_tx(charlie.pk(), charlie.sign(&params, 0, &op)),
+        |mut tx_ctx, call| {
+            Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("witness should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // The quorum certificate should commit to the signing keys.
+    let certificate = Bridge::query_quorum_certificate(
+        &mut ctx,
+        QuorumCertificateQuery {
+            denomination: Denomination::NATIVE,
+            id: 0,
+        },
+    )
+    .expect("quorum certificate should exist");
+    assert_eq!(
+        certificate.witness_set,
+        crypto::witness_set_commitment(&signing_keys, params.threshold)
+    );
+
+    // Witness Bob clears the signing key.
+    ctx.with_tx(set_key_tx(bob.pk(), None), |mut tx_ctx, call| {
+        Bridge::tx_set_witness_signing_key(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("clearing the signing key should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    let signing_keys = Bridge::query_witness_signing_keys(&mut ctx, ())
+        .expect("querying signing keys should succeed");
+    assert_eq!(signing_keys, params.witnesses);
+}
+
+#[test]
+fn test_multisig_witness() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+    let dave = TestWitness::ed25519("dave");
+    let erin = TestWitness::secp256k1("erin");
+
+    // An organization operates a 2-of-3 multisig witness.
+    let multisig = WitnessKey::Multisig(MultisigConfig {
+        signers: vec![
+            MultisigSigner {
+                public_key: bob.pk(),
+                weight: 1,
+            },
+            MultisigSigner {
+                public_key: dave.pk(),
+                weight: 1,
+            },
+            MultisigSigner {
+                public_key: erin.pk(),
+                weight: 1,
+            },
+        ],
+        threshold: 2,
+    });
+
+    init_accounts(&mut ctx);
+    let params = init_bridge_ex(&mut ctx, vec![multisig, charlie.pk().into()]);
+
+    // User Alice locks an amount.
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    let tx = transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Lock".to_owned(),
+            body: cbor::to_value(lock),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(keys::alice::pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    ctx.with_tx(tx, |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    let witness_tx = |signer: PublicKey, signature: Signature| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Witness".to_owned(),
+            body: cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 0,
+                signature,
+                nonce: 0,
+            }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let multisig_signature =
+        |signatures: Vec<Option<Signature>>| MultisigSignature(signatures).to_signature();
+
+    for (signature, reason) in vec![
+        (
+            multisig_signature(vec![Some(bob.sign(&params, 0, &op)), None, None]),
+            "signatures below the multisig threshold",
+        ),
+        (
+            multisig_signature(vec![
+                Some(bob.sign(&params, 0, &op)),
+                Some(dave.sign(&params, 0, &op)),
+            ]),
+            "missing signer entries",
+        ),
+        (
+            multisig_signature(vec![
+                Some(bob.sign(&params, 0, &op)),
+                Some(charlie.sign(&params, 0, &op)),
+                None,
+            ]),
+            "signature by a non-signer",
+        ),
+        (bob.sign(&params, 0, &op), "single signature"),
+    ] {
+        ctx.with_tx(witness_tx(dave.pk(), signature), |mut tx_ctx, call| {
+            let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(
+                matches!(result, Err(Error::InvalidSignature)),
+                "witness with {} should fail",
+                reason
+            );
+        });
+    }
+
+    // Any signer can submit on behalf of the multisig witness.
+    let signature = multisig_signature(vec![
+        Some(bob.sign(&params, 0, &op)),
+        None,
+        Some(erin.sign(&params, 0, &op)),
+    ]);
+    ctx.with_tx(witness_tx(dave.pk(), signature), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body.clone()).unwrap())
+            .expect("witness should succeed");
+
+        // The multisig witness can only sign once.
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AlreadySubmittedSignature)));
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    ctx.with_tx(
+        witness_tx(charlie.pk(), charlie.sign(&params, 0, &op)),
+        |mut tx_ctx, call| {
+            Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("witness should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // The quorum certificate should be verifiable against the witness set.
+    let certificate = Bridge::query_quorum_certificate(
+        &mut ctx,
+        QuorumCertificateQuery {
+            denomination: Denomination::NATIVE,
+            id: 0,
+        },
+    )
+    .expect("quorum certificate should exist");
+    for (index, signature) in certificate.signatures() {
+        crypto::verify_witness(
+            &params.witnesses[index as usize],
+            &params,
+            certificate.id,
+            &certificate.op,
+            signature,
+        )
+        .expect("quorum certificate signatures should be valid");
+    }
+
+    // Multisig witnesses cannot delegate to a signing key.
+    let tx = transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.SetWitnessSigningKey".to_owned(),
+            body: cbor::to_value(SetWitnessSigningKey {
+                key: Some(dave.pk()),
+            }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(bob.pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    ctx.with_tx(tx, |mut tx_ctx, call| {
+        let result =
+            Bridge::tx_set_witness_signing_key(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::NotAuthorized)));
+    });
+}
+
+#[test]
+fn test_threshold_validation() {
+    let bob: WitnessKey = TestWitness::ed25519("bob").pk().into();
+    let charlie: WitnessKey = TestWitness::ed25519("charlie").pk().into();
+    let params = |witnesses: Vec<WitnessKey>, threshold: u64| Parameters {
+        witnesses,
+        threshold,
+        ..Default::default()
+    };
+
+    params(vec![bob.clone(), charlie.clone()], 2)
+        .validate_basic()
+        .expect("reachable threshold should be valid");
+    for (params, reason) in vec![
+        (
+            params(vec![bob.clone(), charlie.clone()], 0),
+            "zero threshold",
+        ),
+        (
+            params(vec![bob.clone(), charlie], 3),
+            "threshold above the witness count",
+        ),
+        (params(vec![], 1), "no witnesses"),
+        (params(vec![bob.clone(), bob], 1), "duplicate witnesses"),
+    ] {
+        assert!(
+            params.validate_basic().is_err(),
+            "parameters with {} should be invalid",
+            reason
+        );
+    }
+}
+
+#[test]
+fn test_multisig_witness_validation() {
+    let signer = |name: &str, weight: u64| MultisigSigner {
+        public_key: TestWitness::ed25519(name).pk(),
+        weight,
+    };
+    let params = |signers: Vec<MultisigSigner>, threshold: u64| Parameters {
+        witnesses: vec![WitnessKey::Multisig(MultisigConfig { signers, threshold })],
+        ..Default::default()
+    };
+
+    params(vec![signer("bob", 1), signer("dave", 2)], 3)
+        .validate_basic()
+        .expect("reachable threshold should be valid");
+
+    for (params, reason) in vec![
+        (params(vec![], 0), "no signers"),
+        (params(vec![signer("bob", 1)], 0), "zero threshold"),
+        (
+            params(vec![signer("bob", 1), signer("dave", 1)], 3),
+            "unreachable threshold",
+        ),
+        (
+            params(vec![signer("bob", 1), signer("bob", 1)], 2),
+            "duplicate signers",
+        ),
+        (
+            params(vec![signer("bob", u64::MAX), signer("dave", 1)], 1),
+            "overflowing weights",
+        ),
+    ] {
+        assert!(
+            params.validate_basic().is_err(),
+            "parameters with {} should be invalid",
+            reason
+        );
+    }
+}
+
+#[test]
+fn test_update_witnesses() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::secp256k1("charlie");
+    let dave = TestWitness::ed25519("dave");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge_ex(&mut ctx, vec![bob.pk().into(), charlie.pk().into()]);
+
+    let update = UpdateWitnesses {
+        id: 0,
+        witnesses: vec![charlie.pk().into(), dave.pk().into()],
+        threshold: 1,
+        signature: None,
+    };
+    let update_tx = |signer: PublicKey, update: UpdateWitnesses| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.UpdateWitnesses".to_owned(),
+            body: cbor::to_value(update),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    for (signer, update, expected, reason) in vec![
+        (
+            keys::alice::pk(),
+            bob.sign_update(&params, update.clone()),
+            Error::NotAuthorized,
+            "non-witness",
+        ),
+        (
+            bob.pk(),
+            bob.sign_update(
+                &params,
+                UpdateWitnesses {
+                    id: 1,
+                    ..update.clone()
+                },
+            ),
+            Error::InvalidSequenceNumber,
+            "invalid sequence number",
+        ),
+        (
+            bob.pk(),
+            bob.sign_update(
+                &params,
+                UpdateWitnesses {
+                    threshold: 3,
+                    ..update.clone()
+                },
+            ),
+            Error::InvalidArgument,
+            "unreachable threshold",
+        ),
+        (
+            bob.pk(),
+            update.clone(),
+            Error::InvalidSignature,
+            "missing signature",
+        ),
+        (
+            bob.pk(),
+            charlie.sign_update(&params, update.clone()),
+            Error::InvalidSignature,
+            "signature by another witness",
+        ),
+    ] {
+        ctx.with_tx(update_tx(signer, update), |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_update_witnesses(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert_eq!(
+                result.map_err(|err| err.to_string()),
+                Err(expected.to_string()),
+                "update by {} should fail",
+                reason
+            );
+        });
+    }
+
+    // Updates by non-witnesses are rejected when checking transactions, while updates by witnesses
+    // are not recorded.
+    drop(ctx);
+    let mut check_ctx = mock.create_check_ctx();
+    check_ctx.with_tx(
+        update_tx(keys::alice::pk(), bob.sign_update(&params, update.clone())),
+        |mut tx_ctx, call| {
+            assert!(tx_ctx.is_check_only());
+            let result =
+                Bridge::tx_update_witnesses(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+    check_ctx.with_tx(
+        update_tx(bob.pk(), bob.sign_update(&params, update.clone())),
+        |mut tx_ctx, call| {
+            Bridge::tx_update_witnesses(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("checking the update should succeed");
+        },
+    );
+    drop(check_ctx);
+    let mut ctx = mock.create_ctx();
+
+    // Witness Bob approves the update.
+    ctx.with_tx(
+        update_tx(bob.pk(), bob.sign_update(&params, update.clone())),
+        |mut tx_ctx, call| {
+            Bridge::tx_update_witnesses(&mut tx_ctx, cbor::from_value(call.body.clone()).unwrap())
+                .expect("update should succeed");
+
+            // Duplicate votes should be rejected.
+            let result =
+                Bridge::tx_update_witnesses(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::AlreadySubmittedSignature)));
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // The update should not be applied before reaching the threshold.
+    let current = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    assert_eq!(current.witnesses, params.witnesses);
+
+    // Witness Charlie approves the update.
+    ctx.with_tx(
+        update_tx(charlie.pk(), charlie.sign_update(&params, update.clone())),
+        |mut tx_ctx, call| {
+            Bridge::tx_update_witnesses(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("update should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    let current = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    assert_eq!(current.witnesses, update.witnesses);
+    assert_eq!(current.threshold, 1);
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("sequence numbers query should succeed");
+    assert_eq!(sequences.witness_set_updates, 1);
+
+    // Witness Bob is no longer part of the witness set.
+    let update = UpdateWitnesses {
+        id: 1,
+        witnesses: vec![bob.pk().into()],
+        threshold: 1,
+        signature: None,
+    };
+    ctx.with_tx(
+        update_tx(bob.pk(), bob.sign_update(&current, update)),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_update_witnesses(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+}
+
+#[test]
+fn test_update_witnesses_clears_pending_votes() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+    let dave = TestWitness::ed25519("dave");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let release = Release {
+        id: 0,
+        target: keys::alice::address(),
+        amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+        tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+        log_index: Some(0),
+        batch: vec![],
+        claims_root: None,
+        remote_chain_id: None,
+        signature: None,
+        nonce: Some(0),
+    };
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // Witness Bob votes for an incoming operation.
+    let body = cbor::to_value(bob.sign_release(&params, release.clone()));
+    ctx.with_tx(
+        make_tx(bob.pk(), "bridge.Release", body),
+        |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // Witnesses replace Bob with Dave, which shifts Charlie to index zero.
+    let update = UpdateWitnesses {
+        id: 0,
+        witnesses: vec![charlie.pk().into(), dave.pk().into()],
+        threshold: 2,
+        signature: None,
+    };
+    for witness in &[&bob, &charlie] {
+        let body = cbor::to_value(witness.sign_update(&params, update.clone()));
+        ctx.with_tx(
+            make_tx(witness.pk(), "bridge.UpdateWitnesses", body),
+            |mut tx_ctx, call| {
+                Bridge::tx_update_witnesses(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("update should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+
+    // Bob's vote no longer counts towards the incoming operation.
+    let pending = Bridge::query_pending_incoming(&mut ctx, Default::default())
+        .expect("pending incoming query should succeed");
+    assert!(pending.is_empty(), "pending votes should be cleared");
+
+    // Witnesses of the new set can vote for it from scratch.
+    let params = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    let body = cbor::to_value(charlie.sign_release(&params, release));
+    ctx.with_tx(
+        make_tx(charlie.pk(), "bridge.Release", body),
+        |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    let info = Bridge::query_in_operation(
+        &mut ctx,
+        InOperationQuery {
+            denomination: "oETH".parse().unwrap(),
+            id: 0,
+        },
+    )
+    .expect("incoming operation query should succeed");
+    assert_eq!(info.signers.iter().collect::<Vec<_>>(), vec![0]);
+}
+
+#[test]
+fn test_admin_witness_changes() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+    let dave = TestWitness::secp256k1("dave");
+    let erin = TestWitness::ed25519("erin");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        admin: Some(keys::alice::address()),
+        ..init_bridge_ex(
+            &mut ctx,
+            vec![bob.pk().into(), charlie.pk().into(), dave.pk().into()],
+        )
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let admin_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let add = |witness: &TestWitness| {
+        cbor::to_value(AddWitness {
+            witness: witness.pk().into(),
+        })
+    };
+    let remove = |witness: &TestWitness| {
+        cbor::to_value(RemoveWitness {
+            witness: witness.pk().into(),
+        })
+    };
+
+    // User Alice locks an amount.
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    ctx.with_tx(
+        admin_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock)),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    let witness_tx = |witness: &TestWitness| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Witness".to_owned(),
+            body: cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 0,
+                signature: witness.sign(&params, 0, &op),
+                nonce: 0,
+            }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(witness.pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // Witness Dave witnesses the local event.
+    ctx.with_tx(witness_tx(&dave), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Only the admin can change the witness set.
+    ctx.with_tx(
+        admin_tx(bob.pk(), "bridge.RemoveWitness", remove(&charlie)),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_remove_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+    ctx.with_tx(
+        admin_tx(keys::alice::pk(), "bridge.RemoveWitness", remove(&erin)),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_remove_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(
+                matches!(result, Err(Error::InvalidArgument)),
+                "removing a non-witness should fail"
+            );
+        },
+    );
+
+    // Admin Alice removes witness Charlie, which shifts Dave's index in the current witness set.
+    ctx.with_tx(
+        admin_tx(keys::alice::pk(), "bridge.RemoveWitness", remove(&charlie)),
+        |mut tx_ctx, call| {
+            Bridge::tx_remove_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("removing a witness should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    let current = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    assert_eq!(
+        current.witnesses,
+        vec![WitnessKey::from(bob.pk()), dave.pk().into()]
+    );
+
+    // The threshold must remain reachable.
+    ctx.with_tx(
+        admin_tx(keys::alice::pk(), "bridge.RemoveWitness", remove(&bob)),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_remove_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(
+                matches!(result, Err(Error::InvalidArgument)),
+                "removing below the threshold should fail"
+            );
+        },
+    );
+
+    // The operation is still verified against the witness set at the time it was created, so
+    // Dave's signature is retained.
+    ctx.with_tx(witness_tx(&dave), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AlreadySubmittedSignature)));
+    });
+
+    // Witness Bob witnesses the local event which completes the signature set.
+    ctx.with_tx(witness_tx(&bob), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    let certificate = Bridge::query_quorum_certificate(
+        &mut ctx,
+        QuorumCertificateQuery {
+            denomination: Denomination::NATIVE,
+            id: 0,
+        },
+    )
+    .expect("quorum certificate should exist");
+    assert_eq!(certificate.signers.iter().collect::<Vec<_>>(), vec![0, 2]);
+    assert_eq!(
+        certificate.witness_set,
+        crypto::witness_set_commitment(&params.witnesses, params.threshold)
+    );
+    for (index, signature) in certificate.signatures() {
+        crypto::verify_witness(
+            &params.witnesses[index as usize],
+            &params,
+            certificate.id,
+            &certificate.op,
+            signature,
+        )
+        .expect("quorum certificate signatures should be valid");
+    }
+
+    // Admin Alice adds witness Erin.
+    ctx.with_tx(
+        admin_tx(keys::alice::pk(), "bridge.AddWitness", add(&erin)),
+        |mut tx_ctx, call| {
+            Bridge::tx_add_witness(&mut tx_ctx, cbor::from_value(call.body.clone()).unwrap())
+                .expect("adding a witness should succeed");
+
+            // Duplicate witnesses should be rejected.
+            let result = Bridge::tx_add_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    let current = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    assert_eq!(
+        current.witnesses,
+        vec![
+            WitnessKey::from(bob.pk()),
+            dave.pk().into(),
+            erin.pk().into()
+        ]
+    );
+}
+
+#[test]
+fn test_rotate_witness_key() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let bob_new = TestWitness::secp256k1("bob-new");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let rotate = |key: PublicKey| cbor::to_value(RotateWitnessKey { key });
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+
+    // User Alice locks an amount before the rotation.
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(lock.clone()),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // Only witnesses can rotate their keys.
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.RotateWitnessKey",
+            rotate(bob_new.pk()),
+        ),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_rotate_witness_key(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+
+    // Rotating to a key of another witness should fail.
+    ctx.with_tx(
+        make_tx(bob.pk(), "bridge.RotateWitnessKey", rotate(charlie.pk())),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_rotate_witness_key(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+        },
+    );
+
+    // Witness Bob rotates to a new key.
+    ctx.with_tx(
+        make_tx(bob.pk(), "bridge.RotateWitnessKey", rotate(bob_new.pk())),
+        |mut tx_ctx, call| {
+            Bridge::tx_rotate_witness_key(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("key rotation should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            assert_eq!(tags.len(), 1, "key rotation event should be emitted");
+        },
+    );
+
+    let current = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    assert_eq!(
+        current.witnesses,
+        vec![WitnessKey::from(bob_new.pk()), charlie.pk().into()]
+    );
+
+    // Rotating back to a previously used key should fail.
+    ctx.with_tx(
+        make_tx(bob_new.pk(), "bridge.RotateWitnessKey", rotate(bob.pk())),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_rotate_witness_key(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+        },
+    );
+
+    // User Alice locks an amount after the rotation.
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock)),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    let witness = |id: u64, signature: Signature, nonce: u64| {
+        cbor::to_value(Witness {
+            denomination: Denomination::NATIVE,
+            id,
+            signature,
+            nonce,
+        })
+    };
+
+    // The old key can still be used for the operation that was in flight.
+    ctx.with_tx(
+        make_tx(
+            bob.pk(),
+            "bridge.Witness",
+            witness(0, bob.sign(&params, 0, &op), 0),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("witness with the old key should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // The new key is not part of the witness set of the operation that was in flight.
+    ctx.with_tx(
+        make_tx(
+            bob_new.pk(),
+            "bridge.Witness",
+            witness(0, bob_new.sign(&params, 0, &op), 0),
+        ),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+
+    // The old key can not be used for operations created after the rotation.
+    ctx.with_tx(
+        make_tx(
+            bob.pk(),
+            "bridge.Witness",
+            witness(1, bob.sign(&params, 1, &op), 1),
+        ),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+
+    // Both operations reach the threshold. Vote nonces carry over to the new key.
+    for (signer, id, nonce) in &[(&bob_new, 1, 0), (&charlie, 0, 0), (&charlie, 1, 1)] {
+        ctx.with_tx(
+            make_tx(
+                signer.pk(),
+                "bridge.Witness",
+                witness(*id, signer.sign(&params, *id, &op), *nonce),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("witness should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+
+    for id in 0..2 {
+        Bridge::query_quorum_certificate(
+            &mut ctx,
+            QuorumCertificateQuery {
+                denomination: Denomination::NATIVE,
+                id,
+            },
+        )
+        .expect("quorum certificate should exist");
+    }
+}
+
+#[test]
+fn test_witness_set_snapshot() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+    let dave = TestWitness::secp256k1("dave");
+    let erin = TestWitness::ed25519("erin");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        admin: Some(keys::alice::address()),
+        ..init_bridge_ex(
+            &mut ctx,
+            vec![bob.pk().into(), charlie.pk().into(), dave.pk().into()],
+        )
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    let lock_tx = || {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(lock.clone()),
+        )
+    };
+    let witness_tx = |witness: &TestWitness, id: u64| {
+        make_tx(
+            witness.pk(),
+            "bridge.Witness",
+            cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id,
+                signature: witness.sign(&params, id, &op),
+                nonce: 0,
+            }),
+        )
+    };
+
+    // User Alice locks an amount.
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Admin Alice replaces witness Charlie with Erin while the operation is in flight.
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.RemoveWitness",
+            cbor::to_value(RemoveWitness {
+                witness: charlie.pk().into(),
+            }),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_remove_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("removing a witness should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.AddWitness",
+            cbor::to_value(AddWitness {
+                witness: erin.pk().into(),
+            }),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_add_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("adding a witness should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    let current = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+
+    // User Alice locks another amount.
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Each operation only accepts witnesses from its own witness set.
+    ctx.with_tx(witness_tx(&erin, 0), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::NotAuthorized)));
+    });
+    ctx.with_tx(witness_tx(&charlie, 1), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::NotAuthorized)));
+    });
+
+    for (witness, id) in &[(&charlie, 0), (&dave, 0), (&bob, 1), (&erin, 1)] {
+        ctx.with_tx(witness_tx(witness, *id), |mut tx_ctx, call| {
+            Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("witness should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+
+    // The witness set of each operation can be queried.
+    for (id, set) in &[(0, &params), (1, &current)] {
+        let witness_set = Bridge::query_witness_set_at(
+            &mut ctx,
+            WitnessSetQuery {
+                denomination: Denomination::NATIVE,
+                id: *id,
+            },
+        )
+        .expect("witness set query should succeed");
+        assert_eq!(witness_set.witnesses, set.witnesses);
+        assert_eq!(witness_set.threshold, set.threshold);
+    }
+    let result = Bridge::query_witness_set_at(
+        &mut ctx,
+        WitnessSetQuery {
+            denomination: Denomination::NATIVE,
+            id: 2,
+        },
+    );
+    assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
+
+    // The quorum certificates commit to the witness set of each operation.
+    for (id, set, signers) in &[(0, &params, vec![1, 2]), (1, &current, vec![0, 2])] {
+        let certificate = Bridge::query_quorum_certificate(
+            &mut ctx,
+            QuorumCertificateQuery {
+                denomination: Denomination::NATIVE,
+                id: *id,
+            },
+        )
+        .expect("quorum certificate should exist");
+        assert_eq!(
+            certificate.witness_set,
+            crypto::witness_set_commitment(&set.witnesses, set.threshold)
+        );
+        assert_eq!(&certificate.signers.iter().collect::<Vec<_>>(), signers);
+        for (index, signature) in certificate.signatures() {
+            crypto::verify_witness(
+                &set.witnesses[index as usize],
+                set,
+                certificate.id,
+                &certificate.op,
+                signature,
+            )
+            .expect("quorum certificate signatures should be valid");
+        }
+    }
+}
+
+#[test]
+fn test_witness_liveness() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+    let dave = TestWitness::secp256k1("dave");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        liveness_window: 8,
+        max_missed_operations: 2,
+        ..init_bridge_ex(
+            &mut ctx,
+            vec![bob.pk().into(), charlie.pk().into(), dave.pk().into()],
+        )
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    let lock_tx = || {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(lock.clone()),
+        )
+    };
+    let witness_tx = |witness: &TestWitness, id: u64| {
+        make_tx(
+            witness.pk(),
+            "bridge.Witness",
+            cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id,
+                signature: witness.sign(&params, id, &op),
+                // Witnesses in this test vote on every operation in order.
+                nonce: id,
+            }),
+        )
+    };
+    let reactivate_tx = |witness: &TestWitness| {
+        make_tx(
+            witness.pk(),
+            "bridge.ReactivateWitness",
+            cbor::to_value(&()),
+        )
+    };
+
+    // Witness Dave misses two operations in a row.
+    for id in 0..2 {
+        ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+        ctx.with_tx(witness_tx(&bob, id), |mut tx_ctx, call| {
+            Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("witness should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+        ctx.with_tx(witness_tx(&charlie, id), |mut tx_ctx, call| {
+            Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("witness should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            // The second miss marks Dave inactive.
+            assert_eq!(tags.len() as u64, 1 + id, "events should be emitted");
+        });
+    }
+
+    let liveness =
+        Bridge::query_witness_liveness(&mut ctx, ()).expect("liveness query should succeed");
+    assert_eq!(liveness[0].signed(params.liveness_window), 2);
+    assert!(!liveness[0].inactive);
+    assert_eq!(liveness[2].signed(params.liveness_window), 0);
+    assert_eq!(liveness[2].missed, 2);
+    assert!(liveness[2].inactive);
+    let witnesses = Bridge::query_witnesses(&mut ctx, ()).expect("witnesses query should succeed");
+    assert_eq!(
+        witnesses.iter().map(|w| w.active).collect::<Vec<_>>(),
+        vec![true, true, false]
+    );
+
+    // Inactive witnesses are left out of new operations.
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    let witness_set = Bridge::query_witness_set_at(
+        &mut ctx,
+        WitnessSetQuery {
+            denomination: Denomination::NATIVE,
+            id: 2,
+        },
+    )
+    .expect("witness set query should succeed");
+    assert_eq!(
+        witness_set.witnesses,
+        vec![WitnessKey::from(bob.pk()), charlie.pk().into()]
+    );
+    ctx.with_tx(witness_tx(&dave, 2), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::NotAuthorized)));
+    });
+
+    // Only inactive witnesses can reactivate themselves.
+    ctx.with_tx(reactivate_tx(&charlie), |mut tx_ctx, call| {
+        let result =
+            Bridge::tx_reactivate_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidArgument)));
+    });
+    ctx.with_tx(reactivate_tx(&dave), |mut tx_ctx, call| {
+        Bridge::tx_reactivate_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("reactivation should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    let liveness =
+        Bridge::query_witness_liveness(&mut ctx, ()).expect("liveness query should succeed");
+    assert_eq!(liveness[2].missed, 0);
+    assert!(!liveness[2].inactive);
+
+    // Reactivated witnesses are part of new operations again.
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    let witness_set = Bridge::query_witness_set_at(
+        &mut ctx,
+        WitnessSetQuery {
+            denomination: Denomination::NATIVE,
+            id: 3,
+        },
+    )
+    .expect("witness set query should succeed");
+    assert_eq!(witness_set.witnesses, params.witnesses);
+}
+
+#[test]
+fn test_witness_metadata() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let metadata_tx = |signer: PublicKey, metadata: WitnessMetadata| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.SetWitnessMetadata".to_owned(),
+            body: cbor::to_value(metadata),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let metadata = WitnessMetadata {
+        name: "Bob".to_owned(),
+        endpoint: "https://bob.example.com".to_owned(),
+        payout_address: Some("00000000000000000000000000000000000000b0".into()),
+    };
+
+    // Only witnesses can publish metadata.
+    ctx.with_tx(
+        metadata_tx(keys::alice::pk(), metadata.clone()),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_set_witness_metadata(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+
+    // Overly long metadata should be rejected.
+    ctx.with_tx(
+        metadata_tx(
+            bob.pk(),
+            WitnessMetadata {
+                name: "B".repeat(65),
+                ..metadata.clone()
+            },
+        ),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_set_witness_metadata(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+        },
+    );
+
+    // Witness Bob publishes metadata.
+    ctx.with_tx(
+        metadata_tx(bob.pk(), metadata.clone()),
+        |mut tx_ctx, call| {
+            Bridge::tx_set_witness_metadata(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("setting metadata should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    let witnesses = Bridge::query_witnesses(&mut ctx, ()).expect("witnesses query should succeed");
+    assert_eq!(
+        witnesses
+            .iter()
+            .map(|w| w.witness.clone())
+            .collect::<Vec<_>>(),
+        params.witnesses
+    );
+    assert_eq!(
+        witnesses.iter().map(|w| w.index).collect::<Vec<_>>(),
+        vec![0, 1]
+    );
+    assert!(witnesses.iter().all(|w| w.active));
+    assert_eq!(witnesses[0].metadata, metadata);
+    assert!(witnesses[1].metadata.is_empty());
+
+    // Publishing empty metadata clears it.
+    ctx.with_tx(
+        metadata_tx(bob.pk(), Default::default()),
+        |mut tx_ctx, call| {
+            Bridge::tx_set_witness_metadata(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("clearing metadata should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    let witnesses = Bridge::query_witnesses(&mut ctx, ()).expect("witnesses query should succeed");
+    assert!(witnesses[0].metadata.is_empty());
+}
+
+#[test]
+fn test_witness_set_handover() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+    let dave = TestWitness::secp256k1("dave");
+    let erin = TestWitness::ed25519("erin");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        handover_window: 10,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    let witness_tx = |witness: &TestWitness, id: u64| {
+        make_tx(
+            witness.pk(),
+            "bridge.Witness",
+            cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id,
+                signature: witness.sign(&params, id, &op),
+                nonce: 0,
+            }),
+        )
+    };
+
+    // User Alice locks two amounts and witness Bob signs the first one.
+    for _ in 0..2 {
+        ctx.with_tx(
+            make_tx(
+                keys::alice::pk(),
+                "bridge.Lock",
+                cbor::to_value(lock.clone()),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("lock should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+    ctx.with_tx(witness_tx(&bob, 0), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Witnesses Bob and Charlie hand over to Dave and Erin.
+    let update = UpdateWitnesses {
+        id: 0,
+        witnesses: vec![dave.pk().into(), erin.pk().into()],
+        threshold: 2,
+        signature: None,
+    };
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(
+            make_tx(
+                witness.pk(),
+                "bridge.UpdateWitnesses",
+                cbor::to_value(witness.sign_update(&params, update.clone())),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_update_witnesses(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("update should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+
+    // The new witness set can sign pending operations during the handover.
+    for witness in &[&dave, &erin] {
+        ctx.with_tx(witness_tx(witness, 0), |mut tx_ctx, call| {
+            Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("witness during handover should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+
+    // The quorum certificate records the signatures of both witness sets.
+    let certificate = Bridge::query_quorum_certificate(
+        &mut ctx,
+        QuorumCertificateQuery {
+            denomination: Denomination::NATIVE,
+            id: 0,
+        },
+    )
+    .expect("quorum certificate should exist");
+    assert_eq!(certificate.signers.iter().collect::<Vec<_>>(), vec![0]);
+    assert_eq!(
+        certificate.witness_set,
+        crypto::witness_set_commitment(&params.witnesses, params.threshold)
+    );
+    let handover = certificate
+        .handover
+        .as_ref()
+        .expect("quorum certificate should include the handover signatures");
+    assert_eq!(handover.signers.iter().collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(
+        handover.witness_set,
+        crypto::witness_set_commitment(&update.witnesses, update.threshold)
+    );
+    for (index, signature) in handover.signatures() {
+        crypto::verify_witness(
+            &update.witnesses[index as usize],
+            &params,
+            certificate.id,
+            &certificate.op,
+            signature,
+        )
+        .expect("handover signatures should be valid");
+    }
+
+    // After the handover window only the original witness set can sign pending operations.
+    drop(ctx);
+    mock.runtime_header.round += params.handover_window + 1;
+    let mut ctx = mock.create_ctx();
+    ctx.with_tx(witness_tx(&dave, 1), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::NotAuthorized)));
+    });
+    ctx.with_tx(witness_tx(&charlie, 1), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+}
+
+#[test]
+fn test_threshold_policy() {
+    let policy = ThresholdPolicy {
+        numerator: 2,
+        denominator: 3,
+        extra: 1,
+    };
+    for (witnesses, threshold) in &[(0, 1), (1, 1), (3, 3), (4, 3), (6, 5), (7, 5)] {
+        assert_eq!(policy.threshold(*witnesses), *threshold);
+    }
+
+    let params = Parameters {
+        threshold: 4,
+        threshold_policy: Some(policy.clone()),
+        ..Default::default()
+    };
+    assert_eq!(
+        params.threshold_for(3),
+        4,
+        "threshold should be the minimum"
+    );
+    assert_eq!(params.threshold_for(9), 7);
+
+    for (numerator, denominator) in &[(1, 0), (4, 3)] {
+        let params = Parameters {
+            threshold_policy: Some(ThresholdPolicy {
+                numerator: *numerator,
+                denominator: *denominator,
+                extra: 0,
+            }),
+            ..Default::default()
+        };
+        assert!(
+            params.validate_basic().is_err(),
+            "invalid threshold policy should be rejected"
+        );
+    }
+
+    // Operations use the threshold derived from the witness set.
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let witnesses = vec![
+        TestWitness::ed25519("bob"),
+        TestWitness::ed25519("charlie"),
+        TestWitness::secp256k1("dave"),
+        TestWitness::ed25519("erin"),
+    ];
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        threshold: 1,
+        threshold_policy: Some(policy),
+        ..init_bridge_ex(&mut ctx, witnesses.iter().map(|w| w.pk().into()).collect())
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock)),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    let witness_set = Bridge::query_witness_set_at(
+        &mut ctx,
+        WitnessSetQuery {
+            denomination: Denomination::NATIVE,
+            id: 0,
+        },
+    )
+    .expect("witness set query should succeed");
+    assert_eq!(witness_set.threshold, 3);
+
+    for (i, witness) in witnesses.iter().take(3).enumerate() {
+        let result = Bridge::query_quorum_certificate(
+            &mut ctx,
+            QuorumCertificateQuery {
+                denomination: Denomination::NATIVE,
+                id: 0,
+            },
+        );
+        assert!(
+            matches!(result, Err(Error::InvalidSequenceNumber)),
+            "operation should not be finalized after {} signatures",
+            i
+        );
+
+        ctx.with_tx(
+            make_tx(
+                witness.pk(),
+                "bridge.Witness",
+                cbor::to_value(Witness {
+                    denomination: Denomination::NATIVE,
+                    id: 0,
+                    signature: witness.sign(&params, 0, &op),
+                    nonce: 0,
+                }),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("witness should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+
+    Bridge::query_quorum_certificate(
+        &mut ctx,
+        QuorumCertificateQuery {
+            denomination: Denomination::NATIVE,
+            id: 0,
+        },
+    )
+    .expect("quorum certificate should exist");
+}
+
+#[test]
+fn test_witness_bonding() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        witness_bond: Some(BaseUnits::new(100.into(), Denomination::NATIVE)),
+        unbonding_delay: 10,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+    assert!(
+        Parameters {
+            witness_bond: Some(BaseUnits::new(100.into(), "oETH".parse().unwrap())),
+            ..params.clone()
+        }
+        .validate_basic()
+        .is_err(),
+        "bonds in remote denominations should be rejected"
+    );
+
+    // Fund the witnesses.
+    for witness in &[&bob, &charlie] {
+        Accounts::transfer(
+            &mut ctx,
+            keys::alice::address(),
+            Address::from_pk(&witness.pk()),
+            &BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        )
+        .expect("transfer should succeed");
+    }
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let bond_tx = |witness: &TestWitness, amount: BaseUnits| {
+        make_tx(witness.pk(), "bridge.Bond", cbor::to_value(Bond { amount }))
+    };
+    let unbond_tx = |witness: &TestWitness, amount: Option<BaseUnits>| {
+        make_tx(
+            witness.pk(),
+            "bridge.Unbond",
+            cbor::to_value(Unbond { amount }),
+        )
+    };
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    let witness_tx = |witness: &TestWitness| {
+        make_tx(
+            witness.pk(),
+            "bridge.Witness",
+            cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 0,
+                signature: witness.sign(&params, 0, &op),
+                nonce: 0,
+            }),
+        )
+    };
+    fn balance<C: Context>(ctx: &mut C, address: Address) -> Quantity {
+        Accounts::get_balances(ctx.runtime_state(), address)
+            .expect("get_balances should succeed")
+            .balances
+            .get(&Denomination::NATIVE)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(lock.clone()),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // Signatures of witnesses without a bond are not accepted.
+    ctx.with_tx(witness_tx(&bob), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InsufficientBond)));
+    });
+
+    ctx.with_tx(
+        bond_tx(
+            &charlie,
+            BaseUnits::new(100.into(), "oETH".parse().unwrap()),
+        ),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_bond(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::UnsupportedDenomination)));
+        },
+    );
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Bond",
+            cbor::to_value(Bond {
+                amount: BaseUnits::new(100.into(), Denomination::NATIVE),
+            }),
+        ),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_bond(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+
+    // A bond can be posted in parts.
+    for witness in &[&bob, &bob, &charlie] {
+        ctx.with_tx(
+            bond_tx(witness, BaseUnits::new(60.into(), Denomination::NATIVE)),
+            |mut tx_ctx, call| {
+                Bridge::tx_bond(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("bond should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+    assert_eq!(balance(&mut ctx, *ADDRESS_WITNESS_BONDS), 180.into());
+    let bonds = Bridge::query_witness_bonds(&mut ctx, ()).expect("bonds query should succeed");
+    assert_eq!(bonds[0].as_ref().unwrap().amount.amount(), &120.into());
+    assert_eq!(
+        bonds[0].as_ref().unwrap().owner,
+        Address::from_pk(&bob.pk())
+    );
+    assert_eq!(bonds[1].as_ref().unwrap().amount.amount(), &60.into());
+
+    ctx.with_tx(witness_tx(&bob), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness with a bond should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    ctx.with_tx(witness_tx(&charlie), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(
+            matches!(result, Err(Error::InsufficientBond)),
+            "witness with a partial bond should fail"
+        );
+    });
+    ctx.with_tx(
+        bond_tx(&charlie, BaseUnits::new(40.into(), Denomination::NATIVE)),
+        |mut tx_ctx, call| {
+            Bridge::tx_bond(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("bond should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(witness_tx(&charlie), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    Bridge::query_quorum_certificate(
+        &mut ctx,
+        QuorumCertificateQuery {
+            denomination: Denomination::NATIVE,
+            id: 0,
+        },
+    )
+    .expect("quorum certificate should exist");
+
+    // Members of the witness set of an operation are covered by their bond after leaving the
+    // current witness set.
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(lock.clone()),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    Bridge::set_params(
+        ctx.runtime_state(),
+        &Parameters {
+            witnesses: vec![charlie.pk().into()],
+            ..params.clone()
+        },
+    );
+    ctx.with_tx(
+        make_tx(
+            bob.pk(),
+            "bridge.Witness",
+            cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 1,
+                signature: bob.sign(&params, 1, &op),
+                nonce: 1,
+            }),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("witness with a bond should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    // Unbonding.
+    ctx.with_tx(
+        unbond_tx(&bob, Some(BaseUnits::new(200.into(), Denomination::NATIVE))),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_unbond(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InsufficientBond)));
+        },
+    );
+    ctx.with_tx(
+        unbond_tx(&bob, Some(BaseUnits::new(120.into(), Denomination::NATIVE))),
+        |mut tx_ctx, call| {
+            Bridge::tx_unbond(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("unbond should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    let bonds = Bridge::query_witness_bonds(&mut ctx, ()).expect("bonds query should succeed");
+    assert!(bonds[0].is_none(), "bond should be removed");
+    assert_eq!(
+        balance(&mut ctx, Address::from_pk(&bob.pk())),
+        880.into(),
+        "unbonded amount should not be withdrawn before the unbonding delay"
+    );
+
+    drop(ctx);
+    mock.runtime_header.round += params.unbonding_delay;
+    let mut ctx = mock.create_ctx();
+
+    ctx.with_tx(unbond_tx(&bob, None), |mut tx_ctx, call| {
+        Bridge::tx_unbond(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("withdrawal should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    assert_eq!(balance(&mut ctx, Address::from_pk(&bob.pk())), 1_000.into());
+    assert_eq!(balance(&mut ctx, *ADDRESS_WITNESS_BONDS), 100.into());
+}
+
+#[test]
+fn test_submit_evidence() {
+    let mut mock = mock::Mock::default();
+    let round = mock.runtime_header.round;
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        witness_bond: Some(BaseUnits::new(100.into(), Denomination::NATIVE)),
+        equivocation_penalty: 5_000,
+        jail_duration: 10,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    Accounts::transfer(
+        &mut ctx,
+        keys::alice::address(),
+        Address::from_pk(&bob.pk()),
+        &BaseUnits::new(100.into(), Denomination::NATIVE),
+    )
+    .expect("transfer should succeed");
+    ctx.with_tx(
+        make_tx(
+            bob.pk(),
+            "bridge.Bond",
+            cbor::to_value(Bond {
+                amount: BaseUnits::new(100.into(), Denomination::NATIVE),
+            }),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_bond(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("bond should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // Bob signs two different locks with the same sequence number, neither of which lands
+    // on-chain.
+    let lock = |amount: u64| {
+        Operation::Lock(Lock {
+            target: "0000000000000000000000000000000000000000".into(),
+            amount: BaseUnits::new(amount.into(), Denomination::NATIVE),
+            remote_chain_id: None,
+            attestation: None,
+        })
+    };
+    let signed = |witness: &TestWitness, op: Operation| SignedOperation {
+        signature: witness.sign(&params, 7, &op),
+        op,
+    };
+    let evidence = |first: SignedOperation, second: SignedOperation| SubmitEvidence {
+        witness: bob.pk().into(),
+        kind: EvidenceKind::Witness,
+        id: 7,
+        first,
+        second,
+    };
+    let evidence_tx = |evidence: SubmitEvidence| {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.SubmitEvidence",
+            cbor::to_value(evidence),
+        )
+    };
+
+    ctx.with_tx(
+        evidence_tx(evidence(
+            signed(&bob, lock(1_000)),
+            signed(&bob, lock(1_000)),
+        )),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_submit_evidence(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(
+                matches!(result, Err(Error::InvalidArgument)),
+                "evidence for the same operation should be rejected"
+            );
+        },
+    );
+    ctx.with_tx(
+        evidence_tx(evidence(
+            signed(&bob, lock(1_000)),
+            signed(&charlie, lock(2_000)),
+        )),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_submit_evidence(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(
+                matches!(result, Err(Error::InvalidSignature)),
+                "evidence signed by another witness should be rejected"
+            );
+        },
+    );
+    ctx.with_tx(
+        evidence_tx(evidence(
+            signed(&bob, lock(1_000)),
+            signed(&bob, lock(2_000)),
+        )),
+        |mut tx_ctx, call| {
+            Bridge::tx_submit_evidence(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("evidence submission should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(
+        evidence_tx(evidence(
+            signed(&bob, lock(1_000)),
+            signed(&bob, lock(3_000)),
+        )),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_submit_evidence(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(
+                matches!(result, Err(Error::DuplicateEvidence)),
+                "witnesses should only be punished once per sequence number"
+            );
+        },
+    );
+
+    let bonds = Bridge::query_witness_bonds(&mut ctx, ()).expect("bonds query should succeed");
+    assert_eq!(
+        bonds[0].as_ref().unwrap().amount.amount(),
+        &50.into(),
+        "equivocating witness should be slashed"
+    );
+    let liveness =
+        Bridge::query_witness_liveness(&mut ctx, ()).expect("liveness query should succeed");
+    assert!(
+        liveness[0].inactive,
+        "equivocating witness should be jailed"
+    );
+    assert_eq!(liveness[0].jailed_until, round + params.jail_duration);
+
+    let reactivate_tx = || make_tx(bob.pk(), "bridge.ReactivateWitness", cbor::to_value(()));
+    ctx.with_tx(reactivate_tx(), |mut tx_ctx, call| {
+        let result =
+            Bridge::tx_reactivate_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(
+            matches!(result, Err(Error::NotAuthorized)),
+            "jailed witness should not be able to reactivate"
+        );
+    });
+
+    drop(ctx);
+    mock.runtime_header.round += params.jail_duration + 1;
+    let mut ctx = mock.create_ctx();
+
+    ctx.with_tx(reactivate_tx(), |mut tx_ctx, call| {
+        Bridge::tx_reactivate_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("reactivation after the jail should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+}
+
+#[test]
+fn test_witness_rewards() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let witnesses = vec![
+        TestWitness::ed25519("bob"),
+        TestWitness::ed25519("charlie"),
+        TestWitness::ed25519("dave"),
+    ];
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        reward_period: 10,
+        ..init_bridge_ex(&mut ctx, witnesses.iter().map(|w| w.pk().into()).collect())
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    // Fund the reward pool.
+    Accounts::transfer(
+        &mut ctx,
+        keys::alice::address(),
+        *ADDRESS_REWARD_POOL,
+        &BaseUnits::new(1_001.into(), Denomination::NATIVE),
+    )
+    .expect("transfer should succeed");
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // Bob and Charlie sign an outgoing operation.
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock)),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    for witness in &witnesses[..2] {
+        ctx.with_tx(
+            make_tx(
+                witness.pk(),
+                "bridge.Witness",
+                cbor::to_value(Witness {
+                    denomination: Denomination::NATIVE,
+                    id: 0,
+                    signature: witness.sign(&params, 0, &op),
+                    nonce: 0,
+                }),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("witness should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+
+    // Bob and Dave sign an incoming operation.
+    for witness in &[&witnesses[0], &witnesses[2]] {
+        let release = witness.sign_release(
+            &params,
+            Release {
+                id: 0,
+                target: keys::alice::address(),
+                amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                log_index: Some(0),
+                batch: vec![],
+                claims_root: None,
+                remote_chain_id: None,
+                signature: None,
+                nonce: Some(0),
+            },
+        );
+        ctx.with_tx(
+            make_tx(witness.pk(), "bridge.Release", cbor::to_value(release)),
+            |mut tx_ctx, call| {
+                Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("release should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+
+    // Nothing is distributed before the end of the reward period.
+    drop(ctx);
+    mock.runtime_header.round = 9;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    let bals = Accounts::get_balances(ctx.runtime_state(), *ADDRESS_REWARD_POOL)
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&Denomination::NATIVE], 1_001.into());
+
+    // Dave is removed from the witness set before the end of the reward period.
+    drop(ctx);
+    mock.runtime_header.round = 10;
+    let mut ctx = mock.create_ctx();
+    let mut reduced = params.clone();
+    reduced.witnesses.truncate(2);
+    Bridge::set_params(ctx.runtime_state(), &reduced);
+    Bridge::end_block(&mut ctx);
+    for (witness, expected) in witnesses.iter().zip(&[667, 333]) {
+        let bals = Accounts::get_balances(ctx.runtime_state(), Address::from_pk(&witness.pk()))
+            .expect("get_balances should succeed");
+        assert_eq!(
+            bals.balances[&Denomination::NATIVE],
+            (*expected).into(),
+            "rewards should be proportional to signed operations"
+        );
+    }
+    let bals = Accounts::get_balances(ctx.runtime_state(), Address::from_pk(&witnesses[2].pk()))
+        .expect("get_balances should succeed");
+    assert!(
+        !bals.balances.contains_key(&Denomination::NATIVE),
+        "removed witnesses should forfeit their rewards"
+    );
+    let bals = Accounts::get_balances(ctx.runtime_state(), *ADDRESS_REWARD_POOL)
+        .expect("get_balances should succeed");
+    assert_eq!(
+        bals.balances[&Denomination::NATIVE],
+        1.into(),
+        "remainder should stay in the reward pool"
+    );
+
+    // Reward points are reset for the next reward period, including those of removed witnesses.
+    drop(ctx);
+    mock.runtime_header.round = 20;
+    let mut ctx = mock.create_ctx();
+    Bridge::set_params(ctx.runtime_state(), &params);
+    Bridge::end_block(&mut ctx);
+    let bals = Accounts::get_balances(ctx.runtime_state(), *ADDRESS_REWARD_POOL)
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&Denomination::NATIVE], 1.into());
+}
+
+#[test]
+fn test_vote_nonces() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    let witness_tx = |id: u64, nonce: u64| {
+        make_tx(
+            bob.pk(),
+            "bridge.Witness",
+            cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id,
+                signature: bob.sign(&params, id, &op),
+                nonce,
+            }),
+        )
+    };
+
+    for _ in 0..2 {
+        ctx.with_tx(
+            make_tx(
+                keys::alice::pk(),
+                "bridge.Lock",
+                cbor::to_value(lock.clone()),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("lock should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+
+    // Each witness signature must carry the next outgoing nonce of the witness.
+    for (id, nonce) in &[(0, 1), (1, 1)] {
+        ctx.with_tx(witness_tx(*id, *nonce), |mut tx_ctx, call| {
+            let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidNonce)));
+        });
+    }
+    ctx.with_tx(witness_tx(0, 0), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    ctx.with_tx(witness_tx(1, 0), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(
+            matches!(result, Err(Error::InvalidNonce)),
+            "reusing a nonce should fail"
+        );
+    });
+    ctx.with_tx(witness_tx(1, 1), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Releases must carry the next incoming nonce, which is tracked separately.
+    let release = Release {
+        id: 0,
+        target: keys::alice::address(),
+        amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+        tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+        log_index: Some(0),
+        batch: vec![],
+        claims_root: None,
+        remote_chain_id: None,
+        signature: None,
+        nonce: None,
+    };
+    for nonce in &[None, Some(1)] {
+        let release = bob.sign_release(
+            &params,
+            Release {
+                nonce: *nonce,
+                ..release.clone()
+            },
+        );
+        ctx.with_tx(
+            make_tx(bob.pk(), "bridge.Release", cbor::to_value(release)),
+            |mut tx_ctx, call| {
+                let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+                assert!(matches!(result, Err(Error::InvalidNonce)));
+            },
+        );
+    }
+    let release = bob.sign_release(
+        &params,
+        Release {
+            nonce: Some(0),
+            ..release
+        },
+    );
+    ctx.with_tx(
+        make_tx(bob.pk(), "bridge.Release", cbor::to_value(release)),
+        |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    let nonces = Bridge::query_vote_nonces(&mut ctx, ()).expect("nonces query should succeed");
+    assert_eq!(
+        nonces,
+        vec![
+            VoteNonces {
+                outgoing: 2,
+                incoming: 1,
+            },
+            VoteNonces::default(),
+        ]
+    );
+}
+
+#[test]
+fn test_lock_fee() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        lock_fee: 100,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+    assert!(
+        Parameters {
+            lock_fee: 10_001,
+            ..params.clone()
+        }
+        .validate_basic()
+        .is_err(),
+        "fees above the whole amount should be rejected"
+    );
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // User Alice locks an amount, 1% of which is deducted as a fee.
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        ),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+            assert_eq!(result.id, 0);
+            assert_eq!(result.fee.amount(), &10.into());
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    for (address, expected) in &[
+        (keys::alice::address(), 999_000),
+        (*ADDRESS_LOCKED_FUNDS, 990),
+        (*ADDRESS_BRIDGE_FEES, 10),
+    ] {
+        let bals = Accounts::get_balances(ctx.runtime_state(), *address)
+            .expect("get_balances should succeed");
+        assert_eq!(bals.balances[&Denomination::NATIVE], (*expected).into());
+    }
+
+    // Witnesses attest to the amount net of the fee.
+    let op = Operation::Lock(Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(990.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    });
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(
+            make_tx(
+                witness.pk(),
+                "bridge.Witness",
+                cbor::to_value(Witness {
+                    denomination: Denomination::NATIVE,
+                    id: 0,
+                    signature: witness.sign(&params, 0, &op),
+                    nonce: 0,
+                }),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("witness should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+    Bridge::query_quorum_certificate(
+        &mut ctx,
+        QuorumCertificateQuery {
+            denomination: Denomination::NATIVE,
+            id: 0,
+        },
+    )
+    .expect("quorum certificate should exist");
+
+    // The fee is split among the signing witnesses once the operation is finalized.
+    for (address, expected) in &[
+        (Address::from_pk(&bob.pk()), 5),
+        (Address::from_pk(&charlie.pk()), 5),
+        (*ADDRESS_BRIDGE_FEES, 0),
+    ] {
+        let bals = Accounts::get_balances(ctx.runtime_state(), *address)
+            .expect("get_balances should succeed");
+        assert_eq!(
+            bals.balances
+                .get(&Denomination::NATIVE)
+                .cloned()
+                .unwrap_or_default(),
+            (*expected).into()
+        );
+    }
+}
+
+#[test]
+fn test_subsidy_pool() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        subsidy_fee: 5.into(),
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx =
+        |signer: PublicKey, method: &str, body: cbor::Value, fee: u64| transaction::Transaction {
+            version: 1,
+            call: transaction::Call {
+                method: method.to_owned(),
+                body,
+            },
+            auth_info: transaction::AuthInfo {
+                signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+                fee: transaction::Fee {
+                    amount: BaseUnits::new(fee.into(), Denomination::NATIVE),
+                    gas: 1000,
+                },
+            },
+        };
+    fn balance<C: Context>(ctx: &mut C, address: Address) -> Quantity {
+        Accounts::get_balances(ctx.runtime_state(), address)
+            .expect("get_balances should succeed")
+            .balances
+            .get(&Denomination::NATIVE)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // User Alice locks an amount and pays the flat subsidy fee.
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock), 0),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    assert_eq!(balance(&mut ctx, keys::alice::address()), 998_995.into());
+    let pool = Bridge::query_subsidy_pool(&mut ctx, ()).expect("subsidy pool query should succeed");
+    assert_eq!(pool.amount(), &5.into());
+
+    // The pool covers the fees of witness transactions by witnesses as far as it allows.
+    let witness_tx = |witness: &TestWitness, method: &str, fee: u64| {
+        make_tx(
+            witness.pk(),
+            method,
+            cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 0,
+                signature: witness.sign(&params, 0, &op),
+                nonce: 0,
+            }),
+            fee,
+        )
+    };
+    let dave = TestWitness::ed25519("dave");
+    for (witness, method, fee, sponsored) in &[
+        (&dave, "bridge.Witness", 3, 0),
+        (&bob, "bridge.Lock", 3, 0),
+        (&bob, "bridge.Witness", 3, 3),
+        (&charlie, "bridge.Witness", 10, 2),
+    ] {
+        let before = balance(&mut ctx, Address::from_pk(&witness.pk()));
+        Bridge::authenticate_tx(&mut ctx, &witness_tx(witness, method, *fee))
+            .expect("authentication should succeed");
+        let after = balance(&mut ctx, Address::from_pk(&witness.pk()));
+        assert_eq!(
+            after.checked_sub(&before).unwrap(),
+            Quantity::from(*sponsored),
+            "{} by {:?} should be sponsored with {}",
+            method,
+            witness.pk(),
+            sponsored
+        );
+    }
+    let pool = Bridge::query_subsidy_pool(&mut ctx, ()).expect("subsidy pool query should succeed");
+    assert_eq!(pool.amount(), &0.into());
+}
+
+#[test]
+fn test_min_lock_amount() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        min_lock_amounts: {
+            let mut amounts = BTreeMap::new();
+            amounts.insert(Denomination::NATIVE, 100.into());
+            amounts
+        },
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let lock_tx = |amount: u64| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Lock".to_owned(),
+            body: cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: BaseUnits::new(amount.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(keys::alice::pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // Locks below the minimum amount are rejected.
+    ctx.with_tx(lock_tx(99), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AmountTooSmall)));
+    });
+    ctx.with_tx(lock_tx(100), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+}
+
+#[test]
+fn test_max_transfer_amount() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        max_transfer_amounts: {
+            let mut amounts = BTreeMap::new();
+            amounts.insert(Denomination::NATIVE, 1_000.into());
+            amounts.insert("oETH".parse().unwrap(), 500.into());
+            amounts
+        },
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+    assert!(
+        Parameters {
+            min_lock_amounts: {
+                let mut amounts = BTreeMap::new();
+                amounts.insert(Denomination::NATIVE, 1_001.into());
+                amounts
+            },
+            ..params.clone()
+        }
+        .validate_basic()
+        .is_err(),
+        "minimum lock amounts above the maximum transfer amount should be rejected"
+    );
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock_tx = |amount: u64| {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: BaseUnits::new(amount.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        )
+    };
+    let release_tx = |amount: u64| {
+        make_tx(
+            bob.pk(),
+            "bridge.Release",
+            cbor::to_value(bob.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(amount.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        )
+    };
+
+    // Locks above the maximum amount are rejected.
+    ctx.with_tx(lock_tx(1_001), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AmountTooLarge)));
+    });
+    ctx.with_tx(lock_tx(1_000), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // So are releases.
+    ctx.with_tx(release_tx(501), |mut tx_ctx, call| {
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AmountTooLarge)));
+    });
+    ctx.with_tx(release_tx(500), |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("release should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+}
+
+#[test]
+fn test_estimate_lock() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        lock_fee: 100,
+        min_lock_amounts: {
+            let mut amounts = BTreeMap::new();
+            amounts.insert(Denomination::NATIVE, 100.into());
+            amounts
+        },
+        rate_limit_window: 10,
+        rate_limits: {
+            let mut limits = BTreeMap::new();
+            limits.insert(Denomination::NATIVE, 5_000.into());
+            limits
+        },
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let native = |amount: u64| BaseUnits::new(amount.into(), Denomination::NATIVE);
+    let estimate = |amount: u64| EstimateLockQuery {
+        amount: native(amount),
+        owner: Some(keys::alice::address()),
+    };
+
+    let result = Bridge::query_estimate_lock(&mut ctx, estimate(1_000))
+        .expect("estimate query should succeed");
+    assert_eq!(
+        result,
+        LockEstimate {
+            id: 0,
+            amount: native(990),
+            fee: native(10),
+            error_code: None,
+        }
+    );
+    let result =
+        Bridge::query_estimate_lock(&mut ctx, estimate(50)).expect("estimate query should succeed");
+    assert_eq!(result.error_code, Some(Error::AmountTooSmall.code()));
+    let result = Bridge::query_estimate_lock(&mut ctx, estimate(6_000))
+        .expect("estimate query should succeed");
+    assert_eq!(result.error_code, Some(Error::RateLimitExceeded.code()));
+
+    // Alice locks an amount, which advances the sequence number and uses up the rate limit.
+    let lock_tx = transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Lock".to_owned(),
+            body: cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: native(1_000),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(keys::alice::pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    ctx.with_tx(lock_tx, |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Estimates do not use up the rate limit themselves.
+    for _ in 0..2 {
+        let result = Bridge::query_estimate_lock(&mut ctx, estimate(4_000))
+            .expect("estimate query should succeed");
+        assert_eq!(result.id, 1);
+        assert_eq!(result.error_code, None);
+    }
+    let result = Bridge::query_estimate_lock(&mut ctx, estimate(4_100))
+        .expect("estimate query should succeed");
+    assert_eq!(result.error_code, Some(Error::RateLimitExceeded.code()));
+}
+
+#[test]
+fn test_rate_limit() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        rate_limit_window: 10,
+        rate_limits: {
+            let mut limits = BTreeMap::new();
+            limits.insert(Denomination::NATIVE, 1_500.into());
+            limits
+        },
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let lock_tx = |amount: u64| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Lock".to_owned(),
+            body: cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: BaseUnits::new(amount.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(keys::alice::pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    ctx.with_tx(lock_tx(1_000), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    // Locks that would exceed the rate limit during the window are rejected.
+    ctx.with_tx(lock_tx(600), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::RateLimitExceeded)));
+    });
+    let volumes = Bridge::query_rate_limit_volumes(&mut ctx, ())
+        .expect("rate limit volumes query should succeed");
+    assert_eq!(volumes[&Denomination::NATIVE], 1_000.into());
+    let quota = Bridge::query_rate_limit_quota(&mut ctx, None)
+        .expect("rate limit quota query should succeed");
+    assert_eq!(quota.window_end, 10);
+    assert_eq!(quota.remaining[&Denomination::NATIVE], 500.into());
+    assert!(quota.account_remaining.is_empty());
+
+    // The volume is not reset before the end of the window.
+    drop(ctx);
+    mock.runtime_header.round = 9;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    ctx.with_tx(lock_tx(600), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::RateLimitExceeded)));
+    });
+
+    drop(ctx);
+    mock.runtime_header.round = 10;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    let volumes = Bridge::query_rate_limit_volumes(&mut ctx, ())
+        .expect("rate limit volumes query should succeed");
+    assert!(volumes.is_empty());
+    let quota = Bridge::query_rate_limit_quota(&mut ctx, None)
+        .expect("rate limit quota query should succeed");
+    assert_eq!(quota.window_end, 20);
+    assert_eq!(quota.remaining[&Denomination::NATIVE], 1_500.into());
+    ctx.with_tx(lock_tx(600), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+}
+
+#[test]
+fn test_account_limit() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        rate_limit_window: 10,
+        account_limits: {
+            let mut limits = BTreeMap::new();
+            limits.insert(Denomination::NATIVE, 1_000.into());
+            limits.insert("oETH".parse().unwrap(), 500.into());
+            limits
+        },
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock_tx = |amount: u64| {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: BaseUnits::new(amount.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        )
+    };
+    let release_tx = |witness: &TestWitness| {
+        make_tx(
+            witness.pk(),
+            "bridge.Release",
+            cbor::to_value(witness.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(600.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        )
+    };
+
+    // Locks beyond the account limit are rejected.
+    ctx.with_tx(lock_tx(800), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    ctx.with_tx(lock_tx(300), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AccountLimitExceeded)));
+    });
+
+    // So are releases to accounts that would receive more than the limit.
+    ctx.with_tx(release_tx(&bob), |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("release should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    // These are left pending, keeping the deciding vote.
+    ctx.with_tx(release_tx(&charlie), |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("release should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    let pending = Bridge::query_pending_incoming(&mut ctx, Default::default())
+        .expect("pending incoming query should succeed");
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].votes, 2);
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("sequence numbers query should succeed");
+    assert_eq!(sequences.incoming.get(&"oETH".parse().unwrap()), None);
+    let quota = Bridge::query_rate_limit_quota(
+        &mut ctx,
+        Some(RateLimitQuotaQuery {
+            account: Some(keys::alice::address()),
+        }),
+    )
+    .expect("rate limit quota query should succeed");
+    assert!(quota.remaining.is_empty());
+    assert_eq!(quota.account_remaining[&Denomination::NATIVE], 200.into());
+    assert_eq!(
+        quota.account_remaining[&"oETH".parse::<Denomination>().unwrap()],
+        500.into()
+    );
+
+    // The limits apply again in the next window.
+    drop(ctx);
+    mock.runtime_header.round = 11;
+    let mut ctx = mock.create_ctx();
+    ctx.with_tx(lock_tx(300), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+}
+
+#[test]
+fn test_set_fee_schedule() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        admin: Some(keys::alice::address()),
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let set_fee_schedule = |signer: PublicKey, id: u64, schedule: FeeSchedule| {
+        make_tx(
+            signer,
+            "bridge.SetFeeSchedule",
+            cbor::to_value(SetFeeSchedule { id, schedule }),
+        )
+    };
+    let lock_tx = || {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        )
+    };
+
+    // Only the admin and witnesses may change the fee schedule.
+    ctx.with_tx(
+        set_fee_schedule(keys::bob::pk(), 0, FeeSchedule::default()),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_set_fee_schedule(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+    // The minimum fee may not exceed the maximum fee.
+    ctx.with_tx(
+        set_fee_schedule(
+            keys::alice::pk(),
+            0,
+            FeeSchedule {
+                min_lock_fee: 20.into(),
+                max_lock_fee: 10.into(),
+                ..Default::default()
+            },
+        ),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_set_fee_schedule(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+        },
+    );
+
+    // The admin changes the fee schedule directly.
+    let admin_schedule = FeeSchedule {
+        lock_fee: 100,
+        min_lock_fee: 20.into(),
+        ..Default::default()
+    };
+    ctx.with_tx(
+        set_fee_schedule(keys::alice::pk(), 0, admin_schedule.clone()),
+        |mut tx_ctx, call| {
+            Bridge::tx_set_fee_schedule(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("setting the fee schedule should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            let changed_key = [MODULE_NAME.as_bytes(), &17u32.to_be_bytes()].concat();
+            assert!(
+                tags.iter().any(|tag| tag.key == changed_key),
+                "fee schedule changed event should be emitted"
+            );
+        },
+    );
+    let current = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    assert_eq!(current.fee_schedule(), admin_schedule);
+    // The 1% fee is below the minimum fee.
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+        assert_eq!(result.fee.amount(), &20.into());
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Witnesses vote on the next fee schedule.
+    let witness_schedule = FeeSchedule {
+        lock_fee: 50,
+        max_lock_fee: 3.into(),
+        ..Default::default()
+    };
+    ctx.with_tx(
+        set_fee_schedule(bob.pk(), 0, witness_schedule.clone()),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_set_fee_schedule(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
+        },
+    );
+    ctx.with_tx(
+        set_fee_schedule(bob.pk(), 1, witness_schedule.clone()),
+        |mut tx_ctx, call| {
+            Bridge::tx_set_fee_schedule(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("voting for the fee schedule should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(
+        set_fee_schedule(bob.pk(), 1, witness_schedule.clone()),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_set_fee_schedule(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::AlreadySubmittedSignature)));
+        },
+    );
+    let current = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    assert_eq!(
+        current.fee_schedule(),
+        admin_schedule,
+        "fee schedule should not change before the threshold is reached"
+    );
+    ctx.with_tx(
+        set_fee_schedule(charlie.pk(), 1, witness_schedule.clone()),
+        |mut tx_ctx, call| {
+            Bridge::tx_set_fee_schedule(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("voting for the fee schedule should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    let current = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    assert_eq!(current.fee_schedule(), witness_schedule);
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("next sequence numbers query should succeed");
+    assert_eq!(sequences.fee_schedule_updates, 2);
+    // The 0.5% fee is above the maximum fee.
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+        assert_eq!(result.fee.amount(), &3.into());
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Witnesses vote as the same witness after rotating their key.
+    let bob_new = TestWitness::secp256k1("bob-new");
+    ctx.with_tx(
+        make_tx(
+            bob.pk(),
+            "bridge.RotateWitnessKey",
+            cbor::to_value(RotateWitnessKey { key: bob_new.pk() }),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_rotate_witness_key(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("key rotation should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(
+        set_fee_schedule(bob.pk(), 2, admin_schedule.clone()),
+        |mut tx_ctx, call| {
+            Bridge::tx_set_fee_schedule(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("voting with the previous key should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(
+        set_fee_schedule(bob_new.pk(), 2, admin_schedule.clone()),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_set_fee_schedule(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::AlreadySubmittedSignature)));
+        },
+    );
+}
+
+#[test]
+fn test_pause() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        guardians: vec![keys::bob::address()],
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    let lock_tx = || {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(lock.clone()),
+        )
+    };
+
+    // User Alice locks an amount before the bridge is paused.
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Only guardians can pause the bridge.
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Pause", cbor::to_value(())),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_pause(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+    ctx.with_tx(
+        make_tx(keys::bob::pk(), "bridge.Pause", cbor::to_value(())),
+        |mut tx_ctx, call| {
+            Bridge::tx_pause(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("pausing should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            let paused_key = [MODULE_NAME.as_bytes(), &19u32.to_be_bytes()].concat();
+            assert!(
+                tags.iter().any(|tag| tag.key == paused_key),
+                "paused event should be emitted"
+            );
+        },
+    );
+    assert!(Bridge::query_paused(&mut ctx, ()).expect("paused query should succeed"));
+    let status =
+        Bridge::query_pause_status(&mut ctx, ()).expect("pause status query should succeed");
+    assert!(status.paused);
+    assert!(status.frozen.is_empty());
+    ctx.with_tx(
+        make_tx(keys::bob::pk(), "bridge.Pause", cbor::to_value(())),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_pause(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+        },
+    );
+
+    // Locks and releases are halted.
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::Paused)));
+    });
+    ctx.with_tx(
+        make_tx(
+            bob.pk(),
+            "bridge.Release",
+            cbor::to_value(bob.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        ),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::Paused)));
+        },
+    );
+
+    // Witnesses can still sign operations that were already created.
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(
+            make_tx(
+                witness.pk(),
+                "bridge.Witness",
+                cbor::to_value(Witness {
+                    denomination: Denomination::NATIVE,
+                    id: 0,
+                    signature: witness.sign(&params, 0, &op),
+                    nonce: 0,
+                }),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("witness should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+    Bridge::query_quorum_certificate(
+        &mut ctx,
+        QuorumCertificateQuery {
+            denomination: Denomination::NATIVE,
+            id: 0,
+        },
+    )
+    .expect("quorum certificate should exist");
+
+    // Once resumed, locks are accepted again.
+    ctx.with_tx(
+        make_tx(keys::bob::pk(), "bridge.Resume", cbor::to_value(())),
+        |mut tx_ctx, call| {
+            Bridge::tx_resume(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("resuming should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            let resumed_key = [MODULE_NAME.as_bytes(), &20u32.to_be_bytes()].concat();
+            assert!(
+                tags.iter().any(|tag| tag.key == resumed_key),
+                "resumed event should be emitted"
+            );
+        },
+    );
+    assert!(!Bridge::query_paused(&mut ctx, ()).expect("paused query should succeed"));
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+}
+
+#[test]
+fn test_freeze_denomination() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        admin: Some(keys::alice::address()),
+        guardians: vec![keys::bob::address()],
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let freeze_tx = |signer: PublicKey, method: &str, denomination: Denomination| {
+        make_tx(
+            signer,
+            method,
+            cbor::to_value(FreezeDenomination { denomination }),
+        )
+    };
+    let lock_tx = || {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        )
+    };
+
+    // Only guardians and the admin can freeze denominations.
+    ctx.with_tx(
+        freeze_tx(
+            charlie.pk(),
+            "bridge.FreezeDenomination",
+            Denomination::NATIVE,
+        ),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_freeze_denomination(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+    ctx.with_tx(
+        freeze_tx(
+            keys::alice::pk(),
+            "bridge.FreezeDenomination",
+            "oETH".parse().unwrap(),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_freeze_denomination(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("freezing should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            let frozen_key = [MODULE_NAME.as_bytes(), &21u32.to_be_bytes()].concat();
+            assert!(
+                tags.iter().any(|tag| tag.key == frozen_key),
+                "denomination frozen event should be emitted"
+            );
+        },
+    );
+    ctx.with_tx(
+        freeze_tx(
+            keys::bob::pk(),
+            "bridge.FreezeDenomination",
+            "oETH".parse().unwrap(),
+        ),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_freeze_denomination(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+        },
+    );
+    let frozen = Bridge::query_frozen_denominations(&mut ctx, ())
+        .expect("frozen denominations query should succeed");
+    assert_eq!(
+        frozen.into_iter().collect::<Vec<_>>(),
+        vec!["oETH".parse().unwrap()]
+    );
+    let status =
+        Bridge::query_pause_status(&mut ctx, ()).expect("pause status query should succeed");
+    assert!(!status.paused);
+    assert_eq!(
+        status.frozen.into_iter().collect::<Vec<_>>(),
+        vec!["oETH".parse().unwrap()]
+    );
+
+    // Releases in the frozen denomination fail, while other denominations are unaffected.
+    ctx.with_tx(
+        make_tx(
+            bob.pk(),
+            "bridge.Release",
+            cbor::to_value(bob.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        ),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::DenominationFrozen)));
+        },
+    );
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Guardian Bob freezes and unfreezes the native denomination.
+    ctx.with_tx(
+        freeze_tx(
+            keys::bob::pk(),
+            "bridge.FreezeDenomination",
+            Denomination::NATIVE,
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_freeze_denomination(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("freezing should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::DenominationFrozen)));
+    });
+    ctx.with_tx(
+        freeze_tx(
+            keys::bob::pk(),
+            "bridge.UnfreezeDenomination",
+            Denomination::NATIVE,
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_unfreeze_denomination(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("unfreezing should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            let unfrozen_key = [MODULE_NAME.as_bytes(), &22u32.to_be_bytes()].concat();
+            assert!(
+                tags.iter().any(|tag| tag.key == unfrozen_key),
+                "denomination unfrozen event should be emitted"
+            );
+        },
+    );
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+}
+
+#[test]
+fn test_circuit_breaker() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        guardians: vec![keys::bob::address()],
+        rate_limit_window: 10,
+        release_baselines: {
+            let mut baselines = BTreeMap::new();
+            baselines.insert("oETH".parse().unwrap(), 100.into());
+            baselines
+        },
+        circuit_breaker_multiplier: 3,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let release_tx = |witness: &TestWitness, id: u64, amount: u64| {
+        make_tx(
+            witness.pk(),
+            "bridge.Release",
+            cbor::to_value(witness.sign_release(
+                &params,
+                Release {
+                    id,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(amount.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), id)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(id),
+                },
+            )),
+        )
+    };
+
+    // Releasing up to the multiple of the baseline does not trip the circuit breaker.
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(release_tx(witness, 0, 300), |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+    Bridge::end_block(&mut ctx);
+    assert!(!Bridge::query_paused(&mut ctx, ()).expect("paused query should succeed"));
+
+    // Releasing more pauses the bridge.
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(release_tx(witness, 1, 100), |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+    Bridge::end_block(&mut ctx);
+    assert!(Bridge::query_paused(&mut ctx, ()).expect("paused query should succeed"));
+    ctx.with_tx(release_tx(&bob, 2, 100), |mut tx_ctx, call| {
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::Paused)));
+    });
+
+    // A guardian needs to resume the bridge.
+    ctx.with_tx(
+        make_tx(keys::bob::pk(), "bridge.Resume", cbor::to_value(())),
+        |mut tx_ctx, call| {
+            Bridge::tx_resume(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("resuming should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(release_tx(&bob, 2, 100), |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("release should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    Bridge::end_block(&mut ctx);
+    assert!(!Bridge::query_paused(&mut ctx, ()).expect("paused query should succeed"));
+}
+
+#[test]
+fn test_challenge_period() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        guardians: vec![keys::bob::address()],
+        challenge_period: 5,
+        challenge_thresholds: {
+            let mut thresholds = BTreeMap::new();
+            thresholds.insert("oETH".parse().unwrap(), 500.into());
+            thresholds
+        },
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let release_tx = |witness: &TestWitness, id: u64, amount: u64| {
+        make_tx(
+            witness.pk(),
+            "bridge.Release",
+            cbor::to_value(witness.sign_release(
+                &params,
+                Release {
+                    id,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(amount.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), id)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(id),
+                },
+            )),
+        )
+    };
+    fn balance<C: Context>(ctx: &mut C) -> Quantity {
+        Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+            .expect("get_balances should succeed")
+            .balances
+            .get(&"oETH".parse().unwrap())
+            .cloned()
+            .unwrap_or_default()
+    }
+    fn status<C: Context>(ctx: &mut C, id: u64) -> OperationStatus {
+        Bridge::query_operation_status(
+            ctx,
+            OperationStatusQuery {
+                direction: Direction::Incoming,
+                denomination: "oETH".parse().unwrap(),
+                id,
+            },
+        )
+        .expect("operation status query should succeed")
+    }
+
+    // Releases up to the threshold are executed immediately, larger ones are queued.
+    for (id, amount) in [(0, 500), (1, 1_000), (2, 2_000)].iter() {
+        for witness in &[&bob, &charlie] {
+            ctx.with_tx(release_tx(witness, *id, *amount), |mut tx_ctx, call| {
+                Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("release should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            });
+        }
+    }
+    assert_eq!(balance(&mut ctx), 500.into());
+    let pending =
+        Bridge::query_pending_releases(&mut ctx, ()).expect("pending query should succeed");
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].id, 1);
+    let deadline = pending[0].deadline;
+    assert_eq!(
+        deadline,
+        mock.runtime_header.round + params.challenge_period
+    );
+    assert_eq!(status(&mut ctx, 0), OperationStatus::Finalized);
+    assert_eq!(status(&mut ctx, 1), OperationStatus::Challenged);
+    assert_eq!(status(&mut ctx, 3), OperationStatus::Unknown);
+
+    // Only guardians and the admin can cancel pending releases.
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.CancelRelease",
+            cbor::to_value(CancelRelease {
+                denomination: "oETH".parse().unwrap(),
+                id: 2,
+            }),
+        ),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_cancel_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+    ctx.with_tx(
+        make_tx(
+            keys::bob::pk(),
+            "bridge.CancelRelease",
+            cbor::to_value(CancelRelease {
+                denomination: "oETH".parse().unwrap(),
+                id: 2,
+            }),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_cancel_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("cancelling should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // The remaining release is executed once the challenge period has passed.
+    drop(ctx);
+    mock.runtime_header.round = deadline - 1;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    assert_eq!(balance(&mut ctx), 500.into());
+
+    drop(ctx);
+    mock.runtime_header.round = deadline;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    assert_eq!(balance(&mut ctx), 1_500.into());
+    assert_eq!(status(&mut ctx, 1), OperationStatus::Finalized);
+    let pending =
+        Bridge::query_pending_releases(&mut ctx, ()).expect("pending query should succeed");
+    assert!(pending.is_empty());
+    let stats = Bridge::query_stats(&mut ctx, ()).expect("stats query should succeed");
+    assert_eq!(stats.releases, 2);
+    let totals =
+        Bridge::query_total_locked(&mut ctx, ()).expect("total locked query should succeed");
+    assert_eq!(
+        totals.minted.get(&"oETH".parse::<Denomination>().unwrap()),
+        Some(&1_500.into())
+    );
+}
+
+#[test]
+fn test_pending_release_without_locked_funds() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    init_bridge(&mut ctx);
+
+    // Queue a release of a local denomination that the locked funds do not cover.
+    let pending = PendingRelease {
+        id: 0,
+        target: keys::bob::address(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        tx_hash: None,
+        log_index: None,
+        batch: vec![],
+        claims_root: None,
+        deadline: mock.runtime_header.round,
+    };
+    {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut pending_releases = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::PENDING_RELEASES,
+        ));
+        pending_releases.insert(sequence_key(&Denomination::NATIVE, 0), &pending);
+    }
+
+    // The release stays queued instead of halting the chain.
+    Bridge::end_block(&mut ctx);
+    let queued =
+        Bridge::query_pending_releases(&mut ctx, ()).expect("pending query should succeed");
+    assert_eq!(queued, vec![pending]);
+
+    // The release is executed once the locked funds cover it.
+    Accounts::transfer(
+        &mut ctx,
+        keys::alice::address(),
+        *ADDRESS_LOCKED_FUNDS,
+        &BaseUnits::new(1_000.into(), Denomination::NATIVE),
+    )
+    .expect("transfer should succeed");
+    Bridge::end_block(&mut ctx);
+    let queued =
+        Bridge::query_pending_releases(&mut ctx, ()).expect("pending query should succeed");
+    assert!(queued.is_empty());
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::bob::address())
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&Denomination::NATIVE], 1_001_000.into());
+}
+
+#[test]
+fn test_failing_pending_batch_release() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    init_bridge(&mut ctx);
+
+    // The locked funds only cover the first transfer of a queued batch release.
+    Accounts::transfer(
+        &mut ctx,
+        keys::alice::address(),
+        *ADDRESS_LOCKED_FUNDS,
+        &BaseUnits::new(100.into(), Denomination::NATIVE),
+    )
+    .expect("transfer should succeed");
+    let pending = PendingRelease {
+        id: 0,
+        target: keys::bob::address(),
+        amount: BaseUnits::new(100.into(), Denomination::NATIVE),
+        tx_hash: None,
+        log_index: None,
+        batch: vec![ReleaseEntry {
+            target: keys::charlie::address(),
+            amount: BaseUnits::new(50.into(), Denomination::NATIVE),
+        }],
+        claims_root: None,
+        deadline: mock.runtime_header.round,
+    };
+    {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut pending_releases = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::PENDING_RELEASES,
+        ));
+        pending_releases.insert(sequence_key(&Denomination::NATIVE, 0), &pending);
+    }
+
+    // The release stays queued without any of its transfers being made.
+    Bridge::end_block(&mut ctx);
+    let queued =
+        Bridge::query_pending_releases(&mut ctx, ()).expect("pending query should succeed");
+    assert_eq!(queued, vec![pending]);
+    for address in &[keys::bob::address(), keys::charlie::address()] {
+        let bals = Accounts::get_balances(ctx.runtime_state(), *address)
+            .expect("get_balances should succeed");
+        assert_eq!(bals.balances[&Denomination::NATIVE], 1_000_000.into());
+    }
+    let stats = Bridge::query_stats(&mut ctx, ()).expect("stats query should succeed");
+    assert_eq!(stats.releases, 0);
+
+    // The release is executed once the locked funds cover all of its transfers.
+    Accounts::transfer(
+        &mut ctx,
+        keys::alice::address(),
+        *ADDRESS_LOCKED_FUNDS,
+        &BaseUnits::new(50.into(), Denomination::NATIVE),
+    )
+    .expect("transfer should succeed");
+    Bridge::end_block(&mut ctx);
+    let queued =
+        Bridge::query_pending_releases(&mut ctx, ()).expect("pending query should succeed");
+    assert!(queued.is_empty());
+    for (address, amount) in &[
+        (keys::bob::address(), 1_000_100u64),
+        (keys::charlie::address(), 1_000_050u64),
+    ] {
+        let bals = Accounts::get_balances(ctx.runtime_state(), *address)
+            .expect("get_balances should succeed");
+        assert_eq!(bals.balances[&Denomination::NATIVE], (*amount).into());
+    }
+    let stats = Bridge::query_stats(&mut ctx, ()).expect("stats query should succeed");
+    assert_eq!(stats.releases, 1);
+}
+
+#[test]
+fn test_failing_follow_up_release() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        release_window: 1,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    // The locked funds only cover the first of two releases.
+    Accounts::transfer(
+        &mut ctx,
+        keys::alice::address(),
+        *ADDRESS_LOCKED_FUNDS,
+        &BaseUnits::new(100.into(), Denomination::NATIVE),
+    )
+    .expect("transfer should succeed");
+
+    let release = |id: u64, amount: u64, nonce: u64| Release {
+        id,
+        target: keys::dave::address(),
+        amount: BaseUnits::new(amount.into(), Denomination::NATIVE),
+        tx_hash: Some(deposit_tx_hash(&Denomination::NATIVE, id)),
+        log_index: Some(0),
+        batch: vec![],
+        claims_root: None,
+        remote_chain_id: None,
+        signature: None,
+        nonce: Some(nonce),
+    };
+    let release_tx = |witness: &TestWitness, release: Release| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Release".to_owned(),
+            body: cbor::to_value(witness.sign_release(&params, release)),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(witness.pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // Witnesses vote for the second release first, then for the first one. The last vote
+    // finalizes the first release, while the second one cannot be covered.
+    for (id, amount, nonce) in [(1, 1_000, 0), (0, 100, 1)] {
+        for witness in &[&bob, &charlie] {
+            ctx.with_tx(
+                release_tx(witness, release(id, amount, nonce)),
+                |mut tx_ctx, call| {
+                    Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                        .expect("release should succeed");
+
+                    let (_tags, _messages) = tx_ctx.commit();
+                },
+            );
+        }
+    }
+
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::dave::address())
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&Denomination::NATIVE], 100.into());
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("sequence numbers query should succeed");
+    assert_eq!(sequences.incoming[&Denomination::NATIVE], 1);
+    let pending = Bridge::query_pending_incoming(&mut ctx, Default::default())
+        .expect("pending incoming query should succeed");
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, 1);
+    assert_eq!(pending[0].votes, 2);
+}
+
+#[test]
+fn test_blocklist() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        admin: Some(keys::dave::address()),
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock_tx = |target: &str| {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(Lock {
+                target: target.into(),
+                amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        )
+    };
+    let release_tx = || {
+        make_tx(
+            bob.pk(),
+            "bridge.Release",
+            cbor::to_value(bob.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        )
+    };
+    let update_tx = |signer: PublicKey, update: UpdateBlocklist| {
+        make_tx(signer, "bridge.UpdateBlocklist", cbor::to_value(update))
+    };
+
+    // Only the admin can update the blocklist.
+    let block = UpdateBlocklist {
+        block: vec![keys::alice::address()],
+        block_remote: vec!["00000000000000000000000000000000000a11ce".into()],
+        ..Default::default()
+    };
+    ctx.with_tx(
+        update_tx(keys::alice::pk(), block.clone()),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_update_blocklist(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+    ctx.with_tx(update_tx(keys::dave::pk(), block), |mut tx_ctx, call| {
+        Bridge::tx_update_blocklist(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("updating the blocklist should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Blocked addresses can neither lock nor receive funds.
+    ctx.with_tx(
+        lock_tx("0000000000000000000000000000000000000000"),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::AddressBlocked)));
+        },
+    );
+    ctx.with_tx(release_tx(), |mut tx_ctx, call| {
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AddressBlocked)));
+    });
+
+    // Unblocking the local address still leaves the remote address blocked.
+    ctx.with_tx(
+        update_tx(
+            keys::dave::pk(),
+            UpdateBlocklist {
+                unblock: vec![keys::alice::address()],
+                ..Default::default()
+            },
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_update_blocklist(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("updating the blocklist should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    let params = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    assert!(params.blocked_addresses.is_empty());
+    assert_eq!(params.blocked_remote_addresses.len(), 1);
+    ctx.with_tx(
+        lock_tx("00000000000000000000000000000000000a11ce"),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::AddressBlocked)));
+        },
+    );
+    ctx.with_tx(
+        lock_tx("0000000000000000000000000000000000000000"),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(release_tx(), |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("release should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+}
+
+#[test]
+fn test_lock_allowlist() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        admin: Some(keys::dave::address()),
+        lock_allowlist: true,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock_tx = || {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        )
+    };
+    let allow_tx = |signer: PublicKey, method: &str| {
+        make_tx(
+            signer,
+            method,
+            cbor::to_value(AllowAddress {
+                address: keys::alice::address(),
+            }),
+        )
+    };
+
+    // Addresses that are not on the allowlist cannot lock funds.
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AddressNotAllowed)));
+    });
+
+    // Only the admin can manage the allowlist.
+    ctx.with_tx(
+        allow_tx(keys::alice::pk(), "bridge.Allow"),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_allow(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+    ctx.with_tx(
+        allow_tx(keys::dave::pk(), "bridge.Allow"),
+        |mut tx_ctx, call| {
+            Bridge::tx_allow(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("allowing should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            let allowed_key = [MODULE_NAME.as_bytes(), &26u32.to_be_bytes()].concat();
+            assert!(
+                tags.iter().any(|tag| tag.key == allowed_key),
+                "address allowed event should be emitted"
+            );
+        },
+    );
+    ctx.with_tx(
+        allow_tx(keys::dave::pk(), "bridge.Allow"),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_allow(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+        },
+    );
+    let allowed = Bridge::query_allowed_addresses(&mut ctx, ())
+        .expect("allowed addresses query should succeed");
+    assert!(allowed.contains(&keys::alice::address()));
+
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Disallowed addresses can no longer lock funds.
+    ctx.with_tx(
+        allow_tx(keys::dave::pk(), "bridge.Disallow"),
+        |mut tx_ctx, call| {
+            Bridge::tx_disallow(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("disallowing should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AddressNotAllowed)));
+    });
+}
+
+#[test]
+fn test_lock_attestation() {
+    let mut mock = mock::Mock::default();
+    mock.runtime_header.round = 10;
+    let mut ctx = mock.create_ctx();
+
+    let attester = TestWitness::secp256k1("attester");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        lock_attester: Some(attester.pk()),
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let lock_tx = |attestation: Option<LockAttestation>| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Lock".to_owned(),
+            body: cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation,
+            }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(keys::alice::pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // Locks without a valid attestation over the caller address are rejected.
+    for attestation in [
+        None,
+        Some(attester.attest_lock(&params, keys::bob::address(), 20)),
+        Some(TestWitness::ed25519("bob").attest_lock(&params, keys::alice::address(), 20)),
+    ]
+    .iter()
+    {
+        ctx.with_tx(lock_tx(attestation.clone()), |mut tx_ctx, call| {
+            let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidSignature)));
+        });
+    }
+    ctx.with_tx(
+        lock_tx(Some(attester.attest_lock(
+            &params,
+            keys::alice::address(),
+            9,
+        ))),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::AttestationExpired)));
+        },
+    );
+
+    ctx.with_tx(
+        lock_tx(Some(attester.attest_lock(
+            &params,
+            keys::alice::address(),
+            10,
+        ))),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+}
+
+#[test]
+fn test_processed_deposits() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        processed_deposit_retention: 10,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let tx_hash = Hash::digest_bytes(b"remote deposit transaction");
+    let release_tx = |witness: &TestWitness, id: u64, log_index: u64| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Release".to_owned(),
+            body: cbor::to_value(witness.sign_release(
+                &params,
+                Release {
+                    id,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(tx_hash),
+                    log_index: Some(log_index),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(id),
+                },
+            )),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(witness.pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let query = |log_index: u64| ProcessedDepositQuery {
+        tx_hash,
+        log_index: Some(log_index),
+    };
+
+    assert!(!Bridge::query_processed_deposit(&mut ctx, query(0))
+        .expect("processed deposit query should succeed"));
+    let mut released = false;
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(release_tx(witness, 0, 0), |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            let release_key = [MODULE_NAME.as_bytes(), &2u32.to_be_bytes()].concat();
+            if let Some(tag) = tags.iter().find(|tag| tag.key == release_key) {
+                match cbor::from_slice(&tag.value).expect("release event should decode") {
+                    Event::Release {
+                        tx_hash: event_tx_hash,
+                        log_index,
+                        ..
+                    } => {
+                        assert_eq!(event_tx_hash, Some(tx_hash));
+                        assert_eq!(log_index, Some(0));
+                        released = true;
+                    }
+                    _ => panic!("release event should be emitted"),
+                }
+            }
+        });
+    }
+    assert!(released, "release event should be emitted");
+    assert!(Bridge::query_processed_deposit(&mut ctx, query(0))
+        .expect("processed deposit query should succeed"));
+    assert!(!Bridge::query_processed_deposit(&mut ctx, query(1))
+        .expect("processed deposit query should succeed"));
+
+    // The same remote deposit cannot be released again under another sequence number.
+    ctx.with_tx(release_tx(&bob, 1, 0), |mut tx_ctx, call| {
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::DepositAlreadyProcessed)));
+    });
+
+    // Another deposit made by the same remote transaction can be released.
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(release_tx(witness, 1, 1), |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+    assert!(Bridge::query_processed_deposit(&mut ctx, query(1))
+        .expect("processed deposit query should succeed"));
+
+    // Processed transactions are forgotten after the retention period.
+    drop(ctx);
+    mock.runtime_header.round = 20;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    assert!(!Bridge::query_processed_deposit(&mut ctx, query(0))
+        .expect("processed deposit query should succeed"));
+}
+
+#[test]
+fn test_processed_deposit_log_index() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let release_tx =
+        |witness: &TestWitness, id: u64, tx_hash: Hash, log_index: Option<u64>, nonce: u64| {
+            transaction::Transaction {
+                version: 1,
+                call: transaction::Call {
+                    method: "bridge.Release".to_owned(),
+                    body: cbor::to_value(witness.sign_release(
+                        &params,
+                        Release {
+                            id,
+                            target: keys::alice::address(),
+                            amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                            tx_hash: Some(tx_hash),
+                            log_index,
+                            batch: vec![],
+                            claims_root: None,
+                            remote_chain_id: None,
+                            signature: None,
+                            nonce: Some(nonce),
+                        },
+                    )),
+                },
+                auth_info: transaction::AuthInfo {
+                    signer_info: vec![transaction::SignerInfo::new(witness.pk(), 0)],
+                    fee: transaction::Fee {
+                        amount: Default::default(),
+                        gas: 1000,
+                    },
+                },
+            }
+        };
+
+    // A deposit released without a log entry covers all log entries of its transaction.
+    let released_tx_hash = Hash::digest_bytes(b"released remote transaction");
+    {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut processed_deposits = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::PROCESSED_DEPOSITS,
+        ));
+        processed_deposits.insert(
+            Bridge::processed_deposit_key(&released_tx_hash, None),
+            &0u64,
+        );
+    }
+    assert!(Bridge::query_processed_deposit(
+        &mut ctx,
+        ProcessedDepositQuery {
+            tx_hash: released_tx_hash,
+            log_index: Some(3),
+        },
+    )
+    .expect("processed deposit query should succeed"));
+    ctx.with_tx(
+        release_tx(&bob, 0, released_tx_hash, Some(0), 0),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::DepositAlreadyProcessed)));
+        },
+    );
+
+    let tx_hash = Hash::digest_bytes(b"remote deposit transaction");
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(
+            release_tx(witness, 0, tx_hash, Some(0), 0),
+            |mut tx_ctx, call| {
+                Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("release should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+
+    // The same deposit cannot be released again, with or without its log entry.
+    ctx.with_tx(release_tx(&bob, 1, tx_hash, None, 1), |mut tx_ctx, call| {
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidArgument)));
+    });
+    ctx.with_tx(
+        release_tx(&bob, 1, tx_hash, Some(0), 1),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::DepositAlreadyProcessed)));
+        },
+    );
+
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&"oETH".parse().unwrap()], 1_000.into());
+}
+
+#[test]
+fn test_invariants() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // Alice locks a local denomination and receives a remote one.
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock)),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    for witness in &[&bob, &charlie] {
+        let release = witness.sign_release(
+            &params,
+            Release {
+                id: 0,
+                target: keys::alice::address(),
+                amount: BaseUnits::new(500.into(), "oETH".parse().unwrap()),
+                tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                log_index: Some(0),
+                batch: vec![],
+                claims_root: None,
+                remote_chain_id: None,
+                signature: None,
+                nonce: Some(0),
+            },
+        );
+        ctx.with_tx(
+            make_tx(witness.pk(), "bridge.Release", cbor::to_value(release)),
+            |mut tx_ctx, call| {
+                Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("release should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+    Bridge::end_block(&mut ctx);
+    assert!(Bridge::query_invariant_violations(&mut ctx, ())
+        .expect("invariant violations query should succeed")
+        .is_empty());
+
+    // Funds leaking from the locked funds account are detected.
+    Accounts::transfer(
+        &mut ctx,
+        *ADDRESS_LOCKED_FUNDS,
+        keys::alice::address(),
+        &BaseUnits::new(100.into(), Denomination::NATIVE),
+    )
+    .expect("transfer should succeed");
+    // So are minted supply counters that are out of sync.
+    let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+    let mut tstore = storage::TypedStore::new(store);
+    let mut reserves: BTreeMap<Denomination, RemoteReserve> =
+        tstore.get(state::REMOTE_RESERVES).unwrap_or_default();
+    reserves.entry("oETH".parse().unwrap()).or_default().minted = 0.into();
+    tstore.insert(state::REMOTE_RESERVES, &reserves);
+    // And remote denominations minted outside of the bridge.
+    Accounts::mint(
+        &mut ctx,
+        keys::alice::address(),
+        &BaseUnits::new(100.into(), "oETH".parse().unwrap()),
+    )
+    .expect("mint should succeed");
+
+    assert_eq!(
+        Bridge::query_invariant_violations(&mut ctx, ())
+            .expect("invariant violations query should succeed"),
+        vec![
+            InvariantViolation {
+                invariant: Invariant::LockedFunds,
+                denomination: Denomination::NATIVE,
+                actual: 900.into(),
+                expected: 1_000.into(),
+            },
+            InvariantViolation {
+                invariant: Invariant::MintedSupply,
+                denomination: "oETH".parse().unwrap(),
+                actual: 0.into(),
+                expected: 500.into(),
+            },
+            InvariantViolation {
+                invariant: Invariant::TotalSupply,
+                denomination: "oETH".parse().unwrap(),
+                actual: 600.into(),
+                expected: 500.into(),
+            },
+        ]
+    );
+
+    // Burning more than was released is detected as well.
+    let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+    Bridge::update_stats(store, |stats| {
+        stats.add_locked(&BaseUnits::new(700.into(), "oETH".parse().unwrap()))
+    });
+    assert_eq!(
+        Bridge::query_invariant_violations(&mut ctx, ())
+            .expect("invariant violations query should succeed"),
+        vec![
+            InvariantViolation {
+                invariant: Invariant::LockedFunds,
+                denomination: Denomination::NATIVE,
+                actual: 900.into(),
+                expected: 1_000.into(),
+            },
+            InvariantViolation {
+                invariant: Invariant::BurnedSupply,
+                denomination: "oETH".parse().unwrap(),
+                actual: 700.into(),
+                expected: 500.into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_recovery() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        admin: Some(keys::dave::address()),
+        guardians: vec![keys::bob::address()],
+        recovery_delay: 10,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let recover_tx = |signer: PublicKey, amount: u64| {
+        make_tx(
+            signer,
+            "bridge.Recover",
+            cbor::to_value(Recover {
+                target: keys::dave::address(),
+                amount: BaseUnits::new(amount.into(), Denomination::NATIVE),
+            }),
+        )
+    };
+    fn balance<C: Context>(ctx: &mut C) -> Quantity {
+        Accounts::get_balances(ctx.runtime_state(), keys::dave::address())
+            .expect("get_balances should succeed")
+            .balances
+            .get(&Denomination::NATIVE)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // Alice locks an amount that gets stuck.
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock)),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // Only the admin can recover funds, and only as much as is locked.
+    ctx.with_tx(recover_tx(keys::bob::pk(), 600), |mut tx_ctx, call| {
+        let result = Bridge::tx_recover(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::NotAuthorized)));
+    });
+    ctx.with_tx(recover_tx(keys::dave::pk(), 1_001), |mut tx_ctx, call| {
+        let result = Bridge::tx_recover(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InsufficientBalance)));
+    });
+    for _ in 0..2 {
+        ctx.with_tx(recover_tx(keys::dave::pk(), 600), |mut tx_ctx, call| {
+            Bridge::tx_recover(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("recover should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            let scheduled_key = [MODULE_NAME.as_bytes(), &29u32.to_be_bytes()].concat();
+            assert!(
+                tags.iter().any(|tag| tag.key == scheduled_key),
+                "recovery scheduled event should be emitted"
+            );
+        });
+    }
+    assert_eq!(
+        Bridge::query_pending_recoveries(&mut ctx, ())
+            .expect("pending recoveries query should succeed")
+            .iter()
+            .map(|recovery| (recovery.id, recovery.deadline))
+            .collect::<Vec<_>>(),
+        vec![(0, 10), (1, 10)]
+    );
+
+    // Guardians can cancel recoveries, other accounts cannot.
+    let cancel_tx = |signer: PublicKey| {
+        make_tx(
+            signer,
+            "bridge.CancelRecovery",
+            cbor::to_value(CancelRecovery { id: 1 }),
+        )
+    };
+    ctx.with_tx(cancel_tx(keys::alice::pk()), |mut tx_ctx, call| {
+        let result = Bridge::tx_cancel_recovery(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::NotAuthorized)));
+    });
+    ctx.with_tx(cancel_tx(keys::bob::pk()), |mut tx_ctx, call| {
+        Bridge::tx_cancel_recovery(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("cancel recovery should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Funds are only transferred after the recovery delay.
+    drop(ctx);
+    mock.runtime_header.round = 9;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    assert_eq!(balance(&mut ctx), Quantity::default());
+
+    drop(ctx);
+    mock.runtime_header.round = 10;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    assert_eq!(balance(&mut ctx), 600.into());
+    assert!(Bridge::query_pending_recoveries(&mut ctx, ())
+        .expect("pending recoveries query should succeed")
+        .is_empty());
+    let stats = Bridge::query_stats(&mut ctx, ()).expect("stats query should succeed");
+    assert_eq!(
+        stats.recovered[&Denomination::NATIVE],
+        600.into(),
+        "recovered amount should be counted"
+    );
+    let totals =
+        Bridge::query_total_locked(&mut ctx, ()).expect("total locked query should succeed");
+    assert_eq!(
+        totals.locked[&Denomination::NATIVE],
+        400.into(),
+        "recovered amount should no longer be locked"
+    );
+}
+
+#[test]
+fn test_max_votes_per_block() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        max_votes_per_block: 1,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock)),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    let witness_tx = || {
+        make_tx(
+            bob.pk(),
+            "bridge.Witness",
+            cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 0,
+                signature: bob.sign(&params, 0, &op),
+                nonce: 0,
+            }),
+        )
+    };
+
+    // Bob votes on an incoming operation.
+    let release = bob.sign_release(
+        &params,
+        Release {
+            id: 0,
+            target: keys::alice::address(),
+            amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+            tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+            log_index: Some(0),
+            batch: vec![],
+            claims_root: None,
+            remote_chain_id: None,
+            signature: None,
+            nonce: Some(0),
+        },
+    );
+    ctx.with_tx(
+        make_tx(bob.pk(), "bridge.Release", cbor::to_value(release)),
+        |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // Further votes in the same block are refused.
+    ctx.with_tx(witness_tx(), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::TooManyVotes)));
+    });
+
+    // The cap applies per block.
+    drop(ctx);
+    mock.runtime_header.round = 1;
+    let mut ctx = mock.create_ctx();
+    ctx.with_tx(witness_tx(), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+}
+
+#[test]
+fn test_release_window() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        release_window: 2,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let release = |witness: &TestWitness, id: u64, amount: u64, nonce: u64| {
+        make_tx(
+            witness.pk(),
+            "bridge.Release",
+            cbor::to_value(witness.sign_release(
+                &params,
+                Release {
+                    id,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(amount.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), id)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(nonce),
+                },
+            )),
+        )
+    };
+
+    // Operations beyond the window are refused.
+    ctx.with_tx(release(&bob, 3, 1_000, 0), |mut tx_ctx, call| {
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
+    });
+
+    // Both witnesses vote on the second operation before the first one.
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(release(witness, 1, 500, 0), |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+
+    // Nothing is released until the first operation is finalized.
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert!(!bals
+        .balances
+        .contains_key(&"oETH".parse::<Denomination>().unwrap()));
+
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(release(witness, 0, 1_000, 1), |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+
+    // Both operations have been finalized in sequence.
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert_eq!(
+        bals.balances[&"oETH".parse().unwrap()],
+        1_500.into(),
+        "both releases should be executed"
+    );
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("next sequence numbers query should succeed");
+    assert_eq!(sequences.incoming[&"oETH".parse().unwrap()], 2);
+}
+
+#[test]
+fn test_sequence_spaces() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let release = |witness: &TestWitness, amount: BaseUnits, nonce: u64| {
+        let tx_hash = deposit_tx_hash(amount.denomination(), 0);
+        make_tx(
+            witness.pk(),
+            "bridge.Release",
+            cbor::to_value(witness.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount,
+                    tx_hash: Some(tx_hash),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(nonce),
+                },
+            )),
+        )
+    };
+
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        ),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+            assert_eq!(result.id, 0);
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // Only Bob votes on the first oETH release, so it stays pending.
+    ctx.with_tx(
+        release(
+            &bob,
+            BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+            0,
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    // The first release of the native denomination is not held back by it.
+    for (witness, nonce) in &[(&bob, 1), (&charlie, 0)] {
+        ctx.with_tx(
+            release(
+                witness,
+                BaseUnits::new(500.into(), Denomination::NATIVE),
+                *nonce,
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("release should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert_eq!(
+        bals.balances[&Denomination::NATIVE],
+        999_500.into(),
+        "native release should be executed"
+    );
+
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("next sequence numbers query should succeed");
+    assert_eq!(sequences.incoming.get(&Denomination::NATIVE), Some(&1));
+    assert_eq!(sequences.incoming.get(&"oETH".parse().unwrap()), None);
+    assert_eq!(sequences.outgoing.get(&Denomination::NATIVE), Some(&1));
+    assert_eq!(sequences.remote_chain_id, params.remote_chain_id);
+
+    // Other remote chains have sequence spaces of their own.
+    let sequences = Bridge::query_next_sequence_numbers(
+        &mut ctx,
+        Some(NextSequenceNumbersQuery {
+            remote_chain_id: Some(params.remote_chain_id + 1),
+            ..Default::default()
+        }),
+    )
+    .expect("next sequence numbers query should succeed");
+    assert!(sequences.incoming.is_empty());
+    assert!(sequences.outgoing.is_empty());
+
+    // The sequence numbers of a single denomination can be selected.
+    let oeth: Denomination = "oETH".parse().unwrap();
+    let sequences = Bridge::query_next_sequence_numbers(
+        &mut ctx,
+        Some(NextSequenceNumbersQuery {
+            denomination: Some(oeth.clone()),
+            ..Default::default()
+        }),
+    )
+    .expect("next sequence numbers query should succeed");
+    assert_eq!(sequences.remote_chain_id, params.remote_chain_id);
+    assert_eq!(
+        sequences.incoming.into_iter().collect::<Vec<_>>(),
+        vec![(oeth.clone(), 0)]
+    );
+    assert_eq!(
+        sequences.outgoing.into_iter().collect::<Vec<_>>(),
+        vec![(oeth, 0)]
+    );
+
+    // The raw storage entries of the sequence numbers can be retrieved for storage proofs.
+    let storage = Bridge::query_sequence_storage(&mut ctx, None)
+        .expect("sequence storage query should succeed");
+    assert_eq!(storage.remote_chain_id, params.remote_chain_id);
+    assert!(storage.incoming.key.starts_with(MODULE_NAME.as_bytes()));
+    assert_ne!(storage.incoming.key, storage.outgoing.key);
+    let incoming: BTreeMap<Denomination, u64> =
+        cbor::from_slice(&storage.incoming.value.expect("incoming entry should exist"))
+            .expect("incoming entry should decode");
+    assert_eq!(incoming.get(&Denomination::NATIVE), Some(&1));
+    let outgoing: BTreeMap<Denomination, u64> =
+        cbor::from_slice(&storage.outgoing.value.expect("outgoing entry should exist"))
+            .expect("outgoing entry should decode");
+    assert_eq!(outgoing.get(&Denomination::NATIVE), Some(&1));
+
+    let storage = Bridge::query_sequence_storage(
+        &mut ctx,
+        Some(SequenceStorageQuery {
+            remote_chain_id: Some(params.remote_chain_id + 1),
+        }),
+    )
+    .expect("sequence storage query should succeed");
+    assert!(storage.incoming.value.is_none());
+    assert!(storage.outgoing.value.is_none());
+
+    // The returned keys are those of the entries written by the module.
+    let remote_chain_id = params.remote_chain_id + 2;
+    let oeth: Denomination = "oETH".parse().unwrap();
+    {
+        let store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        Bridge::advance_sequence(store, remote_chain_id, true, &oeth);
+    }
+    let entries = Bridge::query_sequence_storage(
+        &mut ctx,
+        Some(SequenceStorageQuery {
+            remote_chain_id: Some(remote_chain_id),
+        }),
+    )
+    .expect("sequence storage query should succeed");
+    let mut expected = BTreeMap::new();
+    expected.insert(oeth, 1u64);
+    assert_eq!(entries.outgoing.value, Some(cbor::to_vec(&expected)));
+    assert!(entries.incoming.value.is_none());
+}
+
+#[test]
+fn test_batch_release() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        max_release_batch_size: 2,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let oeth = |amount: u64| BaseUnits::new(amount.into(), "oETH".parse().unwrap());
+    let release_tx = |witness: &TestWitness, batch: Vec<ReleaseEntry>| {
+        make_tx(
+            witness.pk(),
+            "bridge.Release",
+            cbor::to_value(witness.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount: oeth(1_000),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch,
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        )
+    };
+    let batch = vec![
+        ReleaseEntry {
+            target: keys::bob::address(),
+            amount: oeth(200),
+        },
+        ReleaseEntry {
+            target: keys::charlie::address(),
+            amount: oeth(300),
+        },
+    ];
+
+    // Batches are limited in size.
+    let mut oversized = batch.clone();
+    oversized.push(ReleaseEntry {
+        target: keys::dave::address(),
+        amount: oeth(100),
+    });
+    ctx.with_tx(release_tx(&bob, oversized), |mut tx_ctx, call| {
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::BatchTooLarge)));
+    });
+
+    // All transfers of a batch must be of the same denomination.
+    let mixed = vec![ReleaseEntry {
+        target: keys::bob::address(),
+        amount: BaseUnits::new(200.into(), Denomination::NATIVE),
+    }];
+    ctx.with_tx(release_tx(&bob, mixed), |mut tx_ctx, call| {
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidArgument)));
+    });
+
+    // The whole batch is released atomically once the threshold is reached.
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(release_tx(witness, batch.clone()), |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+
+    for (address, amount) in &[
+        (keys::alice::address(), 1_000u64),
+        (keys::bob::address(), 200),
+        (keys::charlie::address(), 300),
+    ] {
+        let bals = Accounts::get_balances(ctx.runtime_state(), *address)
+            .expect("get_balances should succeed");
+        assert_eq!(bals.balances[&"oETH".parse().unwrap()], (*amount).into());
+    }
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("next sequence numbers query should succeed");
+    assert_eq!(sequences.incoming[&"oETH".parse().unwrap()], 1);
+    let stats = Bridge::query_stats(&mut ctx, ()).expect("stats query should succeed");
+    assert_eq!(stats.releases, 1);
+    assert_eq!(stats.released[&"oETH".parse().unwrap()], 1_500.into());
+
+    // Each target can look up the releases it received.
+    fn releases_for<C: Context>(ctx: &mut C, address: Address) -> Vec<CompletedRelease> {
+        Bridge::query_releases_for(
+            ctx,
+            ReleasesForQuery {
+                address,
+                offset: 0,
+                limit: 0,
+            },
+        )
+        .expect("releases query should succeed")
+    }
+    for (address, amount) in &[
+        (keys::alice::address(), 1_000u64),
+        (keys::bob::address(), 200),
+        (keys::charlie::address(), 300),
+    ] {
+        let releases = releases_for(&mut ctx, *address);
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].id, 0);
+        assert_eq!(releases[0].amount, oeth(*amount));
+    }
+    assert!(releases_for(&mut ctx, keys::dave::address()).is_empty());
+}
+
+#[test]
+fn test_claims() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let oeth = |amount: u64| BaseUnits::new(amount.into(), "oETH".parse().unwrap());
+    let claims = vec![
+        (keys::alice::address(), oeth(300)),
+        (keys::bob::address(), oeth(200)),
+        (keys::charlie::address(), oeth(500)),
+    ];
+    let leaves: Vec<_> = claims
+        .iter()
+        .enumerate()
+        .map(|(index, (target, amount))| crypto::claim_leaf(index as u64, target, amount))
+        .collect();
+    let root = crypto::claims_root(&leaves);
+    let release_tx = |witness: &TestWitness, target: Address| {
+        make_tx(
+            witness.pk(),
+            "bridge.Release",
+            cbor::to_value(witness.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target,
+                    amount: oeth(1_000),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: Some(root),
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        )
+    };
+    let claim_tx = |index: usize, amount: BaseUnits| {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Claim",
+            cbor::to_value(Claim {
+                denomination: "oETH".parse().unwrap(),
+                id: 0,
+                index: index as u64,
+                target: claims[index].0,
+                amount,
+                proof: crypto::claims_proof(&leaves, index),
+            }),
+        )
+    };
+
+    // Releases committing to claims must pay into the pending claims account.
+    ctx.with_tx(
+        release_tx(&bob, keys::alice::address()),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidTarget)));
+        },
+    );
+
+    // Nothing can be claimed before the release is finalized.
+    ctx.with_tx(claim_tx(1, oeth(200)), |mut tx_ctx, call| {
+        let result = Bridge::tx_claim(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
+    });
+
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(
+            release_tx(witness, *ADDRESS_PENDING_CLAIMS),
+            |mut tx_ctx, call| {
+                Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("release should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+    let bals = Accounts::get_balances(ctx.runtime_state(), *ADDRESS_PENDING_CLAIMS)
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&"oETH".parse().unwrap()], 1_000.into());
+
+    // Claims must match the committed amounts.
+    ctx.with_tx(claim_tx(1, oeth(1_000)), |mut tx_ctx, call| {
+        let result = Bridge::tx_claim(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidClaimProof)));
+    });
+
+    // Anyone can submit a claim, funds go to its target.
+    ctx.with_tx(claim_tx(1, oeth(200)), |mut tx_ctx, call| {
+        Bridge::tx_claim(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("claim should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::bob::address())
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&"oETH".parse().unwrap()], 200.into());
+
+    // Each claim is only paid out once.
+    ctx.with_tx(claim_tx(1, oeth(200)), |mut tx_ctx, call| {
+        let result = Bridge::tx_claim(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AlreadyClaimed)));
+    });
+
+    let claims_root = Bridge::query_claims_root(
+        &mut ctx,
+        ClaimsRootQuery {
+            denomination: "oETH".parse().unwrap(),
+            id: 0,
+        },
+    )
+    .expect("claims root query should succeed");
+    assert_eq!(claims_root.root, root);
+    assert_eq!(claims_root.remaining, oeth(800));
+}
+
+#[test]
+fn test_witness_batch() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // User Alice locks several amounts while the witnesses are away.
+    let mut ops = vec![];
+    for amount in &[1_000u64, 2_000, 3_000] {
+        let lock = Lock {
+            target: "0000000000000000000000000000000000000000".into(),
+            amount: BaseUnits::new((*amount).into(), Denomination::NATIVE),
+            remote_chain_id: None,
+            attestation: None,
+        };
+        ops.push(Operation::Lock(lock.clone()));
+        ctx.with_tx(
+            make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock)),
+            |mut tx_ctx, call| {
+                Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("lock should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+    let batch_tx = |witness: &TestWitness, nonces: &[u64]| {
+        let votes = ops
+            .iter()
+            .zip(nonces)
+            .enumerate()
+            .map(|(id, (op, nonce))| Witness {
+                denomination: Denomination::NATIVE,
+                id: id as u64,
+                signature: witness.sign(&params, id as u64, op),
+                nonce: *nonce,
+            })
+            .collect();
+        make_tx(
+            witness.pk(),
+            "bridge.WitnessBatch",
+            cbor::to_value(WitnessBatch { votes }),
+        )
+    };
+
+    // Empty batches are rejected.
+    ctx.with_tx(batch_tx(&bob, &[]), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness_batch(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidArgument)));
+    });
+
+    // The whole batch fails if any of its votes is invalid.
+    ctx.with_tx(batch_tx(&bob, &[0, 1, 3]), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness_batch(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidNonce)));
+    });
+
+    // Each witness catches up on all operations in a single transaction.
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(batch_tx(witness, &[0, 1, 2]), |mut tx_ctx, call| {
+            Bridge::tx_witness_batch(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("witness batch should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+
+    for id in 0..3 {
+        Bridge::query_quorum_certificate(
+            &mut ctx,
+            QuorumCertificateQuery {
+                denomination: Denomination::NATIVE,
+                id,
+            },
+        )
+        .expect("quorum certificate should exist");
+    }
+}
+
+#[test]
+fn test_operation_expiry() {
+    let mut mock = mock::Mock::default();
+    mock.runtime_header.round = 5;
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        operation_expiry: 10,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    let witness_tx = |witness: &TestWitness| {
+        make_tx(
+            witness.pk(),
+            "bridge.Witness",
+            cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 0,
+                signature: witness.sign(&params, 0, &op),
+                nonce: 0,
+            }),
+        )
+    };
+
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock)),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    // Only Bob signs before the operation expires.
+    ctx.with_tx(witness_tx(&bob), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    fn status<C: Context>(ctx: &mut C, id: u64) -> OperationStatus {
+        Bridge::query_operation_status(
+            ctx,
+            OperationStatusQuery {
+                direction: Direction::Outgoing,
+                denomination: Denomination::NATIVE,
+                id,
+            },
+        )
+        .expect("operation status query should succeed")
+    }
+    assert_eq!(status(&mut ctx, 0), OperationStatus::Collecting);
+    assert_eq!(status(&mut ctx, 1), OperationStatus::Unknown);
+
+    // Operations are only expired once the expiry period has passed.
+    drop(ctx);
+    mock.runtime_header.round = 10;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    let stats = Bridge::query_stats(&mut ctx, ()).expect("stats query should succeed");
+    assert_eq!(stats.expired_locks, 0);
+
+    drop(ctx);
+    mock.runtime_header.round = 20;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    let stats = Bridge::query_stats(&mut ctx, ()).expect("stats query should succeed");
+    assert_eq!(stats.expired_locks, 1);
+    assert_eq!(stats.pending_locks, 0);
+    assert_eq!(status(&mut ctx, 0), OperationStatus::Expired);
+
+    // Expired operations can no longer be signed, and their funds stay locked.
+    ctx.with_tx(witness_tx(&charlie), |mut tx_ctx, call| {
+        let result = Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
+    });
+    let bals = Accounts::get_balances(ctx.runtime_state(), *ADDRESS_LOCKED_FUNDS)
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&Denomination::NATIVE], 1_000.into());
+}
+
+#[test]
+fn test_stale_votes() {
+    let mut mock = mock::Mock::default();
+    mock.runtime_header.round = 5;
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        stale_vote_age: 10,
+        reset_stale_votes: true,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let release = |amount: u64| Release {
+        id: 0,
+        target: keys::alice::address(),
+        amount: BaseUnits::new(amount.into(), "oETH".parse().unwrap()),
+        tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+        log_index: Some(0),
+        batch: vec![],
+        claims_root: None,
+        remote_chain_id: None,
+        signature: None,
+        nonce: None,
+    };
+    let release_tx = |witness: &TestWitness, amount: u64, nonce: u64| {
+        make_tx(
+            witness.pk(),
+            "bridge.Release",
+            cbor::to_value(witness.sign_release(
+                &params,
+                Release {
+                    nonce: Some(nonce),
+                    ..release(amount)
+                },
+            )),
+        )
+    };
+
+    // The witnesses split so the release can never finalize.
+    for (witness, amount) in &[(&bob, 2_000), (&charlie, 1_000)] {
+        ctx.with_tx(release_tx(witness, *amount, 0), |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+
+    // Votes are only cleared once they are older than the stale vote age.
+    drop(ctx);
+    mock.runtime_header.round = 10;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    ctx.with_tx(release_tx(&bob, 1_000, 1), |mut tx_ctx, call| {
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AlreadySubmittedSignature)));
+    });
+
+    drop(ctx);
+    mock.runtime_header.round = 20;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+
+    // Both witnesses can vote again after the stale votes were cleared.
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(release_tx(witness, 1_000, 1), |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&"oETH".parse().unwrap()], 1_000.into());
+
+    // The cleared vote is not evidence of equivocation.
+    let signed = |amount: u64| SignedOperation {
+        signature: bob
+            .sign_release(&params, release(amount))
+            .signature
+            .unwrap(),
+        op: Operation::Release(release(amount)),
+    };
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.SubmitEvidence",
+            cbor::to_value(SubmitEvidence {
+                witness: bob.pk().into(),
+                kind: EvidenceKind::Release,
+                id: 0,
+                first: signed(2_000),
+                second: signed(1_000),
+            }),
+        ),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_submit_evidence(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+        },
+    );
+}
+
+#[test]
+fn test_max_pending_locks() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        max_pending_locks: 1,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let lock_tx = || {
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(lock.clone()),
+        )
+    };
+
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // No further locks are accepted while the limit of pending operations is reached.
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::TooManyPending)));
+    });
+
+    // Once the pending operation is finalized locks are accepted again.
+    let op = Operation::Lock(lock.clone());
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(
+            make_tx(
+                witness.pk(),
+                "bridge.Witness",
+                cbor::to_value(Witness {
+                    denomination: Denomination::NATIVE,
+                    id: 0,
+                    signature: witness.sign(&params, 0, &op),
+                    nonce: 0,
+                }),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("witness should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+    ctx.with_tx(lock_tx(), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+}
+
+#[test]
+fn test_pull_releases() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        pull_releases: true,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let oeth = |amount: u64| BaseUnits::new(amount.into(), "oETH".parse().unwrap());
+    let release = Release {
+        id: 0,
+        target: keys::alice::address(),
+        amount: oeth(1_000),
+        tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+        log_index: Some(0),
+        batch: vec![],
+        claims_root: None,
+        remote_chain_id: None,
+        signature: None,
+        nonce: Some(0),
+    };
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(
+            make_tx(
+                witness.pk(),
+                "bridge.Release",
+                cbor::to_value(witness.sign_release(&params, release.clone())),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("release should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+
+    // The released funds are credited instead of transferred.
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert!(!bals.balances.contains_key(&"oETH".parse().unwrap()));
+    let claimable = Bridge::query_claimable_balances(
+        &mut ctx,
+        ClaimableBalancesQuery {
+            address: keys::alice::address(),
+        },
+    )
+    .expect("claimable balances query should succeed");
+    assert_eq!(claimable[&"oETH".parse().unwrap()], 1_000.into());
+
+    let claim_tx = |signer: PublicKey, amount: u64| {
+        make_tx(
+            signer,
+            "bridge.ClaimBalance",
+            cbor::to_value(ClaimBalance {
+                amount: oeth(amount),
+            }),
+        )
+    };
+    ctx.with_tx(claim_tx(keys::alice::pk(), 1_500), |mut tx_ctx, call| {
+        let result = Bridge::tx_claim_balance(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InsufficientBalance)));
+    });
+    ctx.with_tx(claim_tx(keys::bob::pk(), 600), |mut tx_ctx, call| {
+        let result = Bridge::tx_claim_balance(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InsufficientBalance)));
+    });
+    ctx.with_tx(claim_tx(keys::alice::pk(), 600), |mut tx_ctx, call| {
+        Bridge::tx_claim_balance(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("claiming should succeed");
+
+        let (tags, _messages) = tx_ctx.commit();
+        let claimed_key = [MODULE_NAME.as_bytes(), &35u32.to_be_bytes()].concat();
+        assert!(
+            tags.iter().any(|tag| tag.key == claimed_key),
+            "balance claimed event should be emitted"
+        );
+    });
+
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&"oETH".parse().unwrap()], 600.into());
+    let claimable = Bridge::query_claimable_balances(
+        &mut ctx,
+        ClaimableBalancesQuery {
+            address: keys::alice::address(),
+        },
+    )
+    .expect("claimable balances query should succeed");
+    assert_eq!(claimable[&"oETH".parse().unwrap()], 400.into());
+}
+
+#[test]
+fn test_out_operation() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    let witness_tx = |witness: &TestWitness| {
+        make_tx(
+            witness.pk(),
+            "bridge.Witness",
+            cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 0,
+                signature: witness.sign(&params, 0, &op),
+                nonce: 0,
+            }),
+        )
+    };
+    let query = OutOperationQuery {
+        denomination: Denomination::NATIVE,
+        id: 0,
+    };
+
+    let result = Bridge::query_out_operation(&mut ctx, query.clone());
+    assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
+
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock)),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(witness_tx(&bob), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // The pending operation includes the signatures collected so far.
+    let info = Bridge::query_out_operation(&mut ctx, query.clone())
+        .expect("outgoing operation query should succeed");
+    assert_eq!(info.id, 0);
+    assert_eq!(info.op.amount(), op.amount());
+    assert_eq!(info.signers.iter().collect::<Vec<_>>(), vec![0]);
+    assert_eq!(
+        info.signatures
+            .iter()
+            .map(|sig| sig.as_ref().to_vec())
+            .collect::<Vec<_>>(),
+        vec![bob.sign(&params, 0, &op).as_ref().to_vec()]
+    );
+
+    // Signing progress reports the witnesses that have not signed yet.
+    let progress_query = SigningProgressQuery {
+        denomination: Denomination::NATIVE,
+        id: 0,
+    };
+    let progress = Bridge::query_signing_progress(&mut ctx, progress_query.clone())
+        .expect("signing progress query should succeed");
+    assert_eq!(
+        progress,
+        SigningProgress {
+            collected: 1,
+            threshold: 2,
+            missing: vec![1],
+        }
+    );
+
+    // Finalized operations are no longer pending.
+    ctx.with_tx(witness_tx(&charlie), |mut tx_ctx, call| {
+        Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("witness should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    let result = Bridge::query_out_operation(&mut ctx, query);
+    assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
+    let result = Bridge::query_signing_progress(&mut ctx, progress_query);
+    assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
+}
+
+#[test]
+fn test_pending_outgoing() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock = |amount: u64| Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(amount.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+
+    for amount in &[1_000, 2_000, 3_000] {
+        ctx.with_tx(
+            make_tx(
+                keys::alice::pk(),
+                "bridge.Lock",
+                cbor::to_value(lock(*amount)),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("lock should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+    ctx.with_tx(
+        make_tx(
+            bob.pk(),
+            "bridge.Witness",
+            cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 1,
+                signature: bob.sign(&params, 1, &Operation::Lock(lock(2_000))),
+                nonce: 0,
+            }),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("witness should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    let pending = Bridge::query_pending_outgoing(&mut ctx, Default::default())
+        .expect("pending outgoing query should succeed");
+    assert_eq!(
+        pending
+            .iter()
+            .map(|op| (op.id, op.votes))
+            .collect::<Vec<_>>(),
+        vec![(0, 0), (1, 1), (2, 0)]
+    );
+    assert_eq!(
+        pending[2].amount,
+        BaseUnits::new(3_000.into(), Denomination::NATIVE)
+    );
+
+    // Pending operations are paginated.
+    let page = Bridge::query_pending_outgoing(
+        &mut ctx,
+        PendingOutgoingQuery {
+            offset: 1,
+            limit: 1,
+        },
+    )
+    .expect("pending outgoing query should succeed");
+    assert_eq!(page, pending[1..2].to_vec());
+    let page = Bridge::query_pending_outgoing(
+        &mut ctx,
+        PendingOutgoingQuery {
+            offset: 3,
+            limit: 0,
+        },
+    )
+    .expect("pending outgoing query should succeed");
+    assert!(page.is_empty());
+}
+
+#[test]
+fn test_history() {
+    let mut mock = mock::Mock::default();
+    mock.runtime_header.round = 3;
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        history_retention: 10,
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    let oeth: Denomination = "oETH".parse().unwrap();
+    let release = Release {
+        id: 0,
+        target: keys::alice::address(),
+        amount: BaseUnits::new(600.into(), oeth.clone()),
+        tx_hash: Some(deposit_tx_hash(&oeth, 0)),
+        log_index: Some(0),
+        batch: vec![],
+        claims_root: None,
+        remote_chain_id: None,
+        signature: None,
+        nonce: Some(0),
+    };
+
+    // Alice locks an amount which both witnesses sign, and both witnesses relay a release.
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock)),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    for witness in &[&bob, &charlie] {
+        let witness_tx = make_tx(
+            witness.pk(),
+            "bridge.Witness",
+            cbor::to_value(Witness {
+                denomination: Denomination::NATIVE,
+                id: 0,
+                signature: witness.sign(&params, 0, &op),
+                nonce: 0,
+            }),
+        );
+        ctx.with_tx(witness_tx, |mut tx_ctx, call| {
+            Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("witness should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+        let release_tx = make_tx(
+            witness.pk(),
+            "bridge.Release",
+            cbor::to_value(witness.sign_release(&params, release.clone())),
+        );
+        ctx.with_tx(release_tx, |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+
+    // Both finalized operations are in the history, in order of finalization.
+    let history =
+        Bridge::query_history(&mut ctx, Default::default()).expect("history query should succeed");
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].direction, Direction::Outgoing);
+    assert_eq!(history[0].id, 0);
+    assert_eq!(history[0].op.amount(), op.amount());
+    assert_eq!(history[0].round, 3);
+    assert_eq!(history[1].direction, Direction::Incoming);
+    assert_eq!(history[1].op.amount(), &release.amount);
+    assert!(matches!(
+        &history[1].op,
+        Operation::Release(Release {
+            signature: None,
+            nonce: None,
+            ..
+        })
+    ));
+
+    // Filters and pagination apply to the history.
+    let history = Bridge::query_history(
+        &mut ctx,
+        HistoryQuery {
+            direction: Some(Direction::Incoming),
+            ..Default::default()
+        },
+    )
+    .expect("history query should succeed");
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].direction, Direction::Incoming);
+    let history = Bridge::query_history(
+        &mut ctx,
+        HistoryQuery {
+            denomination: Some(Denomination::NATIVE),
+            ..Default::default()
+        },
+    )
+    .expect("history query should succeed");
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].direction, Direction::Outgoing);
+    let history = Bridge::query_history(
+        &mut ctx,
+        HistoryQuery {
+            offset: 1,
+            limit: 1,
+            ..Default::default()
+        },
+    )
+    .expect("history query should succeed");
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].direction, Direction::Incoming);
+
+    // Operations are kept for the retention period.
+    drop(ctx);
+    mock.runtime_header.round = 10;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    let history =
+        Bridge::query_history(&mut ctx, Default::default()).expect("history query should succeed");
+    assert_eq!(history.len(), 2);
+
+    drop(ctx);
+    mock.runtime_header.round = 20;
+    let mut ctx = mock.create_ctx();
+    Bridge::end_block(&mut ctx);
+    let history =
+        Bridge::query_history(&mut ctx, Default::default()).expect("history query should succeed");
+    assert!(history.is_empty());
+}
+
+#[test]
+fn test_export_state() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    init_bridge(&mut ctx);
+
+    // Many locks make the state exceed a single chunk.
+    let lock_tx = transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Lock".to_owned(),
+            body: cbor::to_value(Lock {
+                target: "0000000000000000000000000000000000000000".into(),
+                amount: BaseUnits::new(10.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(keys::alice::pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    for _ in 0..1_000 {
+        ctx.with_tx(lock_tx.clone(), |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+
+    let mut exported = vec![];
+    let mut chunks = 0;
+    let mut after = None;
+    loop {
+        let chunk = Bridge::query_export_state(&mut ctx, ExportStateQuery { after })
+            .expect("export query should succeed");
+        assert!(!chunk.entries.is_empty(), "chunks should not be empty");
+        exported.extend(chunk.entries.into_iter().map(|entry| entry.key));
+        chunks += 1;
+        if chunk.next.is_none() {
+            break;
+        }
+        after = chunk.next;
+    }
+    assert!(chunks > 1, "export should be chunked");
+    // Entries are exported exactly once, in key order.
+    assert!(exported.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(exported.contains(&state::STATS.to_vec()));
+    assert_eq!(
+        exported
+            .iter()
+            .filter(|key| key.starts_with(state::OUT_WITNESS_SIGNATURE_SETS))
+            .count(),
+        1_000
+    );
+}
+
+#[test]
+fn test_denomination_info() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        admin: Some(keys::alice::address()),
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let oeth: Denomination = "oETH".parse().unwrap();
+    let metadata = DenominationMetadata {
+        decimals: 18,
+        symbol: "ETH".to_owned(),
+    };
+    let set_metadata = |denomination: &Denomination, metadata: &DenominationMetadata| {
+        cbor::to_value(SetDenominationMetadata {
+            denomination: denomination.clone(),
+            metadata: metadata.clone(),
+        })
+    };
+
+    // All configured denominations are listed, remote ones with their remote identifier.
+    let info = Bridge::query_denomination_info(&mut ctx, ())
+        .expect("denomination info query should succeed");
+    assert_eq!(
+        info,
+        vec![
+            DenominationInfo {
+                denomination: Denomination::NATIVE,
+                remote: None,
+                metadata: Default::default(),
+            },
+            DenominationInfo {
+                denomination: oeth.clone(),
+                remote: Some(params.remote_denominations[&oeth].clone()),
+                metadata: Default::default(),
+            },
+        ]
+    );
+
+    // Remote denomination identifiers map back to their denomination.
+    let remote = params.remote_denominations[&oeth].clone();
+    let local = Bridge::query_local_denomination(
+        &mut ctx,
+        LocalDenominationQuery {
+            remote: remote.clone(),
+        },
+    )
+    .expect("local denomination query should succeed");
+    assert_eq!(local, oeth);
+    let result = Bridge::query_local_denomination(
+        &mut ctx,
+        LocalDenominationQuery {
+            remote: "0000000000000000000000000000000000000000000000000000000000000001".into(),
+        },
+    );
+    assert!(matches!(result, Err(Error::UnsupportedDenomination)));
+
+    // Identifiers must not be shared by multiple denominations.
+    let mut duplicate = params.clone();
+    duplicate
+        .remote_denominations
+        .insert("oBTC".parse().unwrap(), remote);
+    assert!(
+        duplicate.validate_basic().is_err(),
+        "parameters with a duplicate remote denomination should be invalid"
+    );
+
+    // Only the admin can set metadata, and only for configured denominations.
+    ctx.with_tx(
+        make_tx(
+            keys::bob::pk(),
+            "bridge.SetDenominationMetadata",
+            set_metadata(&oeth, &metadata),
+        ),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_set_denomination_metadata(
+                &mut tx_ctx,
+                cbor::from_value(call.body).unwrap(),
+            );
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.SetDenominationMetadata",
+            set_metadata(&"oBTC".parse().unwrap(), &metadata),
+        ),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_set_denomination_metadata(
+                &mut tx_ctx,
+                cbor::from_value(call.body).unwrap(),
+            );
+            assert!(matches!(result, Err(Error::UnsupportedDenomination)));
+        },
+    );
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.SetDenominationMetadata",
+            set_metadata(&oeth, &metadata),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_set_denomination_metadata(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("setting denomination metadata should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    let info = Bridge::query_denomination_info(&mut ctx, ())
+        .expect("denomination info query should succeed");
+    assert!(info[0].metadata.is_empty());
+    assert_eq!(info[1].metadata, metadata);
+
+    // Empty metadata clears it again.
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.SetDenominationMetadata",
+            set_metadata(&oeth, &Default::default()),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_set_denomination_metadata(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("clearing denomination metadata should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    let info = Bridge::query_denomination_info(&mut ctx, ())
+        .expect("denomination info query should succeed");
+    assert!(info[1].metadata.is_empty());
+}
+
+#[test]
+fn test_remote_address_size() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    // Remote addresses can have up to 32 bytes.
+    let long: RemoteAddress =
+        "00000000000000000000000000000000000000000000000000000000000a11ce".into();
+    assert_eq!(long.size(), 32);
+    let decoded: RemoteAddress = cbor::from_slice(&cbor::to_vec(&long)).unwrap();
+    assert_eq!(decoded, long);
+    assert!(RemoteAddress::from_bytes(&[]).is_err());
+    assert!(RemoteAddress::from_bytes(&[0; 33]).is_err());
+    assert!(
+        cbor::from_slice::<RemoteAddress>(&cbor::to_vec(&serde_bytes::Bytes::new(&[0; 33])))
+            .is_err()
+    );
+
+    let lock_tx = |target: RemoteAddress| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Lock".to_owned(),
+            body: cbor::to_value(Lock {
+                target,
+                amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+                remote_chain_id: None,
+                attestation: None,
+            }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(keys::alice::pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // By default lock targets must be 20-byte Ethereum addresses.
+    ctx.with_tx(lock_tx(long), |mut tx_ctx, call| {
+        let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidTarget)));
+    });
+
+    let params = Parameters {
+        remote_address_size: 32,
+        ..params
+    };
+    params.validate_basic().expect("parameters should be valid");
+    Bridge::set_params(ctx.runtime_state(), &params);
+    ctx.with_tx(
+        lock_tx("0000000000000000000000000000000000000000".into()),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidTarget)));
+        },
+    );
+    ctx.with_tx(lock_tx(long), |mut tx_ctx, call| {
+        Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("lock should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    assert!(
+        Parameters {
+            remote_address_size: 33,
+            ..params
+        }
+        .validate_basic()
+        .is_err(),
+        "parameters with a remote address size above the maximum should be invalid"
+    );
+}
+
+#[test]
+fn test_remote_address_checksum() {
+    // Test vectors from EIP-55.
+    for checksummed in &[
+        "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "fB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "dbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "D1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ] {
+        let address = RemoteAddress::from_hex(checksummed).expect("checksum should be valid");
+        assert_eq!(address.to_checksum_hex(), *checksummed);
+        assert_eq!(address.to_string(), *checksummed);
+        assert_eq!(format!("{:x}", address), checksummed.to_ascii_lowercase());
+
+        // Single-case and prefixed addresses are accepted as well.
+        let lowercase = RemoteAddress::from_hex(&checksummed.to_ascii_lowercase())
+            .expect("lowercase address should be valid");
+        assert_eq!(lowercase, address);
+        let uppercase = RemoteAddress::from_hex(&checksummed.to_ascii_uppercase())
+            .expect("uppercase address should be valid");
+        assert_eq!(uppercase, address);
+        let prefixed = RemoteAddress::from_hex(&format!("0x{}", checksummed))
+            .expect("prefixed address should be valid");
+        assert_eq!(prefixed, address);
+    }
+
+    // Mixed-case addresses with a wrong checksum are rejected.
+    assert!(matches!(
+        RemoteAddress::from_hex("5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+        Err(RemoteAddressError::InvalidChecksum)
+    ));
+    assert!(matches!(
+        RemoteAddress::from_hex("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"),
+        Err(RemoteAddressError::InvalidChecksum)
+    ));
+}
+
+/// Verifier trusting any release submitted by Alice, standing in for a proof-based backend.
+struct TrustedRelayer;
+
+impl IncomingVerifier<Accounts> for TrustedRelayer {
+    fn verify_release<C: TxContext>(
+        ctx: &mut C,
+        params: &Parameters,
+        body: Release,
+    ) -> Result<(), Error> {
+        if ctx.tx_caller_address() != keys::alice::address() {
+            return Err(Error::NotAuthorized);
+        }
+        let round = ctx.runtime_header().round;
+        super::Module::<Accounts, Self>::complete_release(ctx, params, round, body)
+    }
+}
+
+#[test]
+fn test_incoming_verifier() {
+    type RelayedBridge = super::Module<Accounts, TrustedRelayer>;
+
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    init_bridge(&mut ctx);
+
+    let release_tx = |signer: PublicKey, id: u64| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Release".to_owned(),
+            body: cbor::to_value(Release {
+                id,
+                target: keys::bob::address(),
+                amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), id)),
+                log_index: Some(0),
+                batch: vec![],
+                claims_root: None,
+                remote_chain_id: None,
+                signature: None,
+                nonce: None,
+            }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // Releases go through the configured verifier instead of witness votes.
+    ctx.with_tx(release_tx(keys::charlie::pk(), 0), |mut tx_ctx, call| {
+        let result = RelayedBridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::NotAuthorized)));
+    });
+    // Verified releases are still completed in sequence.
+    ctx.with_tx(release_tx(keys::alice::pk(), 1), |mut tx_ctx, call| {
+        let result = RelayedBridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
+    });
+    ctx.with_tx(release_tx(keys::alice::pk(), 0), |mut tx_ctx, call| {
+        RelayedBridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("release should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::bob::address())
+        .expect("get_balances should succeed");
+    assert_eq!(
+        bals.balances[&"oETH".parse::<Denomination>().unwrap()],
+        1_000.into()
+    );
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("next sequence numbers query should succeed");
+    assert_eq!(
+        sequences.incoming[&"oETH".parse::<Denomination>().unwrap()],
+        1
+    );
+}
+
+#[test]
+fn test_remote_contract() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    // No remote contract is configured by default.
+    let remote = Bridge::query_remote_contract(&mut ctx, ()).unwrap();
+    assert_eq!(remote.remote_chain_id, 0);
+    assert_eq!(remote.address, None);
+
+    let contract: RemoteAddress = "00000000000000000000000000000000000b1d9e".into();
+    let with_contract = Parameters {
+        remote_contract: Some(contract),
+        ..params.clone()
+    };
+    with_contract
+        .validate_basic()
+        .expect("parameters should be valid");
+    Bridge::set_params(ctx.runtime_state(), &with_contract);
+
+    let remote = Bridge::query_remote_contract(&mut ctx, ()).unwrap();
+    assert_eq!(remote.address, Some(contract));
+
+    // Witness signatures should commit to the remote contract.
+    let op = Operation::Lock(Lock {
+        target: "00000000000000000000000000000000000a11ce".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    });
+    assert_ne!(
+        crypto::witness_message(&params, 0, &op),
+        crypto::witness_message(&with_contract, 0, &op),
+        "witness message should commit to the remote contract"
+    );
+    let abi_with_contract = crypto::abi_message(&with_contract, 0, &op).unwrap();
+    assert_eq!(
+        hex::encode(&abi_with_contract[..32]),
+        "00000000000000000000000000000000000000000000000000000000000b1d9e",
+        "ABI message should be prefixed with the remote contract"
+    );
+    let abi_with_other_contract = crypto::abi_message(
+        &Parameters {
+            remote_contract: Some("00000000000000000000000000000000000ba55e".into()),
+            ..params.clone()
+        },
+        0,
+        &op,
+    )
+    .unwrap();
+    assert_eq!(abi_with_contract[32..], abi_with_other_contract[32..]);
+    // ABI messages cannot be produced without a remote contract.
+    assert!(crypto::abi_message(&params, 0, &op).is_err());
+
+    // The verifying contract of the EIP-712 domain is used when no remote contract is set.
+    let eip712 = Parameters {
+        eip712_domain: Some(Eip712Domain {
+            verifying_contract: contract,
+        }),
+        ..params.clone()
+    };
+    assert_eq!(eip712.remote_contract(), Some(contract));
+
+    assert!(
+        Parameters {
+            remote_contract: Some(
+                "00000000000000000000000000000000000000000000000000000000000b1d9e".into()
+            ),
+            ..params.clone()
+        }
+        .validate_basic()
+        .is_err(),
+        "parameters with a remote contract of the wrong size should be invalid"
+    );
+    assert!(
+        Parameters {
+            remote_contract: Some("00000000000000000000000000000000000ba55e".into()),
+            ..eip712
+        }
+        .validate_basic()
+        .is_err(),
+        "parameters with a remote contract different from the EIP-712 domain should be invalid"
+    );
+}
+
+#[test]
+fn test_set_remote_contract() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        admin: Some(keys::alice::address()),
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, id: u64, contract: RemoteAddress| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.SetRemoteContract".to_owned(),
+            body: cbor::to_value(SetRemoteContract { id, contract }),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let admin_contract: RemoteAddress = "00000000000000000000000000000000000b1d9e".into();
+    let governance_contract: RemoteAddress = "00000000000000000000000000000000000ba55e".into();
+
+    // Only the admin and witnesses may change the remote contract.
+    ctx.with_tx(
+        make_tx(keys::bob::pk(), 0, admin_contract),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_set_remote_contract(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+    // The remote contract must have the remote address size.
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            0,
+            "00000000000000000000000000000000000000000000000000000000000b1d9e".into(),
+        ),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_set_remote_contract(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+        },
+    );
+
+    // The admin changes the remote contract directly.
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), 0, admin_contract),
+        |mut tx_ctx, call| {
+            Bridge::tx_set_remote_contract(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("setting the remote contract should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            let changed_key = [MODULE_NAME.as_bytes(), &36u32.to_be_bytes()].concat();
+            assert!(
+                tags.iter().any(|tag| tag.key == changed_key),
+                "remote contract changed event should be emitted"
+            );
+        },
+    );
+    let remote = Bridge::query_remote_contract(&mut ctx, ()).unwrap();
+    assert_eq!(remote.address, Some(admin_contract));
+
+    // Witnesses relay a remote contract update from the remote chain's governance.
+    ctx.with_tx(
+        make_tx(bob.pk(), 1, governance_contract),
+        |mut tx_ctx, call| {
+            Bridge::tx_set_remote_contract(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("voting for the remote contract should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(
+        make_tx(bob.pk(), 1, governance_contract),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_set_remote_contract(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::AlreadySubmittedSignature)));
+        },
+    );
+    let remote = Bridge::query_remote_contract(&mut ctx, ()).unwrap();
+    assert_eq!(
+        remote.address,
+        Some(admin_contract),
+        "remote contract should not change before the threshold is reached"
+    );
+    ctx.with_tx(
+        make_tx(charlie.pk(), 1, governance_contract),
+        |mut tx_ctx, call| {
+            Bridge::tx_set_remote_contract(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("voting for the remote contract should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    let remote = Bridge::query_remote_contract(&mut ctx, ()).unwrap();
+    assert_eq!(remote.address, Some(governance_contract));
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("next sequence numbers query should succeed");
+    assert_eq!(sequences.remote_contract_updates, 2);
+}
+
+#[test]
+fn test_decimal_scales() {
+    // Local amounts with fewer decimals are scaled up exactly.
+    let up = DecimalScale {
+        local: 6,
+        remote: 18,
+    };
+    assert_eq!(up.to_remote(&1.into()), Some(1_000_000_000_000u64.into()));
+    assert_eq!(up.split_dust(&1_001.into()), Some((1_001.into(), 0.into())));
+    // Local amounts with more decimals lose their dust.
+    let down = DecimalScale {
+        local: 9,
+        remote: 6,
+    };
+    assert_eq!(down.to_remote(&2_000.into()), Some(2.into()));
+    assert_eq!(down.to_remote(&2_001.into()), None);
+    assert_eq!(
+        down.split_dust(&2_001.into()),
+        Some((2_000.into(), 1.into()))
+    );
+
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+    let params = Parameters {
+        decimal_scales: vec![(Denomination::NATIVE, down)].into_iter().collect(),
+        ..params
+    };
+    params.validate_basic().expect("parameters should be valid");
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let lock = |amount: u64| Lock {
+        target: "00000000000000000000000000000000000a11ce".into(),
+        amount: BaseUnits::new(amount.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+
+    // Locks without a remote equivalent are rejected.
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock(999))),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::AmountTooSmall)));
+        },
+    );
+    // The dust of a lock stays with the caller.
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.Lock",
+            cbor::to_value(lock(1_500)),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&Denomination::NATIVE], 999_000.into());
+    let info = Bridge::query_out_operation(
+        &mut ctx,
+        OutOperationQuery {
+            denomination: Denomination::NATIVE,
+            id: 0,
+        },
+    )
+    .expect("out operation query should succeed");
+    assert_eq!(info.op.amount().amount(), &1_000.into());
+
+    // Signed messages carry the remote amount.
+    let message = crypto::abi_message(&params, 0, &Operation::Lock(lock(1_000))).unwrap();
+    assert_eq!(
+        hex::encode(&message[96..]),
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "ABI message should carry the remote amount"
+    );
+
+    // Releases without a remote equivalent are rejected.
+    let release = Release {
+        id: 0,
+        target: keys::alice::address(),
+        amount: BaseUnits::new(1_500.into(), Denomination::NATIVE),
+        tx_hash: Some(deposit_tx_hash(&Denomination::NATIVE, 0)),
+        log_index: Some(0),
+        batch: vec![],
+        claims_root: None,
+        remote_chain_id: None,
+        signature: None,
+        nonce: None,
+    };
+    ctx.with_tx(
+        make_tx(keys::bob::pk(), "bridge.Release", cbor::to_value(release)),
+        |mut tx_ctx, call| {
+            let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+        },
+    );
+
+    assert!(
+        Parameters {
+            decimal_scales: vec![(
+                Denomination::NATIVE,
+                DecimalScale {
+                    local: 0,
+                    remote: 37,
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..params.clone()
+        }
+        .validate_basic()
+        .is_err(),
+        "parameters with too large a decimal scale should be invalid"
+    );
+    assert!(
+        Parameters {
+            decimal_scales: vec![("oBTC".parse().unwrap(), up)].into_iter().collect(),
+            ..params
+        }
+        .validate_basic()
+        .is_err(),
+        "parameters with a decimal scale for an unknown denomination should be invalid"
+    );
+}
+
+#[test]
+fn test_register_denomination() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        admin: Some(keys::alice::address()),
+        ..init_bridge(&mut ctx)
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, body: RegisterDenomination| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.RegisterDenomination".to_owned(),
+            body: cbor::to_value(body),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let registration = |id: u64, denomination: &str, remote: &str| RegisterDenomination {
+        id,
+        denomination: denomination.parse().unwrap(),
+        remote: remote.into(),
+        decimal_scale: None,
+    };
+    let obtc: Denomination = "oBTC".parse().unwrap();
+    let ousdc: Denomination = "oUSDC".parse().unwrap();
+
+    // Only the admin and witnesses may register denominations.
+    ctx.with_tx(
+        make_tx(keys::bob::pk(), registration(0, "oBTC", "0b7c")),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_register_denomination(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+    // Denominations and remote identifiers cannot be registered twice.
+    for body in [
+        registration(0, "oETH", "0b7c"),
+        registration(
+            0,
+            "oBTC",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        ),
+    ] {
+        ctx.with_tx(make_tx(keys::alice::pk(), body), |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_register_denomination(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+        });
+    }
+
+    // The admin registers a denomination directly.
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), registration(0, "oBTC", "0b7c")),
+        |mut tx_ctx, call| {
+            Bridge::tx_register_denomination(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("registering the denomination should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            let registered_key = [MODULE_NAME.as_bytes(), &37u32.to_be_bytes()].concat();
+            assert!(
+                tags.iter().any(|tag| tag.key == registered_key),
+                "denomination registered event should be emitted"
+            );
+        },
+    );
+    let current = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    assert_eq!(current.remote_denominations[&obtc], "0b7c".into());
+
+    // Witnesses vote on the next registration.
+    let body = RegisterDenomination {
+        decimal_scale: Some(DecimalScale {
+            local: 6,
+            remote: 6,
+        }),
+        ..registration(1, "oUSDC", "05dc")
+    };
+    ctx.with_tx(make_tx(bob.pk(), body.clone()), |mut tx_ctx, call| {
+        Bridge::tx_register_denomination(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("voting for the registration should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    ctx.with_tx(make_tx(bob.pk(), body.clone()), |mut tx_ctx, call| {
+        let result =
+            Bridge::tx_register_denomination(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AlreadySubmittedSignature)));
+    });
+    let current = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    assert!(
+        !current.remote_denominations.contains_key(&ousdc),
+        "denomination should not be registered before the threshold is reached"
+    );
+    ctx.with_tx(make_tx(charlie.pk(), body), |mut tx_ctx, call| {
+        Bridge::tx_register_denomination(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("voting for the registration should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    let current = Bridge::query_parameters(&mut ctx, ()).expect("parameters query should succeed");
+    assert_eq!(current.remote_denominations[&ousdc], "05dc".into());
+    assert_eq!(
+        current.decimal_scales[&ousdc],
+        DecimalScale {
+            local: 6,
+            remote: 6,
+        }
+    );
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("next sequence numbers query should succeed");
+    assert_eq!(sequences.denomination_registrations, 2);
+}
+
+/// Rotated witness key as stored in version 1, with sequence numbers shared by all denominations.
+#[derive(serde::Serialize)]
+struct LegacyRotatedWitnessKey {
+    key: PublicKey,
+    successor: PublicKey,
+    #[serde(rename = "in")]
+    incoming: u64,
+    #[serde(rename = "out")]
+    outgoing: u64,
+}
+
+#[test]
+fn test_migrate_sequence_spaces() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let charlie = TestWitness::ed25519("charlie");
+    let oeth: Denomination = "oETH".parse().unwrap();
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    // Store state in the version 1 representation, with a single sequence space per direction.
+    let op = Operation::Lock(Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    });
+    let pending = PendingRelease {
+        id: 4,
+        target: keys::alice::address(),
+        amount: BaseUnits::new(1_000.into(), oeth.clone()),
+        tx_hash: None,
+        log_index: None,
+        batch: vec![],
+        claims_root: None,
+        deadline: 10,
+    };
+    {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut tstore = storage::TypedStore::new(&mut store);
+        tstore.insert(state::LEGACY_NEXT_OUT_SEQUENCE, &3u64);
+        tstore.insert(state::LEGACY_NEXT_IN_SEQUENCE, &5u64);
+        let mut legacy = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::OUT_WITNESS_SIGNATURES,
+        ));
+        legacy.insert(
+            2u64.to_be_bytes(),
+            &LegacyWitnessSignatures {
+                id: 2,
+                op: op.clone(),
+                wits: vec![1],
+                sigs: vec![charlie.sign(&params, 2, &op)],
+            },
+        );
+        let mut pending_releases = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::PENDING_RELEASES,
+        ));
+        pending_releases.insert(4u64.to_be_bytes(), &pending);
+        let mut rotated_keys = storage::TypedStore::new(storage::PrefixStore::new(
+            &mut store,
+            &state::ROTATED_WITNESS_KEYS,
+        ));
+        rotated_keys.insert(
+            keys::dave::address(),
+            &LegacyRotatedWitnessKey {
+                key: keys::dave::pk(),
+                successor: charlie.pk(),
+                incoming: 5,
+                outgoing: 2,
+            },
+        );
+    }
+
+    let mut meta = core::types::Metadata::default();
+    meta.versions.insert(MODULE_NAME.to_owned(), 1);
+    assert!(
+        Bridge::init_or_migrate(&mut ctx, &mut meta, &Default::default()),
+        "migration from version 1 should succeed"
+    );
+    assert_eq!(meta.versions[MODULE_NAME], Bridge::VERSION);
+    assert!(
+        !Bridge::init_or_migrate(&mut ctx, &mut meta, &Default::default()),
+        "migrated state should not be migrated again"
+    );
+
+    // Every denomination continues numbering from the shared sequence numbers.
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("sequence numbers query should succeed");
+    let shared = |next: u64| -> BTreeMap<Denomination, u64> {
+        vec![(Denomination::NATIVE, next), (oeth.clone(), next)]
+            .into_iter()
+            .collect()
+    };
+    assert_eq!(sequences.outgoing, shared(3));
+    assert_eq!(sequences.incoming, shared(5));
+
+    // Operations are moved to the sequence space of their denomination.
+    let pending_outgoing = Bridge::query_pending_outgoing(&mut ctx, Default::default())
+        .expect("pending query should succeed");
+    assert_eq!(
+        pending_outgoing,
+        vec![PendingOutgoing {
+            denomination: Denomination::NATIVE,
+            id: 2,
+            amount: op.amount().clone(),
+            votes: 1,
+        }]
+    );
+    let pending_releases =
+        Bridge::query_pending_releases(&mut ctx, ()).expect("pending query should succeed");
+    assert_eq!(pending_releases, vec![pending]);
+
+    let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+    let out_witness_signatures = storage::TypedStore::new(storage::PrefixStore::new(
+        &mut store,
+        &state::OUT_WITNESS_SIGNATURE_SETS,
+    ));
+    let info: Option<WitnessSignatureSet> =
+        out_witness_signatures.get(sequence_key(&Denomination::NATIVE, 2));
+    assert!(info.is_some(), "legacy signatures should be converted");
+    let tstore = storage::TypedStore::new(&mut store);
+    let next_out: Option<u64> = tstore.get(state::LEGACY_NEXT_OUT_SEQUENCE);
+    assert!(
+        next_out.is_none(),
+        "shared sequence number should be removed"
+    );
+
+    // Key rotations record the sequence numbers of every denomination.
+    let rotated_keys = storage::TypedStore::new(storage::PrefixStore::new(
+        &mut store,
+        &state::ROTATED_WITNESS_KEYS,
+    ));
+    let rotated: RotatedWitnessKey = rotated_keys
+        .get(keys::dave::address())
+        .expect("rotated key should be converted");
+    assert_eq!(rotated.incoming, shared(5));
+    assert_eq!(rotated.outgoing, shared(2));
+}
+
+#[test]
+fn test_migrate_sequence_spaces_remote_chain() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    init_accounts(&mut ctx);
+    let mut params = init_bridge(&mut ctx);
+    params.remote_chain_id = 5;
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    {
+        let mut store = storage::PrefixStore::new(ctx.runtime_state(), &MODULE_NAME);
+        let mut tstore = storage::TypedStore::new(&mut store);
+        tstore.insert(state::LEGACY_NEXT_OUT_SEQUENCE, &3u64);
+        tstore.insert(state::LEGACY_NEXT_IN_SEQUENCE, &5u64);
+    }
+
+    let mut meta = core::types::Metadata::default();
+    meta.versions.insert(MODULE_NAME.to_owned(), 1);
+    assert!(
+        Bridge::init_or_migrate(&mut ctx, &mut meta, &Default::default()),
+        "migration from version 1 should succeed"
+    );
+
+    // The shared sequence numbers were those of the configured remote chain.
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("sequence numbers query should succeed");
+    assert_eq!(sequences.remote_chain_id, 5);
+    assert_eq!(sequences.outgoing[&Denomination::NATIVE], 3);
+    assert_eq!(sequences.incoming[&Denomination::NATIVE], 5);
+    let sequences = Bridge::query_next_sequence_numbers(
+        &mut ctx,
+        Some(NextSequenceNumbersQuery { remote_chain_id: 0 }),
+    )
+    .expect("sequence numbers query should succeed");
+    assert!(sequences.outgoing.is_empty());
+    assert!(sequences.incoming.is_empty());
+}
+
+#[test]
+fn test_witness_bitmap_remove() {
+    let mut bitmap = WitnessBitmap::default();
+    for index in &[0, 3, 8, 9] {
+        bitmap.insert(*index);
+    }
+
+    bitmap.remove_and_shift(3);
+    assert_eq!(bitmap.iter().collect::<Vec<_>>(), vec![0, 7, 8]);
+    bitmap.remove_and_shift(1);
+    assert_eq!(bitmap.iter().collect::<Vec<_>>(), vec![0, 6, 7]);
+
+    assert!(bitmap.remove(7));
+    assert!(
+        !bitmap.remove(7),
+        "removed witness should no longer be a member"
+    );
+    assert_eq!(bitmap.iter().collect::<Vec<_>>(), vec![0, 6]);
+    assert_eq!(bitmap, {
+        let mut expected = WitnessBitmap::default();
+        expected.insert(0);
+        expected.insert(6);
+        expected
+    });
+}
+
+#[test]
+fn test_incoming_basic() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    // Witness Bob witnesses the remote event.
+    let tx = transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Release".to_owned(),
+            body: cbor::to_value(bob.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(bob.pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    ctx.with_tx(tx, |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body.clone()).unwrap())
+            .expect("release should succeed");
+
+        // Check source account balances.
+        let bals = Accounts::get_balances(tx_ctx.runtime_state(), keys::alice::address())
+            .expect("get_balances should succeed");
+        assert_eq!(
+            bals.balances[&Denomination::NATIVE],
+            1_000_000.into(),
+            "balance in target account should be unchanged"
+        );
+        assert_eq!(
+            bals.balances.len(),
+            1,
+            "there should only be one denomination"
+        );
+
+        // Check bridge module account balances.
+        let bals = Accounts::get_balances(tx_ctx.runtime_state(), *ADDRESS_LOCKED_FUNDS)
+            .expect("get_balances should succeed");
+        assert!(
+            bals.balances.is_empty(),
+            "there should be no balances in the bridge module account"
+        );
+
+        // Duplicate releases should be rejected.
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AlreadySubmittedSignature)));
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Witness Charlie witnesses the remote event.
+    let tx = transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Release".to_owned(),
+            body: cbor::to_value(charlie.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(charlie.pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    ctx.with_tx(tx, |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("release should succeed");
+
+        // Check source account balances.
+        let bals = Accounts::get_balances(tx_ctx.runtime_state(), keys::alice::address())
+            .expect("get_balances should succeed");
+        assert_eq!(
+            bals.balances[&Denomination::NATIVE],
+            1_000_000.into(),
+            "native token balance in target account should be unchanged"
+        );
+        assert_eq!(
+            bals.balances[&"oETH".parse().unwrap()],
+            1_000.into(),
+            "tokens should have been minted"
+        );
+        assert_eq!(
+            bals.balances.len(),
+            2,
+            "there should now be two denominations"
+        );
+
+        // Check bridge module account balances.
+        let bals = Accounts::get_balances(tx_ctx.runtime_state(), *ADDRESS_LOCKED_FUNDS)
+            .expect("get_balances should succeed");
+        assert_eq!(
+            bals.balances[&"oETH".parse().unwrap()],
+            0.into(),
+            "no minted tokens should remain in the bridge account"
+        );
+        assert_eq!(
+            bals.balances.len(),
+            1,
+            "there should be a single denomination"
+        );
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+}
+
+#[test]
+fn test_incoming_fail_invalid_sequence() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    // Witness Bob witnesses the remote event.
+    let tx = transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Release".to_owned(),
+            body: cbor::to_value(bob.sign_release(
+                &params,
+                Release {
+                    id: 1, // Invalid sequence as it should be 0.
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 1)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(bob.pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    ctx.with_tx(tx, |mut tx_ctx, call| {
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
+    });
+}
+
+#[test]
+fn test_incoming_fail_internal_target() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    // Witness Bob witnesses a remote event releasing into the bridge-owned account.
+    let tx = transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Release".to_owned(),
+            body: cbor::to_value(bob.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: *ADDRESS_LOCKED_FUNDS,
+                    amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(bob.pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    ctx.with_tx(tx, |mut tx_ctx, call| {
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidTarget)));
+    });
+}
+
+#[test]
+fn test_incoming_fail_invalid_signature() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    // Releases that are not signed by the submitting witness for this asset should be rejected.
+    let release = Release {
+        id: 0,
+        target: keys::alice::address(),
+        amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+        tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+        log_index: Some(0),
+        batch: vec![],
+        claims_root: None,
+        remote_chain_id: None,
+        signature: None,
+        nonce: Some(0),
+    };
+    let other = Release {
+        amount: BaseUnits::new(2_000.into(), "oETH".parse().unwrap()),
+        ..bob.sign_release(&params, release.clone())
+    };
+    let other_token = Parameters {
+        remote_denominations: {
+            let mut rd = BTreeMap::new();
+            rd.insert(
+                "oETH".parse().unwrap(),
+                "0000000000000000000000000000000000000000000000000000000000000001".into(),
+            );
+            rd
+        },
+        ..params.clone()
+    };
+    for body in vec![
+        release.clone(),
+        charlie.sign_release(&params, release.clone()),
+        bob.sign_release(&other_token, release.clone()),
+        other,
+    ] {
+        let tx = transaction::Transaction {
+            version: 1,
+            call: transaction::Call {
+                method: "bridge.Release".to_owned(),
+                body: cbor::to_value(body),
+            },
+            auth_info: transaction::AuthInfo {
+                signer_info: vec![transaction::SignerInfo::new(bob.pk(), 0)],
+                fee: transaction::Fee {
+                    amount: Default::default(),
+                    gas: 1000,
+                },
+            },
+        };
+        ctx.with_tx(tx, |mut tx_ctx, call| {
+            let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidSignature)));
+        });
+    }
+}
+
+#[test]
+fn test_incoming_mixed_schemes() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::secp256k1("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge_ex(&mut ctx, vec![bob.pk().into(), charlie.pk().into()]);
+
+    // Witnesses using different signature schemes witness the remote event.
+    for witness in &[&bob, &charlie] {
+        let tx = transaction::Transaction {
+            version: 1,
+            call: transaction::Call {
+                method: "bridge.Release".to_owned(),
+                body: cbor::to_value(witness.sign_release(
+                    &params,
+                    Release {
+                        id: 0,
+                        target: keys::alice::address(),
+                        amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                        tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                        log_index: Some(0),
+                        batch: vec![],
+                        claims_root: None,
+                        remote_chain_id: None,
+                        signature: None,
+                        nonce: Some(0),
+                    },
+                )),
+            },
+            auth_info: transaction::AuthInfo {
+                signer_info: vec![transaction::SignerInfo::new(witness.pk(), 0)],
+                fee: transaction::Fee {
+                    amount: Default::default(),
+                    gas: 1000,
+                },
+            },
+        };
+        ctx.with_tx(tx, |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+
+    // Check target account balances.
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert_eq!(
+        bals.balances[&"oETH".parse().unwrap()],
+        1_000.into(),
+        "tokens should have been minted"
+    );
+}
+
+#[test]
+fn test_incoming_fail_divergence() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let alice = TestWitness::ed25519("alice");
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge_ex(
+        &mut ctx,
+        vec![alice.pk().into(), bob.pk().into(), charlie.pk().into()],
+    );
+
+    // Witness Bob witnesses the remote event.
+    let tx = transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Release".to_owned(),
+            body: cbor::to_value(bob.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(bob.pk(), 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    ctx.with_tx(tx, |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body.clone()).unwrap())
+            .expect("release should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // Witness Charlie witnesses a diverging event.
+    let tx = transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: "bridge.Release".to_owned(),
+            body: cbor::to_value(charlie.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(2_000.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(charlie.pk(), 0)],
+            fee: transaction::Fee {
                 amount: Default::default(),
                 gas: 1000,
             },
@@ -486,9 +9369,47 @@ fn test_incoming_fail_divergence() {
         Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body.clone()).unwrap())
             .expect("release should succeed");
 
-        let (_tags, _messages) = tx_ctx.commit();
+        let (tags, _messages) = tx_ctx.commit();
+        let divergence_key = [MODULE_NAME.as_bytes(), &28u32.to_be_bytes()].concat();
+        assert!(
+            tags.iter().any(|tag| tag.key == divergence_key),
+            "divergence detected event should be emitted"
+        );
     });
 
+    // Both proposed operations are pending together with their voters.
+    let info = Bridge::query_in_operation(
+        &mut ctx,
+        InOperationQuery {
+            denomination: "oETH".parse().unwrap(),
+            id: 0,
+        },
+    )
+    .expect("incoming operation query should succeed");
+    assert_eq!(info.signers.iter().collect::<Vec<_>>(), vec![1, 2]);
+    let mut voters: Vec<Vec<u16>> = info
+        .ops
+        .values()
+        .map(|op_sigs| op_sigs.signers.iter().collect())
+        .collect();
+    voters.sort();
+    assert_eq!(voters, vec![vec![1], vec![2]]);
+    let pending = Bridge::query_pending_incoming(&mut ctx, Default::default())
+        .expect("pending incoming query should succeed");
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, 0);
+    assert_eq!(pending[0].votes, 2);
+    assert_eq!(pending[0].proposals, 2);
+    let page = Bridge::query_pending_incoming(
+        &mut ctx,
+        PendingIncomingQuery {
+            offset: 1,
+            limit: 0,
+        },
+    )
+    .expect("pending incoming query should succeed");
+    assert!(page.is_empty());
+
     // Check source account balances.
     let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
         .expect("get_balances should succeed");
@@ -516,14 +9437,24 @@ fn test_incoming_fail_divergence() {
         version: 1,
         call: transaction::Call {
             method: "bridge.Release".to_owned(),
-            body: cbor::to_value(Release {
-                id: 0,
-                target: keys::alice::address(),
-                amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
-            }),
+            body: cbor::to_value(alice.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(1_000.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
         },
         auth_info: transaction::AuthInfo {
-            signer_info: vec![transaction::SignerInfo::new(keys::alice::pk(), 0)],
+            signer_info: vec![transaction::SignerInfo::new(alice.pk(), 0)],
             fee: transaction::Fee {
                 amount: Default::default(),
                 gas: 1000,
@@ -569,6 +9500,647 @@ fn test_incoming_fail_divergence() {
         1,
         "there should be a single denomination"
     );
+
+    // The conflict is kept for later audit.
+    let conflicts = Bridge::query_conflicts(&mut ctx, ()).expect("conflicts query should succeed");
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].id, 0);
+    let mut votes: Vec<_> = conflicts[0].votes.values().cloned().collect();
+    votes.sort_by_key(|witnesses| witnesses.len());
+    assert_eq!(votes[0], vec![WitnessKey::PublicKey(charlie.pk())]);
+    assert_eq!(
+        votes[1],
+        vec![
+            WitnessKey::PublicKey(bob.pk()),
+            WitnessKey::PublicKey(alice.pk())
+        ]
+    );
+}
+
+#[test]
+fn test_incoming_divergence_slashing() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let witnesses = vec![
+        TestWitness::ed25519("alice"),
+        TestWitness::ed25519("bob"),
+        TestWitness::ed25519("charlie"),
+    ];
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        witness_bond: Some(BaseUnits::new(100.into(), Denomination::NATIVE)),
+        divergence_penalty: 2_500,
+        ..init_bridge_ex(&mut ctx, witnesses.iter().map(|w| w.pk().into()).collect())
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+    assert!(
+        Parameters {
+            divergence_penalty: 10_001,
+            ..params.clone()
+        }
+        .validate_basic()
+        .is_err(),
+        "penalties above the whole bond should be rejected"
+    );
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // All witnesses post a bond.
+    for witness in &witnesses {
+        Accounts::transfer(
+            &mut ctx,
+            keys::alice::address(),
+            Address::from_pk(&witness.pk()),
+            &BaseUnits::new(100.into(), Denomination::NATIVE),
+        )
+        .expect("transfer should succeed");
+        ctx.with_tx(
+            make_tx(
+                witness.pk(),
+                "bridge.Bond",
+                cbor::to_value(Bond {
+                    amount: BaseUnits::new(100.into(), Denomination::NATIVE),
+                }),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_bond(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("bond should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+
+    // Bob witnesses a diverging event, the others witness the correct event.
+    for (index, amount) in &[(1, 2_000), (2, 1_000), (0, 1_000)] {
+        let witness = &witnesses[*index];
+        let release = witness.sign_release(
+            &params,
+            Release {
+                id: 0,
+                target: keys::alice::address(),
+                amount: BaseUnits::new((*amount).into(), "oETH".parse().unwrap()),
+                tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                log_index: Some(0),
+                batch: vec![],
+                claims_root: None,
+                remote_chain_id: None,
+                signature: None,
+                nonce: Some(0),
+            },
+        );
+        ctx.with_tx(
+            make_tx(witness.pk(), "bridge.Release", cbor::to_value(release)),
+            |mut tx_ctx, call| {
+                Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("release should succeed");
+
+                let (tags, _messages) = tx_ctx.commit();
+                let slashed_key = [MODULE_NAME.as_bytes(), &12u32.to_be_bytes()].concat();
+                assert_eq!(
+                    tags.iter().filter(|tag| tag.key == slashed_key).count(),
+                    if *index == 0 { 1 } else { 0 },
+                    "slashing should happen when the sequence is finalized"
+                );
+            },
+        );
+    }
+
+    let bonds = Bridge::query_witness_bonds(&mut ctx, ()).expect("bonds query should succeed");
+    assert_eq!(bonds[0].as_ref().unwrap().amount.amount(), &100.into());
+    assert_eq!(
+        bonds[1].as_ref().unwrap().amount.amount(),
+        &75.into(),
+        "diverging witness should be slashed"
+    );
+    assert_eq!(bonds[2].as_ref().unwrap().amount.amount(), &100.into());
+
+    let bals = Accounts::get_balances(ctx.runtime_state(), *ADDRESS_WITNESS_BONDS)
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&Denomination::NATIVE], 275.into());
+}
+
+#[test]
+fn test_incoming_divergence_shrunk_witness_set() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let witnesses = vec![
+        TestWitness::ed25519("alice"),
+        TestWitness::ed25519("bob"),
+        TestWitness::ed25519("charlie"),
+        TestWitness::ed25519("dave"),
+    ];
+
+    init_accounts(&mut ctx);
+    let params = Parameters {
+        divergence_penalty: 2_500,
+        ..init_bridge_ex(&mut ctx, witnesses.iter().map(|w| w.pk().into()).collect())
+    };
+    Bridge::set_params(ctx.runtime_state(), &params);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let release_tx = |witness: &TestWitness, amount: u64| {
+        make_tx(
+            witness.pk(),
+            "bridge.Release",
+            cbor::to_value(witness.sign_release(
+                &params,
+                Release {
+                    id: 0,
+                    target: keys::alice::address(),
+                    amount: BaseUnits::new(amount.into(), "oETH".parse().unwrap()),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(0),
+                },
+            )),
+        )
+    };
+
+    // Dave witnesses a diverging event.
+    ctx.with_tx(release_tx(&witnesses[3], 2_000), |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("release should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+
+    // The witness set shrinks while the diverging vote is pending.
+    let shrunk = Parameters {
+        witnesses: params.witnesses[..3].to_vec(),
+        ..params.clone()
+    };
+    Bridge::set_params(ctx.runtime_state(), &shrunk);
+
+    // The remaining witnesses finalize the correct event, skipping the signer that left.
+    for witness in &witnesses[..2] {
+        ctx.with_tx(release_tx(witness, 1_000), |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (tags, _messages) = tx_ctx.commit();
+            let slashed_key = [MODULE_NAME.as_bytes(), &12u32.to_be_bytes()].concat();
+            assert!(
+                !tags.iter().any(|tag| tag.key == slashed_key),
+                "signers outside the witness set should not be slashed"
+            );
+        });
+    }
+
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&"oETH".parse().unwrap()], 1_000.into());
+    let sequences = Bridge::query_next_sequence_numbers(&mut ctx, None)
+        .expect("next sequence numbers query should succeed");
+    assert_eq!(sequences.incoming[&"oETH".parse().unwrap()], 1);
+}
+
+#[test]
+fn test_incoming_retract_vote() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let release = |amount: u64| Release {
+        id: 0,
+        target: keys::alice::address(),
+        amount: BaseUnits::new(amount.into(), "oETH".parse().unwrap()),
+        tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+        log_index: Some(0),
+        batch: vec![],
+        claims_root: None,
+        remote_chain_id: None,
+        signature: None,
+        nonce: None,
+    };
+    let release_tx = |witness: &TestWitness, amount: u64, nonce: u64| {
+        make_tx(
+            witness.pk(),
+            "bridge.Release",
+            cbor::to_value(witness.sign_release(
+                &params,
+                Release {
+                    nonce: Some(nonce),
+                    ..release(amount)
+                },
+            )),
+        )
+    };
+    let retract_tx = |witness: &TestWitness, id: u64| {
+        make_tx(
+            witness.pk(),
+            "bridge.RetractVote",
+            cbor::to_value(RetractVote {
+                denomination: "oETH".parse().unwrap(),
+                id,
+            }),
+        )
+    };
+
+    // Witness Bob votes for the wrong amount.
+    ctx.with_tx(release_tx(&bob, 2_000, 0), |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("release should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    ctx.with_tx(release_tx(&bob, 1_000, 1), |mut tx_ctx, call| {
+        let result = Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::AlreadySubmittedSignature)));
+    });
+
+    // Only pending votes can be retracted.
+    ctx.with_tx(retract_tx(&charlie, 0), |mut tx_ctx, call| {
+        let result = Bridge::tx_retract_vote(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidArgument)));
+    });
+    ctx.with_tx(retract_tx(&bob, 1), |mut tx_ctx, call| {
+        let result = Bridge::tx_retract_vote(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidSequenceNumber)));
+    });
+
+    // Witness Bob retracts the vote and votes again.
+    ctx.with_tx(retract_tx(&bob, 0), |mut tx_ctx, call| {
+        Bridge::tx_retract_vote(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("retracting a vote should succeed");
+
+        let (tags, _messages) = tx_ctx.commit();
+        let retracted_key = [MODULE_NAME.as_bytes(), &15u32.to_be_bytes()].concat();
+        assert!(
+            tags.iter().any(|tag| tag.key == retracted_key),
+            "vote retracted event should be emitted"
+        );
+    });
+    ctx.with_tx(retract_tx(&bob, 0), |mut tx_ctx, call| {
+        let result = Bridge::tx_retract_vote(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+        assert!(matches!(result, Err(Error::InvalidArgument)));
+    });
+    for (witness, nonce) in &[(&bob, 1), (&charlie, 0)] {
+        ctx.with_tx(release_tx(witness, 1_000, *nonce), |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert_eq!(
+        bals.balances[&"oETH".parse().unwrap()],
+        1_000.into(),
+        "only the correct amount should have been released"
+    );
+
+    // The retracted vote is not evidence of equivocation.
+    let signed = |amount: u64| SignedOperation {
+        signature: bob
+            .sign_release(&params, release(amount))
+            .signature
+            .unwrap(),
+        op: Operation::Release(release(amount)),
+    };
+    ctx.with_tx(
+        make_tx(
+            keys::alice::pk(),
+            "bridge.SubmitEvidence",
+            cbor::to_value(SubmitEvidence {
+                witness: bob.pk().into(),
+                kind: EvidenceKind::Release,
+                id: 0,
+                first: signed(2_000),
+                second: signed(1_000),
+            }),
+        ),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_submit_evidence(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::InvalidArgument)));
+        },
+    );
+}
+
+#[test]
+fn test_remote_reserve() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+    let oeth = |amount: u64| BaseUnits::new(amount.into(), "oETH".parse().unwrap());
+    let attest_tx = |signer: PublicKey, amount: BaseUnits| {
+        make_tx(
+            signer,
+            "bridge.AttestRemoteLocked",
+            cbor::to_value(AttestRemoteLocked { amount }),
+        )
+    };
+    let release_tx = |witness: &TestWitness, id: u64, amount: u64, nonce: u64| {
+        make_tx(
+            witness.pk(),
+            "bridge.Release",
+            cbor::to_value(witness.sign_release(
+                &params,
+                Release {
+                    id,
+                    target: keys::alice::address(),
+                    amount: oeth(amount),
+                    tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), id)),
+                    log_index: Some(0),
+                    batch: vec![],
+                    claims_root: None,
+                    remote_chain_id: None,
+                    signature: None,
+                    nonce: Some(nonce),
+                },
+            )),
+        )
+    };
+
+    // Only witnesses can attest to remote denominations.
+    ctx.with_tx(
+        attest_tx(keys::alice::pk(), oeth(1_000)),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_attest_remote_locked(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::NotAuthorized)));
+        },
+    );
+    ctx.with_tx(
+        attest_tx(bob.pk(), BaseUnits::new(1_000.into(), Denomination::NATIVE)),
+        |mut tx_ctx, call| {
+            let result =
+                Bridge::tx_attest_remote_locked(&mut tx_ctx, cbor::from_value(call.body).unwrap());
+            assert!(matches!(result, Err(Error::UnsupportedDenomination)));
+        },
+    );
+
+    // The reserve is only recorded once a threshold of witnesses attested to it.
+    let attested_key = [MODULE_NAME.as_bytes(), &18u32.to_be_bytes()].concat();
+    ctx.with_tx(attest_tx(bob.pk(), oeth(1_000)), |mut tx_ctx, call| {
+        Bridge::tx_attest_remote_locked(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("attestation should succeed");
+
+        let (tags, _messages) = tx_ctx.commit();
+        assert!(!tags.iter().any(|tag| tag.key == attested_key));
+    });
+    ctx.with_tx(attest_tx(charlie.pk(), oeth(500)), |mut tx_ctx, call| {
+        Bridge::tx_attest_remote_locked(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("attestation should succeed");
+
+        let (tags, _messages) = tx_ctx.commit();
+        assert!(
+            tags.iter().any(|tag| tag.key == attested_key),
+            "remote locked attested event should be emitted"
+        );
+    });
+    let reserves =
+        Bridge::query_remote_reserves(&mut ctx, ()).expect("remote reserves query should succeed");
+    let reserve = &reserves[&"oETH".parse().unwrap()];
+    assert_eq!(reserve.locked, Some(500.into()));
+    assert_eq!(reserve.minted, 0.into());
+
+    // Releases are allowed as long as the minted supply stays within the reserve.
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(release_tx(witness, 0, 400, 0), |mut tx_ctx, call| {
+            Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("release should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        });
+    }
+    ctx.with_tx(release_tx(&bob, 1, 200, 1), |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("release should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    // Releases exceeding the reserve are left pending.
+    ctx.with_tx(release_tx(&charlie, 1, 200, 1), |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("release should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    let reserves =
+        Bridge::query_remote_reserves(&mut ctx, ()).expect("remote reserves query should succeed");
+    assert_eq!(reserves[&"oETH".parse().unwrap()].minted, 400.into());
+
+    // Once the witnesses attest to a larger reserve, the release goes through when voting again.
+    ctx.with_tx(attest_tx(charlie.pk(), oeth(1_000)), |mut tx_ctx, call| {
+        Bridge::tx_attest_remote_locked(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("attestation should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    ctx.with_tx(
+        make_tx(
+            charlie.pk(),
+            "bridge.RetractVote",
+            cbor::to_value(RetractVote {
+                denomination: "oETH".parse().unwrap(),
+                id: 1,
+            }),
+        ),
+        |mut tx_ctx, call| {
+            Bridge::tx_retract_vote(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("retracting should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+    ctx.with_tx(release_tx(&charlie, 1, 200, 2), |mut tx_ctx, call| {
+        Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+            .expect("release should succeed");
+
+        let (_tags, _messages) = tx_ctx.commit();
+    });
+    let reserves =
+        Bridge::query_remote_reserves(&mut ctx, ()).expect("remote reserves query should succeed");
+    let reserve = &reserves[&"oETH".parse().unwrap()];
+    assert_eq!(reserve.locked, Some(1_000.into()));
+    assert_eq!(reserve.minted, 600.into());
+
+    let bals = Accounts::get_balances(ctx.runtime_state(), keys::alice::address())
+        .expect("get_balances should succeed");
+    assert_eq!(bals.balances[&"oETH".parse().unwrap()], 600.into());
+}
+
+#[test]
+fn test_stats() {
+    let mut mock = mock::Mock::default();
+    let mut ctx = mock.create_ctx();
+
+    let bob = TestWitness::ed25519("bob");
+    let charlie = TestWitness::ed25519("charlie");
+
+    init_accounts(&mut ctx);
+    let params = init_bridge(&mut ctx);
+
+    let make_tx = |signer: PublicKey, method: &str, body: cbor::Value| transaction::Transaction {
+        version: 1,
+        call: transaction::Call {
+            method: method.to_owned(),
+            body,
+        },
+        auth_info: transaction::AuthInfo {
+            signer_info: vec![transaction::SignerInfo::new(signer, 0)],
+            fee: transaction::Fee {
+                amount: Default::default(),
+                gas: 1000,
+            },
+        },
+    };
+
+    // User Alice locks an amount.
+    let lock = Lock {
+        target: "0000000000000000000000000000000000000000".into(),
+        amount: BaseUnits::new(1_000.into(), Denomination::NATIVE),
+        remote_chain_id: None,
+        attestation: None,
+    };
+    let op = Operation::Lock(lock.clone());
+    ctx.with_tx(
+        make_tx(keys::alice::pk(), "bridge.Lock", cbor::to_value(lock)),
+        |mut tx_ctx, call| {
+            Bridge::tx_lock(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                .expect("lock should succeed");
+
+            let (_tags, _messages) = tx_ctx.commit();
+        },
+    );
+
+    let stats = Bridge::query_stats(&mut ctx, ()).expect("stats query should succeed");
+    assert_eq!(stats.locks, 1);
+    assert_eq!(stats.pending_locks, 1);
+    assert_eq!(stats.finalized_locks, 0);
+    assert_eq!(stats.locked[&Denomination::NATIVE], 1_000.into());
+
+    // Witnesses finalize the lock and release an incoming amount.
+    for witness in &[&bob, &charlie] {
+        ctx.with_tx(
+            make_tx(
+                witness.pk(),
+                "bridge.Witness",
+                cbor::to_value(Witness {
+                    denomination: Denomination::NATIVE,
+                    id: 0,
+                    signature: witness.sign(&params, 0, &op),
+                    nonce: 0,
+                }),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_witness(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("witness should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+        ctx.with_tx(
+            make_tx(
+                witness.pk(),
+                "bridge.Release",
+                cbor::to_value(witness.sign_release(
+                    &params,
+                    Release {
+                        id: 0,
+                        target: keys::alice::address(),
+                        amount: BaseUnits::new(500.into(), "oETH".parse().unwrap()),
+                        tx_hash: Some(deposit_tx_hash(&"oETH".parse().unwrap(), 0)),
+                        log_index: Some(0),
+                        batch: vec![],
+                        claims_root: None,
+                        remote_chain_id: None,
+                        signature: None,
+                        nonce: Some(0),
+                    },
+                )),
+            ),
+            |mut tx_ctx, call| {
+                Bridge::tx_release(&mut tx_ctx, cbor::from_value(call.body).unwrap())
+                    .expect("release should succeed");
+
+                let (_tags, _messages) = tx_ctx.commit();
+            },
+        );
+    }
+
+    let stats = Bridge::query_stats(&mut ctx, ()).expect("stats query should succeed");
+    assert_eq!(stats.locks, 1);
+    assert_eq!(stats.pending_locks, 0);
+    assert_eq!(stats.finalized_locks, 1);
+    assert_eq!(stats.releases, 1);
+    assert_eq!(stats.released[&"oETH".parse().unwrap()], 500.into());
 }
 
 #[test]
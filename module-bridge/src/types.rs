@@ -1,15 +1,20 @@
 //! Bridge module types.
-use std::{collections::BTreeMap, fmt};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use oasis_runtime_sdk::{
-    core::common::{cbor, crypto::hash::Hash},
-    crypto::signature::Signature,
+    core::common::{cbor, crypto::hash::Hash, quantity::Quantity},
+    crypto::signature::{PublicKey, Signature},
     types::{address::Address, token},
 };
 
+use crate::crypto;
+
 /// Remote denomination identifier.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -22,43 +27,117 @@ impl RemoteDenomination {
     // TODO: Enforce maximum length during deserialization.
 }
 
+impl AsRef<[u8]> for RemoteDenomination {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 impl From<&str> for RemoteDenomination {
     fn from(v: &str) -> RemoteDenomination {
         RemoteDenomination(hex::decode(v.as_bytes()).unwrap())
     }
 }
 
-const REMOTE_ADDRESS_SIZE: usize = 20;
-
 /// Remote address-related error.
 #[derive(Error, Debug)]
 pub enum RemoteAddressError {
     #[error("malformed address")]
     MalformedAddress,
+    #[error("invalid address checksum")]
+    InvalidChecksum,
 }
 
-/// Remote address.
-#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct RemoteAddress([u8; REMOTE_ADDRESS_SIZE]);
+/// Remote address of up to `RemoteAddress::MAX_SIZE` bytes.
+///
+/// Bytes past the size of the address are always zero.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RemoteAddress {
+    size: u8,
+    data: [u8; RemoteAddress::MAX_SIZE],
+}
 
 impl RemoteAddress {
+    /// Size of Ethereum addresses.
+    pub const DEFAULT_SIZE: usize = 20;
+    /// Maximum size of a remote address.
+    pub const MAX_SIZE: usize = 32;
+
     /// Tries to create a new remote address from raw bytes.
     pub fn from_bytes(data: &[u8]) -> Result<Self, RemoteAddressError> {
-        if data.len() != REMOTE_ADDRESS_SIZE {
+        if data.is_empty() || data.len() > Self::MAX_SIZE {
             return Err(RemoteAddressError::MalformedAddress);
         }
 
-        let mut a = [0; REMOTE_ADDRESS_SIZE];
-        a.copy_from_slice(data);
+        let mut a = [0; Self::MAX_SIZE];
+        a[..data.len()].copy_from_slice(data);
 
-        Ok(RemoteAddress(a))
+        Ok(RemoteAddress {
+            size: data.len() as u8,
+            data: a,
+        })
     }
 
-    /// Tries to create a new remote address from hex-encoded string.
+    /// Size of the address in bytes.
+    pub fn size(&self) -> usize {
+        self.size as usize
+    }
+
+    /// Tries to create a new remote address from hex-encoded string, optionally prefixed with
+    /// `0x`.
+    ///
+    /// Mixed-case strings must carry a valid EIP-55 checksum, while all-lowercase and
+    /// all-uppercase strings are accepted without one.
     pub fn from_hex(data: &str) -> Result<Self, RemoteAddressError> {
-        let data =
-            hex::decode(data.as_bytes()).map_err(|_| RemoteAddressError::MalformedAddress)?;
-        RemoteAddress::from_bytes(&data)
+        let digits = data.strip_prefix("0x").unwrap_or(data);
+        let raw =
+            hex::decode(digits.as_bytes()).map_err(|_| RemoteAddressError::MalformedAddress)?;
+        let address = RemoteAddress::from_bytes(&raw)?;
+
+        let has_lowercase = digits.chars().any(|c| c.is_ascii_lowercase());
+        let has_uppercase = digits.chars().any(|c| c.is_ascii_uppercase());
+        if has_lowercase && has_uppercase && address.to_checksum_hex() != digits {
+            return Err(RemoteAddressError::InvalidChecksum);
+        }
+
+        Ok(address)
+    }
+
+    /// Hex encoding of the address with the EIP-55 mixed-case checksum.
+    pub fn to_checksum_hex(&self) -> String {
+        let encoded = hex::encode(self.as_ref());
+        let digest = crypto::keccak256(&[encoded.as_bytes()]);
+        encoded
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let nibble = if i % 2 == 0 {
+                    digest[i / 2] >> 4
+                } else {
+                    digest[i / 2] & 0x0f
+                };
+                if nibble >= 8 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for RemoteAddress {
+    fn default() -> Self {
+        RemoteAddress {
+            size: Self::DEFAULT_SIZE as u8,
+            data: [0; Self::MAX_SIZE],
+        }
+    }
+}
+
+impl AsRef<[u8]> for RemoteAddress {
+    fn as_ref(&self) -> &[u8] {
+        &self.data[..self.size()]
     }
 }
 
@@ -70,7 +149,7 @@ impl From<&str> for RemoteAddress {
 
 impl fmt::LowerHex for RemoteAddress {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for i in &self.0[..] {
+        for i in self.as_ref() {
             write!(f, "{:02x}", i)?;
         }
         Ok(())
@@ -85,7 +164,7 @@ impl fmt::Debug for RemoteAddress {
 
 impl fmt::Display for RemoteAddress {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::LowerHex::fmt(self, f)
+        f.write_str(&self.to_checksum_hex())
     }
 }
 
@@ -96,9 +175,9 @@ impl serde::Serialize for RemoteAddress {
     {
         let is_human_readable = serializer.is_human_readable();
         if is_human_readable {
-            serializer.serialize_str(&hex::encode(&self.0))
+            serializer.serialize_str(&self.to_checksum_hex())
         } else {
-            serializer.serialize_bytes(&self.0)
+            serializer.serialize_bytes(self.as_ref())
         }
     }
 }
@@ -140,6 +219,125 @@ impl<'de> serde::Deserialize<'de> for RemoteAddress {
     }
 }
 
+/// Signer of a multisig witness.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MultisigSigner {
+    #[serde(rename = "public_key")]
+    pub public_key: PublicKey,
+
+    #[serde(rename = "weight")]
+    pub weight: u64,
+}
+
+/// Configuration of a multisig witness.
+///
+/// Any of the signers can submit transactions on behalf of the witness, but payload signatures
+/// must be made by signers whose weights add up to at least the threshold.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MultisigConfig {
+    #[serde(rename = "signers")]
+    pub signers: Vec<MultisigSigner>,
+
+    #[serde(rename = "threshold")]
+    pub threshold: u64,
+}
+
+/// Signatures by the signers of a multisig witness, ordered as the signers in the configuration
+/// with missing signatures left empty.
+///
+/// Multisig witnesses submit the CBOR encoding of the signature set as their payload signature.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MultisigSignature(pub Vec<Option<Signature>>);
+
+impl MultisigSignature {
+    /// Decode a multisig signature set from a payload signature.
+    pub fn from_signature(signature: &Signature) -> Option<Self> {
+        cbor::from_slice(signature.as_ref()).ok()
+    }
+
+    /// Encode the multisig signature set as a payload signature.
+    pub fn to_signature(&self) -> Signature {
+        cbor::to_vec(self).into()
+    }
+}
+
+/// Witness in the witness set.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WitnessKey {
+    /// Witness using a single key.
+    PublicKey(PublicKey),
+
+    /// Witness operated by a group of signers with an internal m-of-n policy.
+    Multisig(MultisigConfig),
+}
+
+impl WitnessKey {
+    /// Public key of a single-key witness.
+    pub fn public_key(&self) -> Option<&PublicKey> {
+        match self {
+            WitnessKey::PublicKey(pk) => Some(pk),
+            WitnessKey::Multisig(_) => None,
+        }
+    }
+
+    /// Identifier of the witness, given by the hash of its canonical encoding.
+    pub fn id(&self) -> Hash {
+        Hash::digest_bytes(&cbor::to_vec(&cbor::to_value(self)))
+    }
+
+    /// Whether transactions from the given address can act on behalf of the witness.
+    pub fn is_authorized(&self, address: &Address) -> bool {
+        match self {
+            WitnessKey::PublicKey(pk) => &Address::from_pk(pk) == address,
+            WitnessKey::Multisig(config) => config
+                .signers
+                .iter()
+                .any(|signer| &Address::from_pk(&signer.public_key) == address),
+        }
+    }
+}
+
+impl From<PublicKey> for WitnessKey {
+    fn from(pk: PublicKey) -> Self {
+        WitnessKey::PublicKey(pk)
+    }
+}
+
+/// Encoding of the messages that witnesses sign for outgoing operations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MessageEncoding {
+    /// Canonical CBOR encoding of the operation, see `crypto::witness_message`.
+    #[serde(rename = "cbor")]
+    Cbor,
+
+    /// ABI encoding of the `(contract, id, token, recipient, amount)` tuple expected by the remote
+    /// bridge contract, see `crypto::abi_message`.
+    #[serde(rename = "abi")]
+    Abi,
+}
+
+impl Default for MessageEncoding {
+    fn default() -> Self {
+        MessageEncoding::Cbor
+    }
+}
+
+/// EIP-712 signing domain of the remote bridge contract.
+///
+/// The chain identifier and salt of the domain are the remote chain identifier and the runtime
+/// identifier configured in the bridge parameters.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Eip712Domain {
+    /// Address of the remote contract verifying the signatures.
+    #[serde(rename = "verifying_contract")]
+    pub verifying_contract: RemoteAddress,
+}
+
 /// Lock call.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -149,6 +347,34 @@ pub struct Lock {
 
     #[serde(rename = "amount")]
     pub amount: token::BaseUnits,
+
+    /// Remote chain that the lock targets, which must be the configured remote chain if given.
+    /// Witness signatures commit to the remote chain, so it is never part of the lock operation
+    /// itself.
+    #[serde(rename = "remote_chain_id")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_chain_id: Option<u64>,
+
+    /// Attestation over the caller address. It is required when a lock attester is configured
+    /// and is never part of the lock operation itself.
+    #[serde(rename = "attestation")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attestation: Option<LockAttestation>,
+}
+
+/// Attestation by the lock attester that an address may lock funds, e.g. after passing a KYC
+/// check.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LockAttestation {
+    /// Round after which the attestation is no longer accepted.
+    #[serde(rename = "expiration")]
+    pub expiration: u64,
+
+    #[serde(rename = "sig")]
+    pub signature: Signature,
 }
 
 /// Lock call results.
@@ -157,17 +383,125 @@ pub struct Lock {
 pub struct LockResult {
     #[serde(rename = "id")]
     pub id: u64,
+
+    /// Fee deducted from the locked amount.
+    #[serde(rename = "fee")]
+    pub fee: token::BaseUnits,
+}
+
+/// Lock estimation query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EstimateLockQuery {
+    /// Amount to lock, including the lock fee.
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+
+    /// Account that would lock the amount. Account limits are only checked when set.
+    #[serde(rename = "owner")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Address>,
+}
+
+/// Estimated outcome of a lock.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LockEstimate {
+    /// Sequence number that the next lock of the denomination is assigned.
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    /// Amount that would be bridged, net of the lock fee.
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+
+    /// Fee that would be deducted from the locked amount.
+    #[serde(rename = "fee")]
+    pub fee: token::BaseUnits,
+
+    /// Code of the error that the lock would currently fail with, if any.
+    #[serde(rename = "error_code")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<u32>,
+}
+
+/// Rate limit quota query.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitQuotaQuery {
+    /// Account to also return the remaining account limit quota of.
+    #[serde(rename = "account")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<Address>,
+}
+
+/// Remaining rate limit quota available to operations in the next round.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitQuota {
+    /// Round at the end of which the current rate limit window ends and the quota is reset.
+    #[serde(rename = "window_end")]
+    #[serde(default)]
+    pub window_end: u64,
+
+    /// Remaining amount that can be locked per rate limited denomination.
+    #[serde(rename = "remaining")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub remaining: BTreeMap<token::Denomination, Quantity>,
+
+    /// Remaining amount that the account can lock or receive per denomination with an account
+    /// limit.
+    #[serde(rename = "account_remaining")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub account_remaining: BTreeMap<token::Denomination, Quantity>,
 }
 
 /// Witness event call.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Witness {
+    /// Denomination of the outgoing operation, which determines its sequence space.
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
     #[serde(rename = "id")]
     pub id: u64,
 
     #[serde(rename = "sig")]
     pub signature: Signature,
+
+    /// Outgoing vote nonce of the witness, which must match the next expected nonce.
+    #[serde(rename = "nonce")]
+    pub nonce: u64,
+}
+
+/// Witness batch call.
+///
+/// Submits several witness votes in a single transaction, for example when catching up on pending
+/// outgoing operations after downtime.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WitnessBatch {
+    /// Votes in the order in which they are processed. Their nonces must be consecutive.
+    #[serde(rename = "votes")]
+    pub votes: Vec<Witness>,
+}
+
+/// Set witness signing key call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetWitnessSigningKey {
+    /// Key used to sign witness payloads. When not set, payloads must again be signed using the
+    /// witness' own key.
+    #[serde(rename = "key")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<PublicKey>,
 }
 
 /// Release call.
@@ -182,84 +516,2104 @@ pub struct Release {
 
     #[serde(rename = "amount")]
     pub amount: token::BaseUnits,
+
+    /// Hash of the remote transaction that made the deposit. Required when voting, as releases of
+    /// a remote deposit are only ever processed once.
+    #[serde(rename = "tx_hash")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<Hash>,
+
+    /// Index of the deposit's log entry within the remote transaction. Required when voting.
+    #[serde(rename = "log_index")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_index: Option<u64>,
+
+    /// Further transfers released atomically together with the primary one under the same
+    /// sequence number. All entries must be of the same denomination as the primary amount.
+    #[serde(rename = "batch")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub batch: Vec<ReleaseEntry>,
+
+    /// Root of a Merkle tree of claims (see `crypto::claims_root`) that the release commits to.
+    ///
+    /// Such releases pay the total amount of all claims into the pending claims account, from
+    /// which each claim is then paid out individually through a `bridge.Claim` call.
+    #[serde(rename = "claims_root")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claims_root: Option<Hash>,
+
+    /// Remote chain that the deposit was made on, which must be the configured remote chain if
+    /// given. Like the signature, it is never part of the release operation itself.
+    #[serde(rename = "remote_chain_id")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_chain_id: Option<u64>,
+
+    /// Witness signature over the release. It is required when submitting a release and is
+    /// never part of the release operation itself.
+    #[serde(rename = "sig")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Signature>,
+
+    /// Incoming vote nonce of the witness, which must match the next expected nonce. Like the
+    /// signature, it is required when submitting a release and is never part of the release
+    /// operation itself.
+    #[serde(rename = "nonce")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+}
+
+impl Release {
+    /// Transfers made by the release, starting with the primary one.
+    pub fn entries(&self) -> Vec<ReleaseEntry> {
+        batch_entries(self.target, &self.amount, &self.batch)
+    }
+
+    /// Total amount released, or `None` if the batch mixes denominations or the sum overflows.
+    pub fn total_amount(&self) -> Option<token::BaseUnits> {
+        batch_total(&self.amount, &self.batch)
+    }
+}
+
+/// Single transfer of a batch release.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReleaseEntry {
+    #[serde(rename = "target")]
+    pub target: Address,
+
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
 }
 
-/// Operation.
+/// Primary transfer of a release followed by its batch entries.
+fn batch_entries(
+    target: Address,
+    amount: &token::BaseUnits,
+    batch: &[ReleaseEntry],
+) -> Vec<ReleaseEntry> {
+    let primary = ReleaseEntry {
+        target,
+        amount: amount.clone(),
+    };
+    std::iter::once(primary)
+        .chain(batch.iter().cloned())
+        .collect()
+}
+
+/// Sum of the primary amount of a release and its batch entries.
+fn batch_total(amount: &token::BaseUnits, batch: &[ReleaseEntry]) -> Option<token::BaseUnits> {
+    let total = batch
+        .iter()
+        .try_fold(amount.amount().clone(), |sum, entry| {
+            if entry.amount.denomination() != amount.denomination() {
+                return None;
+            }
+            sum.checked_add(entry.amount.amount())
+        })?;
+    Some(token::BaseUnits::new(total, amount.denomination().clone()))
+}
+
+/// Claim call.
+///
+/// Pays out a single claim committed to by the claims root of a finalized release. Anyone can
+/// submit the claim, the funds always go to its target.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub enum Operation {
-    #[serde(rename = "lock")]
-    Lock(Lock),
+pub struct Claim {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
 
-    #[serde(rename = "release")]
-    Release(Release),
+    /// Incoming sequence number of the release that committed to the claim.
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    /// Index of the claim within the claims tree.
+    #[serde(rename = "index")]
+    pub index: u64,
+
+    #[serde(rename = "target")]
+    pub target: Address,
+
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+
+    /// Sibling hashes on the path from the claim to the claims root, starting at the leaf.
+    #[serde(rename = "proof")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub proof: Vec<Hash>,
 }
 
-/// A unique operation identifier.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
-pub struct OperationId(Hash);
+/// Claims committed to by a finalized release.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClaimsRoot {
+    #[serde(rename = "root")]
+    pub root: Hash,
 
-impl From<&Operation> for OperationId {
-    fn from(op: &Operation) -> OperationId {
-        OperationId(Hash::digest_bytes(&cbor::to_vec(op)))
-    }
+    /// Amount that has not been claimed yet.
+    #[serde(rename = "remaining")]
+    pub remaining: token::BaseUnits,
 }
 
-/// Witness signatures.
+/// Claim balance call.
+///
+/// Withdraws the given amount from the balance that pull releases credited to the caller.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct WitnessSignatures {
+pub struct ClaimBalance {
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+}
+
+/// Retract vote call.
+///
+/// Withdraws the caller's vote for the pending incoming operation with the given sequence number
+/// in the sequence space of the given denomination so that the witness can vote again.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetractVote {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
     #[serde(rename = "id")]
     pub id: u64,
+}
 
-    #[serde(rename = "op")]
-    pub op: Operation,
+/// Metadata published by a witness.
+///
+/// This is also the body of the set witness metadata call, where empty metadata clears any
+/// previously published metadata.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WitnessMetadata {
+    /// Display name of the witness operator.
+    #[serde(rename = "name")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub name: String,
 
-    #[serde(rename = "wits")]
+    /// Contact endpoint of the witness operator, e.g. an e-mail address or URL.
+    #[serde(rename = "endpoint")]
     #[serde(default)]
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub witnesses: Vec<u16>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub endpoint: String,
 
-    #[serde(rename = "sigs")]
+    /// Address on the remote chain that the witness is paid out to.
+    #[serde(rename = "payout_address")]
     #[serde(default)]
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub signatures: Vec<Signature>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payout_address: Option<RemoteAddress>,
 }
 
-impl WitnessSignatures {
-    /// Create a new empty set of witness signatures.
-    pub fn new(id: u64, op: Operation) -> Self {
-        Self {
-            id,
-            op,
-            witnesses: Vec::new(),
-            signatures: Vec::new(),
-        }
+impl WitnessMetadata {
+    /// Whether no metadata is set.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
     }
 }
 
-/// Incoming witness signatures.
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+/// Witness in the witness set together with its published metadata.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct IncomingWitnessSignatures {
-    #[serde(rename = "ops")]
+pub struct WitnessInfo {
+    /// Index of the witness in the witness set of the parameters.
+    ///
+    /// Signatures of operations that were created with a snapshot of the witness set are indexed
+    /// by their position in that snapshot instead.
+    #[serde(rename = "index")]
     #[serde(default)]
-    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
-    pub ops: BTreeMap<OperationId, WitnessSignatures>,
+    pub index: u16,
 
-    #[serde(rename = "wits")]
+    #[serde(rename = "witness")]
+    pub witness: WitnessKey,
+
+    /// Whether the witness has not been marked inactive.
+    #[serde(rename = "active")]
     #[serde(default)]
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub witnesses: Vec<u16>,
+    pub active: bool,
+
+    #[serde(rename = "metadata")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "WitnessMetadata::is_empty")]
+    pub metadata: WitnessMetadata,
 }
 
-/// Next event sequence numbers.
+/// Rotate witness key call.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct NextSequenceNumbers {
+pub struct RotateWitnessKey {
+    #[serde(rename = "key")]
+    pub key: PublicKey,
+}
+
+/// Witness key that has been replaced by a key rotation.
+///
+/// The key remains valid for operations that were in flight at the time of the rotation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RotatedWitnessKey {
+    #[serde(rename = "key")]
+    pub key: PublicKey,
+
+    /// Key that replaced this key.
+    #[serde(rename = "successor")]
+    pub successor: PublicKey,
+
+    /// Next incoming sequence number of each denomination at the time of the rotation.
     #[serde(rename = "in")]
-    pub incoming: u64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub incoming: BTreeMap<token::Denomination, u64>,
 
+    /// Next outgoing sequence number of each denomination at the time of the rotation.
     #[serde(rename = "out")]
-    pub outgoing: u64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub outgoing: BTreeMap<token::Denomination, u64>,
+}
+
+/// Bond call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Bond {
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+}
+
+/// Unbond call.
+///
+/// Starts unbonding the given amount, if any, and withdraws all of the caller's unbonding amounts
+/// whose unbonding delay has passed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Unbond {
+    #[serde(rename = "amount")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<token::BaseUnits>,
+}
+
+/// Bond of a witness.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WitnessBond {
+    /// Account that posted the bond and that unbonded amounts are returned to.
+    #[serde(rename = "owner")]
+    pub owner: Address,
+
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+}
+
+/// Amount that is being unbonded.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Unbonding {
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+
+    /// Round after which the amount can be withdrawn.
+    #[serde(rename = "ends")]
+    pub ends: u64,
+}
+
+/// Kind of payload covered by misbehavior evidence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EvidenceKind {
+    /// Witness signatures over outgoing operations, as submitted via `bridge.Witness`.
+    #[serde(rename = "witness")]
+    Witness,
+
+    /// Witness signatures over incoming operations, as submitted via `bridge.Release`.
+    #[serde(rename = "release")]
+    Release,
+}
+
+/// Operation together with a witness signature over it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SignedOperation {
+    #[serde(rename = "op")]
+    pub op: Operation,
+
+    #[serde(rename = "sig")]
+    pub signature: Signature,
+}
+
+/// Submit evidence call.
+///
+/// Proves that a witness signed two different operations for the same sequence number.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubmitEvidence {
+    #[serde(rename = "witness")]
+    pub witness: WitnessKey,
+
+    #[serde(rename = "kind")]
+    pub kind: EvidenceKind,
+
+    /// Sequence number that both operations were signed for.
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    #[serde(rename = "first")]
+    pub first: SignedOperation,
+
+    #[serde(rename = "second")]
+    pub second: SignedOperation,
+}
+
+/// Add witness call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AddWitness {
+    #[serde(rename = "witness")]
+    pub witness: WitnessKey,
+}
+
+/// Remove witness call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoveWitness {
+    #[serde(rename = "witness")]
+    pub witness: WitnessKey,
+}
+
+/// Update blocklist call.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateBlocklist {
+    /// Local addresses to add to the blocklist.
+    #[serde(rename = "block")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub block: Vec<Address>,
+
+    /// Local addresses to remove from the blocklist.
+    #[serde(rename = "unblock")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unblock: Vec<Address>,
+
+    /// Remote addresses to add to the blocklist.
+    #[serde(rename = "block_remote")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub block_remote: Vec<RemoteAddress>,
+
+    /// Remote addresses to remove from the blocklist.
+    #[serde(rename = "unblock_remote")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unblock_remote: Vec<RemoteAddress>,
+}
+
+/// Update witnesses call.
+///
+/// Proposes a new witness set and threshold, which is applied once enough witnesses of the
+/// current witness set have submitted the same update.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateWitnesses {
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    #[serde(rename = "witnesses")]
+    pub witnesses: Vec<WitnessKey>,
+
+    #[serde(rename = "threshold")]
+    pub threshold: u64,
+
+    /// Witness signature over the update. It is required when submitting an update and is never
+    /// part of the update itself.
+    #[serde(rename = "sig")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Signature>,
+}
+
+/// Bridge fee schedule.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeeSchedule {
+    /// Lock fee in basis points of the locked amount.
+    #[serde(rename = "lock_fee")]
+    #[serde(default)]
+    pub lock_fee: u64,
+
+    /// Minimum lock fee in base units of the locked denomination.
+    #[serde(rename = "min_lock_fee")]
+    #[serde(default)]
+    pub min_lock_fee: Quantity,
+
+    /// Maximum lock fee in base units of the locked denomination. Zero means no maximum.
+    #[serde(rename = "max_lock_fee")]
+    #[serde(default)]
+    pub max_lock_fee: Quantity,
+
+    /// Flat fee in the native denomination that is charged on each lock.
+    #[serde(rename = "subsidy_fee")]
+    #[serde(default)]
+    pub subsidy_fee: Quantity,
+}
+
+/// Number of decimals that local and remote amounts of a denomination are expressed with.
+///
+/// Local amounts with more decimals than remote amounts can only be bridged in multiples of the
+/// scaling factor. The rest of such an amount is dust that stays on the side it came from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DecimalScale {
+    /// Number of decimals of local amounts.
+    #[serde(rename = "local")]
+    #[serde(default)]
+    pub local: u8,
+
+    /// Number of decimals of remote amounts.
+    #[serde(rename = "remote")]
+    #[serde(default)]
+    pub remote: u8,
+}
+
+impl DecimalScale {
+    /// Maximum difference between the number of local and remote decimals.
+    pub const MAX_DIFFERENCE: u8 = 36;
+
+    /// Factor between amounts with more and amounts with fewer decimals.
+    pub fn factor(&self) -> Option<Quantity> {
+        let difference = self.local.max(self.remote) - self.local.min(self.remote);
+        (0..difference).try_fold(Quantity::from(1u64), |factor, _| {
+            factor.checked_mul(&Quantity::from(10u64))
+        })
+    }
+
+    /// Convert the given local amount into the remote amount, or `None` if it has dust.
+    pub fn to_remote(&self, amount: &Quantity) -> Option<Quantity> {
+        let factor = self.factor()?;
+        if self.remote >= self.local {
+            return amount.checked_mul(&factor);
+        }
+        let remote = amount.checked_div(&factor)?;
+        if remote.checked_mul(&factor)? != *amount {
+            return None;
+        }
+        Some(remote)
+    }
+
+    /// Split the given local amount into the part that can be bridged and the dust.
+    ///
+    /// Amounts are rounded down to the closest amount that has a remote equivalent.
+    pub fn split_dust(&self, amount: &Quantity) -> Option<(Quantity, Quantity)> {
+        if self.remote >= self.local {
+            return Some((amount.clone(), Quantity::default()));
+        }
+        let factor = self.factor()?;
+        let bridged = amount.checked_div(&factor)?.checked_mul(&factor)?;
+        let dust = amount.checked_sub(&bridged)?;
+        Some((bridged, dust))
+    }
+}
+
+/// Set fee schedule call.
+///
+/// Applied immediately when submitted by the admin, otherwise once enough witnesses of the
+/// current witness set have submitted the same fee schedule.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetFeeSchedule {
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    #[serde(rename = "schedule")]
+    pub schedule: FeeSchedule,
+}
+
+/// Set remote contract call.
+///
+/// Applied immediately when submitted by the admin, otherwise once enough witnesses of the
+/// current witness set have relayed the same remote contract from the remote chain's governance.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetRemoteContract {
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    #[serde(rename = "contract")]
+    pub contract: RemoteAddress,
+}
+
+/// Register denomination call.
+///
+/// Adds a new remote denomination. Applied immediately when submitted by the admin, otherwise
+/// once enough witnesses of the current witness set have submitted the same registration.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegisterDenomination {
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    /// Local denomination of the wrapped asset.
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    /// Identifier of the asset on the remote side of the bridge.
+    #[serde(rename = "remote")]
+    pub remote: RemoteDenomination,
+
+    /// Number of local and remote decimals if amounts of the denomination are scaled.
+    #[serde(rename = "decimal_scale")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimal_scale: Option<DecimalScale>,
+}
+
+/// Operation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub enum Operation {
+    #[serde(rename = "lock")]
+    Lock(Lock),
+
+    #[serde(rename = "release")]
+    Release(Release),
+}
+
+impl Operation {
+    /// Amount transferred by the operation.
+    pub fn amount(&self) -> &token::BaseUnits {
+        match self {
+            Operation::Lock(lock) => &lock.amount,
+            Operation::Release(release) => &release.amount,
+        }
+    }
+}
+
+/// A unique operation identifier.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct OperationId(Hash);
+
+impl From<&Operation> for OperationId {
+    fn from(op: &Operation) -> OperationId {
+        OperationId(Hash::digest_bytes(&cbor::to_vec(op)))
+    }
+}
+
+/// Witness signatures.
+///
+/// Signatures, if any, are ordered by signer index.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(from = "EncodedWitnessSignatures")]
+pub struct WitnessSignatures {
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    #[serde(rename = "op")]
+    pub op: Operation,
+
+    #[serde(rename = "wits")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "WitnessBitmap::is_empty")]
+    pub signers: WitnessBitmap,
+
+    #[serde(rename = "sigs")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<Signature>,
+}
+
+impl WitnessSignatures {
+    /// Create a new empty set of witness signatures.
+    pub fn new(id: u64, op: Operation) -> Self {
+        Self {
+            id,
+            op,
+            signers: WitnessBitmap::default(),
+            signatures: Vec::new(),
+        }
+    }
+}
+
+/// Witness signatures as stored, with signers either as a bitmap or in the legacy representation
+/// as a list of witness indices in submission order.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EncodedWitnessSignatures {
+    #[serde(rename = "id")]
+    id: u64,
+
+    #[serde(rename = "op")]
+    op: Operation,
+
+    #[serde(rename = "wits")]
+    #[serde(default)]
+    signers: EncodedWitnessIndices,
+
+    #[serde(rename = "sigs")]
+    #[serde(default)]
+    signatures: Vec<Signature>,
+}
+
+impl From<EncodedWitnessSignatures> for WitnessSignatures {
+    fn from(encoded: EncodedWitnessSignatures) -> Self {
+        match encoded.signers {
+            EncodedWitnessIndices::Bitmap(signers) => Self {
+                id: encoded.id,
+                op: encoded.op,
+                signers,
+                signatures: encoded.signatures,
+            },
+            EncodedWitnessIndices::List(indices) => {
+                // Legacy signatures are ordered by submission, reorder them by signer index.
+                // Incoming operations never stored any signatures.
+                let mut signatures: Vec<Option<Signature>> =
+                    encoded.signatures.into_iter().map(Some).collect();
+                signatures.resize(indices.len(), None);
+                let mut pairs: Vec<(u16, Option<Signature>)> =
+                    indices.into_iter().zip(signatures).collect();
+                pairs.sort_by_key(|(index, _)| *index);
+
+                let mut info = Self::new(encoded.id, encoded.op);
+                for (index, signature) in pairs {
+                    if info.signers.insert(index) {
+                        info.signatures.extend(signature);
+                    }
+                }
+                info
+            }
+        }
+    }
+}
+
+/// Set of witness indices, either as a bitmap or as a list of indices.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EncodedWitnessIndices {
+    List(Vec<u16>),
+    Bitmap(WitnessBitmap),
+}
+
+impl Default for EncodedWitnessIndices {
+    fn default() -> Self {
+        EncodedWitnessIndices::Bitmap(WitnessBitmap::default())
+    }
+}
+
+impl From<EncodedWitnessIndices> for WitnessBitmap {
+    fn from(encoded: EncodedWitnessIndices) -> Self {
+        match encoded {
+            EncodedWitnessIndices::List(indices) => {
+                let mut bitmap = WitnessBitmap::default();
+                for index in indices {
+                    bitmap.insert(index);
+                }
+                bitmap
+            }
+            EncodedWitnessIndices::Bitmap(bitmap) => bitmap,
+        }
+    }
+}
+
+/// Set of witness indices, encoded as a bitmap where bit `i` (in little-endian bit order) is set
+/// when the witness with index `i` is a member of the set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct WitnessBitmap(#[serde(with = "serde_bytes")] Vec<u8>);
+
+impl WitnessBitmap {
+    /// Whether the witness with the given index is a member of the set.
+    pub fn contains(&self, index: u16) -> bool {
+        let (byte, mask) = Self::position(index);
+        self.0.get(byte).map(|b| b & mask != 0).unwrap_or(false)
+    }
+
+    /// Add the witness with the given index to the set.
+    ///
+    /// Returns `false` if the witness was already a member of the set.
+    pub fn insert(&mut self, index: u16) -> bool {
+        let (byte, mask) = Self::position(index);
+        if byte >= self.0.len() {
+            self.0.resize(byte + 1, 0);
+        }
+        let inserted = self.0[byte] & mask == 0;
+        self.0[byte] |= mask;
+        inserted
+    }
+
+    /// Remove the witness with the given index from the set.
+    ///
+    /// Returns `false` if the witness was not a member of the set.
+    pub fn remove(&mut self, index: u16) -> bool {
+        if !self.contains(index) {
+            return false;
+        }
+        let (byte, mask) = Self::position(index);
+        self.0[byte] &= !mask;
+        // Keep the encoding canonical.
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+        true
+    }
+
+    /// Number of witnesses in the set.
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    /// Whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of witnesses in the set with an index lower than the given one.
+    pub fn rank(&self, index: u16) -> usize {
+        self.iter().take_while(|i| *i < index).count()
+    }
+
+    /// Iterate over the witness indices in the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        (0..self.0.len() * 8)
+            .map(|i| i as u16)
+            .filter(move |i| self.contains(*i))
+    }
+
+    /// Remove the witness with the given index from the set, shifting the indices of all
+    /// following witnesses down by one.
+    pub fn remove_and_shift(&mut self, index: u16) {
+        let indices: Vec<u16> = self.iter().collect();
+        *self = Self::default();
+        for i in indices.into_iter().filter(|i| *i != index) {
+            self.insert(if i > index { i - 1 } else { i });
+        }
+    }
+
+    fn position(index: u16) -> (usize, u8) {
+        (index as usize / 8, 1 << (index % 8))
+    }
+}
+
+/// Outgoing witness signatures in their compact storage representation.
+///
+/// Signer indices are stored as a bitmap and signatures are ordered by signer index. Ed25519 and
+/// Secp256k1 signatures cannot be aggregated so each signature is still stored individually.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WitnessSignatureSet {
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    #[serde(rename = "op")]
+    pub op: Operation,
+
+    #[serde(rename = "signers")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "WitnessBitmap::is_empty")]
+    pub signers: WitnessBitmap,
+
+    #[serde(rename = "sigs")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<Signature>,
+
+    /// Snapshot of the witness set that signatures are verified against.
+    ///
+    /// Not set for operations created before witness sets were snapshotted, which are verified
+    /// against the current witness set instead.
+    #[serde(rename = "set")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness_set: Option<Hash>,
+
+    /// Signatures by the successor witness set, collected during a witness set handover.
+    #[serde(rename = "handover")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handover: Option<HandoverSignatures>,
+
+    /// Fee attached to the operation, which is split among the witnesses whose signatures
+    /// finalize it.
+    #[serde(rename = "fee")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<token::BaseUnits>,
+
+    /// Round in which the operation was created.
+    ///
+    /// Not set for operations created before creation rounds were tracked, which never expire.
+    #[serde(rename = "created")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<u64>,
+}
+
+impl WitnessSignatureSet {
+    /// Create a new empty set of witness signatures.
+    pub fn new(id: u64, op: Operation) -> Self {
+        Self {
+            id,
+            op,
+            signers: WitnessBitmap::default(),
+            signatures: Vec::new(),
+            witness_set: None,
+            handover: None,
+            fee: None,
+            created: None,
+        }
+    }
+
+    /// Add a signature by the witness with the given index.
+    ///
+    /// Returns `false` if the witness has already submitted a signature.
+    pub fn insert(&mut self, index: u16, signature: Signature) -> bool {
+        if !self.signers.insert(index) {
+            return false;
+        }
+        self.signatures.insert(self.signers.rank(index), signature);
+        true
+    }
+}
+
+impl WitnessSignatureSet {
+    /// Remove the witness with the given index, shifting the indices of all following witnesses
+    /// down by one.
+    pub fn remove_witness(&mut self, index: u16) {
+        if self.signers.contains(index) {
+            self.signatures.remove(self.signers.rank(index));
+        }
+        self.signers.remove_and_shift(index);
+    }
+}
+
+impl From<WitnessSignatures> for WitnessSignatureSet {
+    fn from(info: WitnessSignatures) -> Self {
+        let mut set = Self::new(info.id, info.op);
+        for (index, signature) in info.signers.iter().zip(info.signatures) {
+            set.insert(index, signature);
+        }
+        set
+    }
+}
+
+impl From<WitnessSignatureSet> for WitnessSignatures {
+    fn from(set: WitnessSignatureSet) -> Self {
+        Self {
+            id: set.id,
+            op: set.op,
+            signers: set.signers,
+            signatures: set.signatures,
+        }
+    }
+}
+
+/// Signatures by the successor of an operation's witness set.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HandoverSignatures {
+    /// Commitment to the successor witness set.
+    #[serde(rename = "witness_set")]
+    pub witness_set: Hash,
+
+    #[serde(rename = "signers")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "WitnessBitmap::is_empty")]
+    pub signers: WitnessBitmap,
+
+    #[serde(rename = "sigs")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<Signature>,
+}
+
+impl HandoverSignatures {
+    /// Create a new empty set of signatures by the given witness set.
+    pub fn new(witness_set: Hash) -> Self {
+        Self {
+            witness_set,
+            signers: WitnessBitmap::default(),
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Add a signature by the witness with the given index in the successor witness set.
+    ///
+    /// Returns `false` if the witness has already submitted a signature.
+    pub fn insert(&mut self, index: u16, signature: Signature) -> bool {
+        if !self.signers.insert(index) {
+            return false;
+        }
+        self.signatures.insert(self.signers.rank(index), signature);
+        true
+    }
+
+    /// Iterate over the signer indices and their signatures.
+    pub fn signatures(&self) -> impl Iterator<Item = (u16, &Signature)> + '_ {
+        self.signers.iter().zip(self.signatures.iter())
+    }
+}
+
+/// Handover from a witness set to its successor.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WitnessSetHandover {
+    /// Commitment to the successor witness set.
+    #[serde(rename = "to")]
+    pub to: Hash,
+
+    /// Last round in which the successor witness set can sign pending operations.
+    #[serde(rename = "ends")]
+    pub ends: u64,
+}
+
+/// Threshold given as a fraction of the number of active witnesses.
+///
+/// The threshold for `n` active witnesses is `n * numerator / denominator + extra`, rounded down.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdPolicy {
+    #[serde(rename = "numerator")]
+    pub numerator: u64,
+
+    #[serde(rename = "denominator")]
+    pub denominator: u64,
+
+    #[serde(rename = "extra")]
+    #[serde(default)]
+    pub extra: u64,
+}
+
+impl ThresholdPolicy {
+    /// Threshold for the given number of active witnesses.
+    pub fn threshold(&self, witnesses: u64) -> u64 {
+        if self.denominator == 0 {
+            return u64::MAX;
+        }
+        let fraction = (witnesses as u128) * (self.numerator as u128) / (self.denominator as u128);
+        (fraction as u64).saturating_add(self.extra)
+    }
+}
+
+/// Snapshot of the witness set and threshold.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WitnessSet {
+    #[serde(rename = "witnesses")]
+    pub witnesses: Vec<WitnessKey>,
+
+    #[serde(rename = "threshold")]
+    pub threshold: u64,
+}
+
+/// Participation of a witness in recent outgoing operations.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WitnessLiveness {
+    /// Participation in the most recent outgoing operations, one bit per operation with the least
+    /// significant bit being the most recent operation. Set bits mark signed operations.
+    #[serde(rename = "recent")]
+    #[serde(default)]
+    pub recent: u64,
+
+    /// Number of consecutive most recent outgoing operations that the witness missed.
+    #[serde(rename = "missed")]
+    #[serde(default)]
+    pub missed: u64,
+
+    /// Whether the witness has been marked inactive.
+    #[serde(rename = "inactive")]
+    #[serde(default)]
+    pub inactive: bool,
+
+    /// Round until which the witness is jailed and cannot reactivate itself.
+    #[serde(rename = "jailed_until")]
+    #[serde(default)]
+    pub jailed_until: u64,
+}
+
+impl WitnessLiveness {
+    /// Record whether the witness signed the most recent outgoing operation.
+    pub fn record(&mut self, signed: bool) {
+        self.recent = (self.recent << 1) | (signed as u64);
+        if signed {
+            self.missed = 0;
+        } else {
+            self.missed = self.missed.saturating_add(1);
+        }
+    }
+
+    /// Number of the given number of most recent outgoing operations that the witness signed.
+    pub fn signed(&self, window: u64) -> u64 {
+        let mask = match window {
+            0 => 0,
+            1..=63 => (1 << window) - 1,
+            _ => u64::MAX,
+        };
+        (self.recent & mask).count_ones() as u64
+    }
+}
+
+/// Freeze or unfreeze denomination call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FreezeDenomination {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+}
+
+/// Display metadata of a denomination.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DenominationMetadata {
+    /// Number of decimals that amounts of the denomination are expressed with.
+    #[serde(rename = "decimals")]
+    #[serde(default)]
+    pub decimals: u8,
+
+    /// Ticker symbol of the denomination.
+    #[serde(rename = "symbol")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub symbol: String,
+}
+
+impl DenominationMetadata {
+    /// Whether no metadata is set.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Set denomination metadata call.
+///
+/// Empty metadata clears any previously set metadata.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetDenominationMetadata {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    #[serde(rename = "metadata")]
+    #[serde(default)]
+    pub metadata: DenominationMetadata,
+}
+
+/// Local denomination query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocalDenominationQuery {
+    /// Identifier of the denomination on the remote side of the bridge.
+    #[serde(rename = "remote")]
+    pub remote: RemoteDenomination,
+}
+
+/// Bridge contract on the remote chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteContract {
+    /// Identifier of the remote chain.
+    #[serde(rename = "remote_chain_id")]
+    pub remote_chain_id: u64,
+
+    /// Address of the bridge contract on the remote chain, if configured.
+    #[serde(rename = "address")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<RemoteAddress>,
+}
+
+/// Whether the bridge is paused and which denominations are frozen.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PauseStatus {
+    /// Whether the bridge is paused, halting all locks and releases.
+    #[serde(rename = "paused")]
+    #[serde(default)]
+    pub paused: bool,
+
+    /// Denominations in which locks and releases are halted.
+    #[serde(rename = "frozen")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub frozen: BTreeSet<token::Denomination>,
+}
+
+/// Configured denomination together with its metadata.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DenominationInfo {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    /// Identifier of the denomination on the remote side of the bridge. Not set for local
+    /// denominations.
+    #[serde(rename = "remote")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<RemoteDenomination>,
+
+    #[serde(rename = "metadata")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "DenominationMetadata::is_empty")]
+    pub metadata: DenominationMetadata,
+}
+
+/// Allow or disallow address call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AllowAddress {
+    #[serde(rename = "address")]
+    pub address: Address,
+}
+
+/// Attest remote locked call.
+///
+/// Attests to the total amount of a remote denomination that is locked in the remote bridge
+/// contract.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AttestRemoteLocked {
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+}
+
+/// Reserve backing a remote denomination.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteReserve {
+    /// Amount locked in the remote bridge contract that a threshold of witnesses agreed upon, if
+    /// any. Releases that would mint more than this amount are refused.
+    #[serde(rename = "locked")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked: Option<Quantity>,
+
+    /// Amount minted on this side of the bridge.
+    #[serde(rename = "minted")]
+    #[serde(default)]
+    pub minted: Quantity,
+
+    /// Latest attested locked amount of each witness, keyed by witness identifier.
+    #[serde(rename = "attestations")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub attestations: BTreeMap<Hash, Quantity>,
+}
+
+/// Amounts that an account locked and received during a rate limit window.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountVolume {
+    /// Index of the rate limit window.
+    #[serde(rename = "window")]
+    pub window: u64,
+
+    /// Amounts per denomination.
+    #[serde(rename = "volumes")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub volumes: BTreeMap<token::Denomination, Quantity>,
+}
+
+/// Pending outgoing operations query.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PendingOutgoingQuery {
+    /// Number of pending operations to skip.
+    #[serde(rename = "offset")]
+    #[serde(default)]
+    pub offset: u64,
+
+    /// Maximum number of pending operations to return. Zero returns the maximum page size.
+    #[serde(rename = "limit")]
+    #[serde(default)]
+    pub limit: u64,
+}
+
+/// Summary of an outgoing operation that has not been finalized yet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PendingOutgoing {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+
+    /// Number of witness signatures collected so far.
+    #[serde(rename = "votes")]
+    pub votes: u64,
+}
+
+impl From<&WitnessSignatureSet> for PendingOutgoing {
+    fn from(set: &WitnessSignatureSet) -> Self {
+        Self {
+            denomination: set.op.amount().denomination().clone(),
+            id: set.id,
+            amount: set.op.amount().clone(),
+            votes: set.signers.len() as u64,
+        }
+    }
+}
+
+/// Pending incoming operations query.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PendingIncomingQuery {
+    /// Number of pending operations to skip.
+    #[serde(rename = "offset")]
+    #[serde(default)]
+    pub offset: u64,
+
+    /// Maximum number of pending operations to return. Zero returns the maximum page size.
+    #[serde(rename = "limit")]
+    #[serde(default)]
+    pub limit: u64,
+}
+
+/// Summary of an incoming sequence number that has votes but has not been finalized yet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PendingIncoming {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    /// Number of witnesses that voted.
+    #[serde(rename = "votes")]
+    pub votes: u64,
+
+    /// Number of different operations that the witnesses voted for.
+    #[serde(rename = "proposals")]
+    pub proposals: u64,
+
+    /// Round of the first vote, if known.
+    #[serde(rename = "created")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<u64>,
+}
+
+/// Finalized release that is waiting for its challenge period to pass.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PendingRelease {
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    #[serde(rename = "target")]
+    pub target: Address,
+
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+
+    #[serde(rename = "tx_hash")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<Hash>,
+
+    #[serde(rename = "log_index")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_index: Option<u64>,
+
+    /// Further transfers of a batch release.
+    #[serde(rename = "batch")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub batch: Vec<ReleaseEntry>,
+
+    /// Root of the claims that the release commits to, if any.
+    #[serde(rename = "claims_root")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claims_root: Option<Hash>,
+
+    /// Round after which the release is executed unless a guardian cancels it.
+    #[serde(rename = "deadline")]
+    pub deadline: u64,
+}
+
+impl PendingRelease {
+    /// Transfers made by the release, starting with the primary one.
+    pub fn entries(&self) -> Vec<ReleaseEntry> {
+        batch_entries(self.target, &self.amount, &self.batch)
+    }
+
+    /// Total amount released, or `None` if the batch mixes denominations or the sum overflows.
+    pub fn total_amount(&self) -> Option<token::BaseUnits> {
+        batch_total(&self.amount, &self.batch)
+    }
+}
+
+/// Cancel pending release call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CancelRelease {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    #[serde(rename = "id")]
+    pub id: u64,
+}
+
+/// Recover call, moving funds out of the locked funds account after the recovery delay.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Recover {
+    #[serde(rename = "target")]
+    pub target: Address,
+
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+}
+
+/// Recovery that is waiting for the recovery delay to pass.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PendingRecovery {
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    #[serde(rename = "target")]
+    pub target: Address,
+
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+
+    /// Round after which the funds are transferred unless a guardian cancels the recovery.
+    #[serde(rename = "deadline")]
+    pub deadline: u64,
+}
+
+/// Cancel recovery call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CancelRecovery {
+    #[serde(rename = "id")]
+    pub id: u64,
+}
+
+/// Value held by the bridge per denomination.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TotalLocked {
+    /// Amount of each local denomination held in the locked funds account that has not been
+    /// released or recovered again.
+    #[serde(rename = "locked")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub locked: BTreeMap<token::Denomination, Quantity>,
+
+    /// Outstanding minted supply of each remote denomination.
+    #[serde(rename = "minted")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub minted: BTreeMap<token::Denomination, Quantity>,
+}
+
+/// Bridge statistics, counted since they were introduced.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Stats {
+    /// Number of locks.
+    #[serde(rename = "locks")]
+    #[serde(default)]
+    pub locks: u64,
+
+    /// Number of locks that were finalized by the witnesses.
+    #[serde(rename = "finalized_locks")]
+    #[serde(default)]
+    pub finalized_locks: u64,
+
+    /// Number of locks that are waiting for witness signatures.
+    #[serde(rename = "pending_locks")]
+    #[serde(default)]
+    pub pending_locks: u64,
+
+    /// Number of locks that expired before enough witnesses signed them.
+    #[serde(rename = "expired_locks")]
+    #[serde(default)]
+    pub expired_locks: u64,
+
+    /// Number of releases.
+    #[serde(rename = "releases")]
+    #[serde(default)]
+    pub releases: u64,
+
+    /// Cumulative locked amount per denomination, net of lock fees.
+    #[serde(rename = "locked")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub locked: BTreeMap<token::Denomination, Quantity>,
+
+    /// Cumulative released amount per denomination.
+    #[serde(rename = "released")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub released: BTreeMap<token::Denomination, Quantity>,
+
+    /// Cumulative amount recovered from the locked funds per denomination.
+    #[serde(rename = "recovered")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub recovered: BTreeMap<token::Denomination, Quantity>,
+}
+
+impl Stats {
+    /// Add the given amount to the cumulative locked amount.
+    pub fn add_locked(&mut self, amount: &token::BaseUnits) {
+        add_volume(&mut self.locked, amount);
+    }
+
+    /// Add the given amount to the cumulative released amount.
+    pub fn add_released(&mut self, amount: &token::BaseUnits) {
+        add_volume(&mut self.released, amount);
+    }
+
+    /// Add the given amount to the cumulative recovered amount.
+    pub fn add_recovered(&mut self, amount: &token::BaseUnits) {
+        add_volume(&mut self.recovered, amount);
+    }
+}
+
+/// Add the given amount to a cumulative volume.
+fn add_volume(volume: &mut BTreeMap<token::Denomination, Quantity>, amount: &token::BaseUnits) {
+    let total = volume.entry(amount.denomination().clone()).or_default();
+    if let Some(sum) = total.checked_add(amount.amount()) {
+        *total = sum;
+    }
+}
+
+/// Accounting invariant of the bridge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Invariant {
+    /// The locked funds cover the outstanding remote claims on a local denomination.
+    #[serde(rename = "locked_funds")]
+    LockedFunds,
+
+    /// The minted supply counter of a remote denomination matches the released amount, including
+    /// queued releases, net of the burned amount.
+    #[serde(rename = "minted_supply")]
+    MintedSupply,
+
+    /// No more of a remote denomination was burned than released.
+    #[serde(rename = "burned_supply")]
+    BurnedSupply,
+
+    /// The total supply of a remote denomination matches the released amount net of the burned
+    /// amount.
+    #[serde(rename = "total_supply")]
+    TotalSupply,
+}
+
+/// Accounting invariant of the bridge that does not hold for a denomination.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InvariantViolation {
+    #[serde(rename = "invariant")]
+    pub invariant: Invariant,
+
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    /// Balance of the locked funds account, minted supply counter, burned amount or total supply,
+    /// depending on the invariant.
+    #[serde(rename = "actual")]
+    pub actual: Quantity,
+
+    /// Outstanding remote claims, released amount including queued releases net of the burned
+    /// amount, released amount or released amount net of the burned amount, depending on the
+    /// invariant.
+    #[serde(rename = "expected")]
+    pub expected: Quantity,
+}
+
+/// Votes that an account submitted in a block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BlockVotes {
+    /// Round of the block.
+    #[serde(rename = "round")]
+    pub round: u64,
+
+    /// Number of votes submitted in the block.
+    #[serde(rename = "count")]
+    pub count: u64,
+}
+
+/// Next expected vote nonces of a witness.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VoteNonces {
+    /// Next nonce of witness signatures over outgoing operations.
+    #[serde(rename = "out")]
+    #[serde(default)]
+    pub outgoing: u64,
+
+    /// Next nonce of witness votes on incoming operations.
+    #[serde(rename = "in")]
+    #[serde(default)]
+    pub incoming: u64,
+}
+
+/// Quorum certificate for an outgoing operation.
+///
+/// The certificate is self-contained: it carries the operation, a commitment to the witness set
+/// that signed it, the indices of the signers and their signatures ordered by signer index.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuorumCertificate {
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    #[serde(rename = "op")]
+    pub op: Operation,
+
+    #[serde(rename = "witness_set")]
+    pub witness_set: Hash,
+
+    #[serde(rename = "signers")]
+    pub signers: WitnessBitmap,
+
+    #[serde(rename = "sigs")]
+    pub signatures: Vec<Signature>,
+
+    /// Signatures by the successor witness set if the operation was signed during a witness set
+    /// handover. Its commitment is to the signing keys of the successor witness set.
+    #[serde(rename = "handover")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handover: Option<HandoverSignatures>,
+}
+
+impl QuorumCertificate {
+    /// Create a quorum certificate from a complete set of witness signatures.
+    pub fn new(set: WitnessSignatureSet, witness_set: Hash) -> Self {
+        Self {
+            id: set.id,
+            op: set.op,
+            witness_set,
+            signers: set.signers,
+            signatures: set.signatures,
+            handover: set.handover,
+        }
+    }
+
+    /// Iterate over the signer indices and their signatures.
+    pub fn signatures(&self) -> impl Iterator<Item = (u16, &Signature)> + '_ {
+        self.signers.iter().zip(self.signatures.iter())
+    }
+}
+
+/// Conflicting incoming operations proposed for the same sequence number.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Conflict {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    /// Witnesses that voted for each of the conflicting operations.
+    #[serde(rename = "votes")]
+    pub votes: BTreeMap<OperationId, Vec<WitnessKey>>,
+}
+
+/// Processed deposit query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessedDepositQuery {
+    #[serde(rename = "tx_hash")]
+    pub tx_hash: Hash,
+
+    #[serde(rename = "log_index")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_index: Option<u64>,
+}
+
+/// Incoming witness signatures.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IncomingWitnessSignatures {
+    #[serde(rename = "ops")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub ops: BTreeMap<OperationId, WitnessSignatures>,
+
+    #[serde(rename = "wits")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "WitnessBitmap::is_empty")]
+    #[serde(deserialize_with = "deserialize_witness_indices")]
+    pub signers: WitnessBitmap,
+
+    /// Round of the first vote for the sequence number.
+    ///
+    /// Not set for votes cast before vote rounds were tracked, which are never collected as stale.
+    #[serde(rename = "created")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<u64>,
+}
+
+impl IncomingWitnessSignatures {
+    /// Summary of the votes, if any.
+    pub fn summary(&self) -> Option<PendingIncoming> {
+        let op_sigs = self.ops.values().next()?;
+        Some(PendingIncoming {
+            denomination: op_sigs.op.amount().denomination().clone(),
+            id: op_sigs.id,
+            votes: self.signers.len() as u64,
+            proposals: self.ops.len() as u64,
+            created: self.created,
+        })
+    }
+
+    /// Remove the witness with the given index, shifting the indices of all following witnesses
+    /// down by one.
+    pub fn remove_witness(&mut self, index: u16) {
+        self.signers.remove_and_shift(index);
+        for op in self.ops.values_mut() {
+            op.signers.remove_and_shift(index);
+        }
+    }
+}
+
+/// Witness votes on an update that requires a quorum of witnesses, such as a witness set or fee
+/// schedule update.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateVotes {
+    /// Witnesses that voted for each of the proposed updates, keyed by the hash of the update.
+    #[serde(rename = "updates")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub updates: BTreeMap<Hash, Vec<u16>>,
+
+    #[serde(rename = "wits")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub witnesses: Vec<u16>,
+}
+
+impl UpdateVotes {
+    /// Remove the witness with the given index, shifting the indices of all following witnesses
+    /// down by one.
+    pub fn remove_witness(&mut self, index: u16) {
+        remove_witness_index(&mut self.witnesses, index);
+        for voters in self.updates.values_mut() {
+            remove_witness_index(voters, index);
+        }
+    }
+}
+
+/// Deserialize a set of witness indices given either as a bitmap or in the legacy representation
+/// as a list of indices.
+fn deserialize_witness_indices<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<WitnessBitmap, D::Error> {
+    EncodedWitnessIndices::deserialize(deserializer).map(Into::into)
+}
+
+/// Remove the given witness index from a list of witness indices, shifting the indices of all
+/// following witnesses down by one.
+fn remove_witness_index(indices: &mut Vec<u16>, index: u16) {
+    indices.retain(|i| *i != index);
+    for i in indices.iter_mut().filter(|i| **i > index) {
+        *i -= 1;
+    }
+}
+
+/// Quorum certificate query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuorumCertificateQuery {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    #[serde(rename = "id")]
+    pub id: u64,
+}
+
+/// Outgoing operation query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutOperationQuery {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    #[serde(rename = "id")]
+    pub id: u64,
+}
+
+/// Incoming operation query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InOperationQuery {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    #[serde(rename = "id")]
+    pub id: u64,
+}
+
+/// Signing progress query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SigningProgressQuery {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    #[serde(rename = "id")]
+    pub id: u64,
+}
+
+/// Signing progress of an outgoing operation that has not been finalized yet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SigningProgress {
+    /// Number of witness signatures collected so far.
+    #[serde(rename = "collected")]
+    pub collected: u64,
+
+    /// Number of witness signatures required to finalize the operation.
+    #[serde(rename = "threshold")]
+    pub threshold: u64,
+
+    /// Indices of the witnesses in the operation's witness set that have not signed yet.
+    #[serde(rename = "missing")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<u16>,
+}
+
+/// Direction of an operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    /// From this runtime to the remote chain.
+    #[serde(rename = "outgoing")]
+    Outgoing,
+
+    /// From the remote chain to this runtime.
+    #[serde(rename = "incoming")]
+    Incoming,
+}
+
+/// Operation history query.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HistoryQuery {
+    /// Number of matching operations to skip.
+    #[serde(rename = "offset")]
+    #[serde(default)]
+    pub offset: u64,
+
+    /// Maximum number of operations to return. Zero returns the maximum page size.
+    #[serde(rename = "limit")]
+    #[serde(default)]
+    pub limit: u64,
+
+    /// Only return operations in the given direction.
+    #[serde(rename = "direction")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<Direction>,
+
+    /// Only return operations of the given denomination.
+    #[serde(rename = "denomination")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub denomination: Option<token::Denomination>,
+}
+
+/// Finalized operation in the operation history.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HistoryEntry {
+    #[serde(rename = "direction")]
+    pub direction: Direction,
+
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    /// Finalized operation. Releases are stored without their signature and vote nonce.
+    #[serde(rename = "op")]
+    pub op: Operation,
+
+    /// Round in which the operation was finalized.
+    #[serde(rename = "round")]
+    pub round: u64,
+}
+
+/// Operation status query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OperationStatusQuery {
+    #[serde(rename = "direction")]
+    pub direction: Direction,
+
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    #[serde(rename = "id")]
+    pub id: u64,
+}
+
+/// Status of an operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OperationStatus {
+    /// No operation with the sequence number is known.
+    #[serde(rename = "unknown")]
+    Unknown,
+
+    /// Witness signatures or votes are being collected.
+    #[serde(rename = "collecting")]
+    Collecting,
+
+    /// The operation has been finalized.
+    #[serde(rename = "finalized")]
+    Finalized,
+
+    /// The outgoing operation expired before gathering enough witness signatures.
+    #[serde(rename = "expired")]
+    Expired,
+
+    /// The incoming operation has been finalized and is waiting for its challenge period to pass.
+    #[serde(rename = "challenged")]
+    Challenged,
+}
+
+/// Claims root query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClaimsRootQuery {
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    #[serde(rename = "id")]
+    pub id: u64,
+}
+
+/// Claimable balances query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClaimableBalancesQuery {
+    #[serde(rename = "address")]
+    pub address: Address,
+}
+
+/// Completed releases query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReleasesForQuery {
+    #[serde(rename = "address")]
+    pub address: Address,
+
+    /// Number of completed releases to skip.
+    #[serde(rename = "offset")]
+    #[serde(default)]
+    pub offset: u64,
+
+    /// Maximum number of completed releases to return. Zero returns the maximum page size.
+    #[serde(rename = "limit")]
+    #[serde(default)]
+    pub limit: u64,
+}
+
+/// Release that has been completed to a target address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompletedRelease {
+    /// Incoming sequence number of the release.
+    #[serde(rename = "id")]
+    pub id: u64,
+
+    /// Amount released to the target.
+    #[serde(rename = "amount")]
+    pub amount: token::BaseUnits,
+
+    /// Round in which the release was completed.
+    #[serde(rename = "round")]
+    pub round: u64,
+}
+
+/// Witness set query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WitnessSetQuery {
+    /// Denomination of the operation, which determines its sequence space.
+    #[serde(rename = "denomination")]
+    pub denomination: token::Denomination,
+
+    /// Outgoing sequence number of the operation.
+    #[serde(rename = "id")]
+    pub id: u64,
+}
+
+/// Next sequence numbers query.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NextSequenceNumbersQuery {
+    /// Remote chain to return the incoming and outgoing sequence numbers of. Defaults to the
+    /// currently configured remote chain.
+    #[serde(rename = "remote_chain_id")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_chain_id: Option<u64>,
+
+    /// Only return the incoming and outgoing sequence numbers of the given denomination, which
+    /// are included even if the denomination has no operations yet.
+    #[serde(rename = "denomination")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub denomination: Option<token::Denomination>,
+}
+
+/// Sequence storage entries query.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SequenceStorageQuery {
+    /// Remote chain to return the sequence storage entries of. Defaults to the currently
+    /// configured remote chain.
+    #[serde(rename = "remote_chain_id")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_chain_id: Option<u64>,
+}
+
+/// State export query.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExportStateQuery {
+    /// Only return entries with keys after the given key, as returned by the previous chunk.
+    #[serde(rename = "after")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "serde_bytes")]
+    pub after: Option<Vec<u8>>,
+}
+
+/// Raw entry of the module state.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateEntry {
+    /// Key of the entry within the module state.
+    #[serde(rename = "key")]
+    #[serde(with = "serde_bytes")]
+    pub key: Vec<u8>,
+
+    #[serde(rename = "value")]
+    pub value: cbor::Value,
+}
+
+/// Chunk of the module state export.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateChunk {
+    #[serde(rename = "entries")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<StateEntry>,
+
+    /// Key to continue the export after, if there are more entries.
+    #[serde(rename = "next")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "serde_bytes")]
+    pub next: Option<Vec<u8>>,
+}
+
+/// Raw storage entry in the runtime state.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StorageEntry {
+    /// Full key of the entry in the runtime state, including the module prefix.
+    #[serde(rename = "key")]
+    #[serde(with = "serde_bytes")]
+    pub key: Vec<u8>,
+
+    /// CBOR-encoded value of the entry, if present.
+    #[serde(rename = "value")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(with = "serde_bytes")]
+    pub value: Option<Vec<u8>>,
+}
+
+/// Storage entries holding the next sequence numbers of traffic with a remote chain.
+///
+/// The entries do not carry a Merkle proof. A proof of them against the state root must be
+/// obtained separately for the keys, e.g. through the storage sync interface of a node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SequenceStorage {
+    /// Round of the state the entries were read from.
+    #[serde(rename = "round")]
+    pub round: u64,
+
+    /// Runtime state root of the round.
+    #[serde(rename = "state_root")]
+    pub state_root: Hash,
+
+    #[serde(rename = "remote_chain_id")]
+    #[serde(default)]
+    pub remote_chain_id: u64,
+
+    #[serde(rename = "in")]
+    pub incoming: StorageEntry,
+
+    #[serde(rename = "out")]
+    pub outgoing: StorageEntry,
+}
+
+/// Next event sequence numbers.
+///
+/// Incoming and outgoing operations are numbered independently for each remote chain and
+/// denomination. Denominations without any operations yet are omitted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NextSequenceNumbers {
+    #[serde(rename = "remote_chain_id")]
+    #[serde(default)]
+    pub remote_chain_id: u64,
+
+    #[serde(rename = "in")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub incoming: BTreeMap<token::Denomination, u64>,
+
+    #[serde(rename = "out")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub outgoing: BTreeMap<token::Denomination, u64>,
+
+    #[serde(rename = "witness_set_updates")]
+    #[serde(default)]
+    pub witness_set_updates: u64,
+
+    #[serde(rename = "fee_schedule_updates")]
+    #[serde(default)]
+    pub fee_schedule_updates: u64,
+
+    #[serde(rename = "remote_contract_updates")]
+    #[serde(default)]
+    pub remote_contract_updates: u64,
+
+    #[serde(rename = "denomination_registrations")]
+    #[serde(default)]
+    pub denomination_registrations: u64,
 }
@@ -0,0 +1,34 @@
+//! Verification of incoming operations.
+use oasis_runtime_sdk::{context::TxContext, modules};
+
+use crate::{types, Error, Module, Parameters};
+
+/// Backend verifying that incoming operations were made on the remote chain.
+///
+/// Releases are only handed to the verifier once they passed all checks that do not depend on
+/// how they are verified. Verified releases are completed through `Module::complete_release`,
+/// which only accepts the next expected release of each denomination.
+pub trait IncomingVerifier<Accounts: modules::accounts::API> {
+    /// Verify the release submitted by the caller, completing it once it is verified and is the
+    /// next expected release in the sequence space of its denomination.
+    ///
+    /// The signature and nonce of the release carry the caller's proof, if any.
+    fn verify_release<C: TxContext>(
+        ctx: &mut C,
+        params: &Parameters,
+        body: types::Release,
+    ) -> Result<(), Error>;
+}
+
+/// Verifier accepting releases that a threshold of the witnesses voted for.
+pub struct WitnessQuorum;
+
+impl<Accounts: modules::accounts::API> IncomingVerifier<Accounts> for WitnessQuorum {
+    fn verify_release<C: TxContext>(
+        ctx: &mut C,
+        params: &Parameters,
+        body: types::Release,
+    ) -> Result<(), Error> {
+        Module::<Accounts, Self>::vote_release(ctx, params, body)
+    }
+}
@@ -0,0 +1,20 @@
+//! Scenario-based end-to-end testing framework for the bridge module.
+//!
+//! A scenario spins up a mock runtime with the accounts and bridge modules, a set of simulated
+//! witnesses and a mock remote chain, and then drives a sequence of steps (locks, witness
+//! attestations, remote deposits, relaying, advancing blocks) against it. Expectations about
+//! balances and bridge state can be checked at any point during the scenario.
+#![deny(rust_2018_idioms)]
+
+pub mod remote;
+pub mod scenario;
+pub mod witness;
+
+pub use remote::RemoteChain;
+pub use scenario::{Expect, Expectation, Scenario, Step};
+pub use witness::{Behavior, SimulatedWitness};
+
+/// Accounts module used by the mock runtime.
+pub type Accounts = oasis_runtime_sdk::modules::accounts::Module;
+/// Bridge module used by the mock runtime.
+pub type Bridge = oasis_module_bridge::Module<Accounts>;
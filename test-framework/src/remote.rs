@@ -0,0 +1,128 @@
+//! Mock remote chain.
+use std::collections::{BTreeMap, BTreeSet};
+
+use thiserror::Error;
+
+use oasis_runtime_sdk::{
+    core::common::{crypto::hash::Hash, quantity::Quantity},
+    types::{address::Address, token},
+};
+
+use oasis_module_bridge::types;
+
+/// Errors emitted by the mock remote chain.
+#[derive(Error, Debug)]
+pub enum RemoteError {
+    #[error("bundle already processed")]
+    AlreadyProcessed,
+
+    #[error("not enough witness signatures")]
+    NotEnoughSignatures,
+
+    #[error("unsupported operation")]
+    UnsupportedOperation,
+}
+
+/// A deposit made on the remote chain that needs to be relayed to the runtime by witnesses.
+#[derive(Clone, Debug)]
+pub struct Deposit {
+    /// Incoming sequence number assigned by the remote bridge contract within the sequence space
+    /// of the deposited denomination.
+    pub id: u64,
+    /// Target address in the runtime.
+    pub target: Address,
+    /// Deposited amount.
+    pub amount: token::BaseUnits,
+    /// Hash of the remote transaction that made the deposit.
+    pub tx_hash: Hash,
+    /// Index of the deposit's log entry within the remote transaction.
+    pub log_index: u64,
+}
+
+/// A mock of the remote side of the bridge.
+///
+/// The remote chain accepts signature bundles emitted by the bridge module once enough witnesses
+/// have signed off and credits the remote targets. Deposits made on the remote chain are assigned
+/// sequence numbers for each denomination and are relayed to the runtime by the witnesses.
+pub struct RemoteChain {
+    threshold: u64,
+    balances: BTreeMap<(types::RemoteAddress, token::Denomination), Quantity>,
+    deposits: BTreeMap<token::Denomination, Vec<Deposit>>,
+    processed: BTreeSet<(token::Denomination, u64)>,
+}
+
+impl RemoteChain {
+    /// Create a new mock remote chain requiring the given number of witness signatures.
+    pub fn new(threshold: u64) -> Self {
+        Self {
+            threshold,
+            balances: BTreeMap::new(),
+            deposits: BTreeMap::new(),
+            processed: BTreeSet::new(),
+        }
+    }
+
+    /// Make a deposit on the remote chain, returning its sequence number.
+    pub fn deposit(&mut self, target: Address, amount: token::BaseUnits) -> u64 {
+        // Transaction hashes must be unique across the deposits of all denominations.
+        let count = self.deposits.values().map(Vec::len).sum::<usize>() as u64;
+        let tx_hash = Hash::digest_bytes(&count.to_be_bytes());
+        let deposits = self
+            .deposits
+            .entry(amount.denomination().clone())
+            .or_default();
+        let id = deposits.len() as u64;
+        deposits.push(Deposit {
+            id,
+            target,
+            amount,
+            tx_hash,
+            log_index: 0,
+        });
+        id
+    }
+
+    /// Look up a deposit by its denomination and sequence number.
+    pub fn get_deposit(&self, denomination: &token::Denomination, id: u64) -> Option<&Deposit> {
+        self.deposits.get(denomination)?.get(id as usize)
+    }
+
+    /// Submit a quorum certificate emitted by the bridge module to the remote bridge contract.
+    pub fn submit_bundle(&mut self, bundle: &types::QuorumCertificate) -> Result<(), RemoteError> {
+        let key = (bundle.op.amount().denomination().clone(), bundle.id);
+        if self.processed.contains(&key) {
+            return Err(RemoteError::AlreadyProcessed);
+        }
+        if (bundle.signers.len() as u64) < self.threshold {
+            return Err(RemoteError::NotEnoughSignatures);
+        }
+
+        match &bundle.op {
+            types::Operation::Lock(lock) => {
+                let balance = self
+                    .balances
+                    .entry((lock.target, lock.amount.denomination().clone()))
+                    .or_default();
+                *balance = balance
+                    .checked_add(lock.amount.amount())
+                    .expect("remote balance should not overflow");
+            }
+            _ => return Err(RemoteError::UnsupportedOperation),
+        }
+        self.processed.insert(key);
+
+        Ok(())
+    }
+
+    /// Balance of the given remote account.
+    pub fn balance(
+        &self,
+        address: &types::RemoteAddress,
+        denomination: &token::Denomination,
+    ) -> Quantity {
+        self.balances
+            .get(&(*address, denomination.clone()))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
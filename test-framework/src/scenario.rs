@@ -0,0 +1,835 @@
+//! Scenario DSL and runner.
+use std::collections::{BTreeMap, BTreeSet};
+
+use oasis_runtime_sdk::{
+    context::{BatchContext, Context},
+    core::common::cbor,
+    crypto::signature::PublicKey,
+    error::Error as _,
+    module::{self, BlockHandler as _, MethodHandler as _, MigrationHandler as _, Module as _},
+    modules::{
+        accounts::{self, API as _},
+        core,
+    },
+    testing::{keys, mock},
+    types::{
+        address::Address,
+        token::{BaseUnits, Denomination},
+        transaction::{self, CallResult},
+    },
+};
+
+use oasis_module_bridge::{self as bridge, types};
+
+use crate::{
+    remote::RemoteChain,
+    witness::{Behavior, SimulatedWitness},
+    Accounts, Bridge,
+};
+
+/// Initial native balance of each of the test users and witnesses.
+pub const INITIAL_BALANCE: u64 = 1_000_000;
+
+/// Expected outcome of a transaction.
+#[derive(Debug)]
+pub enum Expect {
+    /// The transaction should succeed.
+    Success,
+    /// The transaction should fail with the given bridge module error.
+    Failure(bridge::Error),
+    /// The transaction may either succeed or fail.
+    Any,
+}
+
+/// A check of the state at a given point in the scenario.
+#[derive(Debug)]
+pub enum Expectation {
+    /// Local balance of the given account.
+    Balance { address: Address, amount: BaseUnits },
+    /// Balance of the given account on the mock remote chain.
+    RemoteBalance {
+        address: types::RemoteAddress,
+        amount: BaseUnits,
+    },
+    /// Next sequence numbers of the bridge module for the given denomination.
+    NextSequenceNumbers {
+        denomination: Denomination,
+        incoming: u64,
+        outgoing: u64,
+    },
+    /// Whether the outgoing operation with the given denomination and identifier has been
+    /// finalized.
+    OutgoingFinalized {
+        denomination: Denomination,
+        id: u64,
+        finalized: bool,
+    },
+    /// Whether the outgoing operation with the given denomination and identifier is pending
+    /// witness signatures in the bridge module.
+    OutgoingPending {
+        denomination: Denomination,
+        id: u64,
+        pending: bool,
+    },
+    /// Whether the bridge is paused.
+    Paused(bool),
+    /// Number of `Slashed` and `WitnessJailed` events emitted so far for the witness with the
+    /// given index.
+    Punishments {
+        witness: usize,
+        slashed: usize,
+        jailed: usize,
+    },
+    /// Number of bridge module events with the given code emitted so far.
+    Events { code: u32, count: usize },
+}
+
+/// A single scenario step.
+#[derive(Debug)]
+pub enum Step {
+    /// A user locks an amount, sending it to the given remote address.
+    Lock {
+        user: PublicKey,
+        target: types::RemoteAddress,
+        amount: BaseUnits,
+        expect: Expect,
+    },
+    /// The given witnesses attest to the outgoing operation with the given denomination and
+    /// identifier. Witnesses that do not behave honestly are not subject to the expected outcome.
+    Witness {
+        witnesses: Vec<usize>,
+        denomination: Denomination,
+        id: u64,
+        expect: Expect,
+    },
+    /// A deposit is made on the mock remote chain.
+    RemoteDeposit { target: Address, amount: BaseUnits },
+    /// The given witnesses relay the remote deposit with the given denomination and identifier.
+    /// Witnesses that do not behave honestly are not subject to the expected outcome.
+    Relay {
+        witnesses: Vec<usize>,
+        denomination: Denomination,
+        id: u64,
+        expect: Expect,
+    },
+    /// A witness submits an arbitrary release. Unsigned releases are signed by the witness.
+    /// Releases without a nonce use the next incoming vote nonce of the witness.
+    Release {
+        witness: usize,
+        release: types::Release,
+        expect: Expect,
+    },
+    /// A witness rotates to its successor key. The previous key keeps attesting to the outgoing
+    /// operations that were in flight at the time of the rotation.
+    RotateWitnessKey { witness: usize, expect: Expect },
+    /// A guardian pauses the bridge.
+    Pause { guardian: PublicKey, expect: Expect },
+    /// A guardian resumes the bridge.
+    Resume { guardian: PublicKey, expect: Expect },
+    /// A witness bonds the given amount.
+    Bond {
+        witness: usize,
+        amount: BaseUnits,
+        expect: Expect,
+    },
+    /// The given account submits evidence of all equivocations by witnesses that have not been
+    /// reported yet.
+    ReportEquivocations { reporter: PublicKey },
+    /// A relayer submits all collected signature bundles to the mock remote chain.
+    SubmitBundles,
+    /// Advance the given number of blocks.
+    AdvanceBlocks(u64),
+    /// Check the given expectation.
+    Check(Expectation),
+}
+
+impl Step {
+    /// A successful lock.
+    pub fn lock(user: PublicKey, target: &str, amount: BaseUnits) -> Self {
+        Step::Lock {
+            user,
+            target: target.into(),
+            amount,
+            expect: Expect::Success,
+        }
+    }
+
+    /// Successful attestations of an outgoing operation by the given witnesses.
+    pub fn witness(witnesses: &[usize], denomination: Denomination, id: u64) -> Self {
+        Step::Witness {
+            witnesses: witnesses.to_vec(),
+            denomination,
+            id,
+            expect: Expect::Success,
+        }
+    }
+
+    /// Successful relaying of a remote deposit by the given witnesses.
+    pub fn relay(witnesses: &[usize], denomination: Denomination, id: u64) -> Self {
+        Step::Relay {
+            witnesses: witnesses.to_vec(),
+            denomination,
+            id,
+            expect: Expect::Success,
+        }
+    }
+
+    /// Check the local balance of the given account.
+    pub fn expect_balance(address: Address, amount: BaseUnits) -> Self {
+        Step::Check(Expectation::Balance { address, amount })
+    }
+
+    /// Check the balance of the given account on the mock remote chain.
+    pub fn expect_remote_balance(address: &str, amount: BaseUnits) -> Self {
+        Step::Check(Expectation::RemoteBalance {
+            address: address.into(),
+            amount,
+        })
+    }
+
+    /// Check the next sequence numbers of the bridge module for the given denomination.
+    pub fn expect_sequences(denomination: Denomination, incoming: u64, outgoing: u64) -> Self {
+        Step::Check(Expectation::NextSequenceNumbers {
+            denomination,
+            incoming,
+            outgoing,
+        })
+    }
+}
+
+/// A bridge test scenario.
+pub struct Scenario {
+    name: String,
+    witnesses: usize,
+    threshold: u64,
+    behaviors: BTreeMap<usize, Behavior>,
+    parameters: bridge::Parameters,
+    steps: Vec<Step>,
+}
+
+impl Scenario {
+    /// Create a new scenario with three witnesses and a threshold of two, the native denomination
+    /// as a local denomination and `oETH` as a remote denomination.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            witnesses: 3,
+            threshold: 2,
+            behaviors: BTreeMap::new(),
+            parameters: bridge::Parameters {
+                local_denominations: {
+                    let mut ld = BTreeSet::new();
+                    ld.insert(Denomination::NATIVE);
+                    ld
+                },
+                remote_denominations: {
+                    let mut rd = BTreeMap::new();
+                    rd.insert(
+                        "oETH".parse().unwrap(),
+                        "0000000000000000000000000000000000000000000000000000000000000000".into(),
+                    );
+                    rd
+                },
+                ..Default::default()
+            },
+            steps: Vec::new(),
+        }
+    }
+
+    /// Configure the number of witnesses and the threshold.
+    pub fn witnesses(mut self, witnesses: usize, threshold: u64) -> Self {
+        self.witnesses = witnesses;
+        self.threshold = threshold;
+        self
+    }
+
+    /// Configure the behavior of the witness with the given index.
+    pub fn witness_behavior(mut self, index: usize, behavior: Behavior) -> Self {
+        self.behaviors.insert(index, behavior);
+        self
+    }
+
+    /// Configure the bridge parameters. The witness set and threshold are always taken from the
+    /// scenario configuration.
+    pub fn parameters(mut self, parameters: bridge::Parameters) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Adjust the default bridge parameters.
+    pub fn configure<F: FnOnce(&mut bridge::Parameters)>(mut self, f: F) -> Self {
+        f(&mut self.parameters);
+        self
+    }
+
+    /// Append a step to the scenario.
+    pub fn step(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Append multiple steps to the scenario.
+    pub fn steps<I: IntoIterator<Item = Step>>(mut self, steps: I) -> Self {
+        self.steps.extend(steps);
+        self
+    }
+
+    /// Run the scenario, panicking in case any of the expectations are not met.
+    pub fn run(self) {
+        let mut mock = mock::Mock::default();
+        let mut world = World::new(self.name, self.witnesses, self.threshold, &self.behaviors);
+
+        let mut parameters = self.parameters;
+        parameters.witnesses = world
+            .witnesses
+            .iter()
+            .map(|w| w.public_key().into())
+            .collect();
+        parameters.threshold = self.threshold;
+        world.init(&mut mock.create_ctx(), parameters);
+
+        let mut steps = self.steps.into_iter();
+        loop {
+            let mut advance = None;
+            {
+                let mut ctx = mock.create_ctx();
+                Bridge::begin_block(&mut ctx);
+                for step in &mut steps {
+                    if let Step::AdvanceBlocks(blocks) = step {
+                        advance = Some(blocks);
+                        break;
+                    }
+                    world.execute(&mut ctx, step);
+                }
+                Bridge::end_block(&mut ctx);
+            }
+
+            match advance {
+                Some(blocks) => {
+                    // Run any empty blocks in between.
+                    for _ in 1..blocks {
+                        mock.runtime_header.round += 1;
+                        let mut ctx = mock.create_ctx();
+                        Bridge::begin_block(&mut ctx);
+                        Bridge::end_block(&mut ctx);
+                    }
+                    mock.runtime_header.round += 1;
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// State of a running scenario.
+struct World {
+    name: String,
+    step: usize,
+    witnesses: Vec<SimulatedWitness>,
+    parameters: bridge::Parameters,
+    remote: RemoteChain,
+    outgoing: BTreeMap<(Denomination, u64), types::Operation>,
+    finalized: BTreeSet<(Denomination, u64)>,
+    released: BTreeSet<(Denomination, u64)>,
+    bundles: Vec<types::QuorumCertificate>,
+    events: Vec<(u32, Vec<u8>)>,
+    reported: BTreeMap<usize, usize>,
+}
+
+impl World {
+    fn new(
+        name: String,
+        witnesses: usize,
+        threshold: u64,
+        behaviors: &BTreeMap<usize, Behavior>,
+    ) -> Self {
+        Self {
+            name,
+            step: 0,
+            witnesses: (0..witnesses)
+                .map(|index| {
+                    SimulatedWitness::with_behavior(
+                        index,
+                        behaviors.get(&index).copied().unwrap_or_default(),
+                    )
+                })
+                .collect(),
+            parameters: Default::default(),
+            remote: RemoteChain::new(threshold),
+            outgoing: BTreeMap::new(),
+            finalized: BTreeSet::new(),
+            released: BTreeSet::new(),
+            bundles: Vec::new(),
+            events: Vec::new(),
+            reported: BTreeMap::new(),
+        }
+    }
+
+    fn init<C: Context>(&mut self, ctx: &mut C, parameters: bridge::Parameters) {
+        self.parameters = parameters.clone();
+
+        // Witnesses are funded as well so that they can bond.
+        let funded: Vec<Address> = [
+            keys::alice::address(),
+            keys::bob::address(),
+            keys::charlie::address(),
+        ]
+        .iter()
+        .copied()
+        .chain(self.witnesses.iter().map(SimulatedWitness::address))
+        .collect();
+        Accounts::init_or_migrate(
+            ctx,
+            &mut core::types::Metadata::default(),
+            &accounts::Genesis {
+                balances: {
+                    let mut balances = BTreeMap::new();
+                    for address in &funded {
+                        let mut denominations = BTreeMap::new();
+                        denominations.insert(Denomination::NATIVE, INITIAL_BALANCE.into());
+                        balances.insert(*address, denominations);
+                    }
+                    balances
+                },
+                total_supplies: {
+                    let mut total_supplies = BTreeMap::new();
+                    total_supplies.insert(
+                        Denomination::NATIVE,
+                        (funded.len() as u64 * INITIAL_BALANCE).into(),
+                    );
+                    total_supplies
+                },
+                ..Default::default()
+            },
+        );
+
+        Bridge::init_or_migrate(
+            ctx,
+            &mut core::types::Metadata::default(),
+            &bridge::Genesis {
+                parameters,
+                ..Default::default()
+            },
+        );
+    }
+
+    fn fail(&self, msg: String) -> ! {
+        panic!("scenario '{}' step {}: {}", self.name, self.step, msg)
+    }
+
+    fn execute<C: BatchContext>(&mut self, ctx: &mut C, step: Step) {
+        self.step += 1;
+
+        match step {
+            Step::Lock {
+                user,
+                target,
+                amount,
+                expect,
+            } => {
+                let body = cbor::to_value(types::Lock {
+                    target,
+                    amount,
+                    remote_chain_id: None,
+                    attestation: None,
+                });
+                self.call(ctx, &user, "bridge.Lock", body, &expect);
+            }
+            Step::Witness {
+                witnesses,
+                denomination,
+                id,
+                expect,
+            } => {
+                let key = (denomination, id);
+                let op = match self.outgoing.get(&key) {
+                    Some(op) => op.clone(),
+                    None => self.fail(format!("unknown outgoing operation {:?}", key)),
+                };
+                let any = Expect::Any;
+                for index in witnesses {
+                    let witness = &mut self.witnesses[index];
+                    if witness.is_honest() && self.finalized.contains(&key) {
+                        // Honest witnesses do not attest to already finalized operations.
+                        continue;
+                    }
+                    let pk = witness.outgoing_public_key(&key.0, id);
+                    let expect = if witness.is_honest() { &expect } else { &any };
+                    let witness_index = witness.index();
+                    for mut body in witness.witness_submissions(&self.parameters, id, &op) {
+                        body.nonce = self.vote_nonces(ctx, witness_index).outgoing;
+                        self.call(ctx, &pk, "bridge.Witness", cbor::to_value(body), expect);
+                    }
+                }
+            }
+            Step::RemoteDeposit { target, amount } => {
+                self.remote.deposit(target, amount);
+            }
+            Step::Relay {
+                witnesses,
+                denomination,
+                id,
+                expect,
+            } => {
+                let deposit = match self.remote.get_deposit(&denomination, id) {
+                    Some(deposit) => deposit.clone(),
+                    None => self.fail(format!("unknown remote deposit {:?}", (denomination, id))),
+                };
+                let key = (denomination, id);
+                let any = Expect::Any;
+                for index in witnesses {
+                    let witness = &mut self.witnesses[index];
+                    if witness.is_honest() && self.released.contains(&key) {
+                        // Honest witnesses do not relay already released deposits.
+                        continue;
+                    }
+                    let pk = witness.public_key();
+                    let expect = if witness.is_honest() { &expect } else { &any };
+                    let witness_index = witness.index();
+                    for mut body in witness.release_submissions(&self.parameters, &deposit) {
+                        body.nonce = Some(self.vote_nonces(ctx, witness_index).incoming);
+                        self.call(ctx, &pk, "bridge.Release", cbor::to_value(body), expect);
+                    }
+                }
+            }
+            Step::Release {
+                witness,
+                release,
+                expect,
+            } => {
+                let pk = self.witnesses[witness].public_key();
+                let mut release = match release.signature {
+                    Some(_) => release,
+                    None => self.witnesses[witness].sign_release(&self.parameters, release),
+                };
+                if release.nonce.is_none() {
+                    release.nonce = Some(self.vote_nonces(ctx, witness).incoming);
+                }
+                self.call(ctx, &pk, "bridge.Release", cbor::to_value(release), &expect);
+            }
+            Step::RotateWitnessKey { witness, expect } => {
+                let next_outgoing = self.next_sequences(ctx).outgoing;
+                let pk = self.witnesses[witness].public_key();
+                let body = cbor::to_value(types::RotateWitnessKey {
+                    key: self.witnesses[witness].successor_key(),
+                });
+                if self.call(ctx, &pk, "bridge.RotateWitnessKey", body, &expect) {
+                    self.witnesses[witness].rotate_key(next_outgoing);
+                }
+            }
+            Step::Pause { guardian, expect } => {
+                self.call(ctx, &guardian, "bridge.Pause", cbor::Value::Null, &expect);
+            }
+            Step::Resume { guardian, expect } => {
+                self.call(ctx, &guardian, "bridge.Resume", cbor::Value::Null, &expect);
+            }
+            Step::Bond {
+                witness,
+                amount,
+                expect,
+            } => {
+                let pk = self.witnesses[witness].public_key();
+                let body = cbor::to_value(types::Bond { amount });
+                self.call(ctx, &pk, "bridge.Bond", body, &expect);
+            }
+            Step::ReportEquivocations { reporter } => {
+                for index in 0..self.witnesses.len() {
+                    let reported = self.reported.get(&index).copied().unwrap_or_default();
+                    let evidence = self.witnesses[index].equivocations();
+                    for body in evidence.into_iter().skip(reported) {
+                        let body = cbor::to_value(body);
+                        self.call(
+                            ctx,
+                            &reporter,
+                            "bridge.SubmitEvidence",
+                            body,
+                            &Expect::Success,
+                        );
+                        *self.reported.entry(index).or_default() += 1;
+                    }
+                }
+            }
+            Step::SubmitBundles => {
+                for bundle in std::mem::take(&mut self.bundles) {
+                    if let Err(err) = self.remote.submit_bundle(&bundle) {
+                        self.fail(format!(
+                            "remote chain rejected bundle {}: {}",
+                            bundle.id, err
+                        ));
+                    }
+                }
+            }
+            Step::AdvanceBlocks(_) => unreachable!("blocks are advanced by the runner"),
+            Step::Check(expectation) => self.check(ctx, expectation),
+        }
+    }
+
+    /// Submit a transaction calling the given method, returning whether it succeeded.
+    fn call<C: BatchContext>(
+        &mut self,
+        ctx: &mut C,
+        caller: &PublicKey,
+        method: &str,
+        body: cbor::Value,
+        expect: &Expect,
+    ) -> bool {
+        let tx = transaction::Transaction {
+            version: 1,
+            call: transaction::Call {
+                method: method.to_owned(),
+                body,
+            },
+            auth_info: transaction::AuthInfo {
+                signer_info: vec![transaction::SignerInfo::new(caller.clone(), 0)],
+                fee: transaction::Fee {
+                    amount: Default::default(),
+                    gas: 1000,
+                },
+            },
+        };
+        let result = ctx.with_tx(tx, |mut tx_ctx, call| {
+            match Bridge::dispatch_call(&mut tx_ctx, &call.method, call.body) {
+                module::DispatchResult::Handled(CallResult::Ok(_)) => {
+                    let (tags, _messages) = tx_ctx.commit();
+                    Ok(tags)
+                }
+                module::DispatchResult::Handled(CallResult::Failed { module, code, .. }) => {
+                    Err((module, code))
+                }
+                module::DispatchResult::Unhandled(_) => Err((String::new(), 0)),
+            }
+        });
+
+        let succeeded = result.is_ok();
+        match (result, expect) {
+            (Ok(tags), Expect::Success) | (Ok(tags), Expect::Any) => {
+                for tag in tags {
+                    self.process_event(&tag.key, &tag.value);
+                }
+            }
+            (Ok(_), Expect::Failure(err)) => {
+                self.fail(format!("{} should fail with '{}'", method, err))
+            }
+            (Err((module, code)), Expect::Success) => self.fail(format!(
+                "{} should succeed, failed with {}/{}",
+                method, module, code
+            )),
+            (Err(_), Expect::Any) => {}
+            (Err((module, code)), Expect::Failure(err)) => {
+                if module != err.module_name() || code != err.code() {
+                    self.fail(format!(
+                        "{} should fail with '{}', failed with {}/{}",
+                        method, err, module, code
+                    ));
+                }
+            }
+        }
+        succeeded
+    }
+
+    fn process_event(&mut self, key: &[u8], value: &[u8]) {
+        let module = Bridge::NAME.as_bytes();
+        if key.len() != module.len() + 4 || &key[..module.len()] != module {
+            return;
+        }
+        let mut code = [0; 4];
+        code.copy_from_slice(&key[module.len()..]);
+        let code = u32::from_be_bytes(code);
+        self.events.push((code, value.to_vec()));
+
+        match code {
+            1 => {
+                // Lock event, remember the operation so witnesses can attest to it.
+                if let Ok(bridge::Event::Lock {
+                    id, target, amount, ..
+                }) = cbor::from_slice(value)
+                {
+                    self.outgoing.insert(
+                        (amount.denomination().clone(), id),
+                        types::Operation::Lock(types::Lock {
+                            target,
+                            amount,
+                            remote_chain_id: None,
+                            attestation: None,
+                        }),
+                    );
+                }
+            }
+            2 => {
+                // Release event, the deposit has been released.
+                if let Ok(bridge::Event::Release { id, amount, .. }) = cbor::from_slice(value) {
+                    self.released.insert((amount.denomination().clone(), id));
+                }
+            }
+            3 => {
+                // Witnesses signed event, collect the quorum certificate for relaying.
+                if let Ok(bundle) = cbor::from_slice::<types::QuorumCertificate>(value) {
+                    self.finalized
+                        .insert((bundle.op.amount().denomination().clone(), bundle.id));
+                    self.bundles.push(bundle);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Next vote nonces of the witness at the given index in the current witness set.
+    fn vote_nonces<C: Context>(&self, ctx: &mut C, index: usize) -> types::VoteNonces {
+        let result = match Bridge::dispatch_query(ctx, "bridge.VoteNonces", cbor::Value::Null) {
+            module::DispatchResult::Handled(Ok(result)) => result,
+            _ => self.fail("vote nonces query should succeed".to_owned()),
+        };
+        let mut nonces: Vec<types::VoteNonces> =
+            cbor::from_value(result).expect("query result should be well-formed");
+        if index >= nonces.len() {
+            self.fail(format!("witness {} is not in the witness set", index));
+        }
+        nonces.swap_remove(index)
+    }
+
+    /// Next sequence numbers of the bridge module.
+    fn next_sequences<C: Context>(&self, ctx: &mut C) -> types::NextSequenceNumbers {
+        let result =
+            match Bridge::dispatch_query(ctx, "bridge.NextSequenceNumbers", cbor::Value::Null) {
+                module::DispatchResult::Handled(Ok(result)) => result,
+                _ => self.fail("sequence numbers query should succeed".to_owned()),
+            };
+        cbor::from_value(result).expect("query result should be well-formed")
+    }
+
+    fn check<C: Context>(&mut self, ctx: &mut C, expectation: Expectation) {
+        match expectation {
+            Expectation::Balance { address, amount } => {
+                let balances = Accounts::get_balances(ctx.runtime_state(), address)
+                    .expect("get_balances should succeed");
+                let balance = balances
+                    .balances
+                    .get(amount.denomination())
+                    .cloned()
+                    .unwrap_or_default();
+                if &balance != amount.amount() {
+                    self.fail(format!(
+                        "balance of {} should be {:?}, got {:?}",
+                        address, amount, balance
+                    ));
+                }
+            }
+            Expectation::RemoteBalance { address, amount } => {
+                let balance = self.remote.balance(&address, amount.denomination());
+                if &balance != amount.amount() {
+                    self.fail(format!(
+                        "remote balance of {} should be {:?}, got {:?}",
+                        address, amount, balance
+                    ));
+                }
+            }
+            Expectation::NextSequenceNumbers {
+                denomination,
+                incoming,
+                outgoing,
+            } => {
+                let sequences = self.next_sequences(ctx);
+                let next = |sequences: &BTreeMap<Denomination, u64>| {
+                    sequences.get(&denomination).copied().unwrap_or_default()
+                };
+                let (next_incoming, next_outgoing) =
+                    (next(&sequences.incoming), next(&sequences.outgoing));
+                if next_incoming != incoming || next_outgoing != outgoing {
+                    self.fail(format!(
+                        "next {:?} sequence numbers should be {}/{}, got {}/{}",
+                        denomination, incoming, outgoing, next_incoming, next_outgoing
+                    ));
+                }
+            }
+            Expectation::OutgoingFinalized {
+                denomination,
+                id,
+                finalized,
+            } => {
+                let key = (denomination, id);
+                if self.finalized.contains(&key) != finalized {
+                    self.fail(format!(
+                        "outgoing operation {:?} finalized should be {}",
+                        key, finalized
+                    ));
+                }
+            }
+            Expectation::OutgoingPending {
+                denomination,
+                id,
+                pending,
+            } => {
+                let args = cbor::to_value(types::OutOperationQuery {
+                    denomination: denomination.clone(),
+                    id,
+                });
+                let found = matches!(
+                    Bridge::dispatch_query(ctx, "bridge.OutOperation", args),
+                    module::DispatchResult::Handled(Ok(_))
+                );
+                if found != pending {
+                    self.fail(format!(
+                        "outgoing operation {:?} pending should be {}",
+                        (denomination, id),
+                        pending
+                    ));
+                }
+            }
+            Expectation::Paused(paused) => {
+                let result = match Bridge::dispatch_query(ctx, "bridge.Paused", cbor::Value::Null) {
+                    module::DispatchResult::Handled(Ok(result)) => result,
+                    _ => self.fail("paused query should succeed".to_owned()),
+                };
+                let is_paused: bool =
+                    cbor::from_value(result).expect("query result should be well-formed");
+                if is_paused != paused {
+                    self.fail(format!("bridge paused should be {}", paused));
+                }
+            }
+            Expectation::Punishments {
+                witness,
+                slashed,
+                jailed,
+            } => {
+                let count = |code| {
+                    self.events
+                        .iter()
+                        .filter(|(c, value)| {
+                            *c == code && event_witness_index(value) == Some(witness)
+                        })
+                        .count()
+                };
+                // Slashed and witness jailed events.
+                let (emitted_slashed, emitted_jailed) = (count(12), count(13));
+                if emitted_slashed != slashed || emitted_jailed != jailed {
+                    self.fail(format!(
+                        "witness {} should be slashed {} and jailed {} times, got {} and {}",
+                        witness, slashed, jailed, emitted_slashed, emitted_jailed
+                    ));
+                }
+            }
+            Expectation::Events { code, count } => {
+                let emitted = self.events.iter().filter(|(c, _)| *c == code).count();
+                if emitted != count {
+                    self.fail(format!(
+                        "{} events with code {} should be emitted, got {}",
+                        count, code, emitted
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Index of the witness that the given bridge module event refers to, if any.
+fn event_witness_index(value: &[u8]) -> Option<usize> {
+    // Events with different payloads share the same fields, so only the witness index is decoded.
+    let fields = match cbor::from_slice(value).ok()? {
+        cbor::Value::Map(fields) => fields,
+        _ => return None,
+    };
+    match fields.get(&cbor::Value::Text("index".to_owned()))? {
+        cbor::Value::Integer(index) => Some(*index as usize),
+        _ => None,
+    }
+}
@@ -0,0 +1,348 @@
+//! Simulated witnesses.
+use std::collections::BTreeMap;
+
+use oasis_runtime_sdk::{
+    core::common::{
+        crypto::signature::{PrivateKey, Signer as _},
+        quantity::Quantity,
+    },
+    crypto::signature::PublicKey,
+    types::{address::Address, token},
+};
+
+use oasis_module_bridge::{crypto, types, Parameters};
+
+use crate::remote::Deposit;
+
+/// Behavior of a simulated witness.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Behavior {
+    /// Follows the protocol.
+    Honest,
+    /// Attests to operations with a wrong (doubled) amount.
+    WrongAmount,
+    /// Attests to both the correct operation and a conflicting one.
+    Equivocate,
+    /// Never submits any attestations.
+    Withhold,
+    /// Resubmits its previous attestation instead of attesting to the current operation.
+    Replay,
+}
+
+impl Default for Behavior {
+    fn default() -> Self {
+        Behavior::Honest
+    }
+}
+
+/// A simulated witness.
+pub struct SimulatedWitness {
+    index: usize,
+    signer: PrivateKey,
+    /// Keys that the witness rotated away from, together with the next outgoing sequence numbers
+    /// at the time of the rotation.
+    retired: Vec<(PrivateKey, BTreeMap<token::Denomination, u64>)>,
+    behavior: Behavior,
+    last_witness: Option<(types::Operation, types::Witness)>,
+    last_release: Option<types::Release>,
+    /// Payloads submitted so far, together with the sequence numbers that they were signed for.
+    submitted: Vec<(types::EvidenceKind, u64, types::SignedOperation)>,
+}
+
+impl SimulatedWitness {
+    /// Create a new honest simulated witness with a deterministic key derived from its index.
+    pub fn new(index: usize) -> Self {
+        Self::with_behavior(index, Behavior::Honest)
+    }
+
+    /// Create a new simulated witness with the given behavior.
+    pub fn with_behavior(index: usize, behavior: Behavior) -> Self {
+        Self {
+            index,
+            signer: Self::key(index, 0),
+            retired: Vec::new(),
+            behavior,
+            last_witness: None,
+            last_release: None,
+            submitted: Vec::new(),
+        }
+    }
+
+    /// Index of the witness in the witness set.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Behavior of the witness.
+    pub fn behavior(&self) -> Behavior {
+        self.behavior
+    }
+
+    /// Whether the witness follows the protocol.
+    pub fn is_honest(&self) -> bool {
+        self.behavior == Behavior::Honest
+    }
+
+    /// Public key of the witness.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::Ed25519(self.signer.public_key().into())
+    }
+
+    /// Address of the witness.
+    pub fn address(&self) -> Address {
+        Address::from_pk(&self.public_key())
+    }
+
+    /// Public key that the witness rotates to on its next key rotation.
+    pub fn successor_key(&self) -> PublicKey {
+        let successor = Self::key(self.index, self.retired.len() + 1);
+        PublicKey::Ed25519(successor.public_key().into())
+    }
+
+    /// Rotate to the successor key.
+    ///
+    /// The previous key keeps signing the outgoing operations that were in flight at the time of
+    /// the rotation, as given by the next outgoing sequence numbers of each denomination.
+    pub fn rotate_key(&mut self, next_outgoing: BTreeMap<token::Denomination, u64>) {
+        let successor = Self::key(self.index, self.retired.len() + 1);
+        let previous = std::mem::replace(&mut self.signer, successor);
+        self.retired.push((previous, next_outgoing));
+    }
+
+    /// Public key that the witness uses for attesting to the outgoing operation with the given
+    /// denomination and identifier.
+    pub fn outgoing_public_key(&self, denomination: &token::Denomination, id: u64) -> PublicKey {
+        PublicKey::Ed25519(self.outgoing_signer(denomination, id).public_key().into())
+    }
+
+    fn key(index: usize, generation: usize) -> PrivateKey {
+        match generation {
+            0 => PrivateKey::from_test_seed(format!(
+                "oasis-bridge/test-framework: witness {}",
+                index
+            )),
+            generation => PrivateKey::from_test_seed(format!(
+                "oasis-bridge/test-framework: witness {} key {}",
+                index, generation
+            )),
+        }
+    }
+
+    /// Key that was current when the outgoing operation with the given denomination and
+    /// identifier was created.
+    fn outgoing_signer(&self, denomination: &token::Denomination, id: u64) -> &PrivateKey {
+        self.retired
+            .iter()
+            .find(|(_, next)| id < next.get(denomination).copied().unwrap_or_default())
+            .map(|(signer, _)| signer)
+            .unwrap_or(&self.signer)
+    }
+
+    /// Produce the body of a `bridge.Witness` call attesting to the given outgoing operation.
+    ///
+    /// The vote nonce is left at zero and must be filled in by the caller before submission.
+    pub fn witness(&self, params: &Parameters, id: u64, op: &types::Operation) -> types::Witness {
+        let message = match &params.eip712_domain {
+            Some(domain) => crypto::eip712_digest(params, domain, id, op)
+                .expect("outgoing operation should be a lock")
+                .to_vec(),
+            None => crypto::witness_message(params, id, op),
+        };
+        let signature = self
+            .outgoing_signer(op.amount().denomination(), id)
+            .sign(crypto::WITNESS_SIGNATURE_CONTEXT, &message)
+            .expect("signing should succeed");
+
+        types::Witness {
+            denomination: op.amount().denomination().clone(),
+            id,
+            signature: signature.as_ref().to_vec().into(),
+            nonce: 0,
+        }
+    }
+
+    /// Sign the given release, replacing any existing signature.
+    pub fn sign_release(&self, params: &Parameters, release: types::Release) -> types::Release {
+        let release = types::Release {
+            signature: None,
+            ..release
+        };
+        let op = types::Operation::Release(types::Release {
+            nonce: None,
+            ..release.clone()
+        });
+        let signature = self
+            .signer
+            .sign(
+                crypto::RELEASE_SIGNATURE_CONTEXT,
+                &crypto::witness_message(params, release.id, &op),
+            )
+            .expect("signing should succeed");
+
+        types::Release {
+            signature: Some(signature.as_ref().to_vec().into()),
+            ..release
+        }
+    }
+
+    /// Produce the body of a `bridge.Release` call relaying the given remote deposit.
+    ///
+    /// The vote nonce is left unset and must be filled in by the caller before submission.
+    pub fn release(&self, params: &Parameters, deposit: &Deposit) -> types::Release {
+        self.sign_release(
+            params,
+            types::Release {
+                id: deposit.id,
+                target: deposit.target,
+                amount: deposit.amount.clone(),
+                tx_hash: Some(deposit.tx_hash),
+                log_index: Some(deposit.log_index),
+                batch: vec![],
+                claims_root: None,
+                remote_chain_id: None,
+                signature: None,
+                nonce: None,
+            },
+        )
+    }
+
+    /// Produce the `bridge.Witness` calls that the witness submits for the given outgoing
+    /// operation according to its behavior.
+    pub fn witness_submissions(
+        &mut self,
+        params: &Parameters,
+        id: u64,
+        op: &types::Operation,
+    ) -> Vec<types::Witness> {
+        let honest = (op.clone(), self.witness(params, id, op));
+        let tampered = tamper_operation(op);
+        let tampered = (tampered.clone(), self.witness(params, id, &tampered));
+        let submissions = match self.behavior {
+            Behavior::Honest => vec![honest.clone()],
+            Behavior::WrongAmount => vec![tampered],
+            Behavior::Equivocate => vec![honest.clone(), tampered],
+            Behavior::Withhold => vec![],
+            Behavior::Replay => vec![self.last_witness.clone().unwrap_or_else(|| honest.clone())],
+        };
+        self.last_witness = Some(honest);
+
+        submissions
+            .into_iter()
+            .map(|(op, body)| {
+                self.submitted.push((
+                    types::EvidenceKind::Witness,
+                    body.id,
+                    types::SignedOperation {
+                        op,
+                        signature: body.signature.clone(),
+                    },
+                ));
+                body
+            })
+            .collect()
+    }
+
+    /// Produce the `bridge.Release` calls that the witness submits for the given remote deposit
+    /// according to its behavior.
+    pub fn release_submissions(
+        &mut self,
+        params: &Parameters,
+        deposit: &Deposit,
+    ) -> Vec<types::Release> {
+        let honest = self.release(params, deposit);
+        let tampered = self.sign_release(
+            params,
+            types::Release {
+                amount: tamper_amount(&honest.amount),
+                ..honest.clone()
+            },
+        );
+        let submissions = match self.behavior {
+            Behavior::Honest => vec![honest.clone()],
+            Behavior::WrongAmount => vec![tampered],
+            Behavior::Equivocate => vec![honest.clone(), tampered],
+            Behavior::Withhold => vec![],
+            Behavior::Replay => vec![self.last_release.clone().unwrap_or_else(|| honest.clone())],
+        };
+        self.last_release = Some(honest);
+
+        for release in &submissions {
+            let op = types::Operation::Release(types::Release {
+                signature: None,
+                nonce: None,
+                ..release.clone()
+            });
+            self.submitted.push((
+                types::EvidenceKind::Release,
+                release.id,
+                types::SignedOperation {
+                    op,
+                    signature: release
+                        .signature
+                        .clone()
+                        .expect("submitted releases should be signed"),
+                },
+            ));
+        }
+        submissions
+    }
+
+    /// Evidence of the witness signing two different operations for the same sequence number,
+    /// collected from the payloads it submitted so far.
+    ///
+    /// At most one piece of evidence is produced for each sequence number, in the order in which
+    /// the witness equivocated.
+    pub fn equivocations(&self) -> Vec<types::SubmitEvidence> {
+        let mut evidence: Vec<types::SubmitEvidence> = Vec::new();
+        for (i, (kind, id, first)) in self.submitted.iter().enumerate() {
+            let denomination = first.op.amount().denomination();
+            if evidence.iter().any(|e| {
+                e.kind == *kind && e.id == *id && e.first.op.amount().denomination() == denomination
+            }) {
+                continue;
+            }
+            let second = self.submitted[i + 1..]
+                .iter()
+                .find(|(k, other_id, second)| {
+                    k == kind
+                        && other_id == id
+                        && second.op.amount().denomination() == denomination
+                        && types::OperationId::from(&second.op)
+                            != types::OperationId::from(&first.op)
+                });
+            if let Some((_, _, second)) = second {
+                evidence.push(types::SubmitEvidence {
+                    witness: self.public_key().into(),
+                    kind: *kind,
+                    id: *id,
+                    first: first.clone(),
+                    second: second.clone(),
+                });
+            }
+        }
+        evidence
+    }
+}
+
+fn tamper_amount(amount: &token::BaseUnits) -> token::BaseUnits {
+    token::BaseUnits::new(
+        amount
+            .amount()
+            .checked_mul(&Quantity::from(2u64))
+            .expect("tampered amount should not overflow"),
+        amount.denomination().clone(),
+    )
+}
+
+fn tamper_operation(op: &types::Operation) -> types::Operation {
+    match op {
+        types::Operation::Lock(lock) => types::Operation::Lock(types::Lock {
+            amount: tamper_amount(&lock.amount),
+            ..lock.clone()
+        }),
+        types::Operation::Release(release) => types::Operation::Release(types::Release {
+            amount: tamper_amount(&release.amount),
+            ..release.clone()
+        }),
+    }
+}
@@ -0,0 +1,176 @@
+//! Chaos scenarios with adversarial witnesses.
+//!
+//! All scenarios use three witnesses with a threshold of two, so a single adversarial witness
+//! must never be able to affect the outcome of any operation. Witnesses with a single adversary
+//! are bonded, so that the adversary is punished while honest witnesses are left untouched.
+use oasis_runtime_sdk::{
+    testing::keys,
+    types::token::{BaseUnits, Denomination},
+};
+
+use oasis_bridge_test_framework::{Behavior, Expect, Expectation, Scenario, Step};
+
+const REMOTE_ALICE: &str = "00000000000000000000000000000000000a11ce";
+
+const BEHAVIORS: &[Behavior] = &[
+    Behavior::WrongAmount,
+    Behavior::Equivocate,
+    Behavior::Withhold,
+    Behavior::Replay,
+];
+
+fn native(amount: u64) -> BaseUnits {
+    BaseUnits::new(amount.into(), Denomination::NATIVE)
+}
+
+fn oeth_denomination() -> Denomination {
+    "oETH".parse().unwrap()
+}
+
+fn oeth(amount: u64) -> BaseUnits {
+    BaseUnits::new(amount.into(), oeth_denomination())
+}
+
+/// A scenario with a single adversarial witness in which all witnesses bonded more than required,
+/// so that misbehavior is slashed and jailed.
+fn adversarial_scenario(name: &str, adversary: usize, behavior: Behavior) -> Scenario {
+    Scenario::new(name)
+        .witness_behavior(adversary, behavior)
+        .configure(|params| {
+            params.witness_bond = Some(native(1_000));
+            params.divergence_penalty = 1_000;
+            params.equivocation_penalty = 1_000;
+            params.jail_duration = 100;
+        })
+        .steps((0..3).map(|witness| Step::Bond {
+            witness,
+            amount: native(2_000),
+            expect: Expect::Success,
+        }))
+}
+
+/// Number of times the adversary is slashed and jailed over the two operations of a scenario.
+fn expected_punishments(behavior: Behavior, adversary: usize, incoming: bool) -> (usize, usize) {
+    match behavior {
+        // Votes for a wrong incoming operation are slashed when it is finalized, unless the
+        // adversary only votes after it has already been finalized. Signatures over wrong
+        // outgoing operations are rejected outright.
+        Behavior::WrongAmount if incoming && adversary < 2 => (2, 0),
+        // Both signatures of an equivocating witness are evidence against it.
+        Behavior::Equivocate => (2, 2),
+        _ => (0, 0),
+    }
+}
+
+/// Report all equivocations and check that only the adversary has been punished.
+fn punishment_steps(behavior: Behavior, adversary: usize, incoming: bool) -> Vec<Step> {
+    let (slashed, jailed) = expected_punishments(behavior, adversary, incoming);
+    let mut steps = vec![Step::ReportEquivocations {
+        reporter: keys::bob::pk(),
+    }];
+    steps.extend((0..3).map(|witness| {
+        let (slashed, jailed) = if witness == adversary {
+            (slashed, jailed)
+        } else {
+            (0, 0)
+        };
+        Step::Check(Expectation::Punishments {
+            witness,
+            slashed,
+            jailed,
+        })
+    }));
+    steps
+}
+
+#[test]
+fn test_chaos_incoming_single_adversary() {
+    for behavior in BEHAVIORS {
+        for adversary in 0..3 {
+            adversarial_scenario(
+                &format!("incoming with witness {} {:?}", adversary, behavior),
+                adversary,
+                *behavior,
+            )
+            .step(Step::RemoteDeposit {
+                target: keys::alice::address(),
+                amount: oeth(1_000),
+            })
+            .step(Step::RemoteDeposit {
+                target: keys::alice::address(),
+                amount: oeth(500),
+            })
+            .step(Step::relay(&[0, 1, 2], oeth_denomination(), 0))
+            .step(Step::AdvanceBlocks(1))
+            .step(Step::relay(&[0, 1, 2], oeth_denomination(), 1))
+            // Only the correct amounts must have been released.
+            .step(Step::expect_balance(keys::alice::address(), oeth(1_500)))
+            .step(Step::expect_sequences(oeth_denomination(), 2, 0))
+            .step(Step::Check(Expectation::Events { code: 2, count: 2 }))
+            .steps(punishment_steps(*behavior, adversary, true))
+            .run();
+        }
+    }
+}
+
+#[test]
+fn test_chaos_outgoing_single_adversary() {
+    for behavior in BEHAVIORS {
+        for adversary in 0..3 {
+            adversarial_scenario(
+                &format!("outgoing with witness {} {:?}", adversary, behavior),
+                adversary,
+                *behavior,
+            )
+            .step(Step::lock(keys::alice::pk(), REMOTE_ALICE, native(1_000)))
+            .step(Step::lock(keys::alice::pk(), REMOTE_ALICE, native(500)))
+            .step(Step::witness(&[0, 1, 2], Denomination::NATIVE, 0))
+            .step(Step::AdvanceBlocks(1))
+            .step(Step::witness(&[0, 1, 2], Denomination::NATIVE, 1))
+            // Each operation must be finalized exactly once.
+            .step(Step::Check(Expectation::OutgoingFinalized {
+                denomination: Denomination::NATIVE,
+                id: 0,
+                finalized: true,
+            }))
+            .step(Step::Check(Expectation::OutgoingFinalized {
+                denomination: Denomination::NATIVE,
+                id: 1,
+                finalized: true,
+            }))
+            .step(Step::Check(Expectation::Events { code: 3, count: 2 }))
+            .step(Step::SubmitBundles)
+            .step(Step::expect_remote_balance(REMOTE_ALICE, native(1_500)))
+            .steps(punishment_steps(*behavior, adversary, false))
+            .run();
+        }
+    }
+}
+
+#[test]
+fn test_chaos_withholding_majority() {
+    // With a majority of witnesses withholding nothing can be finalized, but no funds are lost.
+    Scenario::new("withholding majority")
+        .witness_behavior(0, Behavior::Withhold)
+        .witness_behavior(1, Behavior::Withhold)
+        .step(Step::RemoteDeposit {
+            target: keys::alice::address(),
+            amount: oeth(1_000),
+        })
+        .step(Step::relay(&[0, 1, 2], oeth_denomination(), 0))
+        .step(Step::lock(keys::alice::pk(), REMOTE_ALICE, native(1_000)))
+        .step(Step::witness(&[0, 1, 2], Denomination::NATIVE, 0))
+        .step(Step::expect_balance(keys::alice::address(), oeth(0)))
+        .step(Step::expect_balance(
+            keys::alice::address(),
+            native(999_000),
+        ))
+        .step(Step::expect_sequences(oeth_denomination(), 0, 0))
+        .step(Step::expect_sequences(Denomination::NATIVE, 0, 1))
+        .step(Step::Check(Expectation::OutgoingFinalized {
+            denomination: Denomination::NATIVE,
+            id: 0,
+            finalized: false,
+        }))
+        .run();
+}
@@ -0,0 +1,256 @@
+//! End-to-end bridge scenarios.
+use oasis_runtime_sdk::{
+    core::common::crypto::hash::Hash,
+    testing::keys,
+    types::token::{BaseUnits, Denomination},
+};
+
+use oasis_bridge_test_framework::{Expect, Expectation, Scenario, Step};
+use oasis_module_bridge::{types, Error};
+
+const REMOTE_ALICE: &str = "00000000000000000000000000000000000a11ce";
+
+fn native(amount: u64) -> BaseUnits {
+    BaseUnits::new(amount.into(), Denomination::NATIVE)
+}
+
+fn oeth_denomination() -> Denomination {
+    "oETH".parse().unwrap()
+}
+
+fn oeth(amount: u64) -> BaseUnits {
+    BaseUnits::new(amount.into(), oeth_denomination())
+}
+
+#[test]
+fn test_scenario_lock_and_witness() {
+    Scenario::new("lock and witness")
+        .step(Step::lock(keys::alice::pk(), REMOTE_ALICE, native(1_000)))
+        .step(Step::expect_balance(
+            keys::alice::address(),
+            native(999_000),
+        ))
+        .step(Step::witness(&[0], Denomination::NATIVE, 0))
+        .step(Step::Check(Expectation::OutgoingFinalized {
+            denomination: Denomination::NATIVE,
+            id: 0,
+            finalized: false,
+        }))
+        .step(Step::AdvanceBlocks(1))
+        .step(Step::witness(&[2], Denomination::NATIVE, 0))
+        .step(Step::Check(Expectation::OutgoingFinalized {
+            denomination: Denomination::NATIVE,
+            id: 0,
+            finalized: true,
+        }))
+        .step(Step::SubmitBundles)
+        .step(Step::expect_remote_balance(REMOTE_ALICE, native(1_000)))
+        .step(Step::expect_sequences(Denomination::NATIVE, 0, 1))
+        .run();
+}
+
+#[test]
+fn test_scenario_remote_deposit() {
+    Scenario::new("remote deposit")
+        .step(Step::RemoteDeposit {
+            target: keys::bob::address(),
+            amount: oeth(500),
+        })
+        .step(Step::RemoteDeposit {
+            target: keys::charlie::address(),
+            amount: oeth(700),
+        })
+        .step(Step::relay(&[0, 1], oeth_denomination(), 0))
+        .step(Step::AdvanceBlocks(3))
+        .step(Step::relay(&[1, 2], oeth_denomination(), 1))
+        .step(Step::expect_balance(keys::bob::address(), oeth(500)))
+        .step(Step::expect_balance(keys::charlie::address(), oeth(700)))
+        .step(Step::expect_sequences(oeth_denomination(), 2, 0))
+        .run();
+}
+
+#[test]
+fn test_scenario_round_trip() {
+    Scenario::new("round trip")
+        .step(Step::RemoteDeposit {
+            target: keys::alice::address(),
+            amount: oeth(1_000),
+        })
+        .step(Step::relay(&[0, 1, 2], oeth_denomination(), 0))
+        .step(Step::expect_balance(keys::alice::address(), oeth(1_000)))
+        .step(Step::AdvanceBlocks(1))
+        // Send part of the wrapped tokens back.
+        .step(Step::lock(keys::alice::pk(), REMOTE_ALICE, oeth(400)))
+        .step(Step::expect_balance(keys::alice::address(), oeth(600)))
+        .step(Step::witness(&[0, 1], oeth_denomination(), 0))
+        .step(Step::SubmitBundles)
+        .step(Step::expect_remote_balance(REMOTE_ALICE, oeth(400)))
+        .run();
+}
+
+#[test]
+fn test_scenario_divergence() {
+    Scenario::new("divergence")
+        .step(Step::RemoteDeposit {
+            target: keys::alice::address(),
+            amount: oeth(1_000),
+        })
+        // A corrupted witness proposes a different amount for the same deposit, which is the first
+        // one made on the remote chain.
+        .step(Step::Release {
+            witness: 0,
+            release: types::Release {
+                id: 0,
+                target: keys::alice::address(),
+                amount: oeth(2_000),
+                tx_hash: Some(Hash::digest_bytes(&0u64.to_be_bytes())),
+                log_index: Some(0),
+                batch: vec![],
+                claims_root: None,
+                remote_chain_id: None,
+                signature: None,
+                nonce: None,
+            },
+            expect: Expect::Success,
+        })
+        .step(Step::relay(&[1], oeth_denomination(), 0))
+        .step(Step::expect_balance(keys::alice::address(), oeth(0)))
+        .step(Step::expect_sequences(oeth_denomination(), 0, 0))
+        // The corrupted witness cannot vote again.
+        .step(Step::Relay {
+            witnesses: vec![0],
+            denomination: oeth_denomination(),
+            id: 0,
+            expect: Expect::Failure(Error::AlreadySubmittedSignature),
+        })
+        // An honest witness breaks the tie.
+        .step(Step::relay(&[2], oeth_denomination(), 0))
+        .step(Step::expect_balance(keys::alice::address(), oeth(1_000)))
+        .step(Step::expect_sequences(oeth_denomination(), 1, 0))
+        .run();
+}
+
+#[test]
+fn test_scenario_rotation_mid_flight() {
+    Scenario::new("rotation mid flight")
+        .step(Step::lock(keys::alice::pk(), REMOTE_ALICE, native(1_000)))
+        .step(Step::witness(&[0], Denomination::NATIVE, 0))
+        // A witness rotates its key while the first operation is still in flight.
+        .step(Step::RotateWitnessKey {
+            witness: 1,
+            expect: Expect::Success,
+        })
+        .step(Step::lock(keys::alice::pk(), REMOTE_ALICE, native(500)))
+        .step(Step::AdvanceBlocks(1))
+        // The in-flight operation is attested with the previous key and the new one with the
+        // successor key.
+        .step(Step::witness(&[1], Denomination::NATIVE, 0))
+        .step(Step::witness(&[1, 2], Denomination::NATIVE, 1))
+        .step(Step::Check(Expectation::OutgoingFinalized {
+            denomination: Denomination::NATIVE,
+            id: 0,
+            finalized: true,
+        }))
+        .step(Step::Check(Expectation::OutgoingFinalized {
+            denomination: Denomination::NATIVE,
+            id: 1,
+            finalized: true,
+        }))
+        .step(Step::SubmitBundles)
+        .step(Step::expect_remote_balance(REMOTE_ALICE, native(1_500)))
+        .step(Step::expect_sequences(Denomination::NATIVE, 0, 2))
+        .run();
+}
+
+#[test]
+fn test_scenario_expiry() {
+    Scenario::new("expiry")
+        .configure(|params| params.operation_expiry = 2)
+        .step(Step::lock(keys::alice::pk(), REMOTE_ALICE, native(1_000)))
+        .step(Step::witness(&[0], Denomination::NATIVE, 0))
+        .step(Step::Check(Expectation::OutgoingPending {
+            denomination: Denomination::NATIVE,
+            id: 0,
+            pending: true,
+        }))
+        .step(Step::AdvanceBlocks(4))
+        // The operation expired before enough witnesses signed it.
+        .step(Step::Check(Expectation::OutgoingPending {
+            denomination: Denomination::NATIVE,
+            id: 0,
+            pending: false,
+        }))
+        .step(Step::Witness {
+            witnesses: vec![1],
+            denomination: Denomination::NATIVE,
+            id: 0,
+            expect: Expect::Failure(Error::InvalidSequenceNumber),
+        })
+        .step(Step::Check(Expectation::OutgoingFinalized {
+            denomination: Denomination::NATIVE,
+            id: 0,
+            finalized: false,
+        }))
+        // The funds of the expired operation stay locked.
+        .step(Step::expect_balance(
+            keys::alice::address(),
+            native(999_000),
+        ))
+        // Later operations are not affected.
+        .step(Step::lock(keys::alice::pk(), REMOTE_ALICE, native(500)))
+        .step(Step::witness(&[1, 2], Denomination::NATIVE, 1))
+        .step(Step::SubmitBundles)
+        .step(Step::expect_remote_balance(REMOTE_ALICE, native(500)))
+        .step(Step::expect_sequences(Denomination::NATIVE, 0, 2))
+        .run();
+}
+
+#[test]
+fn test_scenario_pause_and_resume() {
+    Scenario::new("pause and resume")
+        .configure(|params| params.guardians = vec![keys::dave::address()])
+        .step(Step::RemoteDeposit {
+            target: keys::bob::address(),
+            amount: oeth(500),
+        })
+        // Only guardians can pause the bridge.
+        .step(Step::Pause {
+            guardian: keys::alice::pk(),
+            expect: Expect::Failure(Error::NotAuthorized),
+        })
+        .step(Step::Pause {
+            guardian: keys::dave::pk(),
+            expect: Expect::Success,
+        })
+        .step(Step::Check(Expectation::Paused(true)))
+        // Neither locks nor releases go through while paused.
+        .step(Step::Lock {
+            user: keys::alice::pk(),
+            target: REMOTE_ALICE.into(),
+            amount: native(1_000),
+            expect: Expect::Failure(Error::Paused),
+        })
+        .step(Step::Relay {
+            witnesses: vec![0, 1],
+            denomination: oeth_denomination(),
+            id: 0,
+            expect: Expect::Failure(Error::Paused),
+        })
+        .step(Step::expect_balance(keys::bob::address(), oeth(0)))
+        .step(Step::AdvanceBlocks(1))
+        .step(Step::Resume {
+            guardian: keys::dave::pk(),
+            expect: Expect::Success,
+        })
+        .step(Step::Check(Expectation::Paused(false)))
+        // Operations continue where they left off.
+        .step(Step::relay(&[0, 1], oeth_denomination(), 0))
+        .step(Step::expect_balance(keys::bob::address(), oeth(500)))
+        .step(Step::lock(keys::alice::pk(), REMOTE_ALICE, native(1_000)))
+        .step(Step::witness(&[0, 1], Denomination::NATIVE, 0))
+        .step(Step::SubmitBundles)
+        .step(Step::expect_remote_balance(REMOTE_ALICE, native(1_000)))
+        .step(Step::expect_sequences(oeth_denomination(), 1, 0))
+        .step(Step::expect_sequences(Denomination::NATIVE, 0, 1))
+        .run();
+}